@@ -0,0 +1,4 @@
+// migrations/ 의 SQL 파일이 바뀌면 sqlx::migrate!가 다시 읽도록 재빌드
+fn main() {
+    println!("cargo:rerun-if-changed=migrations");
+}
@@ -0,0 +1,28 @@
+-- 길드 설정, 종료된 세션, 사용자별 누적 시간, 진행 중인 채널 활성화.
+-- PRAGMA user_version 방식으로 이미 만들어진 데이터베이스도 그대로 쓸 수 있도록 IF NOT EXISTS 사용
+CREATE TABLE IF NOT EXISTS guild_config (
+    guild_id INTEGER PRIMARY KEY,
+    config   TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS voice_sessions (
+    id         INTEGER PRIMARY KEY AUTOINCREMENT,
+    guild_id   INTEGER NOT NULL,
+    channel_id INTEGER NOT NULL,
+    started_at INTEGER NOT NULL,
+    ended_at   INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS voice_sessions_guild ON voice_sessions (guild_id, started_at);
+
+CREATE TABLE IF NOT EXISTS user_voice_stats (
+    guild_id   INTEGER NOT NULL,
+    user_id    INTEGER NOT NULL,
+    total_secs INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (guild_id, user_id)
+);
+
+CREATE TABLE IF NOT EXISTS active_channels (
+    channel_id INTEGER PRIMARY KEY,
+    guild_id   INTEGER NOT NULL,
+    started_at INTEGER NOT NULL
+);
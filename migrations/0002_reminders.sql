@@ -0,0 +1,11 @@
+-- 예약된 리마인더
+CREATE TABLE IF NOT EXISTS reminders (
+    id         INTEGER PRIMARY KEY AUTOINCREMENT,
+    user_id    INTEGER NOT NULL,
+    channel_id INTEGER NOT NULL,
+    dm         INTEGER NOT NULL DEFAULT 0,
+    message    TEXT NOT NULL,
+    due_at     INTEGER NOT NULL,
+    created_at INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS reminders_user ON reminders (user_id, due_at);
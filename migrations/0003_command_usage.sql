@@ -0,0 +1,20 @@
+-- 커맨드 사용 통계 (하루 단위로 합산). guild_id 0은 DM
+CREATE TABLE IF NOT EXISTS command_usage (
+    day      INTEGER NOT NULL,
+    guild_id INTEGER NOT NULL,
+    command  TEXT NOT NULL,
+    count    INTEGER NOT NULL DEFAULT 0,
+    errors   INTEGER NOT NULL DEFAULT 0,
+    -- 응답 시간 구간별 횟수 (JSON 배열, usage::LATENCY_BUCKETS_MS 순서)
+    latency  TEXT NOT NULL DEFAULT '[]',
+    PRIMARY KEY (day, guild_id, command)
+);
+
+-- 계산기 오류 종류별 횟수
+CREATE TABLE IF NOT EXISTS calc_errors (
+    day      INTEGER NOT NULL,
+    guild_id INTEGER NOT NULL,
+    kind     TEXT NOT NULL,
+    count    INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (day, guild_id, kind)
+);
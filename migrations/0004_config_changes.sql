@@ -0,0 +1,11 @@
+-- 길드 설정 변경 기록 (/config history). 오래된 기록은 예약 작업이 정리
+CREATE TABLE IF NOT EXISTS config_changes (
+    id         INTEGER PRIMARY KEY AUTOINCREMENT,
+    guild_id   INTEGER NOT NULL,
+    user_id    INTEGER NOT NULL,
+    changed_at INTEGER NOT NULL,
+    key        TEXT NOT NULL,
+    old_value  TEXT NOT NULL,
+    new_value  TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS config_changes_guild ON config_changes (guild_id, changed_at);
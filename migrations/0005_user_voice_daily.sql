@@ -0,0 +1,9 @@
+-- 사용자별 하루 보이스 시간 (주간 요약 DM). 퇴장한 날짜에 합산하며 오래된 기록은 예약 작업이 정리
+CREATE TABLE IF NOT EXISTS user_voice_daily (
+    day      INTEGER NOT NULL,
+    guild_id INTEGER NOT NULL,
+    user_id  INTEGER NOT NULL,
+    secs     INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (day, guild_id, user_id)
+);
+CREATE INDEX IF NOT EXISTS user_voice_daily_user ON user_voice_daily (user_id, day);
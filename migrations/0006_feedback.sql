@@ -0,0 +1,16 @@
+-- /feedback로 받은 의견 (하루 전송 횟수 제한 확인용으로도 사용)
+CREATE TABLE IF NOT EXISTS feedback (
+    id         INTEGER PRIMARY KEY AUTOINCREMENT,
+    user_id    INTEGER NOT NULL,
+    guild_id   INTEGER,
+    channel_id INTEGER NOT NULL,
+    content    TEXT NOT NULL,
+    created_at INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS feedback_user ON feedback (user_id, created_at);
+
+-- 봇 소유자가 /feedback block으로 막은 사용자
+CREATE TABLE IF NOT EXISTS feedback_blocks (
+    user_id    INTEGER PRIMARY KEY,
+    blocked_at INTEGER NOT NULL
+);
@@ -0,0 +1,8 @@
+-- 활성화 표시를 붙여 둔 보이스 채널의 원래 이름 (/voiceconfig statusname).
+-- 봇이 꺼진 사이 활성화가 끝난 채널을 재시작 후 되돌릴 때 사용
+CREATE TABLE IF NOT EXISTS channel_renames (
+    channel_id    INTEGER PRIMARY KEY,
+    guild_id      INTEGER NOT NULL,
+    original_name TEXT NOT NULL,
+    renamed_to    TEXT NOT NULL
+);
@@ -0,0 +1,8 @@
+-- 허브 채널(hub_channel)에서 만든 임시 보이스 채널.
+-- 봇이 꺼진 사이 비었거나 남아 있는 임시 채널을 재시작 후 정리할 때 사용
+CREATE TABLE IF NOT EXISTS temp_channels (
+    channel_id INTEGER PRIMARY KEY,
+    guild_id   INTEGER NOT NULL,
+    owner_id   INTEGER NOT NULL,
+    created_at INTEGER NOT NULL
+);
@@ -0,0 +1,4 @@
+-- 활성화 기록의 최대 동시 접속자 수와 활동 점수 (최대 접속자 수 × 활성화 시간(초)).
+-- 이전 기록은 알 수 없으므로 0
+ALTER TABLE voice_sessions ADD COLUMN peak_members INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE voice_sessions ADD COLUMN activity_score INTEGER NOT NULL DEFAULT 0;
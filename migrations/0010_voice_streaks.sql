@@ -0,0 +1,14 @@
+-- 사용자별 연속 보이스 활동 일수 (서버 시간대 기준 날짜). day/day_secs는 그날 쌓은 시간,
+-- last_day는 기준 시간을 채운 마지막 날. remind는 /streak remind, reminded_day는 마지막으로 알린 날
+CREATE TABLE IF NOT EXISTS voice_streaks (
+    guild_id     INTEGER NOT NULL,
+    user_id      INTEGER NOT NULL,
+    current      INTEGER NOT NULL DEFAULT 0,
+    best         INTEGER NOT NULL DEFAULT 0,
+    last_day     INTEGER NOT NULL DEFAULT 0,
+    day          INTEGER NOT NULL DEFAULT 0,
+    day_secs     INTEGER NOT NULL DEFAULT 0,
+    remind       INTEGER NOT NULL DEFAULT 0,
+    reminded_day INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (guild_id, user_id)
+);
@@ -0,0 +1,9 @@
+-- 길드별로 1부터 늘어나는 활성화 번호 (/session). 이전 기록은 길드별 저장 순서대로 번호를 매김.
+-- 진행 중인 활성화도 시작할 때 번호를 받아 두므로 active_channels에도 저장
+ALTER TABLE voice_sessions ADD COLUMN session_id INTEGER NOT NULL DEFAULT 0;
+UPDATE voice_sessions SET session_id = (
+    SELECT COUNT(*) FROM voice_sessions AS earlier
+    WHERE earlier.guild_id = voice_sessions.guild_id AND earlier.id <= voice_sessions.id
+);
+CREATE UNIQUE INDEX IF NOT EXISTS voice_sessions_session_id ON voice_sessions (guild_id, session_id);
+ALTER TABLE active_channels ADD COLUMN session_id INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,4 @@
+-- 활성화를 시작한 사람 (세션의 첫 사람 입장, 봇 제외). 이전 기록과 알 수 없는 경우는 NULL
+ALTER TABLE voice_sessions ADD COLUMN starter_id INTEGER;
+ALTER TABLE active_channels ADD COLUMN starter_id INTEGER;
+CREATE INDEX IF NOT EXISTS voice_sessions_starter ON voice_sessions (guild_id, starter_id);
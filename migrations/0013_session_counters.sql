@@ -0,0 +1,13 @@
+-- 길드별 마지막 활성화 번호. 설정 JSON과 따로 두어 /config 저장과 겹쳐도 번호가 되돌아가지 않음.
+-- 설정에 저장되어 있던 번호와 이미 쓴 가장 큰 번호 중 큰 값에서 이어감
+CREATE TABLE IF NOT EXISTS session_counters (
+    guild_id INTEGER PRIMARY KEY,
+    last_session_id INTEGER NOT NULL
+);
+INSERT INTO session_counters (guild_id, last_session_id)
+SELECT guild_id, MAX(last_session_id) FROM (
+    SELECT guild_id, COALESCE(json_extract(config, '$.last_session_id'), 0) AS last_session_id FROM guild_config
+    UNION ALL SELECT guild_id, session_id FROM voice_sessions
+    UNION ALL SELECT guild_id, session_id FROM active_channels
+)
+GROUP BY guild_id;
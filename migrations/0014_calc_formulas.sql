@@ -0,0 +1,8 @@
+-- 서버별로 저장한 /calc 수식 (/calcstore). /calc expr 자동 완성에 이름으로 표시
+CREATE TABLE IF NOT EXISTS calc_formulas (
+    guild_id   INTEGER NOT NULL,
+    name       TEXT NOT NULL,
+    expression TEXT NOT NULL,
+    created_by INTEGER NOT NULL,
+    PRIMARY KEY (guild_id, name)
+);
@@ -0,0 +1,7 @@
+-- 사용자별 설정 (/timezone, /weeklyreport, /joinannounce). 기본값과 같은 사용자도 한 번 바꾸면 남음
+CREATE TABLE IF NOT EXISTS user_prefs (
+    user_id           INTEGER PRIMARY KEY,
+    timezone          TEXT NOT NULL,
+    weekly_dm_summary INTEGER NOT NULL DEFAULT 0,
+    tts_opt_out       INTEGER NOT NULL DEFAULT 0
+);
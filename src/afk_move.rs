@@ -0,0 +1,171 @@
+use serenity::all::ChannelId;
+use serenity::all::GuildId;
+use serenity::all::Permissions;
+use serenity::all::RoleId;
+use serenity::all::UserId;
+use serenity::prelude::*;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+
+use crate::config_check::get_usable_config;
+use crate::dry_run;
+use crate::error_report::report_error;
+use crate::guild_config::{get_guild_config, AfkNotice};
+use crate::notification::{self, send_notification};
+use crate::voice_tracker::ChannelActivityTracker;
+
+// 조건(혼자, 스피커 끔, 방송 안 함)을 처음 확인한 시각. 예약 작업이 확인할 때마다 갱신
+pub struct AfkCandidates;
+
+impl TypeMapKey for AfkCandidates {
+    type Value = Arc<Mutex<HashMap<(GuildId, UserId), Instant>>>;
+}
+
+pub fn new_afk_store() -> Arc<Mutex<HashMap<(GuildId, UserId), Instant>>> {
+    Arc::new(Mutex::new(HashMap::new()))
+}
+
+// 옮길 멤버 (사용자, 있던 채널, 잠수 채널)
+struct AfkMove {
+    user_id: UserId,
+    from: ChannelId,
+    to: ChannelId,
+}
+
+// 예약 작업: afk_move를 켠 서버에서 추적 중인 채널에 혼자 스피커를 끈 채 afk_move_minutes 넘게 있는
+// 멤버를 서버 잠수 채널로 옮김. 화면 공유 중이거나 afk_bypass_role이 있는 멤버는 제외
+pub async fn move_idle_members(ctx: Context) {
+    let (store, tracker) = {
+        let data = ctx.data.read().await;
+        (data.get::<AfkCandidates>().cloned(), data.get::<ChannelActivityTracker>().cloned())
+    };
+    let (Some(store), Some(tracker)) = (store, tracker) else {
+        return;
+    };
+
+    for guild_id in ctx.cache.guilds() {
+        let config = get_guild_config(&ctx, guild_id).await;
+        let tracked: Vec<ChannelId> = if config.afk_move {
+            tracker.active_sessions(guild_id).await.into_iter().map(|(c, _, _)| c).collect()
+        } else {
+            Vec::new()
+        };
+        let candidates = idle_members(&ctx, guild_id, &tracked, config.afk_bypass_role);
+
+        // 조건이 풀린 멤버는 지우고, 새로 조건에 든 멤버는 지금부터 셈
+        let wait = Duration::from_secs(u64::from(config.afk_move_minutes) * 60);
+        let due: Vec<AfkMove> = {
+            let mut store = store.lock().await;
+            store.retain(|(g, u), _| *g != guild_id || candidates.iter().any(|c| c.user_id == *u));
+            let now = Instant::now();
+            candidates
+                .into_iter()
+                .filter(|c| now.duration_since(*store.entry((guild_id, c.user_id)).or_insert(now)) >= wait)
+                .collect()
+        };
+
+        for afk in due {
+            // 실패해도 매번 다시 시도하지 않도록 대기 시간을 처음부터 다시 셈
+            store.lock().await.remove(&(guild_id, afk.user_id));
+            move_member(&ctx, guild_id, &afk, config.afk_move_minutes, config.afk_move_notice).await;
+        }
+    }
+}
+
+// 추적 중인 채널에서 조건에 맞는 멤버. 봇에 두 채널의 멤버 이동 권한이 없으면 제외
+fn idle_members(
+    ctx: &Context,
+    guild_id: GuildId,
+    tracked: &[ChannelId],
+    bypass_role: Option<RoleId>,
+) -> Vec<AfkMove> {
+    if tracked.is_empty() {
+        return Vec::new();
+    }
+    let Some(guild) = ctx.cache.guild(guild_id) else {
+        return Vec::new();
+    };
+    let Some(afk_channel) = guild.afk_metadata.as_ref().map(|m| m.afk_channel_id) else {
+        return Vec::new();
+    };
+    let bot_id = ctx.cache.current_user().id;
+    let can_move = |channel_id: ChannelId| {
+        let (Some(channel), Some(bot)) = (guild.channels.get(&channel_id), guild.members.get(&bot_id)) else {
+            return false;
+        };
+        guild.user_permissions_in(channel, bot).contains(Permissions::MOVE_MEMBERS)
+    };
+    if !can_move(afk_channel) {
+        return Vec::new();
+    }
+
+    let is_bot = |user_id: UserId| guild.members.get(&user_id).is_some_and(|m| m.user.bot);
+    let mut humans: HashMap<ChannelId, usize> = HashMap::new();
+    for vs in guild.voice_states.values() {
+        if let Some(channel_id) = vs.channel_id
+            && !is_bot(vs.user_id)
+        {
+            *humans.entry(channel_id).or_default() += 1;
+        }
+    }
+
+    guild
+        .voice_states
+        .values()
+        .filter_map(|vs| {
+            let from = vs.channel_id?;
+            let alone = humans.get(&from) == Some(&1);
+            let bypass = bypass_role.is_some_and(|role| {
+                vs.member
+                    .as_ref()
+                    .or_else(|| guild.members.get(&vs.user_id))
+                    .is_some_and(|m| m.roles.contains(&role))
+            });
+            let idle = vs.self_deaf && !vs.self_stream.unwrap_or(false);
+            (from != afk_channel
+                && tracked.contains(&from)
+                && alone
+                && idle
+                && !bypass
+                && !is_bot(vs.user_id)
+                && can_move(from))
+            .then_some(AfkMove {
+                user_id: vs.user_id,
+                from,
+                to: afk_channel,
+            })
+        })
+        .collect()
+}
+
+async fn move_member(ctx: &Context, guild_id: GuildId, afk: &AfkMove, minutes: u32, notice: AfkNotice) {
+    let detail = format!("<@{}> <#{}> → <#{}>", afk.user_id, afk.from, afk.to);
+    if dry_run::suppressed(ctx, Some(guild_id), "잠수 채널로 이동", detail).await {
+        return;
+    }
+    if let Err(e) = guild_id.move_member(&ctx.http, afk.user_id, afk.to).await {
+        report_error(ctx, "잠수 채널로 이동", &e).await;
+        return;
+    }
+    let message = notification::moved_to_afk(afk.user_id, afk.from, minutes);
+    match notice {
+        AfkNotice::Channel => {
+            if let Some(channel_id) = get_usable_config(ctx, guild_id).await.notification_channel {
+                send_notification(ctx, guild_id, channel_id, message, "잠수 채널 이동 알림 전송").await;
+            }
+        }
+        AfkNotice::Dm => {
+            let sent = match afk.user_id.create_dm_channel(&ctx.http).await {
+                Ok(dm) => dm.send_message(&ctx.http, message.into_message()).await.map(|_| ()),
+                Err(e) => Err(e),
+            };
+            // DM을 막아 둔 멤버가 많으므로 오류 보고 없이 로그만
+            if let Err(e) = sent {
+                tracing::warn!("잠수 채널 이동 DM 전송 실패 ({}): {}", afk.user_id, e);
+            }
+        }
+        AfkNotice::Off => {}
+    }
+}
@@ -0,0 +1,361 @@
+use serenity::Client;
+use serenity::all::GatewayError;
+use serenity::all::GatewayIntents;
+use serenity::all::GuildId;
+use serenity::prelude::TypeMap;
+use sqlx::sqlite::SqlitePool;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::afk_move::{self, new_afk_store, AfkCandidates};
+use crate::calc_buttons::{self, new_expression_store, CalcExpressions};
+use crate::calc_cache::{new_calc_cache, CalcCache};
+use crate::calc_log::{new_calc_log, GlobalCalcLog};
+use crate::calc_session::{new_session_store, CalcSessionStore};
+use crate::channel_status::{new_rename_store, ChannelRenames};
+use crate::cli::Cli;
+use crate::commands::{self, CommandsRegistered, RegisterOnly, RegistrationState};
+use crate::config::{self, ConfigFile, FileConfig, LoadedConfig};
+use crate::config_check::{self, new_bad_settings, BadSettings};
+#[cfg(feature = "http-api")]
+use crate::dashboard;
+use crate::dry_run::{self, GlobalDryRun};
+use crate::error_report::{ErrorReportState, ErrorReporter};
+use crate::event_metrics;
+use crate::formulas::{new_formulas_store, FormulasStore};
+use crate::guild_config;
+#[cfg(feature = "http-api")]
+use crate::health;
+use crate::health::HealthState;
+use crate::invites::{new_invite_store, BotInvites};
+use crate::locale;
+use crate::maintenance::{self, new_announcement_store, PendingAnnouncements};
+use crate::notification::{new_notification_queues, NotificationQueues};
+use crate::notification_batch::{new_batch_store, NotificationBatches};
+use crate::notifier::{HttpNotifier, Notifiers};
+use crate::presence::{new_presence_tasks, PresenceConfig, PresenceSettings, PresenceTasks};
+use crate::rate_limit::{CooldownState, Cooldowns, RateLimitState, RateLimiter};
+use crate::retry::RetryPolicy;
+use crate::scheduled_events::{new_event_cache, ScheduledEventCache};
+use crate::scheduler::{Schedule, Scheduler, SchedulerKey};
+use crate::shards::{new_event_counters, ShardEventCounters, ShardManagerKey};
+use crate::storage::{self, GuildSettingsCache, SettingsCache, Storage};
+use crate::streaks;
+use crate::temp_channels::{new_temp_channel_store, TempChannels};
+use crate::tts_announce::{new_tts_queues, TtsQueues};
+use crate::usage::{self, UsageState, UsageStats};
+use crate::user_prefs::{self, UserPreferences};
+use crate::voice_log::{self, new_voice_log, DailyVoiceLog};
+use crate::voice_tracker::{
+    new_tracker_store, restore_tracker, AppState, ChannelActivityTracker, VoiceHandler,
+};
+use crate::weekly_report;
+
+// 설정 파일 (없으면 환경 변수만 사용). 읽지 못하면 종료
+fn load_file_config(cli: &Cli) -> FileConfig {
+    match &cli.config_path {
+        Some(path) => match FileConfig::load(path) {
+            Ok(file) => file,
+            Err(e) => {
+                tracing::error!("설정 파일 읽기 실패: {}", e);
+                std::process::exit(1);
+            }
+        },
+        None => FileConfig::default(),
+    }
+}
+
+// 데이터베이스 연결과 마이그레이션. 실패하면 종료
+async fn open_database(cli: &Cli) -> SqlitePool {
+    let db_url = cli.db_url.as_deref().unwrap_or(storage::DEFAULT_DATABASE_URL);
+    let pool = match storage::connect(db_url).await {
+        Ok(pool) => pool,
+        Err(e) => {
+            tracing::error!("데이터베이스 연결 실패 ({}): {}", db_url, e);
+            std::process::exit(1);
+        }
+    };
+    // 새 버전 배포 시 이전 스키마의 데이터베이스를 먼저 갱신
+    if let Err(e) = storage::run_migrations(&pool).await {
+        tracing::error!("데이터베이스 마이그레이션 실패: {}", e);
+        std::process::exit(1);
+    }
+    pool
+}
+
+pub async fn run_bot(cli: Cli) {
+    warn_missing_localizations();
+    let token = cli.token.clone().unwrap_or_default();
+
+    // /reloadconfig로 다시 읽을 수 있음
+    let file_config = load_file_config(&cli);
+    event_metrics::apply_config(&file_config);
+    let pool = open_database(&cli).await;
+
+    // 재시작 전에 진행 중이던 채널 활성화 복원
+    let tracker = new_tracker_store();
+    match restore_tracker(&pool, &tracker).await {
+        Ok(count) if count > 0 => tracing::info!("진행 중이던 채널 활성화 {}개를 복원했습니다", count),
+        Ok(_) => {}
+        Err(e) => tracing::error!("채널 활성화 복원 실패: {}", e),
+    }
+
+    let health = Arc::new(HealthState::new(Duration::from_secs(cli.health_threshold_secs)));
+    let state = Arc::new(AppState::new(tracker.clone(), pool.clone(), health.clone()));
+    #[cfg(feature = "http-api")]
+    if let Some(port) = cli.http_port {
+        // 대시보드 API는 토큰을 설정한 경우에만 같은 서버에 추가
+        let dashboard = dashboard::token_from_config(&file_config).map(|token| dashboard::router(state.clone(), token));
+        if dashboard.is_some() {
+            tracing::info!("대시보드 API: http://0.0.0.0:{}/guilds/<id>/(active|leaderboard|sessions)", port);
+        }
+        tokio::spawn(async move {
+            if let Err(e) = health::serve(port, health, dashboard).await {
+                tracing::error!("상태 확인 HTTP 서버 실행 실패 (포트 {}): {}", port, e);
+            }
+        });
+        tracing::info!("상태 확인 엔드포인트: http://0.0.0.0:{}/healthz", port);
+    }
+    #[cfg(not(feature = "http-api"))]
+    if cli.http_port.is_some() {
+        tracing::warn!("http-api 기능 없이 빌드되어 --http-port 를 무시합니다");
+    }
+
+    // 주기 작업 (ready에서 시작)
+    let scheduler = Arc::new(
+        Scheduler::new()
+            .job(
+                "calc_expressions_prune",
+                Schedule::Every(Duration::from_secs(600)),
+                calc_buttons::prune_expressions,
+            )
+            .job(
+                "usage_rollup",
+                Schedule::Every(Duration::from_secs(3600)),
+                usage::rollup,
+            )
+            .job(
+                "config_recheck",
+                Schedule::Every(Duration::from_secs(3600)),
+                config_check::recheck_guilds,
+            )
+            .job(
+                "guild_settings_cache_refresh",
+                Schedule::Every(Duration::from_secs(600)),
+                guild_config::refresh_settings_cache,
+            )
+            .job(
+                "config_history_prune",
+                Schedule::DailyAt { hour: 0, minute: 10 },
+                guild_config::prune_config_history,
+            )
+            .job(
+                "event_metrics_summary",
+                Schedule::Every(Duration::from_secs(300)),
+                event_metrics::log_summary,
+            )
+            .job(
+                "config_watch",
+                Schedule::Every(Duration::from_secs(5)),
+                maintenance::watch_config_file,
+            )
+            .job(
+                "weekly_report",
+                Schedule::DailyAt { hour: 9, minute: 0 },
+                weekly_report::send_weekly_reports,
+            )
+            .job(
+                "afk_move",
+                Schedule::Every(Duration::from_secs(60)),
+                afk_move::move_idle_members,
+            )
+            .job(
+                "streak_reminders",
+                Schedule::Every(Duration::from_secs(3600)),
+                streaks::send_streak_reminders,
+            )
+            .job(
+                "voice_log_prune",
+                Schedule::DailyAt { hour: 0, minute: 5 },
+                voice_log::prune,
+            ),
+    );
+
+    let usage_stats = Arc::new(UsageState::new());
+
+    // MESSAGE_CONTENT는 멘션 명령(@봇 calc 2+3)용. 개발자 포털에서 켜야 하는 권한
+    let intents = GatewayIntents::GUILDS
+        | GatewayIntents::GUILD_VOICE_STATES
+        | GatewayIntents::GUILD_MESSAGES
+        | GatewayIntents::MESSAGE_CONTENT;
+
+    // 클라이언트를 다시 만들 때도 그대로 넘겨 추적 상태, 설정 캐시, 통계를 유지
+    let mut data = TypeMap::new();
+    data.insert::<ChannelActivityTracker>(tracker);
+    data.insert::<Storage>(pool.clone());
+    data.insert::<GuildSettingsCache>(Arc::new(SettingsCache::default()));
+    data.insert::<CalcSessionStore>(new_session_store());
+    data.insert::<GlobalCalcLog>(new_calc_log());
+    data.insert::<CalcExpressions>(new_expression_store());
+    data.insert::<ErrorReporter>(Arc::new(ErrorReportState::from_config(&file_config)));
+    data.insert::<GlobalDryRun>(dry_run::from_config(&file_config));
+    data.insert::<ShardEventCounters>(new_event_counters());
+    data.insert::<PresenceSettings>(Arc::new(PresenceConfig::from_config(&file_config)));
+    data.insert::<PresenceTasks>(new_presence_tasks());
+    data.insert::<DailyVoiceLog>(new_voice_log());
+    data.insert::<BotInvites>(new_invite_store());
+    data.insert::<RateLimiter>(Arc::new(RateLimitState::from_config(&file_config)));
+    data.insert::<Cooldowns>(Arc::new(CooldownState::new()));
+    data.insert::<CommandsRegistered>(Arc::new(RegistrationState::new(cli.force_register)));
+    data.insert::<SchedulerKey>(scheduler.clone());
+    data.insert::<NotificationBatches>(new_batch_store());
+    data.insert::<PendingAnnouncements>(new_announcement_store());
+    data.insert::<UsageStats>(usage_stats.clone());
+    data.insert::<UserPreferences>(user_prefs::load_prefs_store(&pool).await);
+    data.insert::<BadSettings>(new_bad_settings());
+    data.insert::<CalcCache>(new_calc_cache());
+    data.insert::<FormulasStore>(new_formulas_store());
+    data.insert::<TtsQueues>(new_tts_queues());
+    data.insert::<NotificationQueues>(new_notification_queues());
+    data.insert::<ChannelRenames>(new_rename_store());
+    data.insert::<TempChannels>(new_temp_channel_store());
+    data.insert::<AfkCandidates>(new_afk_store());
+    data.insert::<ScheduledEventCache>(new_event_cache());
+    data.insert::<ConfigFile>(Arc::new(tokio::sync::RwLock::new(LoadedConfig {
+        path: cli.config_path.clone(),
+        file: file_config.clone(),
+        modified: cli.config_path.as_deref().and_then(config::modified_time),
+    })));
+
+    tracing::info!("봇을 시작합니다... (로그 레벨: {})", cli.log_level);
+    if dry_run::from_config(&file_config) {
+        tracing::info!("드라이런 모드: 알림 전송 등 외부 동작은 실행하지 않고 로그로만 남깁니다");
+    }
+
+    // 클라이언트가 오류로 끝나면 잠시 기다렸다가 같은 TypeMap으로 다시 만듦. /shutdown으로 끝나면 종료
+    let mut rapid_failures = 0;
+    loop {
+        let builder = Client::builder(&token, intents)
+            .event_handler(VoiceHandler::new(state.clone()))
+            .type_map(data);
+        // 보이스 연결 관리자 (활성화 알림음). TypeMap에 넣으므로 type_map 뒤에 등록
+        #[cfg(feature = "chime")]
+        let builder = songbird::SerenityInit::register_songbird(builder);
+        let mut client = builder.await.expect("클라이언트 생성 실패");
+        {
+            let mut data = client.data.write().await;
+            data.insert::<ShardManagerKey>(client.shard_manager.clone());
+            data.insert::<Notifiers>(Arc::new(HttpNotifier::new(client.http.clone())));
+        }
+
+        // 디스코드가 권장하는 샤드 수로 자동 샤딩
+        let started = Instant::now();
+        let result = client.start_autosharded().await;
+        // 이전 클라이언트의 Context를 들고 있는 작업은 빈 TypeMap을 보고 멈춤
+        data = std::mem::replace(&mut *client.data.write().await, TypeMap::new());
+        let Err(why) = result else {
+            break;
+        };
+        tracing::error!("클라이언트 에러: {:?}", why);
+        // 토큰이나 인텐트 문제는 다시 시도해도 같으므로 바로 종료
+        if matches!(
+            why,
+            serenity::Error::Gateway(
+                GatewayError::InvalidAuthentication
+                    | GatewayError::InvalidGatewayIntents
+                    | GatewayError::DisallowedGatewayIntents
+            )
+        ) {
+            shutdown_state(&scheduler, &usage_stats, &pool).await;
+            std::process::exit(1);
+        }
+
+        // 새 클라이언트의 ready에서 스케줄러와 상태 메시지 작업을 새 Context로 다시 시작
+        scheduler.shutdown();
+        if let Some(tasks) = data.get::<PresenceTasks>() {
+            tasks.lock().await.clear();
+        }
+
+        // 한동안 잘 돌다가 끊긴 경우는 새로 셈
+        if started.elapsed() >= STABLE_RUN {
+            rapid_failures = 0;
+        }
+        rapid_failures += 1;
+        if rapid_failures > cli.max_restarts {
+            tracing::error!("클라이언트가 {}번 연속으로 곧바로 종료되어 봇을 종료합니다", rapid_failures);
+            shutdown_state(&scheduler, &usage_stats, &pool).await;
+            std::process::exit(1);
+        }
+        let delay = RetryPolicy::CLIENT_RESTART.backoff(rapid_failures - 1);
+        tracing::warn!(
+            "{:.1}초 뒤 클라이언트를 다시 시작합니다 (연속 실패 {}/{})",
+            delay.as_secs_f64(),
+            rapid_failures,
+            cli.max_restarts
+        );
+        tokio::time::sleep(delay).await;
+    }
+    shutdown_state(&scheduler, &usage_stats, &pool).await;
+}
+
+// 이 시간 넘게 실행된 뒤 끊긴 클라이언트는 연속 실패로 세지 않음
+const STABLE_RUN: Duration = Duration::from_secs(300);
+
+// 종료 전 정리: 스케줄러를 멈추고 아직 저장하지 않은 사용 통계 저장
+async fn shutdown_state(scheduler: &Scheduler, usage_stats: &UsageState, pool: &SqlitePool) {
+    scheduler.shutdown();
+    if let Err(e) = usage_stats.flush(pool).await {
+        tracing::error!("사용 통계 저장 실패: {}", e);
+    }
+}
+
+// 영어 번역이 빠진 커맨드 문구가 있으면 등록 전에 알림 (번역 없는 부분은 한국어로 보임)
+fn warn_missing_localizations() {
+    let definitions: Vec<_> = commands::registry().iter().map(|spec| spec.create()).collect();
+    let missing = locale::check_localizations(&definitions);
+    if !missing.is_empty() {
+        tracing::warn!("영어 번역이 없는 커맨드 문구 {}개:\n  {}", missing.len(), missing.join("\n  "));
+    }
+}
+
+// `aurobot validate-config`: 설정 파일 문법과 키 이름 확인
+pub fn validate_config(cli: &Cli) {
+    let file_config = load_file_config(cli);
+    let unknown = file_config.unknown_keys();
+    if !unknown.is_empty() {
+        eprintln!("알 수 없는 설정 키: {}", unknown.join(", "));
+        std::process::exit(1);
+    }
+    println!("설정 파일에 문제가 없습니다");
+}
+
+// `aurobot register-commands [--guild <id>]`: 커맨드만 동기화하고 종료
+pub async fn register_commands(cli: &Cli, guild: Option<GuildId>) {
+    warn_missing_localizations();
+    let token = cli.token.clone().unwrap_or_default();
+    // 길드 커맨드는 서버에서 끈 커맨드를 빼야 하므로 길드 설정이 필요
+    let pool = open_database(cli).await;
+    let failed = Arc::new(AtomicBool::new(false));
+    let mut client = Client::builder(&token, GatewayIntents::GUILDS)
+        .event_handler(RegisterOnly { guild, failed: failed.clone() })
+        .type_map_insert::<Storage>(pool)
+        .type_map_insert::<CommandsRegistered>(Arc::new(RegistrationState::new(cli.force_register)))
+        .await
+        .expect("클라이언트 생성 실패");
+    client
+        .data
+        .write()
+        .await
+        .insert::<ShardManagerKey>(client.shard_manager.clone());
+    if let Err(why) = client.start().await {
+        eprintln!("클라이언트 에러: {:?}", why);
+        std::process::exit(1);
+    }
+    if failed.load(Ordering::Relaxed) {
+        eprintln!("일부 커맨드를 동기화하지 못했습니다");
+        std::process::exit(1);
+    }
+    println!("커맨드 동기화를 마쳤습니다");
+}
+
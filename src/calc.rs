@@ -6,23 +6,94 @@ enum Op {
     Sub,
     Mul,
     Div,
+    FloorDiv,
     Pow,
+    Mod,
+    // 단항 음수 부호
+    Neg,
+    // 후위 퍼센트 (x% = x / 100)
+    Percent,
 }
 
+// 지원하는 이항 연산자: (기호, 우선순위, 오른쪽 결합 여부). 우선순위가 클수록 먼저 계산
+pub const SUPPORTED_OPERATORS: &[(&str, u8, bool)] = &[
+    ("+", 1, false),
+    ("-", 1, false),
+    ("*", 2, false),
+    ("/", 2, false),
+    ("//", 2, false),
+    ("%", 2, false),
+    ("^", 4, true),
+];
+
+// 단항 '-'의 우선순위: 곱셈/나눗셈보다 먼저, 거듭제곱보다 나중 (-2^2 = -4, -7//2 = -4)
+const NEG_PRECEDENCE: u8 = 3;
+
+// 지원하는 함수: (이름, 인자 수)
+pub const SUPPORTED_FUNCTIONS: &[(&str, u8)] = &[("sqrt", 1), ("sin", 1), ("cos", 1), ("tan", 1)];
+
+// 이름으로 쓸 수 있는 상수 (constant에서 처리)
+pub const SUPPORTED_CONSTANTS: &[&str] = &["pi", "i"];
+
+// 사용자의 직전 계산 결과를 가리키는 이름 (UserCalcSession::last_answer)
+pub const ANSWER_NAMES: &[&str] = &["ans", "_"];
+
 impl Op {
+    fn info(self) -> (u8, bool) {
+        if self == Op::Neg {
+            return (NEG_PRECEDENCE, true);
+        }
+        // 후위 연산자는 연산자 스택에 올라가지 않으므로 우선순위를 쓰지 않음
+        if self == Op::Percent {
+            return (0, false);
+        }
+        SUPPORTED_OPERATORS
+            .iter()
+            .find(|(symbol, _, _)| *symbol == self.symbol())
+            .map(|&(_, precedence, right)| (precedence, right))
+            .unwrap_or((0, false))
+    }
+
     fn precedence(self) -> u8 {
+        self.info().0
+    }
+
+    fn is_right_associative(self) -> bool {
+        self.info().1
+    }
+
+    fn symbol(self) -> &'static str {
         match self {
-            Op::Add | Op::Sub => 1,
-            Op::Mul | Op::Div => 2,
-            Op::Pow => 3,
+            Op::Add => "+",
+            Op::Sub => "-",
+            Op::Mul => "*",
+            Op::Div => "/",
+            Op::FloorDiv => "//",
+            Op::Pow => "^",
+            Op::Mod | Op::Percent => "%",
+            Op::Neg => "-",
         }
     }
 
-    fn is_right_associative(self) -> bool {
-        matches!(self, Op::Pow)
+    // 풀이 설명에 사용하는 PEMDAS 규칙 이름
+    fn rule_name(self) -> &'static str {
+        match self {
+            Op::Pow => "거듭제곱(E)",
+            Op::Mul => "곱셈(M)",
+            Op::Div => "나눗셈(D)",
+            Op::FloorDiv => "정수 나눗셈(D)",
+            Op::Add => "덧셈(A)",
+            Op::Sub => "뺄셈(S)",
+            Op::Mod => "나머지(%)",
+            Op::Neg => "부호(-)",
+            Op::Percent => "퍼센트(%)",
+        }
     }
 }
 
+// 풀이 설명의 최대 줄 수
+const MAX_EXPLAIN_STEPS: usize = 20;
+
 #[derive(Debug, Clone, PartialEq)]
 enum Token {
     Number(f64),
@@ -31,18 +102,156 @@ enum Token {
     RParen,
     Ident(String),
     Func(String),
+    // 나머지인지 퍼센트인지 아직 정하지 않은 '%' (resolve_ambiguous_percent에서 결정)
+    PercentAmbiguous,
+}
+
+// 실수 전용 또는 복소수 허용 계산
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum NumberMode {
+    #[default]
+    Real,
+    Complex,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Complex {
+    pub re: f64,
+    pub im: f64,
+}
+
+impl Complex {
+    fn real(re: f64) -> Self {
+        Self { re, im: 0.0 }
+    }
+
+    fn is_real(self) -> bool {
+        self.im == 0.0
+    }
+
+    fn is_zero(self) -> bool {
+        self.re == 0.0 && self.im == 0.0
+    }
+
+    fn is_finite(self) -> bool {
+        self.re.is_finite() && self.im.is_finite()
+    }
+
+    fn add(self, o: Self) -> Self {
+        Self { re: self.re + o.re, im: self.im + o.im }
+    }
+
+    fn sub(self, o: Self) -> Self {
+        Self { re: self.re - o.re, im: self.im - o.im }
+    }
+
+    fn mul(self, o: Self) -> Self {
+        if self.is_real() && o.is_real() {
+            return Self::real(self.re * o.re);
+        }
+        Self {
+            re: self.re * o.re - self.im * o.im,
+            im: self.re * o.im + self.im * o.re,
+        }
+    }
+
+    fn div(self, o: Self) -> Self {
+        if self.is_real() && o.is_real() {
+            return Self::real(self.re / o.re);
+        }
+        let d = o.re * o.re + o.im * o.im;
+        Self {
+            re: (self.re * o.re + self.im * o.im) / d,
+            im: (self.im * o.re - self.re * o.im) / d,
+        }
+    }
+
+    fn abs(self) -> f64 {
+        self.re.hypot(self.im)
+    }
+
+    fn arg(self) -> f64 {
+        self.im.atan2(self.re)
+    }
+
+    fn from_polar(r: f64, theta: f64) -> Self {
+        Self { re: r * theta.cos(), im: r * theta.sin() }
+    }
+
+    fn sqrt(self) -> Self {
+        Self::from_polar(self.abs().sqrt(), self.arg() / 2.0)
+    }
+
+    fn sin(self) -> Self {
+        Self {
+            re: self.re.sin() * self.im.cosh(),
+            im: self.re.cos() * self.im.sinh(),
+        }
+    }
+
+    fn cos(self) -> Self {
+        Self {
+            re: self.re.cos() * self.im.cosh(),
+            im: -self.re.sin() * self.im.sinh(),
+        }
+    }
+
+    fn pow(self, o: Self) -> Self {
+        // 정수 지수는 반복 곱셈으로 계산해 i^2 = -1 처럼 정확한 값을 유지
+        if o.is_real() && o.re.fract() == 0.0 && o.re.abs() <= 64.0 {
+            let mut result = Self::real(1.0);
+            for _ in 0..(o.re.abs() as u32) {
+                result = result.mul(self);
+            }
+            return if o.re < 0.0 { Self::real(1.0).div(result) } else { result };
+        }
+        if self.is_real() && o.is_real() && self.re >= 0.0 {
+            return Self::real(self.re.powf(o.re));
+        }
+        if self.is_zero() {
+            return Self::real(0.0);
+        }
+        // a^b = exp(b * ln a)
+        let ln = Self { re: self.abs().ln(), im: self.arg() };
+        let e = o.mul(ln);
+        Self::from_polar(e.re.exp(), e.im)
+    }
+}
+
+impl fmt::Display for Complex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let re = format_float(self.re);
+        let im = format_float(self.im);
+        if im == "0" {
+            return write!(f, "{}", re);
+        }
+        let im_part = match im.as_str() {
+            "1" => "i".to_string(),
+            "-1" => "-i".to_string(),
+            _ => format!("{}i", im),
+        };
+        if re == "0" {
+            write!(f, "{}", im_part)
+        } else if im_part.starts_with('-') {
+            write!(f, "{}{}", re, im_part)
+        } else {
+            write!(f, "{}+{}", re, im_part)
+        }
+    }
 }
 
 #[derive(Debug)]
-struct ParseError(String);
+pub struct CalcError(String);
 
-impl fmt::Display for ParseError {
+impl fmt::Display for CalcError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.0)
     }
 }
 
-fn tokenize(input: &str) -> Result<Vec<Token>, ParseError> {
+impl std::error::Error for CalcError {}
+
+fn tokenize(input: &str) -> Result<Vec<Token>, CalcError> {
     let mut tokens: Vec<Token> = Vec::new();
     let mut chars = input.chars().peekable();
     let mut expect_unary = true;
@@ -55,7 +264,7 @@ fn tokenize(input: &str) -> Result<Vec<Token>, ParseError> {
 
         if ch.is_ascii_digit() || ch == '.' {
             let mut s = String::new();
-            let mut dot_seen = ch == '.';
+            let mut dot_seen = false;
             while let Some(&c) = chars.peek() {
                 if c.is_ascii_digit() {
                     s.push(c);
@@ -69,14 +278,19 @@ fn tokenize(input: &str) -> Result<Vec<Token>, ParseError> {
                     break;
                 }
             }
-            let num = s.parse::<f64>().map_err(|_| ParseError("잘못된 숫자 형식".to_string()))?;
+            let num = s.parse::<f64>().map_err(|_| CalcError("잘못된 숫자 형식".to_string()))?;
             tokens.push(Token::Number(num));
             expect_unary = false;
+
+            // 숫자 바로 뒤의 이름은 암묵적 곱셈: 2i -> 2 * i, 2pi -> 2 * pi
+            if chars.peek().is_some_and(|c| c.is_ascii_alphabetic()) {
+                tokens.push(Token::Op(Op::Mul));
+            }
             continue;
         }
 
-        // function or identifier
-        if ch.is_ascii_alphabetic() {
+        // function or identifier ('_'는 직전 결과)
+        if ch.is_ascii_alphabetic() || ch == '_' {
             let mut name = String::new();
             while let Some(&c) = chars.peek() {
                 if c.is_ascii_alphanumeric() || c == '_' {
@@ -86,8 +300,16 @@ fn tokenize(input: &str) -> Result<Vec<Token>, ParseError> {
                     break;
                 }
             }
-            tokens.push(Token::Ident(name));
-            expect_unary = true;
+            // 붙여 쓴 상수는 곱으로 분리: pipi -> pi * pi (분리할 수 없으면 그대로 두어 평가 시 오류)
+            let parts = split_constants(&name).unwrap_or_else(|| vec![name]);
+            for (i, part) in parts.into_iter().enumerate() {
+                if i > 0 {
+                    tokens.push(Token::Op(Op::Mul));
+                }
+                tokens.push(Token::Ident(part));
+            }
+            // 함수라면 바로 뒤에 '('가 오므로, 상수 뒤의 '-'는 이항 연산자로 취급
+            expect_unary = false;
             continue;
         }
 
@@ -100,9 +322,7 @@ fn tokenize(input: &str) -> Result<Vec<Token>, ParseError> {
             '-' => {
                 chars.next();
                 if expect_unary {
-                    // unary minus: treat as 0 - x
-                    tokens.push(Token::Number(0.0));
-                    tokens.push(Token::Op(Op::Sub));
+                    tokens.push(Token::Op(Op::Neg));
                 } else {
                     tokens.push(Token::Op(Op::Sub));
                     expect_unary = true;
@@ -110,12 +330,24 @@ fn tokenize(input: &str) -> Result<Vec<Token>, ParseError> {
             }
             '*' => {
                 chars.next();
-                tokens.push(Token::Op(Op::Mul));
+                // '**'는 '^'와 같은 거듭제곱 (파이썬 표기)
+                if chars.peek() == Some(&'*') {
+                    chars.next();
+                    tokens.push(Token::Op(Op::Pow));
+                } else {
+                    tokens.push(Token::Op(Op::Mul));
+                }
                 expect_unary = true;
             }
             '/' => {
                 chars.next();
-                tokens.push(Token::Op(Op::Div));
+                // '//'는 음의 무한대 방향으로 내림하는 정수 나눗셈
+                if chars.peek() == Some(&'/') {
+                    chars.next();
+                    tokens.push(Token::Op(Op::FloorDiv));
+                } else {
+                    tokens.push(Token::Op(Op::Div));
+                }
                 expect_unary = true;
             }
             '^' => {
@@ -123,6 +355,12 @@ fn tokenize(input: &str) -> Result<Vec<Token>, ParseError> {
                 tokens.push(Token::Op(Op::Pow));
                 expect_unary = true;
             }
+            '%' => {
+                chars.next();
+                tokens.push(Token::PercentAmbiguous);
+                // 뒤의 '-'는 뺄셈 (50% - 3). 나머지로 정해지면 음수 피연산자는 괄호로: 5 % (-3)
+                expect_unary = false;
+            }
             '(' => {
                 chars.next();
                 tokens.push(Token::LParen);
@@ -134,26 +372,72 @@ fn tokenize(input: &str) -> Result<Vec<Token>, ParseError> {
                 expect_unary = false;
             }
             _ => {
-                return Err(ParseError(format!("알 수 없는 문자: {}", ch)));
+                return Err(CalcError(format!("알 수 없는 문자: {}", ch)));
             }
         }
     }
 
-    Ok(tokens)
+    Ok(resolve_ambiguous_percent(tokens))
 }
 
-fn to_rpn(tokens: &[Token]) -> Result<Vec<Token>, ParseError> {
+// '%' 뒤에 피연산자가 오면(5 % 3, 10 % (4), 7 % pi) 나머지, 식의 끝이나 연산자/닫는 괄호
+// 앞이면(50%, 200 * 15% + 1) 퍼센트
+fn resolve_ambiguous_percent(tokens: Vec<Token>) -> Vec<Token> {
+    let operand_follows: Vec<bool> = (0..tokens.len())
+        .map(|i| {
+            matches!(
+                tokens.get(i + 1),
+                Some(Token::Number(_) | Token::Ident(_) | Token::Func(_) | Token::LParen)
+            )
+        })
+        .collect();
+    tokens
+        .into_iter()
+        .zip(operand_follows)
+        .map(|(token, operand_follows)| match token {
+            Token::PercentAmbiguous if operand_follows => Token::Op(Op::Mod),
+            Token::PercentAmbiguous => Token::Op(Op::Percent),
+            other => other,
+        })
+        .collect()
+}
+
+// 이름 하나를 알려진 상수들의 연속으로 분리. 함수/상수 이름 그대로이거나 분리할 수 없으면 None
+fn split_constants(name: &str) -> Option<Vec<String>> {
+    if SUPPORTED_FUNCTIONS.iter().any(|(f, _)| *f == name) || SUPPORTED_CONSTANTS.contains(&name) {
+        return None;
+    }
+    fn split(rest: &str) -> Option<Vec<String>> {
+        if rest.is_empty() {
+            return Some(Vec::new());
+        }
+        SUPPORTED_CONSTANTS.iter().filter(|c| rest.starts_with(**c)).find_map(|c| {
+            let mut parts = split(&rest[c.len()..])?;
+            parts.insert(0, c.to_string());
+            Some(parts)
+        })
+    }
+    split(name)
+}
+
+fn to_rpn(tokens: &[Token]) -> Result<Vec<Token>, CalcError> {
     let mut output: Vec<Token> = Vec::new();
     let mut ops: Vec<Token> = Vec::new();
 
-    for token in tokens.iter().cloned() {
+    for (i, token) in tokens.iter().cloned().enumerate() {
         match token {
             Token::Number(_) => output.push(token),
-            Token::Ident(_) => ops.push(token),
+            // 바로 뒤에 '('가 오면 함수, 아니면 상수
+            Token::Ident(_) if tokens.get(i + 1) == Some(&Token::LParen) => ops.push(token),
+            Token::Ident(_) => output.push(token),
             Token::Func(name) => {
                 // 함수 토큰이 입력에 직접 등장할 일은 없지만, 안전하게 출력으로 전달
                 output.push(Token::Func(name));
             }
+            // 단항 연산자는 앞에 피연산자가 없으므로 스택에서 꺼내지 않고 바로 쌓음
+            Token::Op(Op::Neg) => ops.push(token),
+            // 후위 연산자는 바로 앞 피연산자(또는 괄호 묶음)에 적용되므로 곧바로 출력
+            Token::Op(Op::Percent) => output.push(token),
             Token::Op(op1) => {
                 while let Some(Token::Op(op2)) = ops.last().cloned() {
                     if (op1.precedence() < op2.precedence())
@@ -166,9 +450,14 @@ fn to_rpn(tokens: &[Token]) -> Result<Vec<Token>, ParseError> {
                 }
                 ops.push(Token::Op(op1));
             }
+            Token::PercentAmbiguous => return Err(CalcError("잘못된 % 위치".to_string())),
             Token::LParen => ops.push(Token::LParen),
             Token::RParen => {
-                while let Some(top) = ops.pop() {
+                loop {
+                    // 여는 괄호 없이 닫는 괄호가 나오면 오류 (1+2))
+                    let Some(top) = ops.pop() else {
+                        return Err(CalcError("괄호가 올바르지 않습니다".to_string()));
+                    };
                     if let Token::LParen = top {
                         // If there is a function token on top, move to output
                         if let Some(Token::Ident(name)) = ops.last().cloned() {
@@ -186,7 +475,7 @@ fn to_rpn(tokens: &[Token]) -> Result<Vec<Token>, ParseError> {
 
     while let Some(top) = ops.pop() {
         match top {
-            Token::LParen | Token::RParen => return Err(ParseError("괄호가 올바르지 않습니다".to_string())),
+            Token::LParen | Token::RParen => return Err(CalcError("괄호가 올바르지 않습니다".to_string())),
             _ => output.push(top),
         }
     }
@@ -194,66 +483,725 @@ fn to_rpn(tokens: &[Token]) -> Result<Vec<Token>, ParseError> {
     Ok(output)
 }
 
-fn eval_rpn(rpn: &[Token]) -> Result<f64, ParseError> {
-    let mut stack: Vec<f64> = Vec::new();
+fn eval_rpn(rpn: &[Token], mode: NumberMode, ans: Option<Complex>) -> Result<Complex, CalcError> {
+    eval_rpn_traced(rpn, mode, ans, None)
+}
+
+// 계산하면서 각 단계를 사람이 읽을 수 있는 문장으로 기록 (trace가 있을 때만)
+fn eval_rpn_traced(
+    rpn: &[Token],
+    mode: NumberMode,
+    ans: Option<Complex>,
+    mut trace: Option<&mut Vec<String>>,
+) -> Result<Complex, CalcError> {
+    let mut stack: Vec<Complex> = Vec::new();
     for token in rpn.iter().cloned() {
         match token {
-            Token::Number(n) => stack.push(n),
+            Token::Number(n) => stack.push(Complex::real(n)),
+            Token::Ident(name) => stack.push(constant(&name, mode, ans)?),
             Token::Func(name) => {
-                let x = stack.pop().ok_or_else(|| ParseError("피연산자가 부족합니다".to_string()))?;
-                let v = match name.as_str() {
-                    "sqrt" => {
-                        if x < 0.0 { return Err(ParseError("sqrt의 입력은 음수가 될 수 없습니다".to_string())); }
-                        x.sqrt()
-                    }
-                    "sin" => x.sin(),
-                    "cos" => x.cos(),
-                    "tan" => x.tan(),
-                    _ => return Err(ParseError(format!("알 수 없는 함수: {}", name))),
+                // 지원 목록에 없는 이름은 피연산자를 꺼내기 전에 거부
+                let Some(&(_, arity)) = SUPPORTED_FUNCTIONS.iter().find(|(f, _)| *f == name) else {
+                    return Err(CalcError(format!("알 수 없는 함수: {}", name)));
                 };
-                if v.is_finite() { stack.push(v); } else { return Err(ParseError("유효하지 않은 결과".to_string())); }
+                if stack.len() < usize::from(arity) {
+                    return Err(CalcError("피연산자가 부족합니다".to_string()));
+                }
+                let x = stack.pop().ok_or_else(|| CalcError("피연산자가 부족합니다".to_string()))?;
+                let v = apply_func(&name, x, mode)?;
+                if !v.is_finite() { return Err(CalcError("유효하지 않은 결과".to_string())); }
+                if let Some(steps) = trace.as_deref_mut() {
+                    steps.push(format!("함수 {} 적용: {}({}) = {}", name, name, x, v));
+                }
+                stack.push(v);
+            }
+            Token::Op(Op::Neg) => {
+                let x = stack.pop().ok_or_else(|| CalcError("피연산자가 부족합니다".to_string()))?;
+                let v = Complex::real(0.0).sub(x);
+                if let Some(steps) = trace.as_deref_mut() {
+                    steps.push(format!("{} 적용: -({}) = {}", Op::Neg.rule_name(), x, v));
+                }
+                stack.push(v);
+            }
+            Token::Op(Op::Percent) => {
+                let x = stack.pop().ok_or_else(|| CalcError("피연산자가 부족합니다".to_string()))?;
+                let v = x.div(Complex::real(100.0));
+                if let Some(steps) = trace.as_deref_mut() {
+                    steps.push(format!("{} 적용: {}% = {}", Op::Percent.rule_name(), x, v));
+                }
+                stack.push(v);
             }
             Token::Op(op) => {
-                let b = stack.pop().ok_or_else(|| ParseError("피연산자가 부족합니다".to_string()))?;
-                let a = stack.pop().ok_or_else(|| ParseError("피연산자가 부족합니다".to_string()))?;
+                let b = stack.pop().ok_or_else(|| CalcError("피연산자가 부족합니다".to_string()))?;
+                let a = stack.pop().ok_or_else(|| CalcError("피연산자가 부족합니다".to_string()))?;
                 let v = match op {
-                    Op::Add => a + b,
-                    Op::Sub => a - b,
-                    Op::Mul => a * b,
+                    Op::Add => a.add(b),
+                    Op::Sub => a.sub(b),
+                    Op::Mul => a.mul(b),
                     Op::Div => {
-                        if b == 0.0 {
-                            return Err(ParseError("0으로 나눌 수 없습니다".to_string()));
+                        if b.is_zero() {
+                            return Err(CalcError("0으로 나눌 수 없습니다".to_string()));
+                        }
+                        a.div(b)
+                    }
+                    Op::FloorDiv => {
+                        if b.is_zero() {
+                            return Err(CalcError("0으로 나눌 수 없습니다".to_string()));
+                        }
+                        if a.im != 0.0 || b.im != 0.0 {
+                            return Err(CalcError("// 는 실수에서만 사용할 수 있습니다".to_string()));
                         }
-                        a / b
+                        Complex::real((a.re / b.re).floor())
                     }
-                    Op::Pow => a.powf(b),
+                    // //와 맞춰 나누는 수의 부호를 따름: -7 % 3 = 2
+                    Op::Mod => {
+                        if b.is_zero() {
+                            return Err(CalcError("0으로 나눌 수 없습니다".to_string()));
+                        }
+                        if a.im != 0.0 || b.im != 0.0 {
+                            return Err(CalcError("% 는 실수에서만 사용할 수 있습니다".to_string()));
+                        }
+                        Complex::real(a.re - b.re * (a.re / b.re).floor())
+                    }
+                    Op::Neg | Op::Percent => return Err(CalcError("RPN 단계에서 잘못된 토큰".to_string())),
+                    Op::Pow => match mode {
+                        NumberMode::Real => Complex::real(a.re.powf(b.re)),
+                        NumberMode::Complex => a.pow(b),
+                    },
                 };
+                // 10^400 같은 넘침이나 (-8)^(1/3) 같은 실수 범위 밖의 결과
+                if !v.is_finite() {
+                    return Err(CalcError("유효하지 않은 결과".to_string()));
+                }
+                if let Some(steps) = trace.as_deref_mut() {
+                    steps.push(format!(
+                        "{} 적용: {} {} {} = {}",
+                        op.rule_name(),
+                        a,
+                        op.symbol(),
+                        b,
+                        v
+                    ));
+                }
                 stack.push(v);
             }
-            Token::LParen | Token::RParen => {
-                return Err(ParseError("RPN 단계에서 잘못된 토큰".to_string()));
+            Token::LParen | Token::RParen | Token::PercentAmbiguous => {
+                return Err(CalcError("RPN 단계에서 잘못된 토큰".to_string()));
             }
-            Token::Ident(_) => unreachable!("Ident는 변환 단계에서만 사용됩니다"),
         }
     }
     if stack.len() != 1 {
-        return Err(ParseError("표현식이 올바르지 않습니다".to_string()));
+        return Err(CalcError("표현식이 올바르지 않습니다".to_string()));
     }
     Ok(stack[0])
 }
 
+// 이름으로 상수 값 조회
+fn constant(name: &str, mode: NumberMode, ans: Option<Complex>) -> Result<Complex, CalcError> {
+    if ANSWER_NAMES.contains(&name) {
+        return ans.ok_or_else(|| CalcError(format!("{}: 이전 계산 결과가 없습니다", name)));
+    }
+    match (name, mode) {
+        ("pi", _) => Ok(Complex::real(std::f64::consts::PI)),
+        ("i", NumberMode::Complex) => Ok(Complex { re: 0.0, im: 1.0 }),
+        ("i", NumberMode::Real) => Err(CalcError(
+            "허수 i는 복소수 모드에서만 사용할 수 있습니다 (/calcmode)".to_string(),
+        )),
+        _ => Err(CalcError(format!("알 수 없는 상수: {}", name))),
+    }
+}
+
+fn apply_func(name: &str, x: Complex, mode: NumberMode) -> Result<Complex, CalcError> {
+    if x.is_real() {
+        let r = x.re;
+        return match name {
+            "sqrt" => {
+                if r >= 0.0 {
+                    Ok(Complex::real(r.sqrt()))
+                } else if mode == NumberMode::Complex {
+                    Ok(Complex { re: 0.0, im: (-r).sqrt() })
+                } else {
+                    Err(CalcError("sqrt의 입력은 음수가 될 수 없습니다".to_string()))
+                }
+            }
+            "sin" => Ok(Complex::real(r.sin())),
+            "cos" => Ok(Complex::real(r.cos())),
+            "tan" => Ok(Complex::real(r.tan())),
+            _ => Err(CalcError(format!("알 수 없는 함수: {}", name))),
+        };
+    }
+
+    // 허수부가 있는 값은 복소수 모드에서만 만들어짐
+    match name {
+        "sqrt" => Ok(x.sqrt()),
+        "sin" => Ok(x.sin()),
+        "cos" => Ok(x.cos()),
+        "tan" => {
+            let c = x.cos();
+            if c.is_zero() {
+                return Err(CalcError("유효하지 않은 결과".to_string()));
+            }
+            Ok(x.sin().div(c))
+        }
+        _ => Err(CalcError(format!("알 수 없는 함수: {}", name))),
+    }
+}
+
+// 기간 리터럴 단위: (접미사, 초)
+const DURATION_UNITS: &[(char, u64)] = &[('d', 86400), ('h', 3600), ('m', 60), ('s', 1)];
+
+// "1h30m", "2d", "45s" 같은 기간 리터럴을 초로 변환. 단위는 큰 것부터 한 번씩만 쓸 수 있음
+pub fn parse_duration_literal(input: &str) -> Result<u64, CalcError> {
+    let input = input.trim().to_ascii_lowercase();
+    if input.is_empty() {
+        return Err(CalcError("기간이 비어 있습니다".to_string()));
+    }
+    let mut total: u64 = 0;
+    let mut number = String::new();
+    let mut last_unit = None;
+    for c in input.chars() {
+        if c.is_ascii_digit() {
+            number.push(c);
+            continue;
+        }
+        let Some(index) = DURATION_UNITS.iter().position(|&(unit, _)| unit == c) else {
+            return Err(CalcError(format!("알 수 없는 기간 단위: {}", c)));
+        };
+        if number.is_empty() || last_unit.is_some_and(|last| index <= last) {
+            return Err(CalcError(format!("잘못된 기간: {}", input)));
+        }
+        let value: u64 = number
+            .parse()
+            .map_err(|_| CalcError(format!("잘못된 기간: {}", input)))?;
+        total = value
+            .checked_mul(DURATION_UNITS[index].1)
+            .and_then(|secs| total.checked_add(secs))
+            .ok_or_else(|| CalcError("기간이 너무 깁니다".to_string()))?;
+        number.clear();
+        last_unit = Some(index);
+    }
+    if !number.is_empty() {
+        return Err(CalcError(format!("기간에 단위가 없습니다: {}", input)));
+    }
+    Ok(total)
+}
+
 pub fn evaluate(expression: &str) -> Result<String, String> {
+    evaluate_in_mode(expression, NumberMode::Real)
+}
+
+pub fn evaluate_in_mode(expression: &str, mode: NumberMode) -> Result<String, String> {
+    evaluate_value(expression, mode, None).map(|v| v.to_string())
+}
+
+// ans / _ 를 직전 결과로 두고 계산. 직전 결과가 없으면 ans를 쓴 식은 오류
+pub fn evaluate_value(expression: &str, mode: NumberMode, ans: Option<Complex>) -> Result<Complex, String> {
     let tokens = tokenize(expression).map_err(|e| e.to_string())?;
     let rpn = to_rpn(&tokens).map_err(|e| e.to_string())?;
-    let v = eval_rpn(&rpn).map_err(|e| e.to_string())?;
-    Ok(format_float(v))
+    eval_rpn(&rpn, mode, ans).map_err(|e| e.to_string())
+}
+
+// 식에 ans / _ 가 들어 있는지 (결과가 사용자마다 달라 캐시하면 안 되는 식)
+pub fn uses_answer(expression: &str) -> bool {
+    tokenize(expression).is_ok_and(|tokens| {
+        tokens
+            .iter()
+            .any(|t| matches!(t, Token::Ident(name) if ANSWER_NAMES.contains(&name.as_str())))
+    })
 }
 
+// 계산 과정을 단계별로 설명 (최대 MAX_EXPLAIN_STEPS 줄)
+pub fn explain(expression: &str, mode: NumberMode, ans: Option<Complex>) -> Result<Vec<String>, CalcError> {
+    let tokens = tokenize(expression)?;
+    let rpn = to_rpn(&tokens)?;
+    let mut steps = Vec::new();
+    let v = eval_rpn_traced(&rpn, mode, ans, Some(&mut steps))?;
+
+    if steps.len() > MAX_EXPLAIN_STEPS {
+        let omitted = steps.len() - (MAX_EXPLAIN_STEPS - 1);
+        steps.truncate(MAX_EXPLAIN_STEPS - 1);
+        steps.push(format!("… {}단계 생략, 최종 결과: {}", omitted, v));
+    } else if steps.is_empty() {
+        steps.push(format!("계산할 연산이 없습니다: {}", v));
+    }
+    Ok(steps)
+}
+
+// 결과가 이 상수들과 이만큼 가까우면 응답에 설명을 붙임
+const CONSTANT_TOLERANCE: f64 = 1e-10;
+// (값, 설명). 무리수는 근삿값이라 ≈, 유리수는 =
+const KNOWN_CONSTANTS: &[(f64, &str)] = &[
+    (std::f64::consts::PI, "≈ π"),
+    (std::f64::consts::TAU, "≈ 2π"),
+    (std::f64::consts::FRAC_PI_2, "≈ π/2"),
+    (std::f64::consts::FRAC_PI_3, "≈ π/3"),
+    (std::f64::consts::FRAC_PI_4, "≈ π/4"),
+    (std::f64::consts::FRAC_PI_6, "≈ π/6"),
+    (std::f64::consts::E, "≈ e"),
+    (std::f64::consts::SQRT_2, "≈ √2"),
+    (std::f64::consts::FRAC_1_SQRT_2, "≈ √2/2"),
+    (1.732_050_807_568_877_2, "≈ √3"),
+    (1.618_033_988_749_895, "≈ φ"),
+    (std::f64::consts::LN_2, "≈ ln(2)"),
+    (1.0 / 3.0, "= 1/3"),
+    (2.0 / 3.0, "= 2/3"),
+    (1.0 / 6.0, "= 1/6"),
+    (5.0 / 6.0, "= 5/6"),
+];
+
+// 결과가 잘 알려진 상수와 거의 같으면 "≈ π/4", "= 1/3" 같은 설명
+pub fn approx_constant_name(v: f64) -> Option<&'static str> {
+    KNOWN_CONSTANTS
+        .iter()
+        .find(|(constant, _)| (v - constant).abs() < CONSTANT_TOLERANCE)
+        .map(|(_, name)| *name)
+}
+
+// 소수 12자리에서 반올림한 뒤 그 값의 가장 짧은 표기로 출력.
+// 자릿수를 고정해 자르면 큰 수의 오차가 드러남 (123456.789 -> 123456.789000000004)
 fn format_float(v: f64) -> String {
-    if v == 0.0 { return "0".to_string(); }
-    let s = format!("{:.12}", v);
-    let s = s.trim_end_matches('0').trim_end_matches('.').to_string();
-    if s == "-0" { "0".to_string() } else { s }
+    let rounded: f64 = format!("{:.12}", v).parse().unwrap_or(v);
+    // -0.000000000001 처럼 반올림하면 -0이 되는 값도 0
+    if rounded == 0.0 { "0".to_string() } else { rounded.to_string() }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn explain_follows_pemdas_order() {
+        let steps = explain("2 + 3 * 4", NumberMode::Real, None).unwrap();
+        assert_eq!(steps, vec!["곱셈(M) 적용: 3 * 4 = 12", "덧셈(A) 적용: 2 + 12 = 14"]);
+    }
+
+    #[test]
+    fn explain_caps_steps() {
+        let expression = format!("1{}", "+1".repeat(30));
+        let steps = explain(&expression, NumberMode::Real, None).unwrap();
+        assert_eq!(steps.len(), MAX_EXPLAIN_STEPS);
+        assert_eq!(steps.last().unwrap(), "… 11단계 생략, 최종 결과: 31");
+    }
 
+    fn value(expression: &str) -> Complex {
+        evaluate_value(expression, NumberMode::Real, None).unwrap()
+    }
+
+    fn ident(name: &str) -> Token {
+        Token::Ident(name.to_string())
+    }
+
+    #[test]
+    fn number_before_constant_is_multiplication() {
+        let tokens = tokenize("2pi").unwrap();
+        assert_eq!(tokens, vec![Token::Number(2.0), Token::Op(Op::Mul), ident("pi")]);
+        assert_eq!(value("2pi").re, 2.0 * std::f64::consts::PI);
+    }
+
+    #[test]
+    fn joined_constants_are_split() {
+        let tokens = tokenize("pipi").unwrap();
+        assert_eq!(tokens, vec![ident("pi"), Token::Op(Op::Mul), ident("pi")]);
+        assert_eq!(value("pipi").re, std::f64::consts::PI * std::f64::consts::PI);
+        // pi/2는 p, i로 나누지 않음
+        assert_eq!(
+            tokenize("pi/2").unwrap(),
+            vec![ident("pi"), Token::Op(Op::Div), Token::Number(2.0)]
+        );
+    }
+
+    #[test]
+    fn unknown_identifier_is_error() {
+        // pi 뒤의 e는 상수가 아니므로 나누지 않고 통째로 알 수 없는 이름
+        assert_eq!(tokenize("pie").unwrap(), vec![ident("pie")]);
+        assert_eq!(evaluate("pie"), Err("알 수 없는 상수: pie".to_string()));
+        assert_eq!(evaluate("2pie"), Err("알 수 없는 상수: pie".to_string()));
+    }
+
+    #[test]
+    fn every_supported_function_is_handled() {
+        for &(name, arity) in SUPPORTED_FUNCTIONS {
+            let mut rpn = vec![Token::Number(0.5); usize::from(arity)];
+            rpn.push(Token::Func(name.to_string()));
+            for mode in [NumberMode::Real, NumberMode::Complex] {
+                assert!(eval_rpn(&rpn, mode, None).is_ok(), "{} ({:?})", name, mode);
+            }
+            // 식으로 입력해도 함수로 인식
+            assert!(evaluate(&format!("{}(0.5)", name)).is_ok(), "{}", name);
+        }
+    }
+
+    #[test]
+    fn unsupported_function_is_rejected() {
+        let rpn = vec![Token::Number(1.0), Token::Func("log".to_string())];
+        assert_eq!(eval_rpn(&rpn, NumberMode::Real, None).unwrap_err().to_string(), "알 수 없는 함수: log");
+        assert_eq!(evaluate("log(1)"), Err("알 수 없는 함수: log".to_string()));
+        // 인자가 부족하면 오류
+        let rpn = vec![Token::Func("sqrt".to_string())];
+        assert!(eval_rpn(&rpn, NumberMode::Real, None).is_err());
+    }
+
+    #[test]
+    fn floor_division_rounds_toward_negative_infinity() {
+        assert_eq!(evaluate("7//2"), Ok("3".to_string()));
+        assert_eq!(evaluate("-7//2"), Ok("-4".to_string()));
+        assert_eq!(evaluate("7//-2"), Ok("-4".to_string()));
+        assert_eq!(evaluate("-7//-2"), Ok("3".to_string()));
+        assert_eq!(evaluate("7.5//2"), Ok("3".to_string()));
+        // / 와 같은 우선순위, 왼쪽 결합
+        assert_eq!(evaluate("20//3//2"), Ok("3".to_string()));
+        assert_eq!(evaluate("1 + 7//2 * 2"), Ok("7".to_string()));
+        assert_eq!(tokenize("7//2").unwrap()[1], Token::Op(Op::FloorDiv));
+    }
+
+    #[test]
+    fn floor_division_errors() {
+        assert_eq!(evaluate("7//0"), Err("0으로 나눌 수 없습니다".to_string()));
+        assert_eq!(
+            evaluate_in_mode("i//2", NumberMode::Complex),
+            Err("// 는 실수에서만 사용할 수 있습니다".to_string())
+        );
+    }
+
+    #[test]
+    fn double_star_is_power() {
+        assert_eq!(evaluate("2**8"), Ok("256".to_string()));
+        assert_eq!(value("2**0.5").re, 2f64.sqrt());
+        assert_eq!(evaluate("2**-3"), Ok("0.125".to_string()));
+        // ^ 와 같이 오른쪽 결합
+        assert_eq!(evaluate("2**3**2"), Ok("512".to_string()));
+        assert_eq!(tokenize("2**8").unwrap(), tokenize("2^8").unwrap());
+    }
+
+    #[test]
+    fn triple_star_is_error() {
+        // ** 다음의 * 는 피연산자가 없는 곱셈
+        assert_eq!(
+            tokenize("2***3").unwrap(),
+            vec![Token::Number(2.0), Token::Op(Op::Pow), Token::Op(Op::Mul), Token::Number(3.0)]
+        );
+        assert!(evaluate("2***3").is_err());
+    }
+
+    // (식, 기대 결과). Err(())는 오류 문구와 상관없이 실패해야 하는 식
+    type Case = (&'static str, Result<&'static str, ()>);
+
+    fn check(cases: &[Case]) {
+        for &(expression, expected) in cases {
+            let actual = evaluate(expression);
+            match expected {
+                Ok(v) => assert_eq!(actual.as_deref(), Ok(v), "{}", expression),
+                Err(()) => assert!(actual.is_err(), "{} => {:?}", expression, actual),
+            }
+        }
+    }
+
+    #[test]
+    fn table_arithmetic() {
+        check(&[
+            ("1+2", Ok("3")),
+            ("5-8", Ok("-3")),
+            ("2*3.5", Ok("7")),
+            ("7/2", Ok("3.5")),
+            ("1/3", Ok("0.333333333333")),
+            ("3 +   4", Ok("7")),
+            ("0.1+0.2", Ok("0.3")),
+            (".5+.5", Ok("1")),
+            ("2*-3", Ok("-6")),
+            ("-(-(-1))", Ok("-1")),
+            ("--3", Ok("3")),
+        ]);
+    }
+
+    #[test]
+    fn table_precedence() {
+        check(&[
+            ("2+3*4", Ok("14")),
+            ("2*3+4*5", Ok("26")),
+            ("10-4-3", Ok("3")),
+            ("64/4/2", Ok("8")),
+            ("1+2*3^2", Ok("19")),
+            ("-2^2", Ok("-4")),
+            ("2^3^2", Ok("512")),
+            ("2^-1", Ok("0.5")),
+            ("0^0", Ok("1")),
+        ]);
+    }
+
+    #[test]
+    fn table_parentheses() {
+        check(&[
+            ("(2+3)*4", Ok("20")),
+            ("((1+2))", Ok("3")),
+            ("((((((1))))))", Ok("1")),
+            ("(1+2)*(3+4)", Ok("21")),
+            ("2*(3+(4-1))*2", Ok("24")),
+            ("(-2)^2", Ok("4")),
+            ("(1+2", Err(())),
+            ("1+2)", Err(())),
+            ("()", Err(())),
+            ("sqrt(4", Err(())),
+        ]);
+    }
+
+    #[test]
+    fn table_modulo_and_percent() {
+        check(&[
+            ("10%3", Ok("1")),
+            ("-7%3", Ok("2")),
+            ("5.5%2", Ok("1.5")),
+            ("10%(4)", Ok("2")),
+            ("50%", Ok("0.5")),
+            ("5%", Ok("0.05")),
+            ("200*15%", Ok("30")),
+            ("50%-3", Ok("-2.5")),
+            ("100%%", Ok("0.01")),
+            ("5%0", Err(())),
+        ]);
+    }
+
+    #[test]
+    fn table_functions() {
+        check(&[
+            ("sqrt(16)", Ok("4")),
+            ("sqrt(sqrt(16))", Ok("2")),
+            ("sqrt(2)^2", Ok("2")),
+            ("sin(0)", Ok("0")),
+            ("cos(0)", Ok("1")),
+            ("tan(0)", Ok("0")),
+            ("sin(pi/2)", Ok("1")),
+            ("cos(pi)", Ok("-1")),
+            ("sqrt(-1)", Err(())),
+            ("sin()", Err(())),
+            ("sin", Err(())),
+            ("log(10)", Err(())),
+        ]);
+    }
+
+    #[test]
+    fn table_constants() {
+        check(&[
+            ("pi", Ok("3.14159265359")),
+            ("2pi", Ok("6.28318530718")),
+            ("pi*2", Ok("6.28318530718")),
+            ("i", Err(())),
+            ("abc", Err(())),
+            ("pi(2)", Err(())),
+            ("ans", Err(())),
+            ("1e5", Err(())),
+        ]);
+    }
+
+    #[test]
+    fn table_malformed_input() {
+        check(&[
+            ("", Err(())),
+            ("   ", Err(())),
+            ("1+", Err(())),
+            ("*2", Err(())),
+            ("2 3", Err(())),
+            ("1..2", Err(())),
+            ("1.2.3", Err(())),
+            ("1 $ 2", Err(())),
+            ("1/0", Err(())),
+            ("1/(1-1)", Err(())),
+        ]);
+    }
+
+    #[test]
+    fn table_overflow() {
+        check(&[
+            ("10^400", Err(())),
+            ("10^308*10", Err(())),
+            ("(-8)^(1/3)", Err(())),
+            ("10^15", Ok("1000000000000000")),
+        ]);
+    }
+
+    #[test]
+    fn tokenize_reads_operators_and_numbers() {
+        assert_eq!(
+            tokenize("1.5 // -2").unwrap(),
+            vec![Token::Number(1.5), Token::Op(Op::FloorDiv), Token::Op(Op::Neg), Token::Number(2.0)]
+        );
+        assert_eq!(tokenize("(.5)").unwrap(), vec![Token::LParen, Token::Number(0.5), Token::RParen]);
+        assert_eq!(tokenize("").unwrap(), Vec::new());
+        assert_eq!(tokenize("2 # 3").unwrap_err().to_string(), "알 수 없는 문자: #");
+    }
+
+    #[test]
+    fn to_rpn_orders_by_precedence() {
+        let rpn = to_rpn(&tokenize("2+3*4").unwrap()).unwrap();
+        assert_eq!(
+            rpn,
+            vec![Token::Number(2.0), Token::Number(3.0), Token::Number(4.0), Token::Op(Op::Mul), Token::Op(Op::Add)]
+        );
+        let rpn = to_rpn(&tokenize("sqrt(4)").unwrap()).unwrap();
+        assert_eq!(rpn, vec![Token::Number(4.0), Token::Func("sqrt".to_string())]);
+        assert!(to_rpn(&tokenize("(1").unwrap()).is_err());
+        assert!(to_rpn(&tokenize("1)").unwrap()).is_err());
+    }
+
+    #[test]
+    fn eval_rpn_rejects_malformed_stacks() {
+        let mode = NumberMode::Real;
+        assert!(eval_rpn(&[], mode, None).is_err());
+        assert!(eval_rpn(&[Token::Op(Op::Add)], mode, None).is_err());
+        assert!(eval_rpn(&[Token::Number(1.0), Token::Number(2.0)], mode, None).is_err());
+        assert!(eval_rpn(&[Token::LParen], mode, None).is_err());
+        assert!(eval_rpn(&[Token::PercentAmbiguous], mode, None).is_err());
+        let ans = Some(Complex::real(4.0));
+        assert_eq!(eval_rpn(&[Token::Ident("ans".to_string())], mode, ans).unwrap(), Complex::real(4.0));
+    }
+
+    // 식에서 '%'가 어떻게 정해졌는지 (Mod 또는 Percent) 순서대로
+    fn percent_kinds(expression: &str) -> Vec<Op> {
+        tokenize(expression)
+            .unwrap()
+            .into_iter()
+            .filter_map(|token| match token {
+                Token::Op(op @ (Op::Mod | Op::Percent)) => Some(op),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn percent_before_operand_is_modulo() {
+        assert_eq!(percent_kinds("5 % 3"), vec![Op::Mod]);
+        assert_eq!(percent_kinds("10%(4)"), vec![Op::Mod]);
+        assert_eq!(percent_kinds("7 % pi"), vec![Op::Mod]);
+        assert_eq!(percent_kinds("10 % sqrt(9)"), vec![Op::Mod]);
+    }
+
+    #[test]
+    fn percent_at_end_or_before_operator_is_percentage() {
+        assert_eq!(percent_kinds("50%"), vec![Op::Percent]);
+        assert_eq!(percent_kinds("200 * 15% + 1"), vec![Op::Percent]);
+        assert_eq!(percent_kinds("(50%)*2"), vec![Op::Percent]);
+        // 뒤의 '-'는 뺄셈이므로 퍼센트
+        assert_eq!(percent_kinds("50% - 3"), vec![Op::Percent]);
+        assert_eq!(percent_kinds("100%%"), vec![Op::Percent, Op::Percent]);
+    }
+
+    #[test]
+    fn mixed_percent_and_modulo() {
+        assert_eq!(percent_kinds("50% % 3"), vec![Op::Percent, Op::Mod]);
+        assert_eq!(evaluate("50% % 3"), Ok("0.5".to_string()));
+        assert_eq!(percent_kinds("10 % 4%"), vec![Op::Mod, Op::Percent]);
+        assert_eq!(evaluate("10 % 4%"), Ok("0".to_string()));
+        assert_eq!(evaluate("200 * 15% + 1"), Ok("31".to_string()));
+    }
+
+    #[test]
+    fn resolve_leaves_other_tokens_alone() {
+        let tokens = vec![Token::Number(5.0), Token::PercentAmbiguous, Token::Number(3.0), Token::PercentAmbiguous];
+        assert_eq!(
+            resolve_ambiguous_percent(tokens),
+            vec![Token::Number(5.0), Token::Op(Op::Mod), Token::Number(3.0), Token::Op(Op::Percent)]
+        );
+        assert_eq!(resolve_ambiguous_percent(Vec::new()), Vec::new());
+    }
+
+    #[test]
+    fn format_float_cases() {
+        let cases: &[(f64, &str)] = &[
+            (0.0, "0"),
+            (-0.0, "0"),
+            (1.0, "1"),
+            (-1.5, "-1.5"),
+            (0.1 + 0.2, "0.3"),
+            (1e12, "1000000000000"),
+            (-1e12, "-1000000000000"),
+            (1.23456789012345, "1.234567890123"),
+            (1.0 / 3.0, "0.333333333333"),
+            (2.0 / 3.0, "0.666666666667"),
+            // 끝의 0과 소수점 제거
+            (100.0, "100"),
+            (2.50, "2.5"),
+            (123456.789, "123456.789"),
+            // 소수 12자리에서 반올림
+            (0.999999999999999, "1"),
+            (1e-12, "0.000000000001"),
+            (4e-13, "0"),
+            // 반올림하면 -0.000000000000 이 되는 작은 음수도 0
+            (-1e-13, "0"),
+            (f64::NAN, "NaN"),
+            (f64::INFINITY, "inf"),
+            (f64::NEG_INFINITY, "-inf"),
+        ];
+        for &(v, expected) in cases {
+            assert_eq!(format_float(v), expected, "{:e}", v);
+        }
+    }
+
+    #[test]
+    fn format_float_max() {
+        let s = format_float(f64::MAX);
+        assert!(s.starts_with("17976931348623157"), "{}", s);
+        assert_eq!(s.len(), 309);
+        assert!(s.bytes().all(|b| b.is_ascii_digit()));
+        assert_eq!(format_float(f64::MIN), format!("-{}", s));
+    }
+
+    fn complex(expression: &str) -> Result<String, String> {
+        evaluate_in_mode(expression, NumberMode::Complex)
+    }
+
+    // 복소수 모드에서는 음수의 제곱근과 i를 쓸 수 있음
+    #[test]
+    fn complex_mode_allows_imaginary_results() {
+        assert_eq!(complex("sqrt(-1)"), Ok("i".to_string()));
+        assert_eq!(complex("sqrt(-4)"), Ok("2i".to_string()));
+        assert_eq!(complex("i*i"), Ok("-1".to_string()));
+        assert_eq!(complex("(1+i)*(1-i)"), Ok("2".to_string()));
+        assert_eq!(complex("i^2"), Ok("-1".to_string()));
+    }
+
+    // 실수부/허수부 부호와 1, -1 허수부 표기
+    #[test]
+    fn complex_formatting() {
+        let cases: &[(Complex, &str)] = &[
+            (Complex { re: 0.0, im: 1.0 }, "i"),
+            (Complex { re: 0.0, im: -1.0 }, "-i"),
+            (Complex { re: 3.0, im: 2.0 }, "3+2i"),
+            (Complex { re: 3.0, im: -2.5 }, "3-2.5i"),
+            (Complex { re: -1.0, im: 1.0 }, "-1+i"),
+            (Complex { re: 2.0, im: 0.0 }, "2"),
+            // 반올림하면 0인 허수부는 표시하지 않음
+            (Complex { re: 1.0, im: 1e-13 }, "1"),
+        ];
+        for (v, expected) in cases {
+            assert_eq!(v.to_string(), *expected, "{:?}", v);
+        }
+        assert_eq!(complex("2+3i"), Ok("2+3i".to_string()));
+    }
+
+    // 실수 모드에서는 같은 식이 오류
+    #[test]
+    fn real_mode_rejects_imaginary() {
+        assert_eq!(evaluate("sqrt(-1)"), Err("sqrt의 입력은 음수가 될 수 없습니다".to_string()));
+        assert_eq!(
+            evaluate("i*i"),
+            Err("허수 i는 복소수 모드에서만 사용할 수 있습니다 (/calcmode)".to_string())
+        );
+    }
+
+    // 잘 알려진 상수와 거의 같으면 설명을 붙임
+    #[test]
+    fn approx_constant_names() {
+        assert_eq!(approx_constant_name(std::f64::consts::PI), Some("≈ π"));
+        assert_eq!(approx_constant_name(std::f64::consts::E), Some("≈ e"));
+        assert_eq!(approx_constant_name(value("pi/4").re), Some("≈ π/4"));
+        assert_eq!(approx_constant_name(value("1/3").re), Some("= 1/3"));
+    }
+
+    // 가까워도 허용 오차(1e-10)를 넘으면 설명하지 않음
+    #[test]
+    fn approx_constant_near_miss() {
+        assert_eq!(approx_constant_name(value("355/113").re), None);
+        assert_eq!(approx_constant_name(std::f64::consts::E + 1e-9), None);
+        assert_eq!(approx_constant_name(0.0), None);
+        assert_eq!(approx_constant_name(f64::NAN), None);
+    }
+}
@@ -0,0 +1,307 @@
+use serenity::all::ActionRowComponent;
+use serenity::all::ButtonStyle;
+use serenity::all::ComponentInteraction;
+use serenity::all::CreateActionRow;
+use serenity::all::CreateButton;
+use serenity::all::CreateInputText;
+use serenity::all::CreateInteractionResponse;
+use serenity::all::CreateInteractionResponseMessage;
+use serenity::all::CreateModal;
+use serenity::all::InputTextStyle;
+use serenity::all::ModalInteraction;
+use serenity::all::UserId;
+use serenity::prelude::*;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+
+use crate::calc::NumberMode;
+use crate::calc_cache::evaluate_cached;
+use crate::calc_session::get_session;
+use crate::commands::{explain_embed, validate_text_input, MAX_EXPRESSION_LEN};
+use crate::error_report::report_error;
+use crate::event_metrics::{self, Phase};
+use crate::long_message::{truncate, MESSAGE_LIMIT};
+use crate::usage;
+
+pub const CUSTOM_ID_PREFIX: &str = "calc:";
+
+// 디스코드 custom_id 최대 길이
+const MAX_CUSTOM_ID_LEN: usize = 100;
+
+// custom_id에 담기 어려운 긴 수식을 보관하는 시간
+const EXPRESSION_TTL: Duration = Duration::from_secs(3600);
+
+// 긴 수식 보관소: 원래 커맨드의 인터랙션 ID -> (수식, 저장 시각)
+pub struct CalcExpressions;
+
+impl TypeMapKey for CalcExpressions {
+    type Value = Arc<Mutex<HashMap<u64, (String, Instant)>>>;
+}
+
+pub fn new_expression_store() -> Arc<Mutex<HashMap<u64, (String, Instant)>>> {
+    Arc::new(Mutex::new(HashMap::new()))
+}
+
+async fn expression_store(ctx: &Context) -> Option<Arc<Mutex<HashMap<u64, (String, Instant)>>>> {
+    let data = ctx.data.read().await;
+    data.get::<CalcExpressions>().cloned()
+}
+
+// 예약 작업: 보관 시간이 지난 수식 삭제
+pub async fn prune_expressions(ctx: Context) {
+    if let Some(store) = expression_store(&ctx).await {
+        let now = Instant::now();
+        store
+            .lock()
+            .await
+            .retain(|_, (_, saved)| now.duration_since(*saved) < EXPRESSION_TTL);
+    }
+}
+
+fn mode_code(mode: NumberMode) -> &'static str {
+    match mode {
+        NumberMode::Real => "r",
+        NumberMode::Complex => "c",
+    }
+}
+
+// custom_id: calc:<동작>:<계산한 사용자>:<모드>:<=수식 | #보관 ID>
+fn custom_id(action: &str, invoker: UserId, mode: NumberMode, payload: &str) -> String {
+    format!("{}{}:{}:{}:{}", CUSTOM_ID_PREFIX, action, invoker, mode_code(mode), payload)
+}
+
+// /calc 결과 메시지에 붙는 삭제/다시 계산/스텝 보기 버튼
+pub async fn calc_buttons(
+    ctx: &Context,
+    interaction_id: u64,
+    invoker: UserId,
+    mode: NumberMode,
+    expr: &str,
+) -> CreateActionRow {
+    // 가장 긴 동작 이름 기준으로 수식이 custom_id에 들어가는지 확인
+    let inline = format!("={}", expr);
+    let payload = if custom_id("steps", invoker, mode, &inline).len() <= MAX_CUSTOM_ID_LEN {
+        inline
+    } else {
+        if let Some(store) = expression_store(ctx).await {
+            store.lock().await.insert(interaction_id, (expr.to_string(), Instant::now()));
+        }
+        format!("#{}", interaction_id)
+    };
+
+    CreateActionRow::Buttons(vec![
+        CreateButton::new(custom_id("del", invoker, mode, &payload))
+            .label("🗑 삭제")
+            .style(ButtonStyle::Danger),
+        CreateButton::new(custom_id("re", invoker, mode, &payload))
+            .label("🔁 다시 계산")
+            .style(ButtonStyle::Secondary),
+        CreateButton::new(custom_id("steps", invoker, mode, &payload))
+            .label("📋 스텝 보기")
+            .style(ButtonStyle::Secondary),
+    ])
+}
+
+async fn respond_component(ctx: &Context, comp: &ComponentInteraction, response: CreateInteractionResponse) {
+    if let Err(e) = event_metrics::phase(Phase::Discord, comp.create_response(&ctx.http, response)).await {
+        report_error(ctx, "계산 버튼 응답", &e).await;
+    }
+}
+
+async fn reply_ephemeral(ctx: &Context, comp: &ComponentInteraction, content: &str) {
+    respond_component(
+        ctx,
+        comp,
+        CreateInteractionResponse::Message(
+            CreateInteractionResponseMessage::new()
+                .content(content)
+                .ephemeral(true),
+        ),
+    )
+    .await;
+}
+
+// calc: 로 시작하는 버튼 처리
+pub async fn handle_calc_component(ctx: &Context, comp: &ComponentInteraction) {
+    let Some(rest) = comp.data.custom_id.strip_prefix(CUSTOM_ID_PREFIX) else {
+        return;
+    };
+    let mut parts = rest.splitn(4, ':');
+    let (Some(action), Some(invoker), Some(mode), Some(payload)) =
+        (parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+        return;
+    };
+    let Ok(invoker) = invoker.parse::<u64>().map(UserId::new) else {
+        return;
+    };
+    let mode = if mode == "c" {
+        NumberMode::Complex
+    } else {
+        NumberMode::Real
+    };
+
+    let expr = if let Some(expr) = payload.strip_prefix('=') {
+        Some(expr.to_string())
+    } else {
+        let id = payload.strip_prefix('#').and_then(|id| id.parse::<u64>().ok());
+        match (id, expression_store(ctx).await) {
+            (Some(id), Some(store)) => store.lock().await.get(&id).map(|(expr, _)| expr.clone()),
+            _ => None,
+        }
+    };
+
+    match action {
+        "del" => {
+            if comp.user.id != invoker {
+                reply_ephemeral(ctx, comp, "계산한 사람만 삭제할 수 있습니다").await;
+                return;
+            }
+            respond_component(ctx, comp, CreateInteractionResponse::Acknowledge).await;
+            if let Err(e) = comp.message.delete(&ctx.http).await {
+                report_error(ctx, "계산 결과 삭제", &e).await;
+            }
+        }
+        "re" => {
+            let Some(expr) = expr else {
+                reply_ephemeral(ctx, comp, "오래된 계산이라 다시 계산할 수 없습니다").await;
+                return;
+            };
+            let content = match evaluate_cached(ctx, comp.user.id, &expr, mode).await {
+                Ok(v) => format!("{} = {}", expr, v),
+                Err(e) => format!("{} -> 오류: {}", expr, e),
+            };
+            let content = truncate(&content, MESSAGE_LIMIT);
+            respond_component(
+                ctx,
+                comp,
+                CreateInteractionResponse::UpdateMessage(
+                    CreateInteractionResponseMessage::new().content(content),
+                ),
+            )
+            .await;
+        }
+        "steps" => {
+            let Some(expr) = expr else {
+                reply_ephemeral(ctx, comp, "오래된 계산이라 풀이를 볼 수 없습니다").await;
+                return;
+            };
+            let ans = get_session(ctx, comp.user.id).await.last_answer;
+            respond_component(
+                ctx,
+                comp,
+                CreateInteractionResponse::Message(
+                    CreateInteractionResponseMessage::new()
+                        .embed(explain_embed(&expr, mode, ans))
+                        .ephemeral(true),
+                ),
+            )
+            .await;
+        }
+        _ => {}
+    }
+}
+
+pub const MODAL_ID_PREFIX: &str = "calcmodal:";
+
+// 긴 결과는 디스코드 메시지 길이 제한 안으로 자름
+const MAX_RESULT_LEN: usize = 1900;
+
+// 한 응답에 담을 수 있는 최대 임베드 수
+const MAX_EMBEDS: usize = 10;
+
+// 수식 없이 /calc를 실행하면 여러 줄 입력 창을 띄움. custom_id: calcmodal:<모드>:<explain 0|1>
+pub fn calc_modal(mode: NumberMode, explain: bool) -> CreateModal {
+    let custom_id = format!("{}{}:{}", MODAL_ID_PREFIX, mode_code(mode), u8::from(explain));
+    CreateModal::new(custom_id, "계산기").components(vec![CreateActionRow::InputText(
+        CreateInputText::new(InputTextStyle::Paragraph, "수식 (한 줄에 하나씩)", "expr")
+            .placeholder("1 + 2 * 3\nsqrt(2)^2"),
+    )])
+}
+
+// 입력 창 제출: 줄마다 따로 계산해 결과를 한 줄씩 표시
+pub async fn handle_calc_modal(ctx: &Context, modal: &ModalInteraction) {
+    let Some(rest) = modal.data.custom_id.strip_prefix(MODAL_ID_PREFIX) else {
+        return;
+    };
+    let (mode, explain) = match rest.split_once(':') {
+        Some((mode, explain)) => (mode, explain == "1"),
+        None => (rest, false),
+    };
+    let mode = if mode == "c" {
+        NumberMode::Complex
+    } else {
+        NumberMode::Real
+    };
+
+    let input = modal
+        .data
+        .components
+        .iter()
+        .flat_map(|row| row.components.iter())
+        .find_map(|c| match c {
+            ActionRowComponent::InputText(text) if text.custom_id == "expr" => text.value.clone(),
+            _ => None,
+        })
+        .unwrap_or_default();
+    let lines: Vec<&str> = input.lines().map(str::trim).filter(|l| !l.is_empty()).collect();
+    // 줄마다 /calc 수식과 같은 제한 적용 (줄바꿈은 lines()에서 이미 나눔)
+    let invalid = lines
+        .iter()
+        .enumerate()
+        .find_map(|(i, line)| validate_text_input(line, MAX_EXPRESSION_LEN).err().map(|e| (i + 1, e)));
+
+    let message = if lines.is_empty() {
+        CreateInteractionResponseMessage::new()
+            .content("표현식을 입력하세요.")
+            .ephemeral(true)
+    } else if let Some((line_no, error)) = invalid {
+        CreateInteractionResponseMessage::new()
+            .content(format!("{}번째 줄: {}", line_no, error))
+            .ephemeral(true)
+    } else if explain {
+        let ans = get_session(ctx, modal.user.id).await.last_answer;
+        let embeds = lines
+            .iter()
+            .take(MAX_EMBEDS)
+            .map(|line| explain_embed(line, mode, ans))
+            .collect();
+        CreateInteractionResponseMessage::new()
+            .embeds(embeds)
+            .ephemeral(true)
+    } else {
+        let mut content = String::new();
+        for line in &lines {
+            let result = match evaluate_cached(ctx, modal.user.id, line, mode).await {
+                Ok(v) => format!("{} = {}", line, v),
+                Err(e) => {
+                    usage::record_calc_error(ctx, modal.guild_id, &e).await;
+                    format!("{} -> 오류: {}", line, e)
+                }
+            };
+            if content.len() + result.len() + 1 > MAX_RESULT_LEN {
+                // 첫 줄부터 넘치면 그 줄을 잘라서라도 보여줌
+                if content.is_empty() {
+                    content = truncate(&result, MAX_RESULT_LEN);
+                    break;
+                }
+                content.push('…');
+                break;
+            }
+            if !content.is_empty() {
+                content.push('\n');
+            }
+            content.push_str(&result);
+        }
+        CreateInteractionResponseMessage::new().content(content)
+    };
+
+    if let Err(e) = modal
+        .create_response(&ctx.http, CreateInteractionResponse::Message(message))
+        .await
+    {
+        report_error(ctx, "계산기 입력 창 응답", &e).await;
+    }
+}
@@ -0,0 +1,197 @@
+use serenity::all::UserId;
+use serenity::prelude::*;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use tokio::sync::Mutex;
+
+use crate::calc::{self, Complex, NumberMode};
+use crate::calc_session::{get_session, remember_answer};
+
+// 캐시에 보관하는 계산 결과 수
+pub const CALC_CACHE_CAPACITY: usize = 256;
+
+// 캐시 적중/미스 횟수 (/healthz에 표시)
+static HITS: AtomicU64 = AtomicU64::new(0);
+static MISSES: AtomicU64 = AtomicU64::new(0);
+
+pub fn cache_counts() -> (u64, u64) {
+    (HITS.load(Ordering::Relaxed), MISSES.load(Ordering::Relaxed))
+}
+
+// 가장 오래 쓰지 않은 항목부터 버리는 고정 크기 캐시
+pub struct LruCache<K, V> {
+    capacity: usize,
+    // 키 -> (값, 마지막 사용 순번)
+    entries: HashMap<K, (V, u64)>,
+    // 마지막 사용 순번 -> 키 (가장 작은 순번이 가장 오래된 항목)
+    order: BTreeMap<u64, K>,
+    tick: u64,
+}
+
+impl<K: Hash + Eq + Clone, V: Clone> LruCache<K, V> {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: HashMap::new(),
+            order: BTreeMap::new(),
+            tick: 0,
+        }
+    }
+
+    pub fn get(&mut self, key: &K) -> Option<V> {
+        self.tick += 1;
+        let (value, used) = self.entries.get_mut(key)?;
+        self.order.remove(used);
+        *used = self.tick;
+        self.order.insert(self.tick, key.clone());
+        Some(value.clone())
+    }
+
+    pub fn insert(&mut self, key: K, value: V) {
+        self.tick += 1;
+        if let Some((_, used)) = self.entries.insert(key.clone(), (value, self.tick)) {
+            self.order.remove(&used);
+        }
+        self.order.insert(self.tick, key);
+        while self.entries.len() > self.capacity {
+            let Some((_, oldest)) = self.order.pop_first() else {
+                break;
+            };
+            self.entries.remove(&oldest);
+        }
+    }
+}
+
+// (모드, 수식) -> 계산 결과. 계산은 결정적이므로 만료 없음 (ans를 쓴 식은 저장하지 않음)
+pub struct CalcCache;
+
+impl TypeMapKey for CalcCache {
+    type Value = Arc<Mutex<LruCache<(NumberMode, String), Complex>>>;
+}
+
+pub fn new_calc_cache() -> Arc<Mutex<LruCache<(NumberMode, String), Complex>>> {
+    Arc::new(Mutex::new(LruCache::new(CALC_CACHE_CAPACITY)))
+}
+
+// 사용자의 식을 계산. 성공한 결과는 그 사용자의 ans로 기억
+pub async fn evaluate_cached(
+    ctx: &Context,
+    user_id: UserId,
+    expression: &str,
+    mode: NumberMode,
+) -> Result<String, String> {
+    let value = lookup_or_evaluate(ctx, user_id, expression, mode).await?;
+    remember_answer(ctx, user_id, value).await;
+    Ok(value.to_string())
+}
+
+// 캐시를 먼저 확인하고 없으면 계산. 성공한 결과만 저장
+async fn lookup_or_evaluate(
+    ctx: &Context,
+    user_id: UserId,
+    expression: &str,
+    mode: NumberMode,
+) -> Result<Complex, String> {
+    // ans는 사용자마다 값이 달라 캐시를 거치지 않음
+    if calc::uses_answer(expression) {
+        let ans = get_session(ctx, user_id).await.last_answer;
+        return calc::evaluate_value(expression, mode, ans);
+    }
+    let cache = {
+        let data = ctx.data.read().await;
+        data.get::<CalcCache>().cloned()
+    };
+    let Some(cache) = cache else {
+        return calc::evaluate_value(expression, mode, None);
+    };
+    evaluate_through(&mut *cache.lock().await, expression, mode)
+}
+
+// 캐시에 있으면 그 값(적중), 없으면 계산해서 성공한 결과만 저장(미스)
+fn evaluate_through(
+    cache: &mut LruCache<(NumberMode, String), Complex>,
+    expression: &str,
+    mode: NumberMode,
+) -> Result<Complex, String> {
+    let key = (mode, expression.to_string());
+    if let Some(value) = cache.get(&key) {
+        HITS.fetch_add(1, Ordering::Relaxed);
+        return Ok(value);
+    }
+    MISSES.fetch_add(1, Ordering::Relaxed);
+    let value = calc::evaluate_value(expression, mode, None)?;
+    cache.insert(key, value);
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 가득 차면 가장 오래 쓰지 않은 항목부터 버림
+    #[test]
+    fn evicts_least_recently_used() {
+        let mut cache = LruCache::new(2);
+        cache.insert("a", 1);
+        cache.insert("b", 2);
+        cache.insert("c", 3);
+        assert_eq!(cache.get(&"a"), None);
+        assert_eq!(cache.get(&"b"), Some(2));
+        assert_eq!(cache.get(&"c"), Some(3));
+    }
+
+    // 읽은 항목은 가장 최근에 쓴 것으로 바뀌어 밀려나지 않음
+    #[test]
+    fn get_refreshes_entry() {
+        let mut cache = LruCache::new(2);
+        cache.insert("a", 1);
+        cache.insert("b", 2);
+        assert_eq!(cache.get(&"a"), Some(1));
+        cache.insert("c", 3);
+        assert_eq!(cache.get(&"a"), Some(1));
+        assert_eq!(cache.get(&"b"), None);
+    }
+
+    // 같은 키를 다시 넣으면 값만 바뀌고 크기는 늘지 않음
+    #[test]
+    fn reinsert_replaces_value() {
+        let mut cache = LruCache::new(2);
+        cache.insert("a", 1);
+        cache.insert("a", 10);
+        cache.insert("b", 2);
+        assert_eq!(cache.get(&"a"), Some(10));
+        assert_eq!(cache.entries.len(), 2);
+        assert_eq!(cache.order.len(), 2);
+    }
+
+    // 몇 개를 넣어도 용량을 넘지 않음
+    #[test]
+    fn never_exceeds_capacity() {
+        let mut cache = LruCache::new(CALC_CACHE_CAPACITY);
+        for n in 0..CALC_CACHE_CAPACITY * 3 {
+            cache.insert(n, n);
+            assert!(cache.entries.len() <= CALC_CACHE_CAPACITY);
+        }
+        assert_eq!(cache.entries.len(), CALC_CACHE_CAPACITY);
+        assert_eq!(cache.order.len(), CALC_CACHE_CAPACITY);
+        assert_eq!(cache.get(&(CALC_CACHE_CAPACITY * 2 - 1)), None);
+        assert_eq!(cache.get(&(CALC_CACHE_CAPACITY * 3 - 1)), Some(CALC_CACHE_CAPACITY * 3 - 1));
+    }
+
+    // 처음 계산은 미스, 같은 식은 적중. 실패한 식은 저장하지 않아 매번 미스
+    // (HITS/MISSES는 전역이므로 이 테스트만 evaluate_through를 부름)
+    #[test]
+    fn hits_and_misses_are_counted() {
+        let mut cache = LruCache::new(4);
+        let (hits, misses) = cache_counts();
+        assert_eq!(evaluate_through(&mut cache, "2+3", NumberMode::Real), Ok(Complex { re: 5.0, im: 0.0 }));
+        assert_eq!(evaluate_through(&mut cache, "2+3", NumberMode::Real), Ok(Complex { re: 5.0, im: 0.0 }));
+        // 모드가 다르면 다른 키
+        assert!(evaluate_through(&mut cache, "2+3", NumberMode::Complex).is_ok());
+        assert!(evaluate_through(&mut cache, "1/0", NumberMode::Real).is_err());
+        assert!(evaluate_through(&mut cache, "1/0", NumberMode::Real).is_err());
+        assert_eq!(cache_counts(), (hits + 1, misses + 4));
+    }
+}
@@ -0,0 +1,212 @@
+use serenity::all::CommandDataOptionValue;
+use serenity::all::CommandInteraction;
+use serenity::all::CreateEmbed;
+use serenity::all::CreateEmbedFooter;
+use serenity::all::CreateInteractionResponseMessage;
+use serenity::all::GuildId;
+use serenity::all::UserId;
+use serenity::prelude::*;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+use crate::commands::respond;
+use crate::guild_config::{get_guild_config, update_guild_config};
+use crate::long_message::truncate;
+use crate::storage::unix_now;
+use crate::voice_stats::check_privacy;
+
+// 서버마다 기억하는 최근 /calc 계산 수
+const CALC_LOG_CAPACITY: usize = 50;
+// /calchistory global에 보여주는 계산 수
+const SHOWN_ENTRIES: usize = 10;
+// 임베드 한 줄에 보여주는 식/결과 최대 길이 (글자 수)
+const MAX_SHOWN_LEN: usize = 100;
+
+// 기록을 모으는 곳: 서버 (log_calc_invocations를 켠 서버만) 또는 한 사용자의 DM
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CalcLogScope {
+    Guild(GuildId),
+    Dm(UserId),
+}
+
+// 성공한 /calc 계산 하나
+#[derive(Debug, Clone)]
+pub struct CalcLogEntry {
+    pub user_id: UserId,
+    pub expression: String,
+    pub result: String,
+    pub at: i64,
+}
+
+pub struct GlobalCalcLog;
+
+impl TypeMapKey for GlobalCalcLog {
+    type Value = Arc<RwLock<HashMap<CalcLogScope, VecDeque<CalcLogEntry>>>>;
+}
+
+pub fn new_calc_log() -> Arc<RwLock<HashMap<CalcLogScope, VecDeque<CalcLogEntry>>>> {
+    Arc::new(RwLock::new(HashMap::new()))
+}
+
+async fn calc_log(ctx: &Context) -> Option<Arc<RwLock<HashMap<CalcLogScope, VecDeque<CalcLogEntry>>>>> {
+    let data = ctx.data.read().await;
+    data.get::<GlobalCalcLog>().cloned()
+}
+
+// 성공한 계산을 기록. DM 계산은 그 사용자의 기록으로, 서버 계산은 서버가 기록을 켰을 때만
+pub async fn record(ctx: &Context, guild_id: Option<GuildId>, user_id: UserId, expression: &str, result: &str) {
+    let scope = match guild_id {
+        Some(guild_id) if !get_guild_config(ctx, guild_id).await.log_calc_invocations => return,
+        Some(guild_id) => CalcLogScope::Guild(guild_id),
+        None => CalcLogScope::Dm(user_id),
+    };
+    let Some(log) = calc_log(ctx).await else {
+        return;
+    };
+    push_entry(
+        log.write().await.entry(scope).or_default(),
+        CalcLogEntry {
+            user_id,
+            expression: expression.to_string(),
+            result: result.to_string(),
+            at: unix_now(),
+        },
+    );
+}
+
+// 가장 오래된 계산부터 밀어내며 CALC_LOG_CAPACITY개까지 보관
+fn push_entry(entries: &mut VecDeque<CalcLogEntry>, entry: CalcLogEntry) {
+    if entries.len() >= CALC_LOG_CAPACITY {
+        entries.pop_front();
+    }
+    entries.push_back(entry);
+}
+
+// /calchistory global | logging <on|off>
+pub async fn handle_calchistory(ctx: &Context, cmd: &CommandInteraction) {
+    let Some(sub) = cmd.data.options.first() else {
+        return;
+    };
+    let CommandDataOptionValue::SubCommand(args) = &sub.value else {
+        return;
+    };
+    // DM에서는 DM에서 한 내 계산 기록만 보여줌
+    let Some(guild_id) = cmd.guild_id else {
+        let message = match sub.name.as_str() {
+            "global" => CreateInteractionResponseMessage::new()
+                .embed(history_embed(ctx, CalcLogScope::Dm(cmd.user.id)).await),
+            _ => CreateInteractionResponseMessage::new().content("계산 기록 설정은 서버에서만 바꿀 수 있습니다."),
+        };
+        respond(ctx, cmd, message.ephemeral(true)).await;
+        return;
+    };
+    let is_manager = cmd
+        .member
+        .as_ref()
+        .and_then(|m| m.permissions)
+        .is_some_and(|p| p.administrator() || p.manage_guild());
+
+    let message = match sub.name.as_str() {
+        // 서버 관리 권한이 있거나 보이스 통계 공개 범위가 허용하면 볼 수 있음
+        "global" => match check_privacy(ctx, cmd.member.as_deref(), guild_id).await {
+            Err(_) if !is_manager => CreateInteractionResponseMessage::new()
+                .content("이 서버의 계산 기록은 서버 관리 권한이 있어야 볼 수 있습니다."),
+            _ => CreateInteractionResponseMessage::new()
+                .embed(history_embed(ctx, CalcLogScope::Guild(guild_id)).await),
+        },
+        "logging" => {
+            let on = args
+                .iter()
+                .find(|o| o.name == "state")
+                .and_then(|o| o.value.as_str())
+                .is_some_and(|v| v == "on");
+            let content = if !is_manager {
+                "계산 기록 설정은 서버 관리 권한이 있어야 바꿀 수 있습니다."
+            } else if !update_guild_config(ctx, guild_id, cmd.user.id, |c| c.log_calc_invocations = on).await {
+                "설정을 저장하지 못했습니다. 잠시 후 다시 시도해주세요."
+            } else if on {
+                "이 서버의 /calc 계산을 기록합니다. (/calchistory global)"
+            } else {
+                // 끄면 모아 둔 기록도 지움
+                if let Some(log) = calc_log(ctx).await {
+                    log.write().await.remove(&CalcLogScope::Guild(guild_id));
+                }
+                "이 서버의 /calc 계산을 기록하지 않습니다."
+            };
+            CreateInteractionResponseMessage::new().content(content)
+        }
+        _ => return,
+    };
+    respond(ctx, cmd, message.ephemeral(true)).await;
+}
+
+async fn history_embed(ctx: &Context, scope: CalcLogScope) -> CreateEmbed {
+    let entries: Vec<CalcLogEntry> = match calc_log(ctx).await {
+        Some(log) => log
+            .read()
+            .await
+            .get(&scope)
+            .map(|e| e.iter().rev().take(SHOWN_ENTRIES).cloned().collect())
+            .unwrap_or_default(),
+        None => Vec::new(),
+    };
+    let logging = match scope {
+        CalcLogScope::Guild(guild_id) => get_guild_config(ctx, guild_id).await.log_calc_invocations,
+        CalcLogScope::Dm(_) => true,
+    };
+    let description = if entries.is_empty() {
+        if logging {
+            "아직 기록된 계산이 없습니다.".to_string()
+        } else {
+            "계산 기록이 꺼져 있습니다. `/calchistory logging on` 으로 켤 수 있습니다.".to_string()
+        }
+    } else {
+        entries
+            .iter()
+            .map(|e| {
+                format!(
+                    "<t:{}:R> <@{}> `{}` = `{}`",
+                    e.at,
+                    e.user_id,
+                    truncate(&e.expression, MAX_SHOWN_LEN),
+                    truncate(&e.result, MAX_SHOWN_LEN)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+    CreateEmbed::new()
+        .title("🧮 최근 계산 기록")
+        .description(description)
+        .footer(CreateEmbedFooter::new(format!(
+            "최근 {}개 중 최대 {}개 표시",
+            CALC_LOG_CAPACITY, SHOWN_ENTRIES
+        )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(n: usize) -> CalcLogEntry {
+        CalcLogEntry {
+            user_id: UserId::new(1),
+            expression: n.to_string(),
+            result: n.to_string(),
+            at: n as i64,
+        }
+    }
+
+    // 가득 차면 가장 오래된 계산부터 버림
+    #[test]
+    fn oldest_entry_is_dropped_at_capacity() {
+        let mut entries = VecDeque::new();
+        for n in 0..CALC_LOG_CAPACITY + 2 {
+            push_entry(&mut entries, entry(n));
+        }
+        assert_eq!(entries.len(), CALC_LOG_CAPACITY);
+        assert_eq!(entries.front().unwrap().expression, "2");
+        assert_eq!(entries.back().unwrap().expression, (CALC_LOG_CAPACITY + 1).to_string());
+    }
+}
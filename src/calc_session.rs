@@ -0,0 +1,126 @@
+use serenity::all::CommandInteraction;
+use serenity::all::CreateInteractionResponseMessage;
+use serenity::all::UserId;
+use serenity::prelude::*;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+use crate::calc::{self, Complex, NumberMode};
+use crate::commands::respond;
+
+// 사용자별 계산기 상태
+#[derive(Debug, Clone, Default)]
+pub struct UserCalcSession {
+    pub mode: NumberMode,
+    // 마지막으로 성공한 계산 결과 (식에서 ans 또는 _ 로 참조)
+    pub last_answer: Option<Complex>,
+}
+
+impl UserCalcSession {
+    // 이 세션의 모드와 직전 결과(ans)로 계산하고, 성공하면 다음 식의 ans로 기억
+    pub fn evaluate(&mut self, expression: &str) -> Result<Complex, String> {
+        let value = calc::evaluate_value(expression, self.mode, self.last_answer)?;
+        self.last_answer = Some(value);
+        Ok(value)
+    }
+}
+
+pub struct CalcSessionStore;
+
+impl TypeMapKey for CalcSessionStore {
+    type Value = Arc<RwLock<HashMap<UserId, UserCalcSession>>>;
+}
+
+pub fn new_session_store() -> Arc<RwLock<HashMap<UserId, UserCalcSession>>> {
+    Arc::new(RwLock::new(HashMap::new()))
+}
+
+async fn session_store(ctx: &Context) -> Option<Arc<RwLock<HashMap<UserId, UserCalcSession>>>> {
+    let data = ctx.data.read().await;
+    data.get::<CalcSessionStore>().cloned()
+}
+
+// 사용자 계산기 상태 조회 (없으면 기본값)
+pub async fn get_session(ctx: &Context, user_id: UserId) -> UserCalcSession {
+    match session_store(ctx).await {
+        Some(store) => store.read().await.get(&user_id).cloned().unwrap_or_default(),
+        None => UserCalcSession::default(),
+    }
+}
+
+// 성공한 계산 결과를 다음 식의 ans로 기억
+pub async fn remember_answer(ctx: &Context, user_id: UserId, value: Complex) {
+    if let Some(store) = session_store(ctx).await {
+        store.write().await.entry(user_id).or_default().last_answer = Some(value);
+    }
+}
+
+pub async fn handle_calcmode(ctx: &Context, cmd: &CommandInteraction) {
+    let complex = cmd
+        .data
+        .options
+        .iter()
+        .find(|o| o.name == "complex")
+        .and_then(|o| o.value.as_bool())
+        .unwrap_or(false);
+    let mode = if complex { NumberMode::Complex } else { NumberMode::Real };
+
+    if let Some(store) = session_store(ctx).await {
+        store.write().await.entry(cmd.user.id).or_default().mode = mode;
+    }
+
+    let content = match mode {
+        NumberMode::Complex => "복소수 모드를 켰습니다. 예: `sqrt(-4)` = 2i",
+        NumberMode::Real => "복소수 모드를 껐습니다.",
+    };
+    respond(
+        ctx,
+        cmd,
+        CreateInteractionResponseMessage::new()
+            .content(content)
+            .ephemeral(true),
+    )
+    .await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn answer_carries_over_in_same_session() {
+        let mut session = UserCalcSession::default();
+        assert_eq!(session.evaluate("5*3").unwrap().to_string(), "15");
+        assert_eq!(session.evaluate("ans+7").unwrap().to_string(), "22");
+        // _ 도 같은 값
+        assert_eq!(session.evaluate("_*2").unwrap().to_string(), "44");
+    }
+
+    #[test]
+    fn failed_evaluation_keeps_previous_answer() {
+        let mut session = UserCalcSession::default();
+        session.evaluate("5*3").unwrap();
+        assert!(session.evaluate("ans/0").is_err());
+        assert_eq!(session.evaluate("ans+7").unwrap().to_string(), "22");
+    }
+
+    #[test]
+    fn sessions_do_not_share_answers() {
+        let mut first = UserCalcSession::default();
+        let mut second = UserCalcSession::default();
+        first.evaluate("5*3").unwrap();
+        assert_eq!(second.evaluate("ans+7"), Err("ans: 이전 계산 결과가 없습니다".to_string()));
+        assert_eq!(first.evaluate("ans+7").unwrap().to_string(), "22");
+    }
+
+    #[test]
+    fn answer_follows_session_mode() {
+        let mut session = UserCalcSession {
+            mode: NumberMode::Complex,
+            ..UserCalcSession::default()
+        };
+        assert_eq!(session.evaluate("sqrt(-4)").unwrap().to_string(), "2i");
+        assert_eq!(session.evaluate("ans^2").unwrap().to_string(), "-4");
+    }
+}
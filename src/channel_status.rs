@@ -0,0 +1,256 @@
+use serenity::all::ChannelId;
+use serenity::all::EditChannel;
+use serenity::all::GuildId;
+use serenity::prelude::*;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+use tracing::Instrument;
+
+use crate::dry_run;
+use crate::error_report::report_error;
+use crate::guild_config::get_guild_config;
+use crate::storage;
+use crate::voice_tracker::{is_channel_active, ChannelActivityTracker};
+
+// 활성화된 동안 보이스 채널 이름 앞에 붙이는 표시 (/voiceconfig statusname)
+pub const ACTIVE_PREFIX: &str = "🟢 ";
+// 디스코드 채널 이름 최대 길이 (글자 수)
+const MAX_CHANNEL_NAME: usize = 100;
+// 디스코드는 채널 이름 변경을 채널마다 10분에 2번 정도로 제한하므로 그 안에서만 바꿈
+const RENAME_WINDOW: Duration = Duration::from_secs(600);
+const RENAMES_PER_WINDOW: usize = 2;
+
+// 봇이 바꿔 둔 이름. original은 되돌릴 이름, name은 봇이 붙인 이름
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RenamedChannel {
+    pub original: String,
+    pub name: String,
+}
+
+#[derive(Default)]
+pub struct RenameState {
+    renamed: Option<RenamedChannel>,
+    // RENAME_WINDOW 안에 이름을 바꾼 시각
+    recent: VecDeque<Instant>,
+    // 이 채널의 변경 작업이 실행 중인지
+    running: bool,
+    // 작업이 상태를 확인한 뒤 다시 요청이 들어왔는지 (작업이 끝나기 전에 한 번 더 확인)
+    dirty: bool,
+}
+
+pub struct ChannelRenames;
+
+impl TypeMapKey for ChannelRenames {
+    type Value = Arc<Mutex<HashMap<ChannelId, RenameState>>>;
+}
+
+pub fn new_rename_store() -> Arc<Mutex<HashMap<ChannelId, RenameState>>> {
+    Arc::new(Mutex::new(HashMap::new()))
+}
+
+// 알림 등에 쓸 이름 (봇이 붙인 표시를 뺀 이름)
+pub fn base_name(name: &str) -> &str {
+    name.strip_prefix(ACTIVE_PREFIX).unwrap_or(name)
+}
+
+async fn rename_store(ctx: &Context) -> Option<Arc<Mutex<HashMap<ChannelId, RenameState>>>> {
+    let data = ctx.data.read().await;
+    data.get::<ChannelRenames>().cloned()
+}
+
+// 채널이 활성화/비활성화되었거나 설정이 바뀌었을 때 호출. 실제 변경은 채널별 작업 하나가 차례로 처리하므로
+// 짧은 시간에 여러 번 호출되어도 마지막 상태만 반영됨
+pub async fn refresh(ctx: &Context, guild_id: GuildId, channel_id: ChannelId) {
+    let Some(store) = rename_store(ctx).await else {
+        return;
+    };
+    let mut store = store.lock().await;
+    let state = store.entry(channel_id).or_default();
+    state.dirty = true;
+    if state.running {
+        return;
+    }
+    state.running = true;
+    let ctx = ctx.clone();
+    tokio::spawn(async move { run(ctx, guild_id, channel_id).await }.in_current_span());
+}
+
+// 재시작 후 복구: 저장된 바꾼 이름을 불러와 더 이상 활성화되지 않은 채널은 원래 이름으로 되돌림
+pub async fn recover(ctx: &Context, guild_ids: &[GuildId]) {
+    let (Some(pool), Some(store)) = (storage::pool(ctx).await, rename_store(ctx).await) else {
+        return;
+    };
+    let saved = match storage::load_channel_renames(&pool).await {
+        Ok(saved) => saved,
+        Err(e) => {
+            report_error(ctx, "채널 이름 변경 기록 조회", &e).await;
+            return;
+        }
+    };
+    for (guild_id, channel_id, renamed) in saved {
+        if !guild_ids.contains(&guild_id) {
+            continue;
+        }
+        {
+            let mut store = store.lock().await;
+            let state = store.entry(channel_id).or_default();
+            if state.renamed.is_none() {
+                state.renamed = Some(renamed);
+            }
+        }
+        refresh(ctx, guild_id, channel_id).await;
+    }
+}
+
+// 이 채널 이름에 활성화 표시가 있어야 하는지: 서버가 켜 둔 채널이고 지금 활성화된 경우
+async fn wanted(ctx: &Context, guild_id: GuildId, channel_id: ChannelId) -> bool {
+    if !get_guild_config(ctx, guild_id).await.status_rename_channels.contains(&channel_id) {
+        return false;
+    }
+    let tracker = {
+        let data = ctx.data.read().await;
+        data.get::<ChannelActivityTracker>().cloned()
+    };
+    match tracker {
+        Some(tracker) => is_channel_active(&tracker, guild_id, channel_id).await,
+        None => false,
+    }
+}
+
+// 채널별 변경 작업. 바라는 상태와 현재 이름이 같아질 때까지 속도 제한 안에서 이름을 바꿈
+async fn run(ctx: Context, guild_id: GuildId, channel_id: ChannelId) {
+    let Some(store) = rename_store(&ctx).await else {
+        return;
+    };
+    loop {
+        store.lock().await.entry(channel_id).or_default().dirty = false;
+        let want = wanted(&ctx, guild_id, channel_id).await;
+
+        let (renamed, wait) = {
+            let mut store = store.lock().await;
+            let state = store.entry(channel_id).or_default();
+            let now = Instant::now();
+            while state.recent.front().is_some_and(|&at| now.duration_since(at) >= RENAME_WINDOW) {
+                state.recent.pop_front();
+            }
+            if want == state.renamed.is_some() {
+                if state.dirty {
+                    continue;
+                }
+                state.running = false;
+                return;
+            }
+            let wait = (state.recent.len() >= RENAMES_PER_WINDOW)
+                .then(|| state.recent.front().map(|&at| RENAME_WINDOW.saturating_sub(now.duration_since(at))))
+                .flatten();
+            (state.renamed.clone(), wait)
+        };
+        // 기다리는 동안 상태가 다시 바뀌면 (예: 비활성화 직후 다시 활성화) 되돌리지 않고 끝남
+        if let Some(wait) = wait {
+            tokio::time::sleep(wait).await;
+            continue;
+        }
+
+        let done = match renamed {
+            None => mark_active(&ctx, guild_id, channel_id).await,
+            Some(renamed) => restore_name(&ctx, guild_id, channel_id, renamed).await,
+        };
+        let mut store = store.lock().await;
+        let state = store.entry(channel_id).or_default();
+        match done {
+            Some(result) => {
+                if result.edited {
+                    state.recent.push_back(Instant::now());
+                }
+                state.renamed = result.renamed;
+            }
+            // 실패하면 다음 활성화/비활성화나 재시작 때 다시 시도
+            None => {
+                state.running = false;
+                return;
+            }
+        }
+    }
+}
+
+struct RenameResult {
+    renamed: Option<RenamedChannel>,
+    // 실제로 디스코드에 변경을 요청했는지 (속도 제한 계산용)
+    edited: bool,
+}
+
+fn current_name(ctx: &Context, guild_id: GuildId, channel_id: ChannelId) -> Option<String> {
+    ctx.cache
+        .guild(guild_id)
+        .and_then(|g| g.channels.get(&channel_id).map(|c| c.name.clone()))
+}
+
+async fn edit_name(ctx: &Context, guild_id: GuildId, channel_id: ChannelId, from: &str, to: &str) -> bool {
+    if dry_run::suppressed(ctx, Some(guild_id), "채널 이름 변경", format!("{} → {}", from, to)).await {
+        return false;
+    }
+    match channel_id.edit(&ctx.http, EditChannel::new().name(to)).await {
+        Ok(_) => true,
+        Err(e) => {
+            report_error(ctx, &format!("채널 이름 변경 ({})", channel_id), &e).await;
+            false
+        }
+    }
+}
+
+// 활성화 표시를 붙이고 원래 이름을 저장
+async fn mark_active(ctx: &Context, guild_id: GuildId, channel_id: ChannelId) -> Option<RenameResult> {
+    let current = current_name(ctx, guild_id, channel_id)?;
+    let original = base_name(&current).to_string();
+    let name: String = format!("{}{}", ACTIVE_PREFIX, original)
+        .chars()
+        .take(MAX_CHANNEL_NAME)
+        .collect();
+    // 이전 실행에서 붙여 둔 표시가 남아 있으면 그대로 씀
+    let edited = current != name;
+    if edited && !edit_name(ctx, guild_id, channel_id, &current, &name).await {
+        return None;
+    }
+    let renamed = RenamedChannel { original, name };
+    if let Some(pool) = storage::pool(ctx).await
+        && let Err(e) = storage::save_channel_rename(&pool, guild_id, channel_id, &renamed).await
+    {
+        report_error(ctx, "채널 이름 변경 기록 저장", &e).await;
+    }
+    Some(RenameResult {
+        renamed: Some(renamed),
+        edited,
+    })
+}
+
+// 원래 이름으로 되돌림. 활성화 중에 누군가 이름을 직접 바꿨으면 그 이름을 유지
+async fn restore_name(
+    ctx: &Context,
+    guild_id: GuildId,
+    channel_id: ChannelId,
+    renamed: RenamedChannel,
+) -> Option<RenameResult> {
+    let mut edited = false;
+    match current_name(ctx, guild_id, channel_id) {
+        Some(current) if current == renamed.name => {
+            if !edit_name(ctx, guild_id, channel_id, &current, &renamed.original).await {
+                return None;
+            }
+            edited = true;
+        }
+        Some(current) => tracing::info!(
+            "채널 이름이 활성화 중에 바뀌어 되돌리지 않습니다 ({}): {}",
+            channel_id, current
+        ),
+        // 채널이 삭제된 경우 기록만 지움
+        None => {}
+    }
+    if let Some(pool) = storage::pool(ctx).await
+        && let Err(e) = storage::delete_channel_rename(&pool, channel_id).await
+    {
+        report_error(ctx, "채널 이름 변경 기록 삭제", &e).await;
+    }
+    Some(RenameResult { renamed: None, edited })
+}
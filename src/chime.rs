@@ -0,0 +1,193 @@
+use serenity::all::ChannelId;
+use serenity::all::CommandInteraction;
+use serenity::all::CreateInteractionResponseMessage;
+use serenity::all::GuildId;
+use serenity::all::Permissions;
+use serenity::async_trait;
+use serenity::prelude::*;
+use songbird::events::{Event, EventContext, EventHandler as VoiceEventHandler, TrackEvent};
+use songbird::input::Input;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Notify;
+
+use crate::commands::respond;
+use crate::guild_config::get_guild_config;
+
+// 활성화 알림음 (0.65초, 24kHz 모노 WAV)
+static CHIME: &[u8] = include_bytes!("../assets/chime.wav");
+
+// 재생이 끝났다는 이벤트가 오지 않아도 이 시간이 지나면 채널에서 나감
+const MAX_PLAY: Duration = Duration::from_secs(5);
+
+// 알림음을 재생하지 못한 이유
+#[derive(Debug, PartialEq, Eq, thiserror::Error)]
+pub enum ChimeError {
+    #[error("봇에게 이 채널의 연결/말하기 권한이 없습니다")]
+    MissingPermissions,
+    #[error("채널 인원이 가득 찼습니다")]
+    ChannelFull,
+    #[error("봇이 이 서버의 다른 보이스 채널에 이미 들어가 있습니다")]
+    Busy,
+    #[error("채널 정보를 찾을 수 없습니다")]
+    UnknownChannel,
+    #[error("보이스 연결 실패: {0}")]
+    Join(String),
+}
+
+// 들어가도 되는지 확인. permissions는 봇의 채널 권한, members는 지금 채널 인원
+fn join_check(permissions: Permissions, user_limit: Option<u32>, members: usize, in_call: bool) -> Result<(), ChimeError> {
+    if in_call {
+        return Err(ChimeError::Busy);
+    }
+    if !permissions.contains(Permissions::CONNECT | Permissions::SPEAK) {
+        return Err(ChimeError::MissingPermissions);
+    }
+    // 멤버 이동 권한이 있으면 정원을 넘어 들어갈 수 있음
+    let full = user_limit.is_some_and(|limit| limit > 0 && members >= limit as usize);
+    if full && !permissions.contains(Permissions::MOVE_MEMBERS) {
+        return Err(ChimeError::ChannelFull);
+    }
+    Ok(())
+}
+
+// 캐시에서 봇의 채널 권한, 정원, 인원을 읽음
+fn channel_state(ctx: &Context, guild_id: GuildId, channel_id: ChannelId) -> Option<(Permissions, Option<u32>, usize)> {
+    let guild = ctx.cache.guild(guild_id)?;
+    let channel = guild.channels.get(&channel_id)?;
+    let bot = guild.members.get(&ctx.cache.current_user().id)?;
+    let members = guild
+        .voice_states
+        .values()
+        .filter(|vs| vs.channel_id == Some(channel_id))
+        .count();
+    Some((guild.user_permissions_in(channel, bot), channel.user_limit, members))
+}
+
+// 재생이 끝나면 기다리는 쪽을 깨움
+struct TrackEnded(Arc<Notify>);
+
+#[async_trait]
+impl VoiceEventHandler for TrackEnded {
+    async fn act(&self, _ctx: &EventContext<'_>) -> Option<Event> {
+        self.0.notify_one();
+        None
+    }
+}
+
+// 채널에 들어가 알림음을 한 번 재생하고 나옴
+pub async fn play(ctx: &Context, guild_id: GuildId, channel_id: ChannelId, volume: u8) -> Result<(), ChimeError> {
+    let manager = songbird::get(ctx)
+        .await
+        .ok_or_else(|| ChimeError::Join("songbird가 등록되지 않았습니다".to_string()))?;
+    let (permissions, user_limit, members) =
+        channel_state(ctx, guild_id, channel_id).ok_or(ChimeError::UnknownChannel)?;
+    join_check(permissions, user_limit, members, manager.get(guild_id).is_some())?;
+
+    let call = manager
+        .join(guild_id, channel_id)
+        .await
+        .map_err(|e| ChimeError::Join(e.to_string()))?;
+    let ended = Arc::new(Notify::new());
+    let track = call.lock().await.play_input(Input::from(CHIME));
+    let _ = track.set_volume(f32::from(volume) / 100.0);
+    for event in [TrackEvent::End, TrackEvent::Error] {
+        let _ = track.add_event(Event::Track(event), TrackEnded(ended.clone()));
+    }
+    let _ = tokio::time::timeout(MAX_PLAY, ended.notified()).await;
+    if let Err(e) = manager.remove(guild_id).await {
+        tracing::warn!("알림음 재생 후 보이스 채널 나가기 실패 (길드 {}): {}", guild_id, e);
+    }
+    Ok(())
+}
+
+// 채널 활성화 시 호출. 텍스트 알림과 무관하게 별도 작업에서 재생하고, 실패하면 로그만 남김
+pub fn spawn_activation_chime(ctx: &Context, guild_id: GuildId, channel_id: ChannelId, volume: u8) {
+    let ctx = ctx.clone();
+    tokio::spawn(async move {
+        if let Err(e) = play(&ctx, guild_id, channel_id, volume).await {
+            tracing::info!("활성화 알림음을 재생하지 않았습니다 (길드 {}, 채널 {}): {}", guild_id, channel_id, e);
+        }
+    });
+}
+
+// /testchime [channel]: 지정한 채널(없으면 내가 있는 보이스 채널)에서 알림음을 재생
+pub async fn handle_testchime(ctx: &Context, cmd: &CommandInteraction) {
+    let Some(guild_id) = cmd.guild_id else {
+        return;
+    };
+    let option = cmd
+        .data
+        .options
+        .iter()
+        .find(|o| o.name == "channel")
+        .and_then(|o| o.value.as_channel_id());
+    let current = || {
+        ctx.cache
+            .guild(guild_id)
+            .and_then(|g| g.voice_states.get(&cmd.user.id).and_then(|vs| vs.channel_id))
+    };
+    let content = match option.or_else(current) {
+        None => "재생할 보이스 채널을 고르거나 보이스 채널에 들어간 뒤 다시 실행해주세요.".to_string(),
+        Some(channel_id) => {
+            let volume = get_guild_config(ctx, guild_id).await.chime_volume;
+            match play(ctx, guild_id, channel_id, volume).await {
+                Ok(()) => format!("<#{}> 채널에서 알림음을 재생했습니다. (크기 {}%)", channel_id, volume),
+                Err(e) => format!("알림음을 재생하지 못했습니다: {}", e),
+            }
+        }
+    };
+    respond(
+        ctx,
+        cmd,
+        CreateInteractionResponseMessage::new()
+            .content(content)
+            .ephemeral(true),
+    )
+    .await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VOICE: Permissions = Permissions::CONNECT.union(Permissions::SPEAK);
+
+    #[test]
+    fn joins_with_connect_and_speak() {
+        assert_eq!(join_check(VOICE, None, 3, false), Ok(()));
+        assert_eq!(join_check(VOICE, Some(5), 4, false), Ok(()));
+    }
+
+    // 연결이나 말하기 권한 중 하나라도 없으면 들어가지 않음
+    #[test]
+    fn missing_connect_or_speak_is_rejected() {
+        assert_eq!(join_check(Permissions::CONNECT, None, 1, false), Err(ChimeError::MissingPermissions));
+        assert_eq!(join_check(Permissions::SPEAK, None, 1, false), Err(ChimeError::MissingPermissions));
+    }
+
+    // 정원이 찼으면 멤버 이동 권한이 있을 때만 들어감 (정원 0은 제한 없음)
+    #[test]
+    fn full_channel_needs_move_members() {
+        assert_eq!(join_check(VOICE, Some(2), 2, false), Err(ChimeError::ChannelFull));
+        assert_eq!(join_check(VOICE | Permissions::MOVE_MEMBERS, Some(2), 2, false), Ok(()));
+        assert_eq!(join_check(VOICE, Some(0), 10, false), Ok(()));
+    }
+
+    // 같은 서버에서 이미 통화 중이면 옮겨 가지 않음
+    #[test]
+    fn existing_call_is_left_alone() {
+        assert_eq!(join_check(VOICE, None, 1, true), Err(ChimeError::Busy));
+    }
+
+    // 함께 넣은 알림음을 songbird가 디코딩할 수 있어야 함 (symphonia의 wav 기능)
+    #[tokio::test]
+    async fn bundled_chime_decodes() {
+        use songbird::input::codecs::{get_codec_registry, get_probe};
+        let input = Input::from(CHIME)
+            .make_playable_async(get_codec_registry(), get_probe())
+            .await
+            .unwrap();
+        assert!(input.is_playable());
+    }
+}
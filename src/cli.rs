@@ -0,0 +1,132 @@
+use clap::{Parser, Subcommand, ValueEnum};
+use std::path::PathBuf;
+
+use crate::health::DEFAULT_DISCONNECT_THRESHOLD_SECS;
+use crate::retry::RetryPolicy;
+
+// 로그 출력 수준
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum LogLevel {
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl std::fmt::Display for LogLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            LogLevel::Error => "error",
+            LogLevel::Warn => "warn",
+            LogLevel::Info => "info",
+            LogLevel::Debug => "debug",
+            LogLevel::Trace => "trace",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+// 실행할 작업. 생략하면 run과 같음
+#[derive(Debug, Clone, Subcommand)]
+pub enum Command {
+    /// 디스코드에 연결해 봇 실행 (기본)
+    Run,
+    /// 디스코드 없이 식 하나를 계산해 출력 (오류면 종료 코드 1)
+    Calc {
+        /// 계산할 식 (예: "2 + 3 * 4")
+        expression: String,
+    },
+    /// 로그인해서 슬래시 커맨드만 동기화하고 종료
+    RegisterCommands {
+        /// 글로벌 대신 이 길드의 커맨드를 동기화
+        #[arg(long)]
+        guild: Option<u64>,
+    },
+    /// 디스코드에 연결하지 않고 계산기 REPL 실행 (--repl과 같음)
+    Repl,
+    /// 디스코드에 연결하지 않고 설정 파일만 검사
+    ValidateConfig,
+}
+
+/// AuroBOT: 보이스 채널 활동 알림과 계산기를 제공하는 디스코드 봇
+#[derive(Debug, Parser)]
+#[command(version, about)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// 디스코드 봇 토큰
+    #[arg(long, env = "DISCORD_TOKEN", hide_env_values = true)]
+    pub token: Option<String>,
+
+    /// 데이터베이스 URL (예: sqlite://aurobot.db)
+    #[arg(long, env = "DATABASE_URL")]
+    pub db_url: Option<String>,
+
+    /// 설정 파일 경로
+    #[arg(long, env = "AUROBOT_CONFIG")]
+    pub config_path: Option<PathBuf>,
+
+    /// 로그 출력 수준
+    #[arg(long, env = "LOG_LEVEL", value_enum, default_value_t = LogLevel::Info)]
+    pub log_level: LogLevel,
+
+    /// 상태 확인용 HTTP 서버 포트
+    #[arg(long, env = "HTTP_PORT")]
+    pub http_port: Option<u16>,
+
+    /// 게이트웨이 연결이 이 시간(초) 넘게 끊기면 /healthz가 503을 반환
+    #[arg(long, env = "HEALTH_DISCONNECT_THRESHOLD_SECS", default_value_t = DEFAULT_DISCONNECT_THRESHOLD_SECS)]
+    pub health_threshold_secs: u64,
+
+    /// 변경 여부를 비교하지 않고 모든 슬래시 커맨드를 다시 등록
+    #[arg(long)]
+    pub force_register: bool,
+
+    /// 클라이언트가 곧바로 종료되는 일이 이 횟수 넘게 연속되면 재시작을 멈추고 종료
+    #[arg(long, env = "MAX_CLIENT_RESTARTS", default_value_t = RetryPolicy::CLIENT_RESTART.max_attempts)]
+    pub max_restarts: u32,
+
+    /// 디스코드에 연결하지 않고 계산기 REPL 실행
+    #[arg(long)]
+    pub repl: bool,
+}
+
+impl Cli {
+    // 디스코드에 로그인하는 작업인지 (REPL, calc, validate-config는 토큰이 필요 없음)
+    fn needs_token(&self) -> bool {
+        match self.command {
+            None | Some(Command::Run) => !self.repl,
+            Some(Command::RegisterCommands { .. }) => true,
+            Some(Command::Calc { .. } | Command::Repl | Command::ValidateConfig) => false,
+        }
+    }
+
+    // 옵션 조합 검증. 실패 시 사용자에게 보여줄 메시지 반환
+    pub fn validate(&self) -> Result<(), String> {
+        if self.needs_token() && self.token.as_deref().is_none_or(|t| t.trim().is_empty()) {
+            return Err("--token 또는 DISCORD_TOKEN 환경 변수가 필요합니다".to_string());
+        }
+        if let Some(db_url) = &self.db_url
+            && !db_url.starts_with("sqlite:")
+        {
+            return Err(format!("지원하지 않는 데이터베이스 URL입니다: {}", db_url));
+        }
+        if let Some(path) = &self.config_path
+            && !path.is_file()
+        {
+            return Err(format!("설정 파일을 찾을 수 없습니다: {}", path.display()));
+        }
+        if matches!(self.command, Some(Command::ValidateConfig)) && self.config_path.is_none() {
+            return Err("validate-config에는 --config-path 또는 AUROBOT_CONFIG가 필요합니다".to_string());
+        }
+        if matches!(self.command, Some(Command::RegisterCommands { guild: Some(0) })) {
+            return Err("--guild는 0이 될 수 없습니다".to_string());
+        }
+        if self.http_port == Some(0) {
+            return Err("--http-port는 0이 될 수 없습니다".to_string());
+        }
+        Ok(())
+    }
+}
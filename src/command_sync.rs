@@ -0,0 +1,370 @@
+use serde_json::{Map, Number, Value};
+use serenity::all::Command;
+use serenity::all::CommandId;
+use serenity::all::CommandInteraction;
+use serenity::all::CreateCommand;
+use serenity::all::CreateInteractionResponseMessage;
+use serenity::all::GuildId;
+use serenity::prelude::*;
+use std::fmt;
+
+use crate::commands::{is_owner, respond};
+use crate::error_report::report_retry_error;
+use crate::retry::{retrying, RetryError, RetryPolicy};
+
+// 커맨드를 등록할 범위
+#[derive(Debug, Clone, Copy)]
+pub enum Scope {
+    Global,
+    Guild(GuildId),
+}
+
+impl fmt::Display for Scope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Scope::Global => write!(f, "글로벌"),
+            Scope::Guild(guild_id) => write!(f, "길드 {}", guild_id),
+        }
+    }
+}
+
+impl Scope {
+    async fn fetch(self, ctx: &Context) -> serenity::Result<Vec<Command>> {
+        match self {
+            // 번역(영어 설명)이 바뀐 것도 비교하도록 전체 로캘 값을 함께 받음
+            Scope::Global => Command::get_global_commands_with_localizations(&ctx.http).await,
+            Scope::Guild(guild_id) => guild_id.get_commands_with_localizations(&ctx.http).await,
+        }
+    }
+
+    async fn create(self, ctx: &Context, builder: CreateCommand) -> serenity::Result<Command> {
+        match self {
+            Scope::Global => Command::create_global_command(&ctx.http, builder).await,
+            Scope::Guild(guild_id) => guild_id.create_command(&ctx.http, builder).await,
+        }
+    }
+
+    async fn edit(self, ctx: &Context, id: CommandId, builder: CreateCommand) -> serenity::Result<Command> {
+        match self {
+            Scope::Global => Command::edit_global_command(&ctx.http, id, builder).await,
+            Scope::Guild(guild_id) => guild_id.edit_command(&ctx.http, id, builder).await,
+        }
+    }
+
+    async fn delete(self, ctx: &Context, id: CommandId) -> serenity::Result<()> {
+        match self {
+            Scope::Global => Command::delete_global_command(&ctx.http, id).await,
+            Scope::Guild(guild_id) => guild_id.delete_command(&ctx.http, id).await,
+        }
+    }
+
+    async fn overwrite(self, ctx: &Context, builders: Vec<CreateCommand>) -> serenity::Result<Vec<Command>> {
+        match self {
+            Scope::Global => Command::set_global_commands(&ctx.http, builders).await,
+            Scope::Guild(guild_id) => guild_id.set_commands(&ctx.http, builders).await,
+        }
+    }
+}
+
+// 동기화 결과 (로그용)
+#[derive(Debug, Default)]
+pub struct SyncSummary {
+    pub added: usize,
+    pub changed: usize,
+    pub removed: usize,
+    pub failed: usize,
+}
+
+impl fmt::Display for SyncSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "추가 {}, 변경 {}, 삭제 {}",
+            self.added, self.changed, self.removed
+        )?;
+        if self.failed > 0 {
+            write!(f, ", 실패 {}", self.failed)?;
+        }
+        Ok(())
+    }
+}
+
+// 이미 등록된 커맨드와 비교해 달라진 것만 생성/수정/삭제.
+// force면 비교하지 않고 전체를 한 번에 덮어씀 (디스코드 쪽 상태가 의심될 때)
+pub async fn sync_commands(
+    ctx: &Context,
+    scope: Scope,
+    desired: Vec<(&'static str, CreateCommand)>,
+    force: bool,
+) -> SyncSummary {
+    let mut summary = SyncSummary::default();
+
+    if force {
+        let operation = format!("커맨드 전체 등록 ({})", scope);
+        let builders: Vec<CreateCommand> = desired.into_iter().map(|(_, builder)| builder).collect();
+        let count = builders.len();
+        match retrying(&operation, RetryPolicy::REGISTRATION, || scope.overwrite(ctx, builders.clone())).await {
+            Ok(_) => summary.changed = count,
+            Err(e) => {
+                report_retry_error(ctx, &operation, e).await;
+                summary.failed = count;
+            }
+        }
+        return summary;
+    }
+
+    let operation = format!("등록된 커맨드 조회 ({})", scope);
+    let existing = match retrying(&operation, RetryPolicy::REGISTRATION, || scope.fetch(ctx)).await {
+        Ok(commands) => commands,
+        Err(e) => {
+            report_retry_error(ctx, &operation, e).await;
+            summary.failed = desired.len();
+            return summary;
+        }
+    };
+
+    let plan = plan_sync(&existing, &desired);
+    for (name, builder) in plan.create {
+        let operation = format!("/{} 등록 ({})", name, scope);
+        match retrying(&operation, RetryPolicy::REGISTRATION, || scope.create(ctx, builder.clone())).await {
+            Ok(_) => summary.added += 1,
+            Err(e) => {
+                report_retry_error(ctx, &operation, e).await;
+                summary.failed += 1;
+            }
+        }
+    }
+    for (command, builder) in plan.edit {
+        let operation = format!("/{} 수정 ({})", command.name, scope);
+        match retrying(&operation, RetryPolicy::REGISTRATION, || scope.edit(ctx, command.id, builder.clone())).await {
+            Ok(_) => summary.changed += 1,
+            Err(e) => {
+                report_retry_error(ctx, &operation, e).await;
+                summary.failed += 1;
+            }
+        }
+    }
+    for command in plan.delete {
+        let operation = format!("/{} 삭제 ({})", command.name, scope);
+        match retrying(&operation, RetryPolicy::REGISTRATION, || scope.delete(ctx, command.id)).await {
+            Ok(()) => summary.removed += 1,
+            Err(e) => {
+                report_retry_error(ctx, &operation, e).await;
+                summary.failed += 1;
+            }
+        }
+    }
+    summary
+}
+
+// 등록된 커맨드와 원하는 커맨드를 비교한 결과
+struct SyncPlan<'a> {
+    // 아직 없는 커맨드
+    create: Vec<(&'static str, &'a CreateCommand)>,
+    // 있지만 정의가 달라진 커맨드
+    edit: Vec<(&'a Command, &'a CreateCommand)>,
+    // 레지스트리에서 빠진 커맨드
+    delete: Vec<&'a Command>,
+}
+
+fn plan_sync<'a>(existing: &'a [Command], desired: &'a [(&'static str, CreateCommand)]) -> SyncPlan<'a> {
+    let mut plan = SyncPlan {
+        create: Vec::new(),
+        edit: Vec::new(),
+        delete: Vec::new(),
+    };
+    for (name, builder) in desired {
+        match existing.iter().find(|c| c.name == *name) {
+            None => plan.create.push((name, builder)),
+            Some(command) if !same_definition(command, builder) => plan.edit.push((command, builder)),
+            Some(_) => {}
+        }
+    }
+    plan.delete = existing
+        .iter()
+        .filter(|c| !desired.iter().any(|(name, _)| c.name == *name))
+        .collect();
+    plan
+}
+
+// 이름, 설명(번역 포함), 옵션, 기본 권한, 사용 가능한 곳이 같은지 비교.
+// 양쪽을 JSON으로 바꾼 뒤 기본값(빈 값, false, null)과 요청 로캘 기준 필드(*_localized)를 지워서 비교
+fn same_definition(existing: &Command, desired: &CreateCommand) -> bool {
+    let (Ok(existing), Ok(desired)) = (serde_json::to_value(existing), serde_json::to_value(desired)) else {
+        return false;
+    };
+    let mut existing = top_level(existing);
+    let desired = top_level(desired);
+    // 사용 가능한 곳을 지정하지 않은 커맨드(길드 커맨드)는 디스코드가 채운 값과 비교하지 않음
+    if !desired.contains_key("contexts") {
+        existing.remove("contexts");
+    }
+    existing == desired
+}
+
+const COMPARED_FIELDS: &[&str] = &[
+    "name",
+    "name_localizations",
+    "description",
+    "description_localizations",
+    "options",
+    "default_member_permissions",
+    "contexts",
+];
+
+fn top_level(value: Value) -> Map<String, Value> {
+    let Value::Object(map) = value else {
+        return Map::new();
+    };
+    let mut map: Map<String, Value> = map
+        .into_iter()
+        .filter(|(key, _)| COMPARED_FIELDS.contains(&key.as_str()))
+        .filter_map(|(key, value)| canonical(value).map(|v| (key, v)))
+        .collect();
+    // 순서만 다른 경우는 같은 설정
+    if let Some(Value::Array(contexts)) = map.get_mut("contexts") {
+        contexts.sort_by_key(|c| c.as_f64().map(|n| n as i64));
+    }
+    map
+}
+
+// 기본값은 None으로 (생략된 필드와 같게), 숫자는 1과 1.0이 같도록 실수로 통일
+fn canonical(value: Value) -> Option<Value> {
+    match value {
+        Value::Null | Value::Bool(false) => None,
+        Value::Array(items) if items.is_empty() => None,
+        Value::Object(map) if map.is_empty() => None,
+        Value::Array(items) => Some(Value::Array(items.into_iter().filter_map(canonical).collect())),
+        Value::Object(map) => Some(Value::Object(
+            map.into_iter()
+                .filter(|(key, _)| !key.ends_with("_localized"))
+                .filter_map(|(key, value)| canonical(value).map(|v| (key, v)))
+                .collect(),
+        )),
+        Value::Number(n) => Some(n.as_f64().and_then(Number::from_f64).map_or(Value::Number(n), Value::Number)),
+        other => Some(other),
+    }
+}
+
+// 이 범위에 등록된 커맨드를 모두 삭제하고 삭제한 수를 돌려줌. 하나라도 실패하면 거기서 멈춤
+pub async fn clear_commands(ctx: &Context, scope: Scope) -> Result<usize, (String, RetryError)> {
+    let operation = format!("등록된 커맨드 조회 ({})", scope);
+    let existing = retrying(&operation, RetryPolicy::REGISTRATION, || scope.fetch(ctx))
+        .await
+        .map_err(|e| (operation, e))?;
+    let mut deleted = 0;
+    for command in &existing {
+        let operation = format!("/{} 삭제 ({})", command.name, scope);
+        retrying(&operation, RetryPolicy::REGISTRATION, || scope.delete(ctx, command.id))
+            .await
+            .map_err(|e| (operation, e))?;
+        tracing::info!("커맨드 삭제 ({}): /{}", scope, command.name);
+        deleted += 1;
+    }
+    Ok(deleted)
+}
+
+// /clearcommands [global]: 길드(또는 글로벌) 커맨드를 모두 등록 해제. 다음 시작 때 다시 등록됨
+pub async fn handle_clearcommands(ctx: &Context, cmd: &CommandInteraction) {
+    let Some(guild_id) = cmd.guild_id else {
+        return;
+    };
+    let global = cmd
+        .data
+        .options
+        .iter()
+        .find(|o| o.name == "global")
+        .and_then(|o| o.value.as_bool())
+        .unwrap_or(false);
+
+    // 글로벌 커맨드는 모든 서버에 영향을 주므로 봇 소유자만
+    let content = if global && !is_owner(ctx, cmd.user.id).await {
+        "글로벌 커맨드 삭제는 봇 소유자만 할 수 있습니다.".to_string()
+    } else {
+        let scope = if global { Scope::Global } else { Scope::Guild(guild_id) };
+        match clear_commands(ctx, scope).await {
+            Ok(deleted) => format!("{} 커맨드 {}개를 삭제했습니다. 봇을 다시 시작하면 다시 등록됩니다.", scope, deleted),
+            Err((operation, e)) => {
+                report_retry_error(ctx, &operation, e).await;
+                format!("{} 커맨드를 모두 삭제하지 못했습니다. 잠시 후 다시 시도해주세요.", scope)
+            }
+        }
+    };
+    respond(
+        ctx,
+        cmd,
+        CreateInteractionResponseMessage::new()
+            .content(content)
+            .ephemeral(true),
+    )
+    .await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use serenity::all::{CommandOptionType, CreateCommandOption};
+
+    // 디스코드가 돌려주는 것처럼 id 등을 붙여 등록된 커맨드를 만듦
+    fn registered(id: u64, builder: &CreateCommand) -> Command {
+        let mut value = serde_json::to_value(builder).unwrap();
+        value["id"] = json!(id.to_string());
+        value["application_id"] = json!("1");
+        value["version"] = json!("1");
+        value["type"] = json!(1);
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn calc(description: &str) -> CreateCommand {
+        CreateCommand::new("calc")
+            .description(description)
+            .add_option(CreateCommandOption::new(CommandOptionType::String, "expr", "수식"))
+    }
+
+    fn plan_names(plan: &SyncPlan) -> (Vec<&'static str>, Vec<String>, Vec<String>) {
+        (
+            plan.create.iter().map(|(name, _)| *name).collect(),
+            plan.edit.iter().map(|(c, _)| c.name.clone()).collect(),
+            plan.delete.iter().map(|c| c.name.clone()).collect(),
+        )
+    }
+
+    // 같은 정의면 아무것도 하지 않음
+    #[test]
+    fn unchanged_commands_are_left_alone() {
+        let existing = vec![registered(10, &calc("계산")), registered(11, &CreateCommand::new("ping").description("핑"))];
+        let desired = vec![("calc", calc("계산")), ("ping", CreateCommand::new("ping").description("핑"))];
+        let plan = plan_sync(&existing, &desired);
+        assert_eq!(plan_names(&plan), (vec![], vec![], vec![]));
+    }
+
+    // 설명만 바뀌어도 수정, 기존 커맨드 id로
+    #[test]
+    fn changed_description_is_edited() {
+        let existing = vec![registered(10, &calc("계산"))];
+        let desired = vec![("calc", calc("수식을 계산합니다"))];
+        let plan = plan_sync(&existing, &desired);
+        assert_eq!(plan_names(&plan), (vec![], vec!["calc".to_string()], vec![]));
+        assert_eq!(plan.edit[0].0.id, CommandId::new(10));
+    }
+
+    // 없는 커맨드는 추가, 레지스트리에서 빠진 커맨드는 삭제
+    #[test]
+    fn added_and_removed_commands() {
+        let existing = vec![registered(10, &calc("계산")), registered(11, &CreateCommand::new("old").description("옛"))];
+        let desired = vec![("calc", calc("계산")), ("ping", CreateCommand::new("ping").description("핑"))];
+        let plan = plan_sync(&existing, &desired);
+        assert_eq!(plan_names(&plan), (vec!["ping"], vec![], vec!["old".to_string()]));
+        assert_eq!(plan.delete[0].id, CommandId::new(11));
+    }
+
+    // 등록된 것이 없으면 모두 추가, 원하는 것이 없으면 모두 삭제
+    #[test]
+    fn empty_sides() {
+        let desired = vec![("calc", calc("계산"))];
+        assert_eq!(plan_names(&plan_sync(&[], &desired)), (vec!["calc"], vec![], vec![]));
+        let existing = vec![registered(10, &calc("계산"))];
+        assert_eq!(plan_names(&plan_sync(&existing, &[])), (vec![], vec![], vec!["calc".to_string()]));
+    }
+}
@@ -0,0 +1,1710 @@
+use serenity::all::ChannelType;
+use serenity::all::CommandDataOptionValue;
+use serenity::all::CommandInteraction;
+use serenity::all::CommandOptionType;
+use serenity::all::ComponentInteraction;
+use serenity::all::CreateCommand;
+use serenity::all::CreateCommandOption;
+use serenity::all::CreateEmbed;
+use serenity::all::CreateInteractionResponse;
+use serenity::all::CreateInteractionResponseFollowup;
+use serenity::all::CreateInteractionResponseMessage;
+use serenity::all::EditInteractionResponse;
+use serenity::all::GuildId;
+use serenity::all::InteractionContext;
+use serenity::all::ModalInteraction;
+use serenity::all::Permissions;
+use serenity::all::Ready;
+use serenity::all::UserId;
+use serenity::async_trait;
+use serenity::prelude::*;
+use std::cell::Cell;
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+use crate::calc::{self, Complex, NumberMode};
+use crate::calc_buttons::{self, calc_buttons, calc_modal, handle_calc_component, handle_calc_modal};
+use crate::calc_cache::evaluate_cached;
+use crate::calc_log::{self, handle_calchistory};
+use crate::calc_session::{get_session, handle_calcmode};
+#[cfg(feature = "chime")]
+use crate::chime::handle_testchime;
+use crate::command_sync::{handle_clearcommands, sync_commands, Scope, SyncSummary};
+use crate::error::BotError;
+use crate::error_report::report_error;
+use crate::event_metrics::{self, Phase};
+use crate::feedback::{handle_feedback, MAX_FEEDBACK_LEN, MIN_FEEDBACK_LEN};
+use crate::formulas::{handle_calc_autocomplete, handle_calcstore};
+use crate::guild_config::{
+    check_command_channel, get_guild_config, handle_config, handle_permission, handle_setchannel, handle_setrole,
+    handle_voiceconfig, handle_voiceconfig_component, GuildConfig, HISTORY_EXTRA_KEYS, MAX_STREAK_MINUTES,
+    SETTINGS, VOICECONFIG_PREFIX,
+};
+use crate::invites::{handle_invitecreate, handle_invitelist, EXPIRY_CHOICES};
+use crate::locale::{command, option, string_choice};
+use crate::long_message::{
+    split_content, truncate, EMBED_DESCRIPTION_LIMIT, MAX_CHUNKS, MESSAGE_LIMIT,
+};
+use crate::maintenance::{
+    handle_announce, handle_announce_component, handle_reloadconfig, handle_shutdown, ANNOUNCE_PREFIX,
+};
+use crate::rate_limit::{CooldownScope, Cooldowns, RateLimiter};
+use crate::nick::{handle_nick, MAX_NICK_LEN};
+use crate::reminders::{handle_remind, MAX_MESSAGE_LEN};
+use crate::roleinfo::handle_roleinfo;
+use crate::scheduler::handle_jobs;
+use crate::setup::{handle_setup, handle_setup_component, SETUP_PREFIX};
+use crate::shards::{handle_shards, ShardManagerKey};
+use crate::storage::unix_now;
+use crate::streaks::handle_streak;
+use crate::slowmode::{handle_slowmode, MAX_SLOWMODE_SECS};
+use crate::tts_announce::handle_joinannounce;
+use crate::usage::{self, handle_usage};
+use crate::user_prefs::handle_timezone;
+use crate::voice_log::handle_voicelog;
+use crate::voice_stats::{
+    handle_adjust, handle_session, handle_starters, handle_voiceduration, handle_voicestats, handle_voicetop,
+    handle_voicetop_refresh, VOICETOP_REFRESH_PREFIX,
+};
+use crate::weekly_report::handle_weeklyreport;
+
+// 애플리케이션 소유자 (ready에서 조회)
+pub struct BotOwner;
+
+impl TypeMapKey for BotOwner {
+    type Value = UserId;
+}
+
+// 커맨드 분류. 일반 커맨드만 서버의 command_channels 제한을 받음
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommandCategory {
+    Utility,
+    Admin,
+}
+
+// 응답을 미룰지: 오래 걸리는 커맨드는 먼저 Defer로 응답하고 결과는 나중에 원래 응답을 수정해 전달
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Defer {
+    No,
+    Public,
+    Ephemeral,
+}
+
+// 응답을 미룬 커맨드가 이 시간 안에 끝나지 않으면 시간 초과 안내로 응답을 수정
+const DEFERRED_TIMEOUT: Duration = Duration::from_secs(30);
+// 서버 전용 커맨드를 DM에서 실행했을 때 응답
+pub const GUILD_ONLY: &str = "서버에서만 사용 가능합니다";
+
+tokio::task_local! {
+    // 지금 실행 중인 커맨드가 Defer로 응답했는지 (respond가 수정으로 전환)
+    static DEFERRED: Cell<bool>;
+}
+
+// 슬래시 커맨드 정의와 실행에 필요한 권한
+pub struct CommandSpec {
+    pub name: &'static str,
+    definition: fn() -> CreateCommand,
+    required_permissions: Permissions,
+    owner_only: bool,
+    cooldown: Option<(CooldownScope, Duration)>,
+    dm_allowed: bool,
+    category: CommandCategory,
+    defer: fn(&CommandInteraction) -> Defer,
+}
+
+impl CommandSpec {
+    const fn new(name: &'static str, definition: fn() -> CreateCommand) -> Self {
+        Self {
+            name,
+            definition,
+            required_permissions: Permissions::empty(),
+            owner_only: false,
+            cooldown: None,
+            dm_allowed: false,
+            category: CommandCategory::Utility,
+            defer: |_| Defer::No,
+        }
+    }
+
+    // 옵션을 보고 응답을 미룰지 결정 (모달을 띄우는 경우 등은 Defer::No여야 함)
+    const fn deferred(mut self, when: fn(&CommandInteraction) -> Defer) -> Self {
+        self.defer = when;
+        self
+    }
+
+    // 서버뿐 아니라 봇과의 DM에서도 쓸 수 있는 커맨드 (길드 정보에 의존하지 않아야 함)
+    const fn dm_allowed(mut self) -> Self {
+        self.dm_allowed = true;
+        self
+    }
+
+    // 실행 후 같은 사용자/길드가 duration 동안 다시 실행할 수 없음
+    const fn cooldown(mut self, scope: CooldownScope, duration: Duration) -> Self {
+        self.cooldown = Some((scope, duration));
+        self
+    }
+
+    // 봇 소유자만 실행할 수 있는 커맨드 (관리 커맨드로 분류)
+    const fn owner_only(mut self) -> Self {
+        self.owner_only = true;
+        self.category = CommandCategory::Admin;
+        self
+    }
+
+    // 실행에 필요한 권한 선언. 등록 시 기본 권한으로 설정되고, 실행 시에도 다시 확인 (관리 커맨드로 분류)
+    const fn requires_permissions(mut self, permissions: Permissions) -> Self {
+        self.required_permissions = permissions;
+        self.category = CommandCategory::Admin;
+        self
+    }
+
+    pub fn create(&self) -> CreateCommand {
+        let cmd = (self.definition)();
+        if self.required_permissions.is_empty() {
+            cmd
+        } else {
+            cmd.default_member_permissions(self.required_permissions)
+        }
+    }
+
+    // 글로벌 커맨드는 DM 전용. 서버에서는 길드 커맨드만 보이도록 서버 컨텍스트는 빼고 등록
+    fn create_global(&self) -> CreateCommand {
+        self.create()
+            .contexts(vec![InteractionContext::BotDm, InteractionContext::PrivateChannel])
+    }
+
+    // 서버가 /permission으로 역할 규칙을 정할 수 있는 커맨드 (소유자 전용과 /permission 자신은 제외)
+    pub fn allows_role_rule(&self) -> bool {
+        !self.owner_only && self.name != "permission"
+    }
+
+    // 서버 관리자가 UI에서 커맨드 권한을 바꿀 수 있으므로 호출자의 실제 권한을 확인
+    fn is_permitted(&self, cmd: &CommandInteraction) -> bool {
+        if self.required_permissions.is_empty() {
+            return true;
+        }
+        cmd.member
+            .as_ref()
+            .and_then(|m| m.permissions)
+            .is_some_and(|p| p.administrator() || p.contains(self.required_permissions))
+    }
+}
+
+// 봇이 제공하는 모든 슬래시 커맨드
+pub fn registry() -> Vec<CommandSpec> {
+    vec![
+        CommandSpec::new("calc", calc_command).dm_allowed().deferred(calc_defer),
+        CommandSpec::new("calcmode", calcmode_command).dm_allowed(),
+        CommandSpec::new("calchelp", calchelp_command).dm_allowed(),
+        CommandSpec::new("calchistory", calchistory_command).dm_allowed(),
+        CommandSpec::new("calcstore", calcstore_command).deferred(|_| Defer::Ephemeral),
+        CommandSpec::new("setchannel", setchannel_command)
+            .requires_permissions(Permissions::MANAGE_GUILD),
+        CommandSpec::new("setrole", setrole_command)
+            .requires_permissions(Permissions::MANAGE_GUILD),
+        CommandSpec::new("shards", shards_command).owner_only(),
+        CommandSpec::new("jobs", jobs_command).owner_only(),
+        CommandSpec::new("shutdown", shutdown_command).owner_only().dm_allowed(),
+        CommandSpec::new("reloadconfig", reloadconfig_command).owner_only().dm_allowed(),
+        CommandSpec::new("announce", announce_command).owner_only().dm_allowed(),
+        CommandSpec::new("voicestats", voicestats_command),
+        CommandSpec::new("voiceduration", voiceduration_command),
+        CommandSpec::new("session", session_command),
+        CommandSpec::new("voicetop", voicetop_command)
+            .cooldown(CooldownScope::Guild, Duration::from_secs(30)),
+        CommandSpec::new("starters", starters_command)
+            .cooldown(CooldownScope::Guild, Duration::from_secs(30)),
+        CommandSpec::new("voiceconfig", voiceconfig_command)
+            .requires_permissions(Permissions::MANAGE_GUILD),
+        CommandSpec::new("adjust", adjust_command).requires_permissions(Permissions::MANAGE_GUILD),
+        CommandSpec::new("config", config_command)
+            .requires_permissions(Permissions::MANAGE_GUILD),
+        CommandSpec::new("slowmode", slowmode_command)
+            .requires_permissions(Permissions::MANAGE_CHANNELS),
+        CommandSpec::new("invitecreate", invitecreate_command),
+        CommandSpec::new("nick", nick_command),
+        CommandSpec::new("roleinfo", roleinfo_command),
+        CommandSpec::new("voicelog", voicelog_command)
+            .requires_permissions(Permissions::MANAGE_GUILD),
+        CommandSpec::new("invitelist", invitelist_command)
+            .requires_permissions(Permissions::MANAGE_GUILD)
+            .cooldown(CooldownScope::User, Duration::from_secs(10)),
+        CommandSpec::new("remind", remind_command).dm_allowed(),
+        CommandSpec::new("timezone", timezone_command).dm_allowed(),
+        CommandSpec::new("weeklyreport", weeklyreport_command).dm_allowed(),
+        CommandSpec::new("joinannounce", joinannounce_command).dm_allowed(),
+        CommandSpec::new("streak", streak_command),
+        CommandSpec::new("feedback", feedback_command).dm_allowed(),
+        CommandSpec::new("usage", usage_command).requires_permissions(Permissions::MANAGE_GUILD),
+        CommandSpec::new("permission", permission_command)
+            .requires_permissions(Permissions::ADMINISTRATOR),
+        CommandSpec::new("setup", setup_command).requires_permissions(Permissions::MANAGE_GUILD),
+        CommandSpec::new("clearcommands", clearcommands_command)
+            .requires_permissions(Permissions::ADMINISTRATOR)
+            .deferred(|_| Defer::Ephemeral),
+        #[cfg(feature = "chime")]
+        CommandSpec::new("testchime", testchime_command)
+            .requires_permissions(Permissions::MANAGE_GUILD)
+            .deferred(|_| Defer::Ephemeral),
+    ]
+}
+
+// /calc 수식 최대 길이 (바이트). 결과 메시지 길이 제한과 토크나이저 부담을 막기 위함
+pub const MAX_EXPRESSION_LEN: usize = 256;
+
+// 문자열 옵션 값 검사: 길이(바이트)와 제어 문자. 문제가 있으면 사용자에게 보여줄 문구
+pub fn validate_text_input(value: &str, max_len: usize) -> Result<(), String> {
+    if value.len() > max_len {
+        return Err(format!(
+            "입력이 너무 깁니다. 최대 {}바이트까지 입력할 수 있습니다. (현재 {}바이트)",
+            max_len,
+            value.len()
+        ));
+    }
+    if value.chars().any(char::is_control) {
+        return Err("입력에 제어 문자를 넣을 수 없습니다.".to_string());
+    }
+    Ok(())
+}
+
+fn calc_command() -> CreateCommand {
+    command("calc", "수식을 PEMDAS 우선순위로 계산합니다")
+        .add_option(
+            option(
+                CommandOptionType::String,
+                "expr",
+                "계산할 수식 (비우면 여러 줄 입력 창)",
+            )
+            .max_length(MAX_EXPRESSION_LEN as u16)
+            .set_autocomplete(true),
+        )
+        .add_option(option(
+            CommandOptionType::Boolean,
+            "explain",
+            "계산 과정을 단계별로 설명합니다",
+        ))
+}
+
+fn calchelp_command() -> CreateCommand {
+    command("calchelp", "계산기가 지원하는 연산자와 함수를 보여줍니다")
+}
+
+fn calcstore_command() -> CreateCommand {
+    command("calcstore", "이 서버에서 /calc 자동 완성으로 쓸 수식을 관리합니다")
+        .add_option(
+            option(CommandOptionType::SubCommand, "save", "수식을 이름을 붙여 저장합니다 (서버 관리 권한)")
+                .add_sub_option(
+                    option(CommandOptionType::String, "name", "수식 이름")
+                        .required(true)
+                        .max_length(32),
+                )
+                .add_sub_option(
+                    option(CommandOptionType::String, "expr", "저장할 수식")
+                        .required(true)
+                        .max_length(100),
+                ),
+        )
+        .add_option(
+            option(CommandOptionType::SubCommand, "delete", "저장한 수식을 지웁니다 (서버 관리 권한)")
+                .add_sub_option(
+                    option(CommandOptionType::String, "name", "지울 수식 이름")
+                        .required(true)
+                        .max_length(32),
+                ),
+        )
+        .add_option(option(
+            CommandOptionType::SubCommand,
+            "list",
+            "저장한 수식 목록을 봅니다",
+        ))
+}
+
+fn calchistory_command() -> CreateCommand {
+    command("calchistory", "최근 /calc 계산 기록을 봅니다 (DM에서는 내 기록)")
+        .add_option(option(
+            CommandOptionType::SubCommand,
+            "global",
+            "최근 계산 10개를 봅니다 (서버: 모든 사용자, DM: 나)",
+        ))
+        .add_option(
+            option(
+                CommandOptionType::SubCommand,
+                "logging",
+                "이 서버의 /calc 계산을 기록할지 정합니다 (서버 관리 권한 필요)",
+            )
+            .add_sub_option(
+                option(CommandOptionType::String, "state", "기록 여부")
+                    .add_string_choice("on", "on")
+                    .add_string_choice("off", "off")
+                    .required(true),
+            ),
+        )
+}
+
+fn calcmode_command() -> CreateCommand {
+    command("calcmode", "계산기 모드를 설정합니다")
+        .add_option(
+            option(
+                CommandOptionType::Boolean,
+                "complex",
+                "복소수 결과 허용 (예: sqrt(-1) = i)",
+            )
+            .required(true),
+        )
+}
+
+fn setchannel_command() -> CreateCommand {
+    command("setchannel", "보이스 채널 알림을 보낼 텍스트 채널을 설정합니다")
+        .add_option(
+            option(CommandOptionType::Channel, "channel", "알림 채널")
+                .channel_types(vec![ChannelType::Text])
+                .required(true),
+        )
+}
+
+fn setrole_command() -> CreateCommand {
+    command("setrole", "채널 활성화 시 멘션할 역할을 설정합니다 (비우면 멘션 안 함)")
+        .add_option(option(
+            CommandOptionType::Role,
+            "role",
+            "멘션할 역할",
+        ))
+}
+
+fn shards_command() -> CreateCommand {
+    command("shards", "샤드 상태를 확인합니다 (봇 소유자 전용)")
+}
+
+fn jobs_command() -> CreateCommand {
+    command("jobs", "예약 작업과 다음 실행 시각을 확인합니다 (봇 소유자 전용)")
+}
+
+fn shutdown_command() -> CreateCommand {
+    command("shutdown", "봇을 안전하게 종료합니다 (봇 소유자 전용)")
+}
+
+fn reloadconfig_command() -> CreateCommand {
+    command("reloadconfig", "설정 파일을 다시 읽습니다 (봇 소유자 전용)")
+}
+
+fn announce_command() -> CreateCommand {
+    command("announce", "모든 서버의 알림 채널에 공지를 보냅니다 (봇 소유자 전용)")
+        .add_option(
+            option(CommandOptionType::String, "text", "공지 내용")
+                .max_length(1900)
+                .required(true),
+        )
+}
+
+fn voicestats_command() -> CreateCommand {
+    command("voicestats", "누적 보이스 채널 이용 시간을 확인합니다")
+        .add_option(option(
+            CommandOptionType::User,
+            "user",
+            "조회할 사용자 (비우면 본인)",
+        ))
+}
+
+fn voiceduration_command() -> CreateCommand {
+    command("voiceduration", "보이스 채널이 활성화된 지 얼마나 됐는지 확인합니다").add_option(
+        option(CommandOptionType::Channel, "channel", "확인할 보이스 채널 (비우면 활성화된 채널 전체)")
+            .channel_types(vec![ChannelType::Voice, ChannelType::Stage]),
+    )
+}
+
+fn session_command() -> CreateCommand {
+    command("session", "활성화 번호로 보이스 채널 활성화 기록을 확인합니다").add_option(
+        option(CommandOptionType::Integer, "id", "서버의 활성화 번호 (1부터)")
+            .min_int_value(1)
+            .required(true),
+    )
+}
+
+fn voicetop_command() -> CreateCommand {
+    command("voicetop", "보이스 채널 이용 시간 순위를 확인합니다")
+}
+
+fn starters_command() -> CreateCommand {
+    command("starters", "이번 달 보이스 채널을 가장 많이 활성화한 멤버를 확인합니다")
+}
+
+fn adjust_command() -> CreateCommand {
+    let user_option = || option(CommandOptionType::User, "user", "대상 사용자").required(true);
+    let duration_option =
+        || option(CommandOptionType::String, "duration", "기간 (예: 45m, 2h30m, 1d)").required(true);
+    let reason_option = || option(CommandOptionType::String, "reason", "사유 (감사 로그에 기록)").max_length(200);
+    command("adjust", "기록된 누적 보이스 시간을 보정합니다")
+        .add_option(
+            option(CommandOptionType::SubCommand, "add", "누적 보이스 시간을 더합니다")
+                .add_sub_option(user_option())
+                .add_sub_option(duration_option())
+                .add_sub_option(reason_option()),
+        )
+        .add_option(
+            option(CommandOptionType::SubCommand, "remove", "누적 보이스 시간을 뺍니다 (0 밑으로는 줄지 않음)")
+                .add_sub_option(user_option())
+                .add_sub_option(duration_option())
+                .add_sub_option(reason_option()),
+        )
+        .add_option(
+            option(CommandOptionType::SubCommand, "reset", "누적 보이스 시간을 0으로 되돌립니다")
+                .add_sub_option(user_option())
+                .add_sub_option(reason_option()),
+        )
+}
+
+fn voiceconfig_command() -> CreateCommand {
+    command("voiceconfig", "보이스 통계와 알림 설정을 변경합니다")
+        .add_option(
+            option(
+                CommandOptionType::SubCommand,
+                "privacy",
+                "보이스 통계 공개 범위를 설정합니다",
+            )
+            .add_sub_option(
+                [("모두 공개", "public"), ("서버 멤버만", "members"), ("관리자만", "admin")]
+                    .into_iter()
+                    .fold(
+                        option(CommandOptionType::String, "level", "공개 범위"),
+                        |option, (name, value)| string_choice(option, name, value),
+                    )
+                    .required(true),
+            ),
+        )
+        .add_option(
+            option(
+                CommandOptionType::SubCommand,
+                "notifybots",
+                "다른 봇의 보이스 입장/퇴장 알림을 켜거나 끕니다",
+            )
+            .add_sub_option(
+                option(CommandOptionType::String, "state", "알림 여부")
+                    .add_string_choice("on", "on")
+                    .add_string_choice("off", "off")
+                    .required(true),
+            ),
+        )
+        .add_option(
+            option(
+                CommandOptionType::SubCommand,
+                "useembeds",
+                "알림을 임베드로 보낼지 일반 텍스트로 보낼지 정합니다",
+            )
+            .add_sub_option(
+                option(CommandOptionType::String, "state", "임베드 사용 여부")
+                    .add_string_choice("on", "on")
+                    .add_string_choice("off", "off")
+                    .required(true),
+            ),
+        )
+        .add_option(
+            option(
+                CommandOptionType::SubCommand,
+                "streak",
+                "연속 보이스 기록에 하루로 인정할 최소 접속 시간을 정합니다",
+            )
+            .add_sub_option(
+                option(CommandOptionType::Integer, "minutes", "하루 최소 접속 시간 (분)")
+                    .min_int_value(1)
+                    .max_int_value(u64::from(MAX_STREAK_MINUTES))
+                    .required(true),
+            ),
+        )
+        .add_option(
+            option(
+                CommandOptionType::SubCommand,
+                "statusname",
+                "채널이 활성화된 동안 이름 앞에 표시를 붙일지 정합니다",
+            )
+            .add_sub_option(
+                option(CommandOptionType::Channel, "channel", "대상 보이스 채널")
+                    .channel_types(vec![ChannelType::Voice, ChannelType::Stage])
+                    .required(true),
+            )
+            .add_sub_option(
+                option(CommandOptionType::String, "state", "표시 여부")
+                    .add_string_choice("on", "on")
+                    .add_string_choice("off", "off")
+                    .required(true),
+            ),
+        )
+        .add_option(
+            [
+                ("join", "입장 알림 문구를 바꿉니다 ({user}, {channel}, {count})"),
+                ("leave", "퇴장 알림 문구를 바꿉니다 ({user}, {channel}, {count})"),
+                ("activate", "활성화 알림 문구를 바꿉니다 ({user}, {channel}, {count})"),
+                ("deactivate", "비활성화 알림 문구를 바꿉니다 ({channel}, {duration})"),
+            ]
+            .into_iter()
+            .fold(
+                option(CommandOptionType::SubCommandGroup, "message", "알림 문구를 바꿉니다"),
+                |group, (name, description)| {
+                    group.add_sub_option(
+                        option(CommandOptionType::SubCommand, name, description).add_sub_option(option(
+                            CommandOptionType::String,
+                            "template",
+                            "새 문구 (비우면 기본 문구로)",
+                        )),
+                    )
+                },
+            ),
+        )
+        .add_option(option(
+            CommandOptionType::SubCommand,
+            "reset",
+            "서버 설정 전체를 기본값으로 되돌립니다 (관리자 전용)",
+        ))
+}
+
+// 설정 키 선택지는 guild_config::SETTINGS에서 생성
+fn config_command() -> CreateCommand {
+    let key_option = || {
+        SETTINGS.iter().fold(
+            option(CommandOptionType::String, "key", "설정 이름").required(true),
+            |option, spec| option.add_string_choice(spec.key, spec.key),
+        )
+    };
+    command("config", "서버 설정을 확인하거나 변경합니다")
+        .add_option(option(
+            CommandOptionType::SubCommand,
+            "show",
+            "모든 설정의 현재 값과 기본값을 표시합니다",
+        ))
+        .add_option(
+            option(CommandOptionType::SubCommand, "set", "설정 값을 변경합니다")
+                .add_sub_option(key_option())
+                .add_sub_option(
+                    option(
+                        CommandOptionType::String,
+                        "value",
+                        "새 값 (#채널, 여러 #채널, @역할, on/off, 분, 선택지, none으로 비우기)",
+                    )
+                    .required(true),
+                ),
+        )
+        .add_option(
+            option(
+                CommandOptionType::SubCommand,
+                "reset",
+                "설정을 기본값으로 되돌립니다",
+            )
+            .add_sub_option(key_option()),
+        )
+        .add_option(
+            option(CommandOptionType::SubCommand, "history", "최근 설정 변경 기록 20개")
+                .add_sub_option(
+                    SETTINGS
+                        .iter()
+                        .map(|spec| spec.key)
+                        .chain(HISTORY_EXTRA_KEYS.iter().copied())
+                        .fold(
+                            option(CommandOptionType::String, "key", "이 설정만 보기"),
+                            |option, key| option.add_string_choice(key, key),
+                        ),
+                ),
+        )
+        .add_option(option(
+            CommandOptionType::SubCommand,
+            "export",
+            "채널/역할을 뺀 설정을 JSON 파일로 내보냅니다 (관리자 전용)",
+        ))
+        .add_option(
+            option(
+                CommandOptionType::SubCommand,
+                "import",
+                "내보낸 설정 파일을 가져옵니다 (관리자 전용)",
+            )
+            .add_sub_option(
+                option(CommandOptionType::Attachment, "file", "/config export 로 만든 JSON 파일")
+                    .required(true),
+            ),
+        )
+        .add_option(
+            option(
+                CommandOptionType::SubCommandGroup,
+                "mention",
+                "채널 활성화 알림에서 멘션할 역할/사용자를 관리합니다",
+            )
+            .add_sub_option(
+                option(CommandOptionType::SubCommand, "add", "활성화 알림 멘션 대상을 추가합니다")
+                    .add_sub_option(mention_target_option())
+                    .add_sub_option(mention_channel_option()),
+            )
+            .add_sub_option(
+                option(CommandOptionType::SubCommand, "remove", "활성화 알림 멘션 대상을 뺍니다")
+                    .add_sub_option(mention_target_option())
+                    .add_sub_option(mention_channel_option()),
+            ),
+        )
+        .add_option(
+            option(CommandOptionType::SubCommand, "enable", "꺼 둔 커맨드를 다시 켭니다")
+                .add_sub_option(
+                    option(CommandOptionType::String, "command", "커맨드 이름 (예: calc)")
+                        .required(true),
+                ),
+        )
+        .add_option(
+            option(CommandOptionType::SubCommand, "disable", "이 서버에서 커맨드를 끕니다")
+                .add_sub_option(
+                    option(CommandOptionType::String, "command", "커맨드 이름 (예: calc)")
+                        .required(true),
+                ),
+        )
+}
+
+fn mention_target_option() -> CreateCommandOption {
+    option(CommandOptionType::Mentionable, "target", "멘션할 역할 또는 사용자").required(true)
+}
+
+fn mention_channel_option() -> CreateCommandOption {
+    option(
+        CommandOptionType::Channel,
+        "channel",
+        "이 보이스 채널에만 적용 (비우면 모든 채널)",
+    )
+    .channel_types(vec![ChannelType::Voice, ChannelType::Stage])
+}
+
+fn nick_command() -> CreateCommand {
+    command("nick", "봇을 통해 닉네임을 바꿉니다")
+        .add_option(
+            option(CommandOptionType::String, "name", "새 닉네임")
+                .max_length(MAX_NICK_LEN)
+                .required(true),
+        )
+        .add_option(option(
+            CommandOptionType::User,
+            "user",
+            "닉네임을 바꿀 멤버 (닉네임 관리 권한 필요)",
+        ))
+}
+
+fn slowmode_command() -> CreateCommand {
+    let channel_option = || {
+        option(CommandOptionType::Channel, "channel", "대상 채널 (비우면 현재 채널)")
+            .channel_types(vec![ChannelType::Text])
+    };
+    command("slowmode", "텍스트 채널의 슬로우 모드를 관리합니다")
+        .add_option(
+            option(CommandOptionType::SubCommand, "set", "슬로우 모드를 설정합니다")
+                .add_sub_option(
+                    option(
+                        CommandOptionType::Integer,
+                        "seconds",
+                        "메시지 간 대기 시간 (초, 0이면 끔)",
+                    )
+                    .min_int_value(0)
+                    .max_int_value(u64::from(MAX_SLOWMODE_SECS))
+                    .required(true),
+                )
+                .add_sub_option(channel_option()),
+        )
+        .add_option(
+            option(
+                CommandOptionType::SubCommand,
+                "status",
+                "현재 슬로우 모드를 확인합니다",
+            )
+            .add_sub_option(channel_option()),
+        )
+}
+
+fn invitecreate_command() -> CreateCommand {
+    let expires_in = EXPIRY_CHOICES.iter().fold(
+        option(CommandOptionType::String, "expires_in", "만료 시간 (기본 24시간)"),
+        |option, (value, _, label)| string_choice(option, label, value),
+    );
+    command("invitecreate", "보이스 채널 초대 링크를 만듭니다")
+        .add_option(
+            option(CommandOptionType::Channel, "channel", "초대할 보이스 채널")
+                .channel_types(vec![ChannelType::Voice, ChannelType::Stage])
+                .required(true),
+        )
+        .add_option(
+            option(
+                CommandOptionType::Integer,
+                "max_uses",
+                "사용 가능 횟수 (기본 1회, 2회 이상은 서버 관리 권한 필요)",
+            )
+            .min_int_value(1)
+            .max_int_value(100),
+        )
+        .add_option(expires_in)
+}
+
+fn invitelist_command() -> CreateCommand {
+    command("invitelist", "봇이 만든 유효한 초대 링크를 확인합니다")
+}
+
+fn voicelog_command() -> CreateCommand {
+    command("voicelog", "보이스 채널 입장/퇴장 기록을 확인합니다")
+        .add_option(option(CommandOptionType::User, "user", "사용자로 거르기"))
+        .add_option(
+            option(CommandOptionType::Channel, "channel", "채널로 거르기")
+                .channel_types(vec![ChannelType::Voice, ChannelType::Stage]),
+        )
+        .add_option(option(
+            CommandOptionType::String,
+            "date",
+            "날짜 (YYYY-MM-DD, UTC, 비우면 오늘, 최근 7일까지)",
+        ))
+        .add_option(
+            option(CommandOptionType::Integer, "page", "페이지").min_int_value(1),
+        )
+}
+
+fn timezone_command() -> CreateCommand {
+    command("timezone", "시간을 표시할 때 사용할 내 시간대를 설정합니다")
+        .add_option(
+            option(CommandOptionType::SubCommand, "set", "시간대를 설정합니다")
+                .add_sub_option(
+                    option(CommandOptionType::String, "name", "IANA 시간대 이름 (예: Asia/Seoul)")
+                        .required(true),
+                ),
+        )
+        .add_option(option(
+            CommandOptionType::SubCommand,
+            "clear",
+            "시간대 설정을 지우고 UTC로 표시합니다",
+        ))
+}
+
+fn weeklyreport_command() -> CreateCommand {
+    command("weeklyreport", "매주 보이스 시간 요약 DM을 받을지 설정합니다")
+        .add_option(option(
+            CommandOptionType::SubCommand,
+            "opt-in",
+            "매주 일요일에 지난 7일 요약을 DM으로 받습니다",
+        ))
+        .add_option(option(
+            CommandOptionType::SubCommand,
+            "opt-out",
+            "주간 요약 DM을 그만 받습니다",
+        ))
+}
+
+fn joinannounce_command() -> CreateCommand {
+    command("joinannounce", "보이스 채널에 들어올 때 내 이름을 TTS로 읽을지 설정합니다")
+        .add_option(option(
+            CommandOptionType::SubCommand,
+            "opt-in",
+            "서버가 입장 안내를 켜 두었다면 내 이름도 읽습니다",
+        ))
+        .add_option(option(
+            CommandOptionType::SubCommand,
+            "opt-out",
+            "내 이름은 읽지 않습니다",
+        ))
+}
+
+fn streak_command() -> CreateCommand {
+    command("streak", "연속 보이스 활동 기록을 확인합니다")
+        .add_option(option(
+            CommandOptionType::SubCommand,
+            "show",
+            "내 연속 보이스 활동 일수를 확인합니다",
+        ))
+        .add_option(
+            option(
+                CommandOptionType::SubCommand,
+                "remind",
+                "연속 기록이 끊기기 전 저녁에 DM으로 알려줄지 정합니다",
+            )
+            .add_sub_option(
+                option(CommandOptionType::String, "state", "알림 여부")
+                    .add_string_choice("on", "on")
+                    .add_string_choice("off", "off")
+                    .required(true),
+            ),
+        )
+}
+
+fn feedback_command() -> CreateCommand {
+    let user_option = || option(CommandOptionType::User, "user", "대상 사용자").required(true);
+    command("feedback", "봇 개발자에게 버그 제보나 제안을 보냅니다")
+        .add_option(
+            option(CommandOptionType::SubCommand, "send", "피드백을 보냅니다").add_sub_option(
+                option(CommandOptionType::String, "text", "버그 제보나 제안 내용")
+                    .min_length(MIN_FEEDBACK_LEN as u16)
+                    .max_length(MAX_FEEDBACK_LEN)
+                    .required(true),
+            ),
+        )
+        .add_option(
+            option(CommandOptionType::SubCommand, "block", "사용자의 피드백을 막습니다 (봇 소유자 전용)")
+                .add_sub_option(user_option()),
+        )
+        .add_option(
+            option(CommandOptionType::SubCommand, "unblock", "피드백 차단을 해제합니다 (봇 소유자 전용)")
+                .add_sub_option(user_option()),
+        )
+}
+
+fn remind_command() -> CreateCommand {
+    command("remind", "정해진 시간 뒤에 알림을 보냅니다")
+        .add_option(
+            option(CommandOptionType::SubCommand, "set", "리마인더를 예약합니다")
+                .add_sub_option(
+                    option(CommandOptionType::String, "in", "기간 (예: 10m, 1h30m, 2d)")
+                        .required(true),
+                )
+                .add_sub_option(
+                    option(CommandOptionType::String, "message", "알림 내용")
+                        .max_length(MAX_MESSAGE_LEN)
+                        .required(true),
+                )
+                .add_sub_option(option(
+                    CommandOptionType::Boolean,
+                    "dm",
+                    "이 채널 대신 DM으로 받습니다",
+                )),
+        )
+        .add_option(
+            option(CommandOptionType::SubCommand, "cancel", "리마인더를 취소합니다")
+                .add_sub_option(
+                    option(CommandOptionType::Integer, "id", "리마인더 ID (/remind list)")
+                        .min_int_value(1)
+                        .required(true),
+                ),
+        )
+        .add_option(option(
+            CommandOptionType::SubCommand,
+            "list",
+            "대기 중인 리마인더를 확인합니다",
+        ))
+}
+
+fn usage_command() -> CreateCommand {
+    command("usage", "커맨드 사용 통계를 확인합니다")
+        .add_option(option(
+            CommandOptionType::SubCommand,
+            "guild",
+            "이 서버의 최근 7일 통계",
+        ))
+        .add_option(option(
+            CommandOptionType::SubCommand,
+            "global",
+            "모든 서버의 최근 7일 통계 (봇 소유자 전용)",
+        ))
+}
+
+fn setup_command() -> CreateCommand {
+    command("setup", "알림 채널, 멘션 역할 등을 고르는 설정 마법사를 엽니다")
+}
+
+fn roleinfo_command() -> CreateCommand {
+    command("roleinfo", "역할의 색상, 위치, 멤버 수, 주요 권한을 확인합니다").add_option(
+        option(CommandOptionType::Role, "role", "확인할 역할").required(true),
+    )
+}
+
+fn clearcommands_command() -> CreateCommand {
+    command("clearcommands", "이 서버에 등록된 슬래시 커맨드를 모두 삭제합니다")
+        .add_option(option(
+            CommandOptionType::Boolean,
+            "global",
+            "서버 커맨드 대신 글로벌 커맨드를 삭제 (봇 소유자 전용)",
+        ))
+}
+
+#[cfg(feature = "chime")]
+fn testchime_command() -> CreateCommand {
+    command("testchime", "채널 활성화 알림음을 지금 재생해 봅니다").add_option(
+        option(CommandOptionType::Channel, "channel", "재생할 보이스 채널 (비우면 내가 있는 채널)")
+            .channel_types(vec![ChannelType::Voice, ChannelType::Stage]),
+    )
+}
+
+fn permission_command() -> CreateCommand {
+    command("permission", "커맨드별로 필요한 역할을 정합니다")
+        .add_option(
+            option(CommandOptionType::SubCommand, "set", "커맨드에 필요한 역할 지정")
+                .add_sub_option(
+                    option(CommandOptionType::String, "command", "커맨드 이름 (예: voicetop)")
+                        .required(true),
+                )
+                .add_sub_option(
+                    option(CommandOptionType::Role, "role", "필요한 역할").required(true),
+                ),
+        )
+        .add_option(
+            option(CommandOptionType::SubCommand, "clear", "역할 규칙 삭제")
+                .add_sub_option(
+                    option(CommandOptionType::String, "command", "커맨드 이름")
+                        .required(true),
+                ),
+        )
+        .add_option(option(
+            CommandOptionType::SubCommand,
+            "list",
+            "역할 규칙 목록",
+        ))
+}
+
+// 이미 등록한 커맨드 (재연결 때마다 ready가 다시 와도 한 번만 등록).
+// force면 변경 여부와 관계없이 전체를 덮어씀 (--force-register)
+pub struct RegistrationState {
+    global: OnceLock<bool>,
+    guilds: Mutex<HashSet<GuildId>>,
+    force: bool,
+}
+
+impl RegistrationState {
+    pub fn new(force: bool) -> Self {
+        Self {
+            global: OnceLock::new(),
+            guilds: Mutex::new(HashSet::new()),
+            force,
+        }
+    }
+
+    // 글로벌 커맨드를 이번에 등록할 차례인지 (처음 부른 쪽만 true)
+    fn claim_global(&self) -> bool {
+        self.global.set(true).is_ok()
+    }
+
+    // 이 길드 커맨드를 이번에 등록할 차례인지 (처음 부른 쪽만 true)
+    async fn claim_guild(&self, guild_id: GuildId) -> bool {
+        self.guilds.lock().await.insert(guild_id)
+    }
+
+    // 실패했거나 설정이 바뀌어 다음에 다시 등록하도록
+    async fn release_guild(&self, guild_id: GuildId) {
+        self.guilds.lock().await.remove(&guild_id);
+    }
+}
+
+pub struct CommandsRegistered;
+
+impl TypeMapKey for CommandsRegistered {
+    type Value = Arc<RegistrationState>;
+}
+
+async fn registration_state(ctx: &Context) -> Option<Arc<RegistrationState>> {
+    let data = ctx.data.read().await;
+    data.get::<CommandsRegistered>().cloned()
+}
+
+// 글로벌로 등록할 커맨드: DM에서 쓸 수 있는 것만
+fn global_commands() -> Vec<(&'static str, CreateCommand)> {
+    registry()
+        .iter()
+        .filter(|spec| spec.dm_allowed)
+        .map(|spec| (spec.name, spec.create_global()))
+        .collect()
+}
+
+// 길드에 등록할 커맨드: 서버에서 끈 커맨드는 빼므로 이미 등록돼 있으면 삭제됨
+fn guild_commands(config: &GuildConfig) -> Vec<(&'static str, CreateCommand)> {
+    registry()
+        .iter()
+        .filter(|spec| !config.is_disabled(spec.name))
+        .map(|spec| (spec.name, spec.create()))
+        .collect()
+}
+
+// 글로벌(DM) 커맨드 등록 (프로세스당 한 번). 이미 등록된 것과 달라진 커맨드만 보냄.
+// 서버에서 보이는 커맨드는 길드마다 따로 등록하므로 끈 커맨드가 글로벌 목록으로 다시 보이지 않음.
+// 이번에 동기화했으면 결과를 돌려줌
+pub async fn register_global_commands(ctx: &Context) -> Option<SyncSummary> {
+    let state = registration_state(ctx).await;
+    if let Some(state) = &state
+        && !state.claim_global()
+    {
+        return None;
+    }
+    let force = state.is_some_and(|s| s.force);
+    let summary = sync_commands(ctx, Scope::Global, global_commands(), force).await;
+    tracing::info!("커맨드 동기화 (글로벌): {}", summary);
+    Some(summary)
+}
+
+// 길드 스코프 커맨드 등록 (길드마다 한 번, 실패하면 다음 기회에 다시 시도)
+pub async fn register_guild_commands(ctx: &Context, guild_id: GuildId) -> Option<SyncSummary> {
+    let state = registration_state(ctx).await;
+    if let Some(state) = &state
+        && !state.claim_guild(guild_id).await
+    {
+        return None;
+    }
+    let force = state.as_ref().is_some_and(|s| s.force);
+    let config = get_guild_config(ctx, guild_id).await;
+    let summary = sync_commands(ctx, Scope::Guild(guild_id), guild_commands(&config), force).await;
+    if summary.added + summary.changed + summary.removed + summary.failed > 0 {
+        tracing::info!("커맨드 동기화 (길드 {}): {}", guild_id, summary);
+    }
+    if summary.failed > 0 && let Some(state) = &state {
+        state.release_guild(guild_id).await;
+    }
+    Some(summary)
+}
+
+// 설정이 바뀐 길드의 커맨드 목록을 다시 맞춤 (/config enable|disable)
+pub async fn resync_guild_commands(ctx: &Context, guild_id: GuildId) {
+    if let Some(state) = registration_state(ctx).await {
+        state.release_guild(guild_id).await;
+    }
+    register_guild_commands(ctx, guild_id).await;
+}
+
+// `aurobot register-commands [--guild <id>]`: 로그인해서 커맨드만 동기화하고 모든 샤드를 종료.
+// 실패한 커맨드가 있으면 failed를 세움 (종료 코드용)
+pub struct RegisterOnly {
+    pub guild: Option<GuildId>,
+    pub failed: Arc<AtomicBool>,
+}
+
+#[async_trait]
+impl EventHandler for RegisterOnly {
+    async fn ready(&self, ctx: Context, _ready: Ready) {
+        let summary = match self.guild {
+            Some(guild_id) => register_guild_commands(&ctx, guild_id).await,
+            None => register_global_commands(&ctx).await,
+        };
+        // 다른 샤드가 먼저 동기화한 경우는 None
+        let Some(summary) = summary else {
+            return;
+        };
+        if summary.failed > 0 {
+            self.failed.store(true, Ordering::Relaxed);
+        }
+        let manager = {
+            let data = ctx.data.read().await;
+            data.get::<ShardManagerKey>().cloned()
+        };
+        if let Some(manager) = manager {
+            manager.shutdown_all().await;
+        }
+    }
+}
+
+// 권한을 확인한 뒤 커맨드 핸들러로 전달
+pub async fn dispatch(ctx: &Context, cmd: &CommandInteraction) {
+    let Some(spec) = registry().into_iter().find(|s| s.name == cmd.data.name) else {
+        return;
+    };
+    // 호출 수, 오류, 응답 시간 집계 (/usage)
+    usage::track(ctx, cmd.guild_id, spec.name, run_command(ctx, cmd, &spec)).await;
+}
+
+async fn run_command(ctx: &Context, cmd: &CommandInteraction, spec: &CommandSpec) {
+    if cmd.guild_id.is_none() && !spec.dm_allowed {
+        respond(
+            ctx,
+            cmd,
+            CreateInteractionResponseMessage::new()
+                .content(GUILD_ONLY)
+                .ephemeral(true),
+        )
+        .await;
+        return;
+    }
+
+    if spec.owner_only && !is_owner(ctx, cmd.user.id).await {
+        respond(
+            ctx,
+            cmd,
+            CreateInteractionResponseMessage::new()
+                .content("봇 소유자만 사용할 수 있는 명령입니다")
+                .ephemeral(true),
+        )
+        .await;
+        return;
+    }
+
+    let config = match cmd.guild_id {
+        Some(guild_id) => Some(get_guild_config(ctx, guild_id).await),
+        None => None,
+    };
+
+    if let Some(config) = &config
+        && config.is_disabled(spec.name)
+    {
+        respond(
+            ctx,
+            cmd,
+            CreateInteractionResponseMessage::new()
+                .content(format!("이 서버에서는 /{} 을(를) 사용하지 않도록 설정되어 있습니다", spec.name))
+                .ephemeral(true),
+        )
+        .await;
+        return;
+    }
+
+    // 서버가 역할 규칙을 정한 커맨드는 역할로 확인하고, 규칙이 없으면 디스코드 기본 권한으로 확인
+    let required_role = config
+        .as_ref()
+        .filter(|_| spec.allows_role_rule())
+        .and_then(|c| c.required_role(spec.name));
+    if let Some(role_id) = required_role {
+        let permitted = cmd.member.as_ref().is_some_and(|m| {
+            m.roles.contains(&role_id) || m.permissions.is_some_and(|p| p.administrator())
+        });
+        if !permitted {
+            let embed = CreateEmbed::new()
+                .title("🔒 권한이 없습니다")
+                .description(format!("/{} 은(는) <@&{}> 역할이 있어야 사용할 수 있습니다.", spec.name, role_id));
+            respond(
+                ctx,
+                cmd,
+                CreateInteractionResponseMessage::new()
+                    .embed(embed)
+                    .ephemeral(true),
+            )
+            .await;
+            return;
+        }
+    } else if !spec.is_permitted(cmd) {
+        let content = if spec.required_permissions.contains(Permissions::MANAGE_GUILD) {
+            "이 명령은 서버 관리 권한이 필요합니다".to_string()
+        } else {
+            format!("이 명령은 다음 권한이 필요합니다: {}", spec.required_permissions)
+        };
+        respond(
+            ctx,
+            cmd,
+            CreateInteractionResponseMessage::new()
+                .content(content)
+                .ephemeral(true),
+        )
+        .await;
+        return;
+    }
+
+    // 일반 커맨드는 서버가 지정한 채널에서만 (관리 커맨드는 어디서나)
+    if spec.category == CommandCategory::Utility
+        && let Some(config) = &config
+    {
+        let parent_id = cmd.channel.as_ref().and_then(|c| c.parent_id);
+        if let Err(message) = check_command_channel(config, cmd.channel_id, parent_id) {
+            respond(
+                ctx,
+                cmd,
+                CreateInteractionResponseMessage::new()
+                    .content(message)
+                    .ephemeral(true),
+            )
+            .await;
+            return;
+        }
+    }
+
+    // 관리자는 실행 빈도 제한을 받지 않음
+    let is_admin = cmd
+        .member
+        .as_ref()
+        .and_then(|m| m.permissions)
+        .is_some_and(|p| p.administrator());
+    let limiter = {
+        let data = ctx.data.read().await;
+        data.get::<RateLimiter>().cloned()
+    };
+    if !is_admin
+        && let Some(limiter) = limiter
+        && let Err(wait) = limiter.check(cmd.user.id, spec.name).await
+    {
+        respond(
+            ctx,
+            cmd,
+            CreateInteractionResponseMessage::new()
+                .content(format!("잠시 후 다시 시도해주세요 ({}초)", wait.as_secs_f64().ceil().max(1.0) as u64))
+                .ephemeral(true),
+        )
+        .await;
+        return;
+    }
+
+    if let Some((scope, duration)) = spec.cooldown {
+        let cooldowns = {
+            let data = ctx.data.read().await;
+            data.get::<Cooldowns>().cloned()
+        };
+        let id = match scope {
+            CooldownScope::User => cmd.user.id.get(),
+            CooldownScope::Guild => cmd.guild_id.map_or(cmd.user.id.get(), |g| g.get()),
+        };
+        if let Some(cooldowns) = cooldowns
+            && let Err(remaining) = cooldowns.try_start(spec.name, scope, id, duration).await
+        {
+            let ready_at = unix_now() + remaining.as_secs_f64().ceil() as i64;
+            let who = match scope {
+                CooldownScope::User => "",
+                CooldownScope::Guild => "이 서버에서 ",
+            };
+            respond(
+                ctx,
+                cmd,
+                CreateInteractionResponseMessage::new()
+                    .content(format!("{}/{} 은(는) <t:{}:R> 다시 사용할 수 있습니다", who, spec.name, ready_at))
+                    .ephemeral(true),
+            )
+            .await;
+            return;
+        }
+    }
+
+    match (spec.defer)(cmd) {
+        Defer::No => run_handler(ctx, cmd, spec.name).await,
+        defer => run_deferred(ctx, cmd, spec.name, defer == Defer::Ephemeral).await,
+    }
+}
+
+// 먼저 Defer로 응답한 뒤 핸들러 실행. 핸들러의 respond는 원래 응답 수정으로 바뀜
+async fn run_deferred(ctx: &Context, cmd: &CommandInteraction, name: &str, ephemeral: bool) {
+    let defer = CreateInteractionResponse::Defer(CreateInteractionResponseMessage::new().ephemeral(ephemeral));
+    if let Err(e) = event_metrics::phase(Phase::Discord, cmd.create_response(&ctx.http, defer)).await {
+        report_error(ctx, &format!("/{} 응답 지연", name), &e).await;
+        return;
+    }
+    let finished = DEFERRED
+        .scope(Cell::new(true), tokio::time::timeout(DEFERRED_TIMEOUT, run_handler(ctx, cmd, name)))
+        .await;
+    if finished.is_err() {
+        report_error(ctx, &format!("/{} 실행", name), &"시간 초과").await;
+        let message = EditInteractionResponse::new().content(format!(
+            "⏱️ 처리 시간이 {}초를 넘어 중단했습니다. 잠시 후 다시 시도해주세요.",
+            DEFERRED_TIMEOUT.as_secs()
+        ));
+        if let Err(e) = cmd.edit_response(&ctx.http, message).await {
+            report_error(ctx, &format!("/{} 응답", name), &e).await;
+        }
+    }
+}
+
+async fn run_handler(ctx: &Context, cmd: &CommandInteraction, name: &str) {
+    match name {
+        "calc" => handle_calc(ctx, cmd).await,
+        "calcmode" => handle_calcmode(ctx, cmd).await,
+        "calchelp" => handle_calchelp(ctx, cmd).await,
+        "calchistory" => handle_calchistory(ctx, cmd).await,
+        "calcstore" => handle_calcstore(ctx, cmd).await,
+        "setchannel" => handle_setchannel(ctx, cmd).await,
+        "setrole" => handle_setrole(ctx, cmd).await,
+        "shards" => handle_shards(ctx, cmd).await,
+        "jobs" => handle_jobs(ctx, cmd).await,
+        "shutdown" => handle_shutdown(ctx, cmd).await,
+        "reloadconfig" => handle_reloadconfig(ctx, cmd).await,
+        "announce" => handle_announce(ctx, cmd).await,
+        "voicestats" => handle_voicestats(ctx, cmd).await,
+        "session" => handle_session(ctx, cmd).await,
+        "voiceduration" => handle_voiceduration(ctx, cmd).await,
+        "voicetop" => handle_voicetop(ctx, cmd).await,
+        "starters" => handle_starters(ctx, cmd).await,
+        "voiceconfig" => handle_voiceconfig(ctx, cmd).await,
+        "adjust" => handle_adjust(ctx, cmd).await,
+        "config" => handle_config(ctx, cmd).await,
+        "slowmode" => handle_slowmode(ctx, cmd).await,
+        "invitecreate" => handle_invitecreate(ctx, cmd).await,
+        "nick" => handle_nick(ctx, cmd).await,
+        "roleinfo" => handle_roleinfo(ctx, cmd).await,
+        "voicelog" => handle_voicelog(ctx, cmd).await,
+        "invitelist" => handle_invitelist(ctx, cmd).await,
+        "remind" => handle_remind(ctx, cmd).await,
+        "timezone" => handle_timezone(ctx, cmd).await,
+        "weeklyreport" => handle_weeklyreport(ctx, cmd).await,
+        "joinannounce" => handle_joinannounce(ctx, cmd).await,
+        "streak" => handle_streak(ctx, cmd).await,
+        "feedback" => handle_feedback(ctx, cmd).await,
+        "usage" => handle_usage(ctx, cmd).await,
+        "permission" => handle_permission(ctx, cmd).await,
+        "setup" => handle_setup(ctx, cmd).await,
+        "clearcommands" => handle_clearcommands(ctx, cmd).await,
+        #[cfg(feature = "chime")]
+        "testchime" => handle_testchime(ctx, cmd).await,
+        _ => {}
+    }
+}
+
+// 버튼 등 메시지 컴포넌트는 custom_id 접두사로 담당 모듈을 찾음
+pub async fn dispatch_component(ctx: &Context, comp: &ComponentInteraction) {
+    let custom_id = comp.data.custom_id.as_str();
+    if custom_id.starts_with(calc_buttons::CUSTOM_ID_PREFIX) {
+        handle_calc_component(ctx, comp).await;
+    } else if custom_id.starts_with(VOICETOP_REFRESH_PREFIX) {
+        handle_voicetop_refresh(ctx, comp).await;
+    } else if custom_id.starts_with(SETUP_PREFIX) {
+        handle_setup_component(ctx, comp).await;
+    } else if custom_id.starts_with(VOICECONFIG_PREFIX) {
+        handle_voiceconfig_component(ctx, comp).await;
+    } else if custom_id.starts_with(ANNOUNCE_PREFIX) {
+        // 공지 확인 버튼도 커맨드와 같이 소유자만
+        if !is_owner(ctx, comp.user.id).await {
+            let response = CreateInteractionResponse::Message(
+                CreateInteractionResponseMessage::new()
+                    .content("봇 소유자만 사용할 수 있는 명령입니다")
+                    .ephemeral(true),
+            );
+            if let Err(e) = comp.create_response(&ctx.http, response).await {
+                report_error(ctx, "공지 버튼 응답", &e).await;
+            }
+            return;
+        }
+        handle_announce_component(ctx, comp).await;
+    }
+}
+
+// 입력 창(모달) 제출도 custom_id 접두사로 담당 모듈을 찾음
+// 자동 완성 요청을 커맨드별로 나눔
+pub async fn dispatch_autocomplete(ctx: &Context, ac: &CommandInteraction) {
+    if ac.data.name == "calc" {
+        handle_calc_autocomplete(ctx, ac).await;
+    }
+}
+
+pub async fn dispatch_modal(ctx: &Context, modal: &ModalInteraction) {
+    if modal.data.custom_id.starts_with(calc_buttons::MODAL_ID_PREFIX) {
+        handle_calc_modal(ctx, modal).await;
+    }
+}
+
+pub async fn is_owner(ctx: &Context, user_id: UserId) -> bool {
+    let data = ctx.data.read().await;
+    data.get::<BotOwner>() == Some(&user_id)
+}
+
+// 커맨드에 메시지로 응답하고, 실패하면 오류 보고. 응답을 미룬 커맨드면 미뤄 둔 응답을 수정
+pub async fn respond(
+    ctx: &Context,
+    cmd: &CommandInteraction,
+    message: CreateInteractionResponseMessage,
+) {
+    let send = async {
+        if DEFERRED.try_with(Cell::get).unwrap_or(false) {
+            edit_deferred(ctx, cmd, &message).await
+        } else {
+            cmd.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+                .await
+        }
+    };
+    let result = event_metrics::phase(Phase::Discord, send).await;
+    if let Err(e) = result {
+        report_error(ctx, &format!("/{} 응답", cmd.data.name), &e).await;
+    }
+}
+
+// 긴 본문을 나눠 응답: 첫 조각은 message(버튼 등 포함)에 담아 응답하고 나머지는 후속 메시지로
+pub async fn respond_chunks(
+    ctx: &Context,
+    cmd: &CommandInteraction,
+    content: &str,
+    message: CreateInteractionResponseMessage,
+) {
+    let mut chunks = split_content(content, MESSAGE_LIMIT);
+    if chunks.len() > MAX_CHUNKS {
+        chunks.truncate(MAX_CHUNKS);
+        if let Some(last) = chunks.last_mut() {
+            *last = truncate(last, MESSAGE_LIMIT - 1) + "…";
+        }
+    }
+    let mut chunks = chunks.into_iter();
+    respond(ctx, cmd, message.content(chunks.next().unwrap_or_default())).await;
+    for chunk in chunks {
+        let followup = cmd.create_followup(&ctx.http, CreateInteractionResponseFollowup::new().content(chunk));
+        if let Err(e) = event_metrics::phase(Phase::Discord, followup).await {
+            report_error(ctx, &format!("/{} 후속 응답", cmd.data.name), &e).await;
+            break;
+        }
+    }
+}
+
+// 응답 메시지를 원래 응답 수정으로 전송. 공개 여부는 Defer 때 정해지므로 flags 등 수정할 수 없는 값은 뺌
+async fn edit_deferred(
+    ctx: &Context,
+    cmd: &CommandInteraction,
+    message: &CreateInteractionResponseMessage,
+) -> Result<(), serenity::Error> {
+    let mut body = serde_json::to_value(message)?;
+    if let Some(body) = body.as_object_mut() {
+        for key in ["flags", "tts", "poll"] {
+            body.remove(key);
+        }
+    }
+    ctx.http
+        .edit_original_interaction_response(&cmd.token, &body, Vec::new())
+        .await?;
+    Ok(())
+}
+
+// 이보다 긴 /calc 수식은 응답을 미룸
+const SLOW_EXPRESSION_LEN: usize = 64;
+
+// /calc: 풀이 요청이나 긴 수식은 응답을 미룸 (수식이 없으면 입력 창을 띄우므로 미루지 않음)
+fn calc_defer(cmd: &CommandInteraction) -> Defer {
+    let option = |name: &str| cmd.data.options.iter().find(|o| o.name == name).map(|o| &o.value);
+    let expr = option("expr").and_then(|v| v.as_str()).unwrap_or("");
+    if expr.trim().is_empty() {
+        Defer::No
+    } else if option("explain").and_then(|v| v.as_bool()).unwrap_or(false) {
+        Defer::Ephemeral
+    } else if expr.len() > SLOW_EXPRESSION_LEN {
+        Defer::Public
+    } else {
+        Defer::No
+    }
+}
+
+async fn handle_calc(ctx: &Context, cmd: &CommandInteraction) {
+    // expr 옵션 추출
+    let expr_val = cmd
+        .data
+        .options
+        .iter()
+        .find(|o| o.name == "expr")
+        .and_then(|o| match &o.value {
+            CommandDataOptionValue::String(s) => Some(s.as_str()),
+            _ => None,
+        })
+        .unwrap_or("");
+
+    let explain = cmd
+        .data
+        .options
+        .iter()
+        .find(|o| o.name == "explain")
+        .and_then(|o| o.value.as_bool())
+        .unwrap_or(false);
+
+    if let Err(message) = validate_text_input(expr_val, MAX_EXPRESSION_LEN) {
+        respond(
+            ctx,
+            cmd,
+            CreateInteractionResponseMessage::new()
+                .content(message)
+                .ephemeral(true),
+        )
+        .await;
+        return;
+    }
+
+    let session = get_session(ctx, cmd.user.id).await;
+    let mode = session.mode;
+
+    // 수식을 주지 않으면 여러 줄을 입력할 수 있는 창을 띄움
+    if expr_val.is_empty() {
+        let modal = CreateInteractionResponse::Modal(calc_modal(mode, explain));
+        if let Err(e) = cmd.create_response(&ctx.http, modal).await {
+            report_error(ctx, "/calc 입력 창", &e).await;
+        }
+        return;
+    }
+
+    if explain {
+        handle_calc_explain(ctx, cmd, expr_val, mode, session.last_answer).await;
+        return;
+    }
+
+    let result_text = match evaluate_cached(ctx, cmd.user.id, expr_val, mode).await {
+        // π/4, 1/3 처럼 알려진 값이면 설명을 덧붙임 (복소수/분수 결과는 파싱되지 않아 건너뜀)
+        Ok(v) => {
+            calc_log::record(ctx, cmd.guild_id, cmd.user.id, expr_val, &v).await;
+            match v.parse().ok().and_then(calc::approx_constant_name) {
+                Some(note) => format!("{} = {} ({})", expr_val, v, note),
+                None => format!("{} = {}", expr_val, v),
+            }
+        }
+        Err(e) => {
+            usage::record_calc_error(ctx, cmd.guild_id, &e).await;
+            format!("{} -> 오류: {}", expr_val, e)
+        }
+    };
+
+    let buttons = calc_buttons(ctx, cmd.id.get(), cmd.user.id, mode, expr_val).await;
+    // 큰 정수 등 긴 결과는 여러 메시지로 나눔 (버튼은 첫 메시지에)
+    respond_chunks(
+        ctx,
+        cmd,
+        &result_text,
+        CreateInteractionResponseMessage::new().components(vec![buttons]),
+    )
+    .await;
+}
+
+async fn handle_calchelp(ctx: &Context, cmd: &CommandInteraction) {
+    respond(
+        ctx,
+        cmd,
+        CreateInteractionResponseMessage::new()
+            .embed(calchelp_embed())
+            .ephemeral(true),
+    )
+    .await;
+}
+
+// 지원 목록(calc::SUPPORTED_*)에서 도움말 생성
+pub fn calchelp_embed() -> CreateEmbed {
+    let operators = calc::SUPPORTED_OPERATORS
+        .iter()
+        .map(|(symbol, precedence, right)| {
+            let assoc = if *right { "오른쪽 결합" } else { "왼쪽 결합" };
+            format!("`{}` 우선순위 {} · {}", symbol, precedence, assoc)
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    let functions = calc::SUPPORTED_FUNCTIONS
+        .iter()
+        .map(|(name, arity)| {
+            let args = (1..=*arity).map(|i| format!("x{}", i)).collect::<Vec<_>>().join(", ");
+            format!("`{}({})`", name, args)
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+    let constants = calc::SUPPORTED_CONSTANTS
+        .iter()
+        .map(|name| format!("`{}`", name))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    CreateEmbed::new()
+        .title("🧮 계산기 도움말")
+        .description("괄호와 단항 `-`, 숫자 뒤 이름의 암묵적 곱셈(`2pi`)을 지원합니다. `**`는 `^`와 같습니다. `%`는 뒤에 수가 오면 나머지(`7 % 3`), 아니면 퍼센트(`50%`)입니다. `ans`(또는 `_`)는 직전 계산 결과입니다. `i`는 복소수 모드(`/calcmode`)에서만 사용할 수 있습니다.")
+        .field("연산자", operators, false)
+        .field("함수", functions, false)
+        .field("상수", constants, false)
+}
+
+// 계산 과정을 번호 목록으로 담은 임베드를 본인에게만 표시
+async fn handle_calc_explain(
+    ctx: &Context,
+    cmd: &CommandInteraction,
+    expr_val: &str,
+    mode: NumberMode,
+    ans: Option<Complex>,
+) {
+    respond(
+        ctx,
+        cmd,
+        CreateInteractionResponseMessage::new()
+            .embed(explain_embed(expr_val, mode, ans))
+            .ephemeral(true),
+    )
+    .await;
+}
+
+// 식은 제목 길이 제한(256자)을 넘을 수 있으므로 본문 첫 줄에 넣음
+pub fn explain_embed(expr_val: &str, mode: NumberMode, ans: Option<Complex>) -> CreateEmbed {
+    let body = match explain_steps(expr_val, mode, ans) {
+        Ok(body) => body,
+        Err(e) => format!("오류: {}", e),
+    };
+    CreateEmbed::new()
+        .title("🧮 계산 풀이")
+        .description(truncate(&format!("식: `{}`\n\n{}", expr_val, body), EMBED_DESCRIPTION_LIMIT))
+}
+
+// 풀이 단계를 번호 붙인 줄로
+fn explain_steps(expr_val: &str, mode: NumberMode, ans: Option<Complex>) -> Result<String, BotError> {
+    let steps = crate::calc::explain(expr_val, mode, ans)?;
+    Ok(steps
+        .iter()
+        .enumerate()
+        .map(|(i, step)| format!("{}. {}", i + 1, step))
+        .collect::<Vec<_>>()
+        .join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_expression_at_limit() {
+        assert!(validate_text_input("2 + 3 * 4", MAX_EXPRESSION_LEN).is_ok());
+        assert!(validate_text_input(&"1".repeat(MAX_EXPRESSION_LEN), MAX_EXPRESSION_LEN).is_ok());
+    }
+
+    #[test]
+    fn rejects_too_long_input() {
+        let input = "1".repeat(MAX_EXPRESSION_LEN + 1);
+        assert_eq!(
+            validate_text_input(&input, MAX_EXPRESSION_LEN),
+            Err("입력이 너무 깁니다. 최대 256바이트까지 입력할 수 있습니다. (현재 257바이트)".to_string())
+        );
+    }
+
+    #[test]
+    fn length_is_counted_in_bytes() {
+        // 한글은 글자당 3바이트: 86글자 = 258바이트
+        let input = "가".repeat(86);
+        assert!(input.chars().count() < MAX_EXPRESSION_LEN);
+        assert!(validate_text_input(&input, MAX_EXPRESSION_LEN).is_err());
+    }
+
+    #[test]
+    fn rejects_control_characters() {
+        let message = Err("입력에 제어 문자를 넣을 수 없습니다.".to_string());
+        assert_eq!(validate_text_input("1+\0+1", MAX_EXPRESSION_LEN), message);
+        assert_eq!(validate_text_input("1\n+1", MAX_EXPRESSION_LEN), message);
+        assert_eq!(validate_text_input("\u{7f}", MAX_EXPRESSION_LEN), message);
+    }
+
+    fn names(commands: &[(&'static str, CreateCommand)]) -> Vec<&'static str> {
+        commands.iter().map(|(name, _)| *name).collect()
+    }
+
+    // 서버에서 끈 커맨드는 그 길드의 등록 목록에서 빠지고, 나머지는 그대로
+    #[test]
+    fn guild_commands_skip_disabled() {
+        let all = names(&guild_commands(&GuildConfig::default()));
+        assert_eq!(all.len(), registry().len());
+
+        let config = GuildConfig {
+            disabled_commands: vec!["calc".to_string(), "voicetop".to_string()],
+            ..GuildConfig::default()
+        };
+        let filtered = names(&guild_commands(&config));
+        assert!(!filtered.contains(&"calc"));
+        assert!(!filtered.contains(&"voicetop"));
+        assert!(filtered.contains(&"calchelp"));
+        assert_eq!(filtered.len(), all.len() - 2);
+    }
+
+    // 글로벌에는 DM 커맨드만 올려서, 끈 커맨드가 서버에서 글로벌 목록으로 다시 보이지 않음
+    #[test]
+    fn global_commands_are_dm_only() {
+        let global = names(&global_commands());
+        assert!(global.contains(&"calc"));
+        assert!(!global.contains(&"config"));
+        assert!(registry().iter().filter(|s| global.contains(&s.name)).all(|s| s.dm_allowed));
+    }
+
+    // ready가 두 번 와도 글로벌 커맨드는 처음 한 번만 등록
+    #[test]
+    fn global_registration_is_claimed_once() {
+        let state = RegistrationState::new(false);
+        assert!(state.claim_global());
+        assert!(!state.claim_global());
+    }
+
+    // 길드마다 한 번. 실패로 풀어 주면 다음 ready에서 다시 등록
+    #[tokio::test]
+    async fn guild_registration_is_claimed_once_per_guild() {
+        let state = RegistrationState::new(false);
+        let (a, b) = (GuildId::new(1), GuildId::new(2));
+        assert!(state.claim_guild(a).await);
+        assert!(!state.claim_guild(a).await);
+        assert!(state.claim_guild(b).await);
+        state.release_guild(a).await;
+        assert!(state.claim_guild(a).await);
+    }
+
+    // 여러 샤드의 ready가 동시에 와도 한 곳만 등록
+    #[tokio::test]
+    async fn concurrent_ready_registers_once() {
+        let state = Arc::new(RegistrationState::new(false));
+        let tasks: Vec<_> = (0..8)
+            .map(|_| {
+                let state = state.clone();
+                tokio::spawn(async move { (state.claim_global(), state.claim_guild(GuildId::new(1)).await) })
+            })
+            .collect();
+        let mut claimed = Vec::new();
+        for task in tasks {
+            claimed.push(task.await.unwrap());
+        }
+        assert_eq!(claimed.iter().filter(|(global, _)| *global).count(), 1);
+        assert_eq!(claimed.iter().filter(|(_, guild)| *guild).count(), 1);
+    }
+}
@@ -0,0 +1,154 @@
+use serenity::prelude::*;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::SystemTime;
+use tokio::sync::RwLock;
+
+// 실행 중에 다시 읽어 바로 적용할 수 있는 설정 키. 나머지는 재시작해야 반영됨
+pub const HOT_RELOAD_KEYS: &[&str] = &[
+    "presence.enabled",
+    "presence.format",
+    "rate_limit.capacity",
+    "rate_limit.window_secs",
+    "error_report.channel_id",
+    "feedback.channel_id",
+    "dry_run.enabled",
+    "metrics.slow_event_ms",
+];
+
+// 설정 파일에서 읽는 키 전체 (validate-config에서 오타 확인용)
+pub const KNOWN_KEYS: &[&str] = &[
+    "dashboard.token",
+    "dry_run.enabled",
+    "error_report.channel_id",
+    "feedback.channel_id",
+    "metrics.slow_event_ms",
+    "presence.enabled",
+    "presence.format",
+    "presence.interval_secs",
+    "rate_limit.capacity",
+    "rate_limit.window_secs",
+];
+
+// 설정 파일 (--config-path / AUROBOT_CONFIG).
+// TOML 중 [섹션], key = 값 (문자열, 정수, 불리언), # 주석만 지원
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FileConfig {
+    // "섹션.키" -> 값 (문자열은 따옴표를 벗긴 값)
+    values: BTreeMap<String, String>,
+}
+
+impl FileConfig {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| format!("{} 읽기 실패: {}", path.display(), e))?;
+        Self::parse(&text).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut values = BTreeMap::new();
+        let mut section = String::new();
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some(name) = line.strip_prefix('[') {
+                let Some(name) = name.split('#').next().and_then(|n| n.trim().strip_suffix(']')) else {
+                    return Err(format!("{}번째 줄: 섹션 이름이 닫히지 않았습니다", i + 1));
+                };
+                section = name.trim().to_string();
+                continue;
+            }
+            let Some((key, value)) = line.split_once('=') else {
+                return Err(format!("{}번째 줄: key = 값 형식이 아닙니다", i + 1));
+            };
+            let value = parse_value(value.trim()).ok_or_else(|| format!("{}번째 줄: 잘못된 값입니다", i + 1))?;
+            let key = key.trim();
+            let key = if section.is_empty() {
+                key.to_string()
+            } else {
+                format!("{}.{}", section, key)
+            };
+            values.insert(key, value);
+        }
+        Ok(Self { values })
+    }
+
+    // 설정 파일 값이 있으면 그 값, 없으면 환경 변수
+    pub fn value(&self, key: &str, env: &str) -> Option<String> {
+        self.values
+            .get(key)
+            .cloned()
+            .or_else(|| std::env::var(env).ok())
+    }
+
+    // 봇이 읽지 않는 키 (오타일 가능성이 큼)
+    pub fn unknown_keys(&self) -> Vec<&str> {
+        self.values
+            .keys()
+            .map(String::as_str)
+            .filter(|k| !KNOWN_KEYS.contains(k))
+            .collect()
+    }
+
+    // 두 설정 사이에 값이 달라진 키
+    pub fn changed_keys<'a>(&'a self, other: &'a FileConfig) -> Vec<&'a str> {
+        let mut keys: Vec<&str> = self
+            .values
+            .keys()
+            .chain(other.values.keys())
+            .map(String::as_str)
+            .filter(|k| self.values.get(*k) != other.values.get(*k))
+            .collect();
+        keys.sort_unstable();
+        keys.dedup();
+        keys
+    }
+}
+
+// 따옴표로 감싼 문자열은 이스케이프를 풀고, 그 밖의 값은 주석을 떼고 그대로 사용
+fn parse_value(raw: &str) -> Option<String> {
+    let Some(rest) = raw.strip_prefix('"') else {
+        let value = raw.split('#').next().unwrap_or_default().trim();
+        return (!value.is_empty()).then(|| value.to_string());
+    };
+    let mut value = String::new();
+    let mut chars = rest.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                let tail = chars.as_str().trim();
+                return (tail.is_empty() || tail.starts_with('#')).then_some(value);
+            }
+            '\\' => match chars.next()? {
+                'n' => value.push('\n'),
+                't' => value.push('\t'),
+                c @ ('"' | '\\') => value.push(c),
+                _ => return None,
+            },
+            c => value.push(c),
+        }
+    }
+    None
+}
+
+// 현재 적용된 설정 파일 (/reloadconfig, 파일 감시에서 교체)
+pub struct LoadedConfig {
+    pub path: Option<PathBuf>,
+    pub file: FileConfig,
+    // 마지막으로 확인한 파일 수정 시각 (잘못된 파일이어도 갱신해 같은 내용을 다시 읽지 않음)
+    pub modified: Option<SystemTime>,
+}
+
+// 파일 수정 시각. 파일이 없거나 지원하지 않는 파일 시스템이면 None
+pub fn modified_time(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+pub struct ConfigFile;
+
+impl TypeMapKey for ConfigFile {
+    type Value = Arc<RwLock<LoadedConfig>>;
+}
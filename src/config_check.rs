@@ -0,0 +1,216 @@
+use serenity::all::ChannelId;
+use serenity::all::GuildId;
+use serenity::all::Permissions;
+use serenity::prelude::*;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
+
+use crate::error_report::report_error;
+use crate::guild_config::{get_guild_config, GuildConfig};
+
+// 검사 대상 설정
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Setting {
+    NotificationChannel,
+    AuditChannel,
+    MentionRole,
+}
+
+impl Setting {
+    fn label(self) -> &'static str {
+        match self {
+            Setting::NotificationChannel => "알림 채널 (/setchannel)",
+            Setting::AuditChannel => "감사 로그 채널 (audit_channel)",
+            Setting::MentionRole => "멘션 역할 (/setrole)",
+        }
+    }
+}
+
+// 문제가 해결되지 않으면 관리자에게 다시 알리는 간격
+const RENOTIFY_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+
+// 길드 하나의 쓸 수 없는 설정
+#[derive(Debug, Default)]
+pub struct GuildProblems {
+    // 설정 -> 문제 설명
+    settings: HashMap<Setting, String>,
+    // 관리자에게 마지막으로 알린 시각
+    notified_at: Option<Instant>,
+}
+
+// 쓸 수 없는 설정: 길드 -> 문제. 핸들러는 여기 있는 설정을 건너뜀
+pub struct BadSettings;
+
+impl TypeMapKey for BadSettings {
+    type Value = Arc<RwLock<HashMap<GuildId, GuildProblems>>>;
+}
+
+pub fn new_bad_settings() -> Arc<RwLock<HashMap<GuildId, GuildProblems>>> {
+    Arc::new(RwLock::new(HashMap::new()))
+}
+
+async fn bad_settings(ctx: &Context) -> Option<Arc<RwLock<HashMap<GuildId, GuildProblems>>>> {
+    let data = ctx.data.read().await;
+    data.get::<BadSettings>().cloned()
+}
+
+// 알림 등을 보낼 때 쓰는 설정: 검사에서 문제가 발견된 채널/역할은 비워서 돌려줌
+pub async fn get_usable_config(ctx: &Context, guild_id: GuildId) -> GuildConfig {
+    let mut config = get_guild_config(ctx, guild_id).await;
+    let Some(store) = bad_settings(ctx).await else {
+        return config;
+    };
+    if let Some(bad) = store.read().await.get(&guild_id).map(|p| &p.settings) {
+        if bad.contains_key(&Setting::NotificationChannel) {
+            config.notification_channel = None;
+        }
+        if bad.contains_key(&Setting::AuditChannel) {
+            config.audit_channel = None;
+        }
+        if bad.contains_key(&Setting::MentionRole) {
+            config.mention_role = None;
+        }
+    }
+    config
+}
+
+// 캐시 기준으로 설정 검사. (설정, 문제 설명, 관리자에게 알릴지)
+fn find_problems(ctx: &Context, guild_id: GuildId, config: &GuildConfig) -> Vec<(Setting, String, bool)> {
+    let Some(guild) = ctx.cache.guild(guild_id) else {
+        return Vec::new();
+    };
+    let bot_id = ctx.cache.current_user().id;
+    let member = guild.members.get(&bot_id);
+
+    let mut problems = Vec::new();
+    let mut check_channel = |setting: Setting, channel_id: Option<ChannelId>| {
+        let Some(channel_id) = channel_id else {
+            return;
+        };
+        let Some(channel) = guild.channels.get(&channel_id) else {
+            problems.push((setting, format!("<#{}> 채널을 찾을 수 없습니다", channel_id), true));
+            return;
+        };
+        let Some(member) = member else {
+            return;
+        };
+        let missing = (Permissions::VIEW_CHANNEL | Permissions::SEND_MESSAGES)
+            - guild.user_permissions_in(channel, member);
+        if !missing.is_empty() {
+            problems.push((
+                setting,
+                format!("<#{}> 채널에 권한이 없습니다: {}", channel_id, missing.get_permission_names().join(", ")),
+                true,
+            ));
+        }
+    };
+    check_channel(Setting::NotificationChannel, config.notification_channel);
+    check_channel(Setting::AuditChannel, config.audit_channel);
+
+    if let Some(role_id) = config.mention_role {
+        match guild.roles.get(&role_id) {
+            None => problems.push((
+                Setting::MentionRole,
+                format!("역할 `{}` 를 찾을 수 없습니다", role_id),
+                true,
+            )),
+            Some(role) if !role.mentionable => {
+                // 멘션은 알림 채널에서 하므로 그 채널 기준 권한으로 확인
+                let channel = config.notification_channel.and_then(|c| guild.channels.get(&c));
+                let can_mention_all = member.zip(channel).is_some_and(|(m, c)| {
+                    guild.user_permissions_in(c, m).contains(Permissions::MENTION_EVERYONE)
+                });
+                if !can_mention_all {
+                    problems.push((
+                        Setting::MentionRole,
+                        format!("<@&{}> 역할을 멘션할 수 없습니다 (멘션 허용 또는 봇에 모두 멘션 권한 필요)", role_id),
+                        true,
+                    ));
+                }
+            }
+            Some(_) => {}
+        }
+    }
+    problems
+}
+
+// 길드 설정을 검사해 문제 있는 설정을 표시하고, 새로 생긴 문제가 있거나
+// 알린 지 RENOTIFY_INTERVAL이 지났으면 관리자에게 알림. 문제가 없어지면 표시도 지움
+pub async fn check_guild(ctx: &Context, guild_id: GuildId) {
+    let config = get_guild_config(ctx, guild_id).await;
+    let problems = find_problems(ctx, guild_id, &config);
+    let Some(store) = bad_settings(ctx).await else {
+        return;
+    };
+
+    let current: HashMap<Setting, String> = problems
+        .iter()
+        .map(|(setting, problem, _)| (*setting, problem.clone()))
+        .collect();
+    let mut reported: Vec<&(Setting, String, bool)> = {
+        let mut store = store.write().await;
+        if current.is_empty() {
+            store.remove(&guild_id);
+            return;
+        }
+        let entry = store.entry(guild_id).or_default();
+        let has_new = problems
+            .iter()
+            .any(|(setting, problem, notify)| *notify && entry.settings.get(setting) != Some(problem));
+        let due = entry.notified_at.is_none_or(|at| at.elapsed() >= RENOTIFY_INTERVAL);
+        entry.settings = current;
+        let reported: Vec<_> = problems.iter().filter(|(_, _, notify)| *notify).collect();
+        if reported.is_empty() || !(has_new || due) {
+            return;
+        }
+        entry.notified_at = Some(Instant::now());
+        reported
+    };
+    reported.sort_by_key(|(setting, _, _)| *setting);
+    let body = reported
+        .iter()
+        .map(|(setting, problem, _)| format!("• **{}**: {}", setting.label(), problem))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let content = format!(
+        "⚠️ 봇 설정에 문제가 있어 해당 기능을 건너뜁니다. 수정하면 자동으로 다시 사용합니다.\n{}",
+        body
+    );
+    send_diagnostic(ctx, guild_id, content).await;
+}
+
+// 예약 작업: 캐시에 있는 모든 길드를 다시 검사 (이벤트를 놓쳤을 때와 24시간 재알림용)
+pub async fn recheck_guilds(ctx: Context) {
+    for guild_id in ctx.cache.guilds() {
+        check_guild(&ctx, guild_id).await;
+    }
+}
+
+// 서버 소유자에게 DM으로 보내고, 실패하면(DM 차단 등) 시스템 채널에
+async fn send_diagnostic(ctx: &Context, guild_id: GuildId, content: String) {
+    let Some((system_channel, owner_id, name)) = ctx
+        .cache
+        .guild(guild_id)
+        .map(|g| (g.system_channel_id, g.owner_id, g.name.clone()))
+    else {
+        return;
+    };
+    // DM에서는 어느 서버 이야기인지 알 수 있도록 서버 이름을 붙임
+    let dm_content = format!("**{}** 서버\n{}", name, content);
+    let dm_sent = match owner_id.create_dm_channel(&ctx.http).await {
+        Ok(dm) => dm.id.say(&ctx.http, &dm_content).await.map(|_| ()),
+        Err(e) => Err(e),
+    };
+    if dm_sent.is_ok() {
+        return;
+    }
+    let sent = match system_channel {
+        Some(channel_id) => channel_id.say(&ctx.http, &content).await.map(|_| ()),
+        None => dm_sent,
+    };
+    if let Err(e) = sent {
+        report_error(ctx, &format!("설정 진단 전송 ({})", guild_id), &e).await;
+    }
+}
@@ -0,0 +1,221 @@
+use axum::extract::{Path, Query, State};
+use axum::http::{HeaderMap, StatusCode};
+use axum::routing::get;
+use axum::{Json, Router};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use serenity::all::Cache;
+use serenity::all::GuildId;
+use std::sync::Arc;
+
+use crate::config::FileConfig;
+use crate::storage::{self, unix_now};
+use crate::voice_events::activity_score;
+use crate::voice_tracker::AppState;
+
+// 요청마다 이 헤더에 설정 파일 dashboard.token과 같은 값을 넣어야 함
+const TOKEN_HEADER: &str = "x-dashboard-token";
+
+const SECS_PER_DAY: i64 = 86400;
+const DEFAULT_LEADERBOARD_LIMIT: i64 = 10;
+const MAX_LEADERBOARD_LIMIT: i64 = 100;
+const DEFAULT_SESSIONS_LIMIT: i64 = 50;
+const MAX_SESSIONS_LIMIT: i64 = 200;
+
+type ApiError = (StatusCode, Json<Value>);
+type ApiResult = Result<Json<Value>, ApiError>;
+
+// 외부 대시보드용 읽기 전용 API. /healthz와 같은 HTTP 서버에서 제공
+pub struct Dashboard {
+    state: Arc<AppState>,
+    token: String,
+}
+
+// 설정 파일 dashboard.token 또는 DASHBOARD_TOKEN 환경 변수 (없으면 API를 열지 않음)
+pub fn token_from_config(file: &FileConfig) -> Option<String> {
+    file.value("dashboard.token", "DASHBOARD_TOKEN")
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+pub fn router(state: Arc<AppState>, token: String) -> Router {
+    Router::new()
+        .route("/guilds/{id}/active", get(active))
+        .route("/guilds/{id}/leaderboard", get(leaderboard))
+        .route("/guilds/{id}/sessions", get(sessions))
+        .with_state(Arc::new(Dashboard { state, token }))
+}
+
+fn api_error(status: StatusCode, message: &str) -> ApiError {
+    (status, Json(json!({ "error": message })))
+}
+
+fn storage_error(e: sqlx::Error) -> ApiError {
+    tracing::error!("대시보드 API 조회 실패: {}", e);
+    api_error(StatusCode::INTERNAL_SERVER_ERROR, "storage error")
+}
+
+// 길이가 같으면 모든 바이트를 비교 (일치하는 앞부분 길이로 토큰을 추측하지 못하도록)
+fn token_matches(expected: &str, given: &str) -> bool {
+    expected.len() == given.len()
+        && expected.bytes().zip(given.bytes()).fold(0, |diff, (a, b)| diff | (a ^ b)) == 0
+}
+
+impl Dashboard {
+    // 토큰 확인 후 봇이 참가한 길드인지 확인. 토큰이 틀리면 403, 모르는 길드면 404
+    async fn guild(&self, headers: &HeaderMap, id: &str) -> Result<(GuildId, Arc<Cache>), ApiError> {
+        let given = headers.get(TOKEN_HEADER).and_then(|v| v.to_str().ok()).unwrap_or_default();
+        if !token_matches(&self.token, given) {
+            return Err(api_error(StatusCode::FORBIDDEN, "invalid token"));
+        }
+        let Some(cache) = self.state.cache.read().await.clone() else {
+            return Err(api_error(StatusCode::SERVICE_UNAVAILABLE, "not connected to discord yet"));
+        };
+        id.parse::<u64>()
+            .ok()
+            .filter(|&id| id != 0)
+            .map(GuildId::new)
+            .filter(|guild_id| cache.guilds().contains(guild_id))
+            .map(|guild_id| (guild_id, cache))
+            .ok_or_else(|| api_error(StatusCode::NOT_FOUND, "unknown guild"))
+    }
+}
+
+// GET /guilds/{id}/active: 지금 활성화된 채널과 활성화 시간, 접속자 수, 지금까지의 활동 점수
+async fn active(State(dashboard): State<Arc<Dashboard>>, Path(id): Path<String>, headers: HeaderMap) -> ApiResult {
+    let (guild_id, cache) = dashboard.guild(&headers, &id).await?;
+    let sessions = dashboard.state.voice_tracker.active_sessions(guild_id).await;
+
+    let now = unix_now();
+    let mut channels: Vec<Value> = sessions
+        .into_iter()
+        .map(|(channel_id, elapsed, peak)| {
+            let duration_secs = elapsed.as_secs();
+            let (name, members) = cache
+                .guild(guild_id)
+                .map(|g| {
+                    let name = g.channels.get(&channel_id).map(|c| c.name.clone());
+                    let members = g.voice_states.values().filter(|v| v.channel_id == Some(channel_id)).count();
+                    (name, members)
+                })
+                .unwrap_or_default();
+            json!({
+                "channel_id": channel_id.to_string(),
+                "name": name,
+                "started_at": now - duration_secs as i64,
+                "duration_secs": duration_secs,
+                "members": members,
+                "peak_members": peak,
+                "activity_score": activity_score(peak, duration_secs),
+            })
+        })
+        .collect();
+    channels.sort_by_key(|c| std::cmp::Reverse(c["duration_secs"].as_u64().unwrap_or(0)));
+    Ok(Json(json!({
+        "guild_id": guild_id.to_string(),
+        "channels": channels,
+    })))
+}
+
+#[derive(Deserialize)]
+struct LeaderboardQuery {
+    period: Option<String>,
+    limit: Option<i64>,
+}
+
+// GET /guilds/{id}/leaderboard?period=day|week|month|all&limit=: 보이스 시간 순위 (기본 week).
+// 기간별 순위는 일별 기록(35일 보관)에서, all은 누적 시간에서 계산
+async fn leaderboard(
+    State(dashboard): State<Arc<Dashboard>>,
+    Path(id): Path<String>,
+    Query(query): Query<LeaderboardQuery>,
+    headers: HeaderMap,
+) -> ApiResult {
+    let (guild_id, _) = dashboard.guild(&headers, &id).await?;
+    let period = query.period.as_deref().unwrap_or("week");
+    let days = match period {
+        "day" => Some(1),
+        "week" => Some(7),
+        "month" => Some(30),
+        "all" => None,
+        _ => return Err(api_error(StatusCode::BAD_REQUEST, "period must be day, week, month or all")),
+    };
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_LEADERBOARD_LIMIT)
+        .clamp(1, MAX_LEADERBOARD_LIMIT);
+    let pool = &dashboard.state.storage;
+    let rows = match days {
+        Some(days) => {
+            let since_day = unix_now().div_euclid(SECS_PER_DAY) - (days - 1);
+            storage::top_users_since(pool, guild_id, since_day, limit).await
+        }
+        None => storage::top_users(pool, guild_id, limit).await,
+    }
+    .map_err(storage_error)?;
+
+    let users: Vec<Value> = rows
+        .into_iter()
+        .enumerate()
+        .map(|(i, (user_id, secs))| json!({ "rank": i + 1, "user_id": user_id.to_string(), "secs": secs }))
+        .collect();
+    Ok(Json(json!({
+        "guild_id": guild_id.to_string(),
+        "period": period,
+        "users": users,
+    })))
+}
+
+#[derive(Deserialize)]
+struct SessionsQuery {
+    since: Option<i64>,
+    after: Option<i64>,
+    limit: Option<i64>,
+}
+
+// GET /guilds/{id}/sessions?since=<유닉스 초>&after=<id>&limit=: 종료된 활성화 기록.
+// 다음 페이지가 있으면 next_after를 after로 넘겨 이어서 조회
+async fn sessions(
+    State(dashboard): State<Arc<Dashboard>>,
+    Path(id): Path<String>,
+    Query(query): Query<SessionsQuery>,
+    headers: HeaderMap,
+) -> ApiResult {
+    let (guild_id, _) = dashboard.guild(&headers, &id).await?;
+    let limit = query.limit.unwrap_or(DEFAULT_SESSIONS_LIMIT).clamp(1, MAX_SESSIONS_LIMIT);
+    // 다음 페이지가 있는지 알기 위해 하나 더 조회
+    let mut rows = storage::sessions_since(
+        &dashboard.state.storage,
+        guild_id,
+        query.since.unwrap_or(0),
+        query.after.unwrap_or(0),
+        limit + 1,
+    )
+    .await
+    .map_err(storage_error)?;
+    let more = rows.len() as i64 > limit;
+    rows.truncate(limit as usize);
+    let next_after = rows.last().filter(|_| more).map(|(id, _)| *id);
+
+    let sessions: Vec<Value> = rows
+        .into_iter()
+        .map(|(id, session)| {
+            json!({
+                "id": id,
+                "session_id": session.session_id,
+                "channel_id": session.channel_id.to_string(),
+                "started_at": session.started_at,
+                "ended_at": session.ended_at,
+                "duration_secs": session.ended_at - session.started_at,
+                "peak_members": session.peak_members,
+                "activity_score": session.activity_score,
+                "starter_id": session.starter_id.map(|id| id.to_string()),
+            })
+        })
+        .collect();
+    Ok(Json(json!({
+        "guild_id": guild_id.to_string(),
+        "sessions": sessions,
+        "next_after": next_after,
+    })))
+}
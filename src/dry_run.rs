@@ -0,0 +1,60 @@
+use serenity::all::GuildId;
+use serenity::prelude::*;
+use std::fmt::Display;
+
+use crate::config::FileConfig;
+use crate::guild_config::get_guild_config;
+
+// 드라이런이 켜진 범위
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DryRunScope {
+    // 설정 파일 dry_run.enabled (모든 서버)
+    Global,
+    // 서버 설정 dry_run (/config set dry_run on)
+    Guild,
+}
+
+impl DryRunScope {
+    pub fn label(self) -> &'static str {
+        match self {
+            DryRunScope::Global => "봇 전체",
+            DryRunScope::Guild => "이 서버",
+        }
+    }
+}
+
+// 전체 드라이런 여부 (/reloadconfig, 파일 감시에서 교체)
+pub struct GlobalDryRun;
+
+impl TypeMapKey for GlobalDryRun {
+    type Value = bool;
+}
+
+// 설정 파일 dry_run.enabled 또는 DRY_RUN 환경 변수에서 읽음 (기본값 꺼짐)
+pub fn from_config(file: &FileConfig) -> bool {
+    file.value("dry_run.enabled", "DRY_RUN")
+        .is_some_and(|v| matches!(v.trim().to_ascii_lowercase().as_str(), "1" | "true" | "on" | "yes"))
+}
+
+// 이 서버에서 드라이런이 켜져 있으면 그 범위. guild_id가 없으면(DM 등) 전체 설정만 확인
+pub async fn dry_run_scope(ctx: &Context, guild_id: Option<GuildId>) -> Option<DryRunScope> {
+    let global = {
+        let data = ctx.data.read().await;
+        data.get::<GlobalDryRun>().copied().unwrap_or(false)
+    };
+    if global {
+        return Some(DryRunScope::Global);
+    }
+    let guild_id = guild_id?;
+    get_guild_config(ctx, guild_id).await.dry_run.then_some(DryRunScope::Guild)
+}
+
+// 드라이런 중이면 하려던 동작과 내용을 로그로 남기고 true. true를 받은 쪽은 실제 동작을 건너뜀
+pub async fn suppressed(ctx: &Context, guild_id: Option<GuildId>, action: &str, detail: impl Display) -> bool {
+    let Some(scope) = dry_run_scope(ctx, guild_id).await else {
+        return false;
+    };
+    let guild = guild_id.map_or_else(|| "-".to_string(), |id| id.to_string());
+    tracing::info!("[드라이런: {}] {} 생략 (길드 {}): {}", scope.label(), action, guild, detail);
+    true
+}
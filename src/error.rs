@@ -0,0 +1,92 @@
+use serenity::all::{ChannelId, GuildId};
+use serenity::prelude::*;
+use std::any::type_name;
+use std::fmt;
+
+use crate::calc::CalcError;
+use crate::error_report::report_error;
+
+// 핸들러 내부 함수들이 돌려주는 오류. 바깥의 얇은 핸들러가 맥락과 함께 기록/보고
+#[derive(Debug, thiserror::Error)]
+pub enum BotError {
+    // serenity::Error는 커서 Result가 무거워지지 않도록 박싱
+    #[error("디스코드 API 오류: {0}")]
+    Discord(Box<serenity::Error>),
+    #[error("저장소 오류: {0}")]
+    Storage(#[from] sqlx::Error),
+    #[error("{0}")]
+    Calc(#[from] CalcError),
+    #[error("설정 오류: {0}")]
+    Config(String),
+    // main에서 넣어 두지 않은 TypeMap 값 (패닉 대신 해당 기능만 건너뜀)
+    #[error("공유 상태 {0} 가 초기화되지 않았습니다")]
+    MissingState(&'static str),
+}
+
+impl From<serenity::Error> for BotError {
+    fn from(e: serenity::Error) -> Self {
+        BotError::Discord(Box::new(e))
+    }
+}
+
+// 오류가 난 위치. 보고 메시지의 작업 이름에 길드/채널을 덧붙임
+#[derive(Debug, Clone, Copy)]
+pub struct ErrorContext<'a> {
+    pub operation: &'a str,
+    pub guild_id: Option<GuildId>,
+    pub channel_id: Option<ChannelId>,
+}
+
+impl<'a> ErrorContext<'a> {
+    pub fn new(operation: &'a str) -> Self {
+        Self {
+            operation,
+            guild_id: None,
+            channel_id: None,
+        }
+    }
+
+    pub fn guild(mut self, guild_id: GuildId) -> Self {
+        self.guild_id = Some(guild_id);
+        self
+    }
+
+    pub fn channel(mut self, channel_id: ChannelId) -> Self {
+        self.channel_id = Some(channel_id);
+        self
+    }
+}
+
+impl fmt::Display for ErrorContext<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.operation)?;
+        match (self.guild_id, self.channel_id) {
+            (Some(g), Some(c)) => write!(f, " (길드 {}, 채널 {})", g, c),
+            (Some(g), None) => write!(f, " (길드 {})", g),
+            (None, Some(c)) => write!(f, " (채널 {})", c),
+            (None, None) => Ok(()),
+        }
+    }
+}
+
+// 맥락과 함께 로그를 남기고 보고 채널로 전송 (report_error와 같은 중복 억제)
+pub async fn report_bot_error(ctx: &Context, context: ErrorContext<'_>, error: &BotError) {
+    report_error(ctx, &context.to_string(), error).await;
+}
+
+// TypeMap 값 조회. 없으면 expect로 멈추지 않고 MissingState 오류를 돌려줌
+pub async fn require<K>(ctx: &Context) -> Result<K::Value, BotError>
+where
+    K: TypeMapKey,
+    K::Value: Clone,
+{
+    let data = ctx.data.read().await;
+    data.get::<K>()
+        .cloned()
+        .ok_or(BotError::MissingState(short_type_name::<K>()))
+}
+
+fn short_type_name<K>() -> &'static str {
+    let name = type_name::<K>();
+    name.rsplit("::").next().unwrap_or(name)
+}
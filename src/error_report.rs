@@ -0,0 +1,172 @@
+use serenity::all::ChannelId;
+use serenity::all::CreateMessage;
+use serenity::prelude::*;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+
+use crate::config::FileConfig;
+use crate::notifier::{notifier, NotifyTarget};
+use crate::retry::{retrying, RetryError, RetryPolicy};
+use crate::usage;
+
+// 같은 오류의 누적 횟수를 다시 보고하기까지의 간격
+const REPORT_INTERVAL: Duration = Duration::from_secs(10 * 60);
+// 이 시간 동안 다시 발생하지 않은 오류 항목은 정리
+const ENTRY_TTL: Duration = Duration::from_secs(60 * 60);
+
+// 런타임 오류를 관리자 채널로 보고 (중복 억제 포함)
+pub struct ErrorReporter;
+
+impl TypeMapKey for ErrorReporter {
+    type Value = Arc<ErrorReportState>;
+}
+
+struct ReportEntry {
+    last_reported: Instant,
+    last_seen: Instant,
+    suppressed: u32,
+}
+
+pub struct ErrorReportState {
+    channel_id: Option<ChannelId>,
+    entries: Mutex<HashMap<String, ReportEntry>>,
+}
+
+impl ErrorReportState {
+    pub fn new(channel_id: Option<ChannelId>) -> Self {
+        Self {
+            channel_id,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    // 설정 파일 error_report.channel_id 또는 ERROR_REPORT_CHANNEL_ID 환경 변수에서 보고 채널을 읽음 (없으면 로그만 남김)
+    pub fn from_config(file: &FileConfig) -> Self {
+        let channel_id = file
+            .value("error_report.channel_id", "ERROR_REPORT_CHANNEL_ID")
+            .and_then(|v| v.trim().parse::<u64>().ok())
+            .filter(|&id| id != 0)
+            .map(ChannelId::new);
+        Self::new(channel_id)
+    }
+
+    // 보고할 메시지를 결정. None이면 이번 발생은 누적만 하고 보고하지 않음
+    async fn record(&self, key: &str, now: Instant) -> Option<u32> {
+        let mut entries = self.entries.lock().await;
+        entries.retain(|_, e| now.duration_since(e.last_seen) < ENTRY_TTL);
+
+        match entries.get_mut(key) {
+            None => {
+                entries.insert(
+                    key.to_string(),
+                    ReportEntry {
+                        last_reported: now,
+                        last_seen: now,
+                        suppressed: 0,
+                    },
+                );
+                Some(0)
+            }
+            Some(entry) => {
+                entry.last_seen = now;
+                entry.suppressed += 1;
+                if now.duration_since(entry.last_reported) >= REPORT_INTERVAL {
+                    let count = entry.suppressed;
+                    entry.suppressed = 0;
+                    entry.last_reported = now;
+                    Some(count)
+                } else {
+                    None
+                }
+            }
+        }
+    }
+}
+
+// 오류 보고 채널 (다른 전달 수단이 모두 실패했을 때 대신 쓰기도 함)
+pub async fn error_report_channel(ctx: &Context) -> Option<ChannelId> {
+    let data = ctx.data.read().await;
+    data.get::<ErrorReporter>().and_then(|state| state.channel_id)
+}
+
+// 실패한 작업을 로그로 남기고, 보고 채널이 설정되어 있으면 중복을 억제하여 전송
+pub async fn report_error(ctx: &Context, operation: &str, error: &(dyn Display + Sync)) {
+    tracing::error!("{} 실패: {}", operation, error);
+    usage::note_error();
+
+    let state = {
+        let data = ctx.data.read().await;
+        match data.get::<ErrorReporter>() {
+            Some(state) => state.clone(),
+            None => return,
+        }
+    };
+    let Some(channel_id) = state.channel_id else {
+        return;
+    };
+
+    let key = format!("{}: {}", operation, error);
+    let content = match state.record(&key, Instant::now()).await {
+        Some(0) => format!("⚠️ **{}** 실패\n```{}```", operation, error),
+        Some(count) => format!(
+            "⚠️ **{}** 실패 (지난 보고 이후 {}회 추가 발생)\n```{}```",
+            operation, count, error
+        ),
+        None => return,
+    };
+
+    if let Err(e) = channel_id.say(&ctx.http, content).await {
+        tracing::error!("오류 보고 전송 실패: {:?}", e);
+    }
+}
+
+// 채널에 메시지를 보내고, 실패하면 오류 보고
+pub async fn notify_or_report(
+    ctx: &Context,
+    channel_id: ChannelId,
+    content: impl Into<String>,
+    operation: &str,
+) {
+    send_or_report(ctx, channel_id, CreateMessage::new().content(content), operation).await;
+}
+
+// 임베드 등 메시지 전체를 보내고, 실패하면 오류 보고 (일시적인 오류는 짧게 재시도)
+pub async fn send_or_report(
+    ctx: &Context,
+    channel_id: ChannelId,
+    message: CreateMessage,
+    operation: &str,
+) {
+    let policy = RetryPolicy::NOTIFICATION;
+    let notifier = notifier(ctx).await;
+    let send = || notifier.send(NotifyTarget::Channel(channel_id), message.clone());
+    match retrying(operation, policy, send).await {
+        Ok(_) => {}
+        Err(RetryError::Failed(e)) => report_error(ctx, operation, &e).await,
+        Err(RetryError::TimedOut) => {
+            let guild_id = ctx.cache.guilds().into_iter().find(|&g| {
+                ctx.cache
+                    .guild(g)
+                    .is_some_and(|g| g.channels.contains_key(&channel_id))
+            });
+            tracing::warn!(
+                "{} 시간 초과 ({}초, 길드 {:?}, 채널 {})",
+                operation,
+                policy.deadline.as_secs(),
+                guild_id.map(|g| g.get()),
+                channel_id
+            );
+        }
+    }
+}
+
+// retrying이 실패했을 때 보고 (시간 초과도 오류로 보고)
+pub async fn report_retry_error(ctx: &Context, operation: &str, error: RetryError) {
+    match error {
+        RetryError::Failed(e) => report_error(ctx, operation, &e).await,
+        RetryError::TimedOut => report_error(ctx, operation, &"응답 시간 초과").await,
+    }
+}
@@ -0,0 +1,281 @@
+use serde_json::{json, Value};
+use serenity::prelude::*;
+use std::cell::RefCell;
+use std::future::Future;
+use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+use tracing::Instrument;
+
+use crate::config::FileConfig;
+use crate::usage::LATENCY_BUCKETS_MS;
+
+// 기본값: 이벤트 하나를 1초 넘게 처리하면 경고
+pub const DEFAULT_SLOW_EVENT_MS: u64 = 1000;
+
+static SLOW_EVENT_MS: AtomicU64 = AtomicU64::new(DEFAULT_SLOW_EVENT_MS);
+// 지금 처리 중인 이벤트 핸들러 수
+static IN_FLIGHT: AtomicI64 = AtomicI64::new(0);
+
+// 설정 파일 metrics.slow_event_ms 또는 SLOW_EVENT_MS 환경 변수 (시작할 때와 /reloadconfig에서 적용)
+pub fn apply_config(file: &FileConfig) {
+    let ms = file
+        .value("metrics.slow_event_ms", "SLOW_EVENT_MS")
+        .and_then(|v| v.trim().parse::<u64>().ok())
+        .filter(|&ms| ms > 0)
+        .unwrap_or(DEFAULT_SLOW_EVENT_MS);
+    SLOW_EVENT_MS.store(ms, Ordering::Relaxed);
+}
+
+// 시간을 따로 재는 처리 단계
+#[derive(Debug, Clone, Copy)]
+pub enum Phase {
+    // 디스코드 캐시와 길드 설정 캐시 조회
+    Cache,
+    Storage,
+    // 디스코드 API 호출 (응답, 메시지 전송)
+    Discord,
+}
+
+const PHASES: [Phase; 3] = [Phase::Cache, Phase::Storage, Phase::Discord];
+
+impl Phase {
+    fn label(self) -> &'static str {
+        match self {
+            Phase::Cache => "캐시 조회",
+            Phase::Storage => "저장소",
+            Phase::Discord => "디스코드 전송",
+        }
+    }
+
+    // 트레이싱 스팬 필드 값
+    fn name(self) -> &'static str {
+        match self {
+            Phase::Cache => "cache",
+            Phase::Storage => "storage",
+            Phase::Discord => "discord",
+        }
+    }
+}
+
+// 측정하는 이벤트 핸들러
+#[derive(Debug, Clone, Copy)]
+pub enum Handler {
+    VoiceStateUpdate,
+    InteractionCreate,
+}
+
+const HANDLERS: [Handler; 2] = [Handler::VoiceStateUpdate, Handler::InteractionCreate];
+
+impl Handler {
+    fn name(self) -> &'static str {
+        match self {
+            Handler::VoiceStateUpdate => "voice_state_update",
+            Handler::InteractionCreate => "interaction_create",
+        }
+    }
+
+    fn stats(self) -> &'static HandlerStats {
+        &STATS[self as usize]
+    }
+}
+
+tokio::task_local! {
+    // 처리 중인 이벤트의 단계별 누적 시간 (Phase 순서)
+    static PHASE_TIMES: RefCell<[Duration; 3]>;
+}
+
+// 처리 시간 분포 (구간은 /usage와 같은 LATENCY_BUCKETS_MS)
+struct Histogram {
+    buckets: [AtomicU64; LATENCY_BUCKETS_MS.len()],
+    count: AtomicU64,
+    total_ms: AtomicU64,
+    max_ms: AtomicU64,
+}
+
+impl Histogram {
+    const fn new() -> Self {
+        Self {
+            buckets: [const { AtomicU64::new(0) }; LATENCY_BUCKETS_MS.len()],
+            count: AtomicU64::new(0),
+            total_ms: AtomicU64::new(0),
+            max_ms: AtomicU64::new(0),
+        }
+    }
+
+    fn record(&self, elapsed_ms: u64) {
+        let index = LATENCY_BUCKETS_MS
+            .iter()
+            .position(|&bound| elapsed_ms <= bound)
+            .unwrap_or(LATENCY_BUCKETS_MS.len() - 1);
+        self.buckets[index].fetch_add(1, Ordering::Relaxed);
+        self.count.fetch_add(1, Ordering::Relaxed);
+        self.total_ms.fetch_add(elapsed_ms, Ordering::Relaxed);
+        self.max_ms.fetch_max(elapsed_ms, Ordering::Relaxed);
+    }
+
+    // 값을 읽고 (reset이면) 0으로 되돌림
+    fn snapshot(&self, reset: bool) -> HistogramSnapshot {
+        let read = |value: &AtomicU64| if reset { value.swap(0, Ordering::Relaxed) } else { value.load(Ordering::Relaxed) };
+        HistogramSnapshot {
+            buckets: self.buckets.iter().map(read).collect(),
+            count: read(&self.count),
+            total_ms: read(&self.total_ms),
+            max_ms: read(&self.max_ms),
+        }
+    }
+}
+
+struct HistogramSnapshot {
+    buckets: Vec<u64>,
+    count: u64,
+    total_ms: u64,
+    max_ms: u64,
+}
+
+impl HistogramSnapshot {
+    // 백분위수가 속한 구간의 상한 (표본이 없으면 None)
+    fn percentile(&self, p: f64) -> Option<u64> {
+        if self.count == 0 {
+            return None;
+        }
+        let rank = ((self.count as f64) * p).ceil().max(1.0) as u64;
+        let mut seen = 0;
+        self.buckets
+            .iter()
+            .zip(LATENCY_BUCKETS_MS)
+            .find(|(count, _)| {
+                seen += **count;
+                seen >= rank
+            })
+            .map(|(_, bound)| *bound)
+    }
+
+    fn summary(&self) -> String {
+        let bound = |p| match self.percentile(p) {
+            Some(u64::MAX) => format!(">{}ms", LATENCY_BUCKETS_MS[LATENCY_BUCKETS_MS.len() - 2]),
+            Some(ms) => format!("≤{}ms", ms),
+            None => "-".to_string(),
+        };
+        let average = self.total_ms.checked_div(self.count).unwrap_or(0);
+        format!(
+            "{}건, 평균 {}ms, p50 {}, p95 {}, 최대 {}ms",
+            self.count,
+            average,
+            bound(0.5),
+            bound(0.95),
+            self.max_ms
+        )
+    }
+}
+
+// 핸들러별 분포: 시작 이후 전체(/healthz)와 마지막 요약 이후(5분 요약 로그)
+struct HandlerStats {
+    total: Histogram,
+    window: Histogram,
+}
+
+static STATS: [HandlerStats; 2] = [const {
+    HandlerStats {
+        total: Histogram::new(),
+        window: Histogram::new(),
+    }
+}; 2];
+
+// 이벤트 처리를 감싸 처리 시간과 동시 처리 수를 기록. 오래 걸리면 단계별 시간과 함께 경고
+pub async fn instrument<F>(handler: Handler, run: F)
+where
+    F: Future<Output = ()>,
+{
+    IN_FLIGHT.fetch_add(1, Ordering::Relaxed);
+    let started = Instant::now();
+    let phases = PHASE_TIMES
+        .scope(RefCell::new([Duration::ZERO; 3]), async {
+            run.await;
+            PHASE_TIMES.with(|times| *times.borrow())
+        })
+        .await;
+    IN_FLIGHT.fetch_sub(1, Ordering::Relaxed);
+
+    let elapsed = started.elapsed();
+    let elapsed_ms = elapsed.as_millis() as u64;
+    let stats = handler.stats();
+    stats.total.record(elapsed_ms);
+    stats.window.record(elapsed_ms);
+
+    if elapsed_ms < SLOW_EVENT_MS.load(Ordering::Relaxed) {
+        return;
+    }
+    let other = elapsed.saturating_sub(phases.iter().sum());
+    let (slowest, slowest_time) = PHASES
+        .iter()
+        .zip(phases)
+        .map(|(phase, time)| (phase.label(), time))
+        .chain(std::iter::once(("기타", other)))
+        .max_by_key(|&(_, time)| time)
+        .unwrap_or(("기타", other));
+    let breakdown = PHASES
+        .iter()
+        .zip(phases)
+        .map(|(phase, time)| format!("{} {}ms", phase.label(), time.as_millis()))
+        .chain(std::iter::once(format!("기타 {}ms", other.as_millis())))
+        .collect::<Vec<_>>()
+        .join(", ");
+    tracing::warn!(
+        "{} 처리에 {}ms 걸림 (가장 오래 걸린 단계: {} {}ms / {})",
+        handler.name(),
+        elapsed_ms,
+        slowest,
+        slowest_time.as_millis(),
+        breakdown
+    );
+}
+
+// 처리 단계 하나의 시간을 재어 현재 이벤트에 더함 (instrument 밖에서는 그냥 실행).
+// 트레이싱에서는 현재 이벤트 스팬 아래의 phase 스팬으로 기록
+pub async fn phase<F: Future>(phase: Phase, run: F) -> F::Output {
+    let started = Instant::now();
+    let output = run.instrument(tracing::info_span!("phase", phase = phase.name())).await;
+    let _ = PHASE_TIMES.try_with(|times| times.borrow_mut()[phase as usize] += started.elapsed());
+    output
+}
+
+// /healthz에 넣을 값
+pub fn report() -> Value {
+    let mut handlers = serde_json::Map::new();
+    for handler in HANDLERS {
+        let snapshot = handler.stats().total.snapshot(false);
+        let buckets: serde_json::Map<String, Value> = LATENCY_BUCKETS_MS
+            .iter()
+            .zip(&snapshot.buckets)
+            .map(|(bound, count)| {
+                let key = if *bound == u64::MAX { "inf".to_string() } else { bound.to_string() };
+                (key, json!(count))
+            })
+            .collect();
+        handlers.insert(
+            handler.name().to_string(),
+            json!({
+                "count": snapshot.count,
+                "total_ms": snapshot.total_ms,
+                "max_ms": snapshot.max_ms,
+                "buckets_ms": buckets,
+            }),
+        );
+    }
+    json!({
+        "in_flight": IN_FLIGHT.load(Ordering::Relaxed),
+        "slow_threshold_ms": SLOW_EVENT_MS.load(Ordering::Relaxed),
+        "handlers": handlers,
+    })
+}
+
+// 예약 작업: 지난 요약 이후의 처리 시간을 로그로 남김
+pub async fn log_summary(_ctx: Context) {
+    for handler in HANDLERS {
+        let snapshot = handler.stats().window.snapshot(true);
+        if snapshot.count > 0 {
+            tracing::info!("이벤트 처리 시간 {}: {}", handler.name(), snapshot.summary());
+        }
+    }
+    tracing::info!("처리 중인 이벤트 핸들러: {}개", IN_FLIGHT.load(Ordering::Relaxed));
+}
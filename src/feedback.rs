@@ -0,0 +1,169 @@
+use serenity::all::ChannelId;
+use serenity::all::CommandDataOptionValue;
+use serenity::all::CommandInteraction;
+use serenity::all::CreateEmbed;
+use serenity::all::CreateEmbedFooter;
+use serenity::all::CreateInteractionResponseMessage;
+use serenity::all::CreateMessage;
+use serenity::all::Timestamp;
+use serenity::prelude::*;
+
+use crate::commands::{is_owner, respond, BotOwner};
+use crate::config::ConfigFile;
+use crate::error_report::{error_report_channel, report_error};
+use crate::storage::{self, unix_now};
+
+// 피드백 길이 제한 (문자 수)
+pub const MIN_FEEDBACK_LEN: usize = 10;
+pub const MAX_FEEDBACK_LEN: u16 = 1500;
+// 사용자당 24시간 동안 보낼 수 있는 피드백 수
+const DAILY_FEEDBACK_LIMIT: i64 = 3;
+
+async fn reply(ctx: &Context, cmd: &CommandInteraction, content: String) {
+    respond(
+        ctx,
+        cmd,
+        CreateInteractionResponseMessage::new()
+            .content(content)
+            .ephemeral(true),
+    )
+    .await;
+}
+
+// /feedback send <text> | block <user> | unblock <user>
+pub async fn handle_feedback(ctx: &Context, cmd: &CommandInteraction) {
+    let Some(sub) = cmd.data.options.first() else {
+        return;
+    };
+    let CommandDataOptionValue::SubCommand(args) = &sub.value else {
+        return;
+    };
+    let Some(pool) = storage::pool(ctx).await else {
+        return;
+    };
+
+    match sub.name.as_str() {
+        "send" => {
+            let Some(text) = args.iter().find(|o| o.name == "text").and_then(|o| o.value.as_str()) else {
+                return;
+            };
+            let text = text.trim();
+            if text.chars().count() < MIN_FEEDBACK_LEN {
+                reply(ctx, cmd, format!("피드백은 {}자 이상 적어주세요.", MIN_FEEDBACK_LEN)).await;
+                return;
+            }
+            let allowed = async {
+                if storage::is_feedback_blocked(&pool, cmd.user.id).await? {
+                    return Ok(Err("피드백을 보낼 수 없습니다.".to_string()));
+                }
+                let sent = storage::feedback_count_since(&pool, cmd.user.id, unix_now() - 86400).await?;
+                if sent >= DAILY_FEEDBACK_LIMIT {
+                    return Ok(Err(format!(
+                        "피드백은 하루에 {}번까지 보낼 수 있습니다. 내일 다시 보내주세요.",
+                        DAILY_FEEDBACK_LIMIT
+                    )));
+                }
+                storage::insert_feedback(&pool, cmd.user.id, cmd.guild_id, cmd.channel_id, text, unix_now()).await?;
+                Ok::<_, sqlx::Error>(Ok(()))
+            };
+            match allowed.await {
+                Ok(Ok(())) => {}
+                Ok(Err(message)) => {
+                    reply(ctx, cmd, message).await;
+                    return;
+                }
+                Err(e) => {
+                    report_error(ctx, "피드백 저장", &e).await;
+                    reply(ctx, cmd, "피드백을 보내지 못했습니다. 잠시 후 다시 시도해주세요.".to_string()).await;
+                    return;
+                }
+            }
+            forward(ctx, cmd, text).await;
+            reply(ctx, cmd, "📨 피드백을 보냈습니다. 감사합니다!".to_string()).await;
+        }
+        "block" | "unblock" => {
+            if !is_owner(ctx, cmd.user.id).await {
+                reply(ctx, cmd, "피드백 차단은 봇 소유자만 할 수 있습니다.".to_string()).await;
+                return;
+            }
+            let Some(user_id) = args.iter().find(|o| o.name == "user").and_then(|o| o.value.as_user_id()) else {
+                return;
+            };
+            let block = sub.name == "block";
+            let content = match storage::set_feedback_blocked(&pool, user_id, block).await {
+                Ok(true) if block => format!("<@{}> 님의 피드백을 더 받지 않습니다.", user_id),
+                Ok(true) => format!("<@{}> 님의 피드백 차단을 해제했습니다.", user_id),
+                Ok(false) if block => format!("<@{}> 님은 이미 차단되어 있습니다.", user_id),
+                Ok(false) => format!("<@{}> 님은 차단되어 있지 않습니다.", user_id),
+                Err(e) => {
+                    report_error(ctx, "피드백 차단 변경", &e).await;
+                    "차단 상태를 바꾸지 못했습니다.".to_string()
+                }
+            };
+            reply(ctx, cmd, content).await;
+        }
+        _ => {}
+    }
+}
+
+// 설정 파일 feedback.channel_id 또는 FEEDBACK_CHANNEL_ID 환경 변수 (없으면 소유자 DM)
+async fn feedback_channel(ctx: &Context) -> Option<ChannelId> {
+    let loaded = {
+        let data = ctx.data.read().await;
+        data.get::<ConfigFile>().cloned()
+    }?;
+    let loaded = loaded.read().await;
+    loaded
+        .file
+        .value("feedback.channel_id", "FEEDBACK_CHANNEL_ID")
+        .and_then(|v| v.trim().parse::<u64>().ok())
+        .filter(|&id| id != 0)
+        .map(ChannelId::new)
+}
+
+// 피드백 채널 -> 소유자 DM 순으로 전달하고, 모두 실패하면 오류 보고 채널로
+async fn forward(ctx: &Context, cmd: &CommandInteraction, text: &str) {
+    let origin = match cmd.guild_id {
+        Some(guild_id) => {
+            let name = ctx.cache.guild(guild_id).map(|g| g.name.clone()).unwrap_or_default();
+            format!("{} (`{}`)\n<#{}>", name, guild_id, cmd.channel_id)
+        }
+        None => "DM".to_string(),
+    };
+    let embed = CreateEmbed::new()
+        .title("📨 새 피드백")
+        .description(text)
+        .field("보낸 사람", format!("<@{}> ({}, `{}`)", cmd.user.id, cmd.user.name, cmd.user.id), false)
+        .field("보낸 곳", origin, false)
+        .footer(CreateEmbedFooter::new("/feedback block 으로 이 사용자의 피드백을 막을 수 있습니다"))
+        .timestamp(Timestamp::now());
+    let message = CreateMessage::new().embed(embed);
+
+    if let Some(channel_id) = feedback_channel(ctx).await {
+        match channel_id.send_message(&ctx.http, message.clone()).await {
+            Ok(_) => return,
+            Err(e) => report_error(ctx, "피드백 채널 전송", &e).await,
+        }
+    }
+    let owner = {
+        let data = ctx.data.read().await;
+        data.get::<BotOwner>().copied()
+    };
+    if let Some(owner) = owner {
+        let sent = match owner.create_dm_channel(&ctx.http).await {
+            Ok(dm) => dm.id.send_message(&ctx.http, message.clone()).await.map(|_| ()),
+            Err(e) => Err(e),
+        };
+        match sent {
+            Ok(()) => return,
+            Err(e) => report_error(ctx, "피드백 소유자 DM", &e).await,
+        }
+    }
+    let Some(channel_id) = error_report_channel(ctx).await else {
+        tracing::warn!("피드백을 전달할 곳이 없습니다 ({}): {}", cmd.user.id, text);
+        return;
+    };
+    if let Err(e) = channel_id.send_message(&ctx.http, message).await {
+        tracing::warn!("피드백 전달 실패 ({}): {}", e, text);
+    }
+}
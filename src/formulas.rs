@@ -0,0 +1,261 @@
+use serenity::all::AutocompleteChoice;
+use serenity::all::CommandDataOptionValue;
+use serenity::all::CommandInteraction;
+use serenity::all::CreateAutocompleteResponse;
+use serenity::all::CreateEmbed;
+use serenity::all::CreateInteractionResponse;
+use serenity::all::CreateInteractionResponseMessage;
+use serenity::all::GuildId;
+use serenity::prelude::*;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+use crate::calc::{self, Complex, NumberMode};
+use crate::commands::{respond, validate_text_input};
+use crate::error_report::report_error;
+use crate::storage;
+
+// 서버마다 저장할 수 있는 수식 수
+const MAX_FORMULAS: usize = 100;
+// 수식 이름 최대 길이 (글자 수)
+const MAX_NAME_LEN: usize = 32;
+// 자동 완성 선택지 값은 디스코드 제한상 100자까지
+const MAX_FORMULA_LEN: usize = 100;
+// 자동 완성으로 보여줄 수 있는 최대 선택지 수 (디스코드 제한)
+const MAX_CHOICES: usize = 25;
+
+type Formulas = Arc<BTreeMap<String, String>>;
+
+// 서버별로 저장한 수식 (이름 -> 수식). 저장소에서 처음 읽을 때 채우고 /calcstore로 바꿀 때 갱신
+pub struct FormulasStore;
+
+impl TypeMapKey for FormulasStore {
+    type Value = Arc<RwLock<HashMap<GuildId, Formulas>>>;
+}
+
+pub fn new_formulas_store() -> Arc<RwLock<HashMap<GuildId, Formulas>>> {
+    Arc::new(RwLock::new(HashMap::new()))
+}
+
+async fn formulas_store(ctx: &Context) -> Option<Arc<RwLock<HashMap<GuildId, Formulas>>>> {
+    let data = ctx.data.read().await;
+    data.get::<FormulasStore>().cloned()
+}
+
+// 서버에 저장한 수식. 캐시에 없으면 저장소에서 읽어 채움 (읽지 못하면 빈 목록)
+pub async fn guild_formulas(ctx: &Context, guild_id: GuildId) -> Formulas {
+    let Some(store) = formulas_store(ctx).await else {
+        return Formulas::default();
+    };
+    if let Some(formulas) = store.read().await.get(&guild_id) {
+        return formulas.clone();
+    }
+    let Some(pool) = storage::pool(ctx).await else {
+        return Formulas::default();
+    };
+    match storage::guild_formulas(&pool, guild_id).await {
+        Ok(rows) => {
+            let formulas = Arc::new(rows.into_iter().collect::<BTreeMap<_, _>>());
+            store.write().await.insert(guild_id, formulas.clone());
+            formulas
+        }
+        Err(e) => {
+            tracing::warn!("저장한 수식 조회 실패 (길드 {}): {}", guild_id, e);
+            Formulas::default()
+        }
+    }
+}
+
+// 저장소를 바꾼 뒤 캐시를 비워 다음 조회 때 다시 읽음
+async fn invalidate(ctx: &Context, guild_id: GuildId) {
+    if let Some(store) = formulas_store(ctx).await {
+        store.write().await.remove(&guild_id);
+    }
+}
+
+// 이름이 입력 중인 글자로 시작하는 수식 (대소문자 무시, 이름 순, 최대 25개). (이름, 수식)
+pub fn matching_formulas<'a>(formulas: &'a BTreeMap<String, String>, partial: &str) -> Vec<(&'a str, &'a str)> {
+    let partial = partial.trim().to_lowercase();
+    formulas
+        .iter()
+        .filter(|(name, _)| name.to_lowercase().starts_with(&partial))
+        .take(MAX_CHOICES)
+        .map(|(name, expression)| (name.as_str(), expression.as_str()))
+        .collect()
+}
+
+// 수식 이름 검사 (앞뒤 공백 제거). 문제가 있으면 사용자에게 보여줄 문구
+fn validate_name(name: &str) -> Result<String, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("수식 이름을 입력해주세요.".to_string());
+    }
+    if name.chars().count() > MAX_NAME_LEN {
+        return Err(format!("수식 이름은 {}자까지 입력할 수 있습니다.", MAX_NAME_LEN));
+    }
+    validate_text_input(name, MAX_NAME_LEN * 4)?;
+    Ok(name.to_string())
+}
+
+// 저장할 수식 검사: 길이와 제어 문자, 그리고 계산되는 수식인지.
+// 쓰는 사람의 모드와 직전 결과는 알 수 없으므로 복소수 모드, ans = 0으로 확인
+fn validate_formula(expression: &str) -> Result<String, String> {
+    let expression = expression.trim();
+    if expression.chars().count() > MAX_FORMULA_LEN {
+        return Err(format!("저장할 수식은 {}자까지 입력할 수 있습니다.", MAX_FORMULA_LEN));
+    }
+    validate_text_input(expression, MAX_FORMULA_LEN * 4)?;
+    let ans = Some(Complex { re: 0.0, im: 0.0 });
+    calc::evaluate_value(expression, NumberMode::Complex, ans).map_err(|e| format!("계산할 수 없는 수식입니다: {}", e))?;
+    Ok(expression.to_string())
+}
+
+// /calc expr 자동 완성: 서버에 저장한 수식을 이름으로 보여주고, 고르면 수식이 입력됨 (DM에서는 없음)
+pub async fn handle_calc_autocomplete(ctx: &Context, ac: &CommandInteraction) {
+    let partial = ac.data.autocomplete().map_or("", |option| option.value);
+    let formulas = match ac.guild_id {
+        Some(guild_id) => guild_formulas(ctx, guild_id).await,
+        None => Formulas::default(),
+    };
+    let choices = matching_formulas(&formulas, partial)
+        .into_iter()
+        .map(|(name, expression)| AutocompleteChoice::new(name, expression))
+        .collect();
+    let response = CreateAutocompleteResponse::new().set_choices(choices);
+    if let Err(e) = ac
+        .create_response(&ctx.http, CreateInteractionResponse::Autocomplete(response))
+        .await
+    {
+        tracing::debug!("/calc 자동 완성 응답 실패: {}", e);
+    }
+}
+
+// /calcstore save <name> <expr> | delete <name> | list
+pub async fn handle_calcstore(ctx: &Context, cmd: &CommandInteraction) {
+    let Some(guild_id) = cmd.guild_id else {
+        return;
+    };
+    let Some(sub) = cmd.data.options.first() else {
+        return;
+    };
+    let CommandDataOptionValue::SubCommand(args) = &sub.value else {
+        return;
+    };
+    let arg = |name: &str| args.iter().find(|o| o.name == name).and_then(|o| o.value.as_str()).unwrap_or("");
+    let is_manager = cmd
+        .member
+        .as_ref()
+        .and_then(|m| m.permissions)
+        .is_some_and(|p| p.administrator() || p.manage_guild());
+
+    let message = match sub.name.as_str() {
+        "list" => CreateInteractionResponseMessage::new().embed(list_embed(&*guild_formulas(ctx, guild_id).await)),
+        // 서버 전체가 같이 쓰는 목록이므로 바꾸는 것은 서버 관리 권한이 있어야 함
+        "save" | "delete" if !is_manager => CreateInteractionResponseMessage::new()
+            .content("수식을 저장하거나 지우려면 서버 관리 권한이 필요합니다."),
+        "save" => CreateInteractionResponseMessage::new().content(save(ctx, cmd, guild_id, arg("name"), arg("expr")).await),
+        "delete" => CreateInteractionResponseMessage::new().content(delete(ctx, guild_id, arg("name")).await),
+        _ => return,
+    };
+    respond(ctx, cmd, message.ephemeral(true)).await;
+}
+
+async fn save(ctx: &Context, cmd: &CommandInteraction, guild_id: GuildId, name: &str, expression: &str) -> String {
+    let (name, expression) = match (validate_name(name), validate_formula(expression)) {
+        (Ok(name), Ok(expression)) => (name, expression),
+        (Err(e), _) | (_, Err(e)) => return e,
+    };
+    let formulas = guild_formulas(ctx, guild_id).await;
+    if !formulas.contains_key(&name) && formulas.len() >= MAX_FORMULAS {
+        return format!("수식은 서버마다 {}개까지 저장할 수 있습니다.", MAX_FORMULAS);
+    }
+    let Some(pool) = storage::pool(ctx).await else {
+        return "저장소를 사용할 수 없습니다.".to_string();
+    };
+    if let Err(e) = storage::save_formula(&pool, guild_id, &name, &expression, cmd.user.id).await {
+        report_error(ctx, "수식 저장", &e).await;
+        return "수식을 저장하지 못했습니다. 잠시 후 다시 시도해주세요.".to_string();
+    }
+    invalidate(ctx, guild_id).await;
+    format!("`{}` 수식을 저장했습니다: `{}`\n/calc 의 expr 에서 이름으로 고를 수 있습니다.", name, expression)
+}
+
+async fn delete(ctx: &Context, guild_id: GuildId, name: &str) -> String {
+    let name = name.trim();
+    let Some(pool) = storage::pool(ctx).await else {
+        return "저장소를 사용할 수 없습니다.".to_string();
+    };
+    match storage::delete_formula(&pool, guild_id, name).await {
+        Ok(true) => {
+            invalidate(ctx, guild_id).await;
+            format!("`{}` 수식을 지웠습니다.", name)
+        }
+        Ok(false) => format!("`{}` 이름으로 저장한 수식이 없습니다.", name),
+        Err(e) => {
+            report_error(ctx, "수식 삭제", &e).await;
+            "수식을 지우지 못했습니다. 잠시 후 다시 시도해주세요.".to_string()
+        }
+    }
+}
+
+fn list_embed(formulas: &BTreeMap<String, String>) -> CreateEmbed {
+    let description = if formulas.is_empty() {
+        "저장한 수식이 없습니다. `/calcstore save` 로 추가할 수 있습니다.".to_string()
+    } else {
+        formulas
+            .iter()
+            .map(|(name, expression)| format!("**{}** `{}`", name, expression))
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+    CreateEmbed::new()
+        .title(format!("🧮 저장한 수식 ({}/{})", formulas.len(), MAX_FORMULAS))
+        .description(description)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn formulas(entries: &[(&str, &str)]) -> BTreeMap<String, String> {
+        entries.iter().map(|(n, e)| (n.to_string(), e.to_string())).collect()
+    }
+
+    // 입력 중인 글자로 시작하는 이름만, 대소문자 무시
+    #[test]
+    fn matches_by_name_prefix() {
+        let formulas = formulas(&[("Area", "pi*r^2"), ("arc", "2*pi"), ("tax", "0.1"), ("원넓이", "pi*2^2")]);
+        assert_eq!(matching_formulas(&formulas, "ar"), vec![("Area", "pi*r^2"), ("arc", "2*pi")]);
+        assert_eq!(matching_formulas(&formulas, "AREA"), vec![("Area", "pi*r^2")]);
+        assert_eq!(matching_formulas(&formulas, "원"), vec![("원넓이", "pi*2^2")]);
+        assert!(matching_formulas(&formulas, "x").is_empty());
+    }
+
+    // 아무것도 입력하지 않으면 전부, 단 25개까지
+    #[test]
+    fn empty_input_lists_up_to_limit() {
+        let many: BTreeMap<String, String> = (0..40).map(|i| (format!("f{:02}", i), i.to_string())).collect();
+        let matched = matching_formulas(&many, "");
+        assert_eq!(matched.len(), MAX_CHOICES);
+        assert_eq!(matched[0], ("f00", "0"));
+    }
+
+    #[test]
+    fn name_validation() {
+        assert_eq!(validate_name("  area "), Ok("area".to_string()));
+        assert!(validate_name("   ").is_err());
+        assert!(validate_name(&"가".repeat(MAX_NAME_LEN + 1)).is_err());
+        assert!(validate_name(&"가".repeat(MAX_NAME_LEN)).is_ok());
+    }
+
+    // 계산되지 않거나 선택지 값 제한(100자)을 넘는 수식은 저장하지 않음
+    #[test]
+    fn formula_validation() {
+        assert_eq!(validate_formula(" pi*2^2 "), Ok("pi*2^2".to_string()));
+        assert!(validate_formula("1+").is_err());
+        assert!(validate_formula("sqrt(-1)").is_ok());
+        assert!(validate_formula("ans*2").is_ok());
+        assert!(validate_formula(&"1+".repeat(60)).is_err());
+    }
+}
@@ -0,0 +1,2019 @@
+use serenity::all::ButtonStyle;
+use serenity::all::ChannelId;
+use serenity::all::ChannelType;
+use serenity::all::CommandDataOption;
+use serenity::all::CommandDataOptionValue;
+use serenity::all::CommandInteraction;
+use serenity::all::ComponentInteraction;
+use serenity::all::CreateActionRow;
+use serenity::all::CreateAllowedMentions;
+use serenity::all::CreateAttachment;
+use serenity::all::CreateButton;
+use serenity::all::CreateEmbed;
+use serenity::all::CreateEmbedFooter;
+use serenity::all::CreateInteractionResponse;
+use serenity::all::CreateInteractionResponseMessage;
+use serenity::all::GuildId;
+use serenity::all::GenericId;
+use serenity::all::Member;
+use serenity::all::RoleId;
+use serenity::all::UserId;
+use serenity::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::sync::Arc;
+
+use crate::channel_status;
+use crate::commands::{registry, respond, resync_guild_commands};
+use crate::config_check::{check_guild, get_usable_config};
+use crate::dry_run::dry_run_scope;
+use crate::error_report::{notify_or_report, report_error};
+use crate::event_metrics::{self, Phase};
+use crate::long_message::{truncate, EMBED_DESCRIPTION_LIMIT, EMBED_FIELD_LIMIT, MESSAGE_LIMIT};
+use crate::notification::{self, TemplateKind};
+use crate::storage::{self, unix_now, ConfigChange};
+use crate::user_prefs::{find_timezone, TIMEZONE_EXAMPLES};
+
+pub const SAVE_FAILED: &str = "설정을 저장하지 못했습니다. 잠시 후 다시 시도해주세요.";
+
+// 설정 변경 기록 보관 기간과 길드별 최대 개수 (예약 작업이 정리)
+const CONFIG_HISTORY_RETENTION_DAYS: i64 = 180;
+const MAX_CONFIG_HISTORY_PER_GUILD: i64 = 500;
+// /config history에 보여줄 개수와 값 하나의 최대 표시 길이
+const CONFIG_HISTORY_LIMIT: i64 = 20;
+const CHANGE_VALUE_LIMIT: usize = 100;
+
+// 내보내기/가져오기에서 빼는 항목. 다른 서버에서는 의미 없는 채널/역할 ID
+const SERVER_SPECIFIC_FIELDS: &[&str] = &[
+    "notification_channel",
+    "mention_role",
+    "voice_stats_role",
+    "audit_channel",
+    "command_channels",
+    "command_permissions",
+    "mention_targets",
+    "status_rename_channels",
+    "hub_channel",
+    "afk_bypass_role",
+];
+// 가져올 설정 파일 최대 크기 (바이트)
+const MAX_IMPORT_BYTES: u32 = 64 * 1024;
+// 활성화 표시를 붙일 수 있는 보이스 채널 최대 수 (/voiceconfig statusname)
+const MAX_STATUS_RENAME_CHANNELS: usize = 10;
+// 서버별 활성화 알림 멘션 대상 최대 수 (/config mention add)
+const MAX_MENTION_TARGETS: usize = 50;
+// 알림 문구 템플릿 최대 길이 (글자 수, /voiceconfig message)
+const MAX_TEMPLATE_LEN: usize = 300;
+// 연속 보이스 기록의 하루 최소 접속 시간 상한 (분, /voiceconfig streak)
+pub const MAX_STREAK_MINUTES: u32 = 240;
+// 템플릿 미리보기에 쓰는 예시 값
+const TEMPLATE_PREVIEW_VARS: &[(&str, &str)] = &[
+    ("user", "오로라"),
+    ("channel", "일반"),
+    ("count", "3"),
+    ("duration", "1시간 5분 0초"),
+];
+
+// /voiceconfig reset 확인 버튼의 custom_id 접두사: voiceconfig:<reset|cancel>
+pub const VOICECONFIG_PREFIX: &str = "voiceconfig:";
+
+// /config history에서 고를 수 있는 설정 외 항목
+pub const HISTORY_EXTRA_KEYS: &[&str] = &[
+    "command_permissions",
+    "disabled_commands",
+    "mention_targets",
+    "status_rename_channels",
+];
+
+// 보이스 통계(/voicestats, /voicetop)를 볼 수 있는 범위
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PrivacyLevel {
+    #[default]
+    Public,
+    MembersOnly,
+    AdminOnly,
+}
+
+const PRIVACY_CHOICES: &[(&str, &str)] = &[
+    ("public", "모두 공개"),
+    ("members", "서버 멤버만"),
+    ("admin", "관리자만"),
+];
+
+// 오래 혼자 있는 멤버를 잠수 채널로 옮겼을 때 알리는 방법
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AfkNotice {
+    #[default]
+    Channel,
+    Dm,
+    Off,
+}
+
+// 알림음 크기 선택지 (백분율)
+#[cfg(feature = "chime")]
+const CHIME_VOLUME_CHOICES: &[(&str, &str)] = &[("25", "25%"), ("50", "50%"), ("75", "75%"), ("100", "100%")];
+
+const AFK_NOTICE_CHOICES: &[(&str, &str)] = &[
+    ("channel", "알림 채널"),
+    ("dm", "옮겨진 멤버에게 DM"),
+    ("off", "알리지 않음"),
+];
+
+impl AfkNotice {
+    fn from_choice(value: &str) -> Option<Self> {
+        match value {
+            "channel" => Some(Self::Channel),
+            "dm" => Some(Self::Dm),
+            "off" => Some(Self::Off),
+            _ => None,
+        }
+    }
+
+    fn choice(self) -> &'static str {
+        match self {
+            Self::Channel => "channel",
+            Self::Dm => "dm",
+            Self::Off => "off",
+        }
+    }
+}
+
+impl PrivacyLevel {
+    fn from_choice(value: &str) -> Option<Self> {
+        match value {
+            "public" => Some(Self::Public),
+            "members" => Some(Self::MembersOnly),
+            "admin" => Some(Self::AdminOnly),
+            _ => None,
+        }
+    }
+
+    fn choice(self) -> &'static str {
+        match self {
+            Self::Public => "public",
+            Self::MembersOnly => "members",
+            Self::AdminOnly => "admin",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Public => "모두 공개",
+            Self::MembersOnly => "서버 멤버만",
+            Self::AdminOnly => "관리자만",
+        }
+    }
+}
+
+// 이 역할이 있어야 커맨드를 실행할 수 있음 (/permission). 서버 관리자는 항상 실행 가능
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommandPermission {
+    pub command_name: String,
+    pub required_role: RoleId,
+}
+
+// 채널 활성화 알림에서 멘션할 역할 또는 사용자
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MentionTarget {
+    Role(RoleId),
+    User(UserId),
+}
+
+impl MentionTarget {
+    pub fn mention(self) -> String {
+        match self {
+            MentionTarget::Role(id) => format!("<@&{}>", id),
+            MentionTarget::User(id) => format!("<@{}>", id),
+        }
+    }
+}
+
+// 활성화 알림 멘션 대상 (/config mention). channel이 있으면 그 보이스 채널에만 적용
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ActivationMention {
+    pub target: MentionTarget,
+    pub channel: Option<ChannelId>,
+}
+
+// 길드별 봇 설정 (storage에 JSON으로 저장, 없는 필드는 기본값)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GuildConfig {
+    pub notification_channel: Option<ChannelId>,
+    pub mention_role: Option<RoleId>,
+    pub voice_stats_privacy: PrivacyLevel,
+    // 공개 범위가 "서버 멤버만"일 때 통계를 볼 수 있는 역할. 없으면 규칙 동의를 마친 멤버 모두
+    pub voice_stats_role: Option<RoleId>,
+    // 관리 명령 실행 기록을 남길 채널
+    pub audit_channel: Option<ChannelId>,
+    // 스레드 생성/보관/삭제를 알림 채널에 알릴지 여부
+    pub notify_thread_events: bool,
+    // 입장/퇴장 이벤트 기록 (/voicelog)
+    pub enable_voice_log: bool,
+    // 일반 커맨드(/calc 등)를 쓸 수 있는 채널. 비어 있으면 제한 없음
+    pub command_channels: Vec<ChannelId>,
+    // 커맨드별 필요 역할. 규칙이 없는 커맨드는 디스코드 기본 권한으로 확인
+    pub command_permissions: Vec<CommandPermission>,
+    // 보이스 입장/퇴장 알림 여부 (끄면 채널 활성화/비활성화만 알림)
+    pub notify_join_leave: bool,
+    // 보이스 채널 입장을 채널 채팅에 TTS 메시지로 읽을지
+    pub tts_join_announcements: bool,
+    // 채널이 활성화되면 봇이 잠깐 들어가 알림음을 재생할지 (chime 기능으로 빌드한 경우)
+    pub activation_chime: bool,
+    // 알림음 크기 (백분율, CHIME_VOLUME_CHOICES 중 하나)
+    pub chime_volume: u8,
+    // 멤버가 /nick으로 자신의 닉네임을 바꿀 수 있는지
+    pub allow_self_nick: bool,
+    // 다른 봇의 보이스 입장/퇴장도 알릴지 여부 (끄면 인원 집계에만 반영)
+    pub notify_bots: bool,
+    // 이 서버에서 끈 커맨드 (/config disable). 길드 커맨드 목록에서도 빠짐
+    pub disabled_commands: Vec<String>,
+    // /roleinfo를 서버 인사이트 보기 권한이 있는 멤버만 쓸 수 있는지 (끄면 모든 멤버)
+    pub roleinfo_requires_insights: bool,
+    // 알림 전송 등 외부 동작을 실행하지 않고 로그로만 남김 (설정/기준 조정을 실제 활동으로 확인할 때)
+    pub dry_run: bool,
+    // mention_role 외에 활성화 알림에서 멘션할 대상 (서버 전체 또는 채널별)
+    pub mention_targets: Vec<ActivationMention>,
+    // 활성화된 동안 이름 앞에 표시를 붙일 보이스 채널 (/voiceconfig statusname)
+    pub status_rename_channels: Vec<ChannelId>,
+    // 들어오면 그 멤버의 임시 보이스 채널을 만들어 주는 허브 채널
+    pub hub_channel: Option<ChannelId>,
+    // 혼자 스피커를 끈 채 오래 있는 멤버를 잠수 채널로 옮길지 (afk_move 예약 작업)
+    pub afk_move: bool,
+    // 옮기기 전까지 기다리는 시간 (분)
+    pub afk_move_minutes: u32,
+    // 이 역할이 있는 멤버는 옮기지 않음
+    pub afk_bypass_role: Option<RoleId>,
+    pub afk_move_notice: AfkNotice,
+    // 알림 문구 템플릿 (/voiceconfig message). 없으면 기본 문구
+    pub join_template: Option<String>,
+    pub leave_template: Option<String>,
+    pub activate_template: Option<String>,
+    pub deactivate_template: Option<String>,
+    // 채널에서 예약 이벤트가 진행 중이면 멘션 역할 대신 이벤트에 관심 표시한 멤버를 멘션
+    pub event_mentions: bool,
+    // 알림을 임베드로 보낼지 (끄면 일반 텍스트, 임베드를 막아 둔 채널용)
+    pub use_embeds: bool,
+    // 서버 기준 날짜를 나눌 IANA 시간대 이름 (연속 보이스 기록 등)
+    pub timezone: String,
+    // 연속 보이스 기록에 하루로 인정하는 최소 접속 시간 (분, /voiceconfig streak)
+    pub streak_min_minutes: u32,
+    // /calc 계산을 서버별로 기억해 /calchistory global로 보여줄지
+    pub log_calc_invocations: bool,
+}
+
+impl GuildConfig {
+    pub fn template(&self, kind: TemplateKind) -> Option<&str> {
+        self.template_slot(kind).as_deref()
+    }
+
+    fn template_slot(&self, kind: TemplateKind) -> &Option<String> {
+        match kind {
+            TemplateKind::Join => &self.join_template,
+            TemplateKind::Leave => &self.leave_template,
+            TemplateKind::Activate => &self.activate_template,
+            TemplateKind::Deactivate => &self.deactivate_template,
+        }
+    }
+
+    fn template_slot_mut(&mut self, kind: TemplateKind) -> &mut Option<String> {
+        match kind {
+            TemplateKind::Join => &mut self.join_template,
+            TemplateKind::Leave => &mut self.leave_template,
+            TemplateKind::Activate => &mut self.activate_template,
+            TemplateKind::Deactivate => &mut self.deactivate_template,
+        }
+    }
+
+    pub fn is_disabled(&self, command_name: &str) -> bool {
+        self.disabled_commands.iter().any(|c| c == command_name)
+    }
+
+    pub fn required_role(&self, command_name: &str) -> Option<RoleId> {
+        self.command_permissions
+            .iter()
+            .find(|p| p.command_name == command_name)
+            .map(|p| p.required_role)
+    }
+
+    // 이 보이스 채널이 활성화될 때 멘션할 대상. 채널 전용 대상이 있으면 그것만,
+    // 없으면 mention_role과 서버 전체 대상 (중복 제거는 알림을 만들 때)
+    pub fn activation_mentions(&self, channel_id: ChannelId) -> Vec<MentionTarget> {
+        let for_channel: Vec<MentionTarget> = self
+            .mention_targets
+            .iter()
+            .filter(|m| m.channel == Some(channel_id))
+            .map(|m| m.target)
+            .collect();
+        if !for_channel.is_empty() {
+            return for_channel;
+        }
+        self.mention_role
+            .map(MentionTarget::Role)
+            .into_iter()
+            .chain(self.mention_targets.iter().filter(|m| m.channel.is_none()).map(|m| m.target))
+            .collect()
+    }
+}
+
+impl Default for GuildConfig {
+    fn default() -> Self {
+        Self {
+            // 새 서버는 설정 마법사(/setup)나 /setchannel, /setrole로 정할 때까지 알림을 보내지 않음
+            notification_channel: None,
+            mention_role: None,
+            voice_stats_privacy: PrivacyLevel::default(),
+            voice_stats_role: None,
+            audit_channel: None,
+            notify_thread_events: false,
+            enable_voice_log: false,
+            command_channels: Vec::new(),
+            command_permissions: Vec::new(),
+            notify_join_leave: true,
+            tts_join_announcements: false,
+            activation_chime: false,
+            chime_volume: 50,
+            allow_self_nick: false,
+            notify_bots: false,
+            disabled_commands: Vec::new(),
+            roleinfo_requires_insights: false,
+            dry_run: false,
+            mention_targets: Vec::new(),
+            status_rename_channels: Vec::new(),
+            hub_channel: None,
+            afk_move: false,
+            afk_move_minutes: 30,
+            afk_bypass_role: None,
+            afk_move_notice: AfkNotice::default(),
+            join_template: None,
+            leave_template: None,
+            activate_template: None,
+            deactivate_template: None,
+            event_mentions: false,
+            use_embeds: true,
+            timezone: "UTC".to_string(),
+            streak_min_minutes: 10,
+            log_calc_invocations: false,
+        }
+    }
+}
+
+// 설정 값의 종류. /config set에서 입력을 검증하는 방식이 결정됨
+pub enum SettingKind {
+    Channel,
+    VoiceChannel,
+    Role,
+    ChannelList,
+    Toggle,
+    // 분 단위 시간 (최소, 최대)
+    Minutes(u32, u32),
+    // IANA 시간대 이름
+    Timezone,
+    // (값, 표시 이름)
+    Choice(&'static [(&'static str, &'static str)]),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SettingValue {
+    Channel(Option<ChannelId>),
+    Channels(Vec<ChannelId>),
+    Role(Option<RoleId>),
+    Toggle(bool),
+    Minutes(u32),
+    Choice(&'static str),
+}
+
+// /config에서 다루는 길드 설정 하나. SETTINGS에 추가하면 show/set/reset에 자동으로 나타남
+pub struct SettingSpec {
+    pub key: &'static str,
+    pub description: &'static str,
+    pub kind: SettingKind,
+    get: fn(&GuildConfig) -> SettingValue,
+    set: fn(&mut GuildConfig, SettingValue),
+}
+
+pub const SETTINGS: &[SettingSpec] = &[
+    SettingSpec {
+        key: "notification_channel",
+        description: "보이스 채널 알림을 보낼 텍스트 채널",
+        kind: SettingKind::Channel,
+        get: |c| SettingValue::Channel(c.notification_channel),
+        set: |c, v| {
+            if let SettingValue::Channel(id) = v {
+                c.notification_channel = id;
+            }
+        },
+    },
+    SettingSpec {
+        key: "mention_role",
+        description: "채널 활성화 시 멘션할 역할",
+        kind: SettingKind::Role,
+        get: |c| SettingValue::Role(c.mention_role),
+        set: |c, v| {
+            if let SettingValue::Role(id) = v {
+                c.mention_role = id;
+            }
+        },
+    },
+    SettingSpec {
+        key: "voice_stats_privacy",
+        description: "보이스 통계 공개 범위",
+        kind: SettingKind::Choice(PRIVACY_CHOICES),
+        get: |c| SettingValue::Choice(c.voice_stats_privacy.choice()),
+        set: |c, v| {
+            if let SettingValue::Choice(choice) = v {
+                c.voice_stats_privacy = PrivacyLevel::from_choice(choice).unwrap_or_default();
+            }
+        },
+    },
+    SettingSpec {
+        key: "voice_stats_role",
+        description: "공개 범위가 서버 멤버만일 때 보이스 통계를 볼 수 있는 역할",
+        kind: SettingKind::Role,
+        get: |c| SettingValue::Role(c.voice_stats_role),
+        set: |c, v| {
+            if let SettingValue::Role(id) = v {
+                c.voice_stats_role = id;
+            }
+        },
+    },
+    SettingSpec {
+        key: "audit_channel",
+        description: "관리 명령 실행 기록을 남길 텍스트 채널",
+        kind: SettingKind::Channel,
+        get: |c| SettingValue::Channel(c.audit_channel),
+        set: |c, v| {
+            if let SettingValue::Channel(id) = v {
+                c.audit_channel = id;
+            }
+        },
+    },
+    SettingSpec {
+        key: "notify_thread_events",
+        description: "스레드 생성/보관/삭제 알림",
+        kind: SettingKind::Toggle,
+        get: |c| SettingValue::Toggle(c.notify_thread_events),
+        set: |c, v| {
+            if let SettingValue::Toggle(on) = v {
+                c.notify_thread_events = on;
+            }
+        },
+    },
+    SettingSpec {
+        key: "enable_voice_log",
+        description: "보이스 입장/퇴장 기록 (/voicelog)",
+        kind: SettingKind::Toggle,
+        get: |c| SettingValue::Toggle(c.enable_voice_log),
+        set: |c, v| {
+            if let SettingValue::Toggle(on) = v {
+                c.enable_voice_log = on;
+            }
+        },
+    },
+    #[cfg(feature = "chime")]
+    SettingSpec {
+        key: "activation_chime",
+        description: "채널이 활성화되면 봇이 들어가 알림음 재생",
+        kind: SettingKind::Toggle,
+        get: |c| SettingValue::Toggle(c.activation_chime),
+        set: |c, v| {
+            if let SettingValue::Toggle(on) = v {
+                c.activation_chime = on;
+            }
+        },
+    },
+    #[cfg(feature = "chime")]
+    SettingSpec {
+        key: "chime_volume",
+        description: "활성화 알림음 크기",
+        kind: SettingKind::Choice(CHIME_VOLUME_CHOICES),
+        get: |c| {
+            let volume = c.chime_volume.to_string();
+            SettingValue::Choice(CHIME_VOLUME_CHOICES.iter().find(|(v, _)| *v == volume).map_or("50", |(v, _)| v))
+        },
+        set: |c, v| {
+            if let SettingValue::Choice(choice) = v {
+                c.chime_volume = choice.parse().unwrap_or(50);
+            }
+        },
+    },
+    SettingSpec {
+        key: "notify_join_leave",
+        description: "보이스 입장/퇴장 알림",
+        kind: SettingKind::Toggle,
+        get: |c| SettingValue::Toggle(c.notify_join_leave),
+        set: |c, v| {
+            if let SettingValue::Toggle(on) = v {
+                c.notify_join_leave = on;
+            }
+        },
+    },
+    SettingSpec {
+        key: "notify_bots",
+        description: "다른 봇의 보이스 입장/퇴장 알림",
+        kind: SettingKind::Toggle,
+        get: |c| SettingValue::Toggle(c.notify_bots),
+        set: |c, v| {
+            if let SettingValue::Toggle(on) = v {
+                c.notify_bots = on;
+            }
+        },
+    },
+    SettingSpec {
+        key: "tts_join_announcements",
+        description: "보이스 채널 입장을 채널 채팅에서 TTS로 읽기",
+        kind: SettingKind::Toggle,
+        get: |c| SettingValue::Toggle(c.tts_join_announcements),
+        set: |c, v| {
+            if let SettingValue::Toggle(on) = v {
+                c.tts_join_announcements = on;
+            }
+        },
+    },
+    SettingSpec {
+        key: "allow_self_nick",
+        description: "멤버가 /nick으로 자신의 닉네임 변경",
+        kind: SettingKind::Toggle,
+        get: |c| SettingValue::Toggle(c.allow_self_nick),
+        set: |c, v| {
+            if let SettingValue::Toggle(on) = v {
+                c.allow_self_nick = on;
+            }
+        },
+    },
+    SettingSpec {
+        key: "roleinfo_requires_insights",
+        description: "/roleinfo를 서버 인사이트 보기 권한이 있는 멤버만 사용",
+        kind: SettingKind::Toggle,
+        get: |c| SettingValue::Toggle(c.roleinfo_requires_insights),
+        set: |c, v| {
+            if let SettingValue::Toggle(on) = v {
+                c.roleinfo_requires_insights = on;
+            }
+        },
+    },
+    SettingSpec {
+        key: "dry_run",
+        description: "드라이런: 알림을 보내지 않고 봇 로그에만 기록",
+        kind: SettingKind::Toggle,
+        get: |c| SettingValue::Toggle(c.dry_run),
+        set: |c, v| {
+            if let SettingValue::Toggle(on) = v {
+                c.dry_run = on;
+            }
+        },
+    },
+    SettingSpec {
+        key: "hub_channel",
+        description: "들어오면 임시 보이스 채널을 만들어 주는 허브 채널",
+        kind: SettingKind::VoiceChannel,
+        get: |c| SettingValue::Channel(c.hub_channel),
+        set: |c, v| {
+            if let SettingValue::Channel(id) = v {
+                c.hub_channel = id;
+            }
+        },
+    },
+    SettingSpec {
+        key: "use_embeds",
+        description: "알림을 임베드로 보내기 (끄면 일반 텍스트)",
+        kind: SettingKind::Toggle,
+        get: |c| SettingValue::Toggle(c.use_embeds),
+        set: |c, v| {
+            if let SettingValue::Toggle(on) = v {
+                c.use_embeds = on;
+            }
+        },
+    },
+    SettingSpec {
+        key: "event_mentions",
+        description: "예약 이벤트 진행 중에는 관심 표시한 멤버를 멘션",
+        kind: SettingKind::Toggle,
+        get: |c| SettingValue::Toggle(c.event_mentions),
+        set: |c, v| {
+            if let SettingValue::Toggle(on) = v {
+                c.event_mentions = on;
+            }
+        },
+    },
+    SettingSpec {
+        key: "afk_move",
+        description: "혼자 스피커를 끈 채 오래 있는 멤버를 잠수 채널로 이동",
+        kind: SettingKind::Toggle,
+        get: |c| SettingValue::Toggle(c.afk_move),
+        set: |c, v| {
+            if let SettingValue::Toggle(on) = v {
+                c.afk_move = on;
+            }
+        },
+    },
+    SettingSpec {
+        key: "afk_move_minutes",
+        description: "잠수 채널로 옮기기까지 기다리는 시간",
+        kind: SettingKind::Minutes(5, 1440),
+        get: |c| SettingValue::Minutes(c.afk_move_minutes),
+        set: |c, v| {
+            if let SettingValue::Minutes(minutes) = v {
+                c.afk_move_minutes = minutes;
+            }
+        },
+    },
+    SettingSpec {
+        key: "afk_bypass_role",
+        description: "잠수 채널로 옮기지 않을 역할",
+        kind: SettingKind::Role,
+        get: |c| SettingValue::Role(c.afk_bypass_role),
+        set: |c, v| {
+            if let SettingValue::Role(id) = v {
+                c.afk_bypass_role = id;
+            }
+        },
+    },
+    SettingSpec {
+        key: "afk_move_notice",
+        description: "잠수 채널로 옮겼을 때 알리는 방법",
+        kind: SettingKind::Choice(AFK_NOTICE_CHOICES),
+        get: |c| SettingValue::Choice(c.afk_move_notice.choice()),
+        set: |c, v| {
+            if let SettingValue::Choice(choice) = v {
+                c.afk_move_notice = AfkNotice::from_choice(choice).unwrap_or_default();
+            }
+        },
+    },
+    SettingSpec {
+        key: "timezone",
+        description: "서버 기준 날짜를 나눌 시간대 (연속 보이스 기록)",
+        kind: SettingKind::Timezone,
+        get: |c| SettingValue::Choice(find_timezone(&c.timezone).map_or("UTC", |tz| tz.name())),
+        set: |c, v| {
+            if let SettingValue::Choice(tz) = v {
+                c.timezone = tz.to_string();
+            }
+        },
+    },
+    SettingSpec {
+        key: "command_channels",
+        description: "일반 커맨드(/calc 등)를 쓸 수 있는 채널 (none이면 제한 없음)",
+        kind: SettingKind::ChannelList,
+        get: |c| SettingValue::Channels(c.command_channels.clone()),
+        set: |c, v| {
+            if let SettingValue::Channels(ids) = v {
+                c.command_channels = ids;
+            }
+        },
+    },
+];
+
+// 설정 하나에 넣을 수 있는 최대 채널 수
+const MAX_COMMAND_CHANNELS: usize = 10;
+
+impl SettingSpec {
+    pub fn find(key: &str) -> Option<&'static SettingSpec> {
+        SETTINGS.iter().find(|s| s.key == key)
+    }
+
+    fn format(&self, value: &SettingValue) -> String {
+        match value {
+            SettingValue::Channel(Some(id)) => format!("<#{}>", id),
+            SettingValue::Channels(ids) if ids.is_empty() => "제한 없음".to_string(),
+            SettingValue::Channels(ids) => format_channels(ids),
+            SettingValue::Role(Some(id)) => format!("<@&{}>", id),
+            SettingValue::Channel(None) | SettingValue::Role(None) => "없음".to_string(),
+            SettingValue::Toggle(true) => "켜짐".to_string(),
+            SettingValue::Toggle(false) => "꺼짐".to_string(),
+            SettingValue::Minutes(minutes) => format!("{}분", minutes),
+            SettingValue::Choice(choice) => match &self.kind {
+                SettingKind::Choice(choices) => choices
+                    .iter()
+                    .find(|(v, _)| v == choice)
+                    .map(|(_, label)| label.to_string())
+                    .unwrap_or_else(|| choice.to_string()),
+                _ => choice.to_string(),
+            },
+        }
+    }
+
+    // 입력 문자열을 검증해 설정 값으로 변환. "none"은 채널/역할 설정을 비움
+    fn parse(&self, ctx: &Context, guild_id: GuildId, input: &str) -> Result<SettingValue, String> {
+        let input = input.trim();
+        let clear = input.eq_ignore_ascii_case("none");
+        match &self.kind {
+            SettingKind::Channel => {
+                if clear {
+                    return Ok(SettingValue::Channel(None));
+                }
+                Ok(SettingValue::Channel(Some(parse_text_channel(ctx, guild_id, input)?)))
+            }
+            SettingKind::VoiceChannel => {
+                if clear {
+                    return Ok(SettingValue::Channel(None));
+                }
+                Ok(SettingValue::Channel(Some(parse_voice_channel(ctx, guild_id, input)?)))
+            }
+            SettingKind::ChannelList => {
+                if clear {
+                    return Ok(SettingValue::Channels(Vec::new()));
+                }
+                let mut ids = Vec::new();
+                for part in input.split(|c: char| c.is_whitespace() || c == ',').filter(|p| !p.is_empty()) {
+                    let channel_id = parse_text_channel(ctx, guild_id, part)?;
+                    if !ids.contains(&channel_id) {
+                        ids.push(channel_id);
+                    }
+                }
+                if ids.is_empty() {
+                    return Err("채널을 #채널 형식으로 입력하세요.".to_string());
+                }
+                if ids.len() > MAX_COMMAND_CHANNELS {
+                    return Err(format!("채널은 최대 {}개까지 지정할 수 있습니다.", MAX_COMMAND_CHANNELS));
+                }
+                Ok(SettingValue::Channels(ids))
+            }
+            SettingKind::Role => {
+                if clear {
+                    return Ok(SettingValue::Role(None));
+                }
+                let id = parse_mention(input, "<@&")
+                    .ok_or_else(|| "역할을 @역할 형식으로 입력하세요.".to_string())?;
+                let role_id = RoleId::new(id);
+                let exists = ctx
+                    .cache
+                    .guild(guild_id)
+                    .is_some_and(|g| g.roles.contains_key(&role_id));
+                if !exists {
+                    return Err("이 서버의 역할이 아닙니다.".to_string());
+                }
+                Ok(SettingValue::Role(Some(role_id)))
+            }
+            SettingKind::Toggle => match input.to_ascii_lowercase().as_str() {
+                "on" | "true" | "yes" | "1" => Ok(SettingValue::Toggle(true)),
+                "off" | "false" | "no" | "0" => Ok(SettingValue::Toggle(false)),
+                _ => Err("on 또는 off를 입력하세요.".to_string()),
+            },
+            SettingKind::Minutes(min, max) => input
+                .trim_end_matches('분')
+                .trim()
+                .parse::<u32>()
+                .ok()
+                .filter(|m| (*min..=*max).contains(m))
+                .map(SettingValue::Minutes)
+                .ok_or_else(|| format!("{}에서 {} 사이의 분 단위 숫자를 입력하세요.", min, max)),
+            SettingKind::Timezone => find_timezone(input)
+                .map(|tz| SettingValue::Choice(tz.name()))
+                .ok_or_else(|| format!("IANA 시간대 이름을 입력하세요. 예: {}", TIMEZONE_EXAMPLES.join(", "))),
+            SettingKind::Choice(choices) => choices
+                .iter()
+                .find(|(v, _)| v.eq_ignore_ascii_case(input))
+                .map(|(v, _)| SettingValue::Choice(v))
+                .ok_or_else(|| {
+                    let list: Vec<&str> = choices.iter().map(|(v, _)| *v).collect();
+                    format!("다음 중 하나를 입력하세요: {}", list.join(", "))
+                }),
+        }
+    }
+}
+
+// 이 서버의 텍스트 채널 멘션(또는 ID)인지 확인
+fn parse_text_channel(ctx: &Context, guild_id: GuildId, input: &str) -> Result<ChannelId, String> {
+    let id = parse_mention(input, "<#").ok_or_else(|| "채널을 #채널 형식으로 입력하세요.".to_string())?;
+    let channel_id = ChannelId::new(id);
+    let is_text = ctx.cache.guild(guild_id).is_some_and(|g| {
+        g.channels
+            .get(&channel_id)
+            .is_some_and(|c| c.kind == ChannelType::Text)
+    });
+    if !is_text {
+        return Err("이 서버의 텍스트 채널이 아닙니다.".to_string());
+    }
+    Ok(channel_id)
+}
+
+// 이 서버의 보이스/스테이지 채널 멘션(또는 ID)인지 확인
+fn parse_voice_channel(ctx: &Context, guild_id: GuildId, input: &str) -> Result<ChannelId, String> {
+    let id = parse_mention(input, "<#").ok_or_else(|| "채널을 #채널 형식으로 입력하세요.".to_string())?;
+    let channel_id = ChannelId::new(id);
+    let is_voice = ctx.cache.guild(guild_id).is_some_and(|g| {
+        g.channels
+            .get(&channel_id)
+            .is_some_and(|c| matches!(c.kind, ChannelType::Voice | ChannelType::Stage))
+    });
+    if !is_voice {
+        return Err("이 서버의 보이스 채널이 아닙니다.".to_string());
+    }
+    Ok(channel_id)
+}
+
+fn format_channels(ids: &[ChannelId]) -> String {
+    ids.iter().map(|id| format!("<#{}>", id)).collect::<Vec<_>>().join(" ")
+}
+
+// 일반 커맨드를 이 채널(스레드면 상위 채널 포함)에서 쓸 수 있는지. 허용되지 않으면 안내 문구
+pub fn check_command_channel(
+    config: &GuildConfig,
+    channel_id: ChannelId,
+    parent_id: Option<ChannelId>,
+) -> Result<(), String> {
+    let allowed = &config.command_channels;
+    if allowed.is_empty()
+        || allowed.contains(&channel_id)
+        || parent_id.is_some_and(|p| allowed.contains(&p))
+    {
+        return Ok(());
+    }
+    Err(format!("이 서버에서는 다음 채널에서만 사용할 수 있습니다: {}", format_channels(allowed)))
+}
+
+// "<#123>", "<@&123>" 같은 멘션 또는 숫자 ID에서 ID 추출
+fn parse_mention(input: &str, prefix: &str) -> Option<u64> {
+    let raw = input
+        .strip_prefix(prefix)
+        .and_then(|rest| rest.strip_suffix('>'))
+        .unwrap_or(input);
+    raw.parse::<u64>().ok().filter(|&id| id != 0)
+}
+
+// 길드 설정 조회 (없거나 조회에 실패하면 기본값)
+pub async fn get_guild_config(ctx: &Context, guild_id: GuildId) -> GuildConfig {
+    let Some(pool) = storage::pool(ctx).await else {
+        return GuildConfig::default();
+    };
+    let loaded = match storage::settings_cache(ctx).await {
+        Some(cache) => cache.get_or_load(&pool, guild_id).await.map(|config| (*config).clone()),
+        None => event_metrics::phase(Phase::Storage, storage::get_guild_settings(&pool, guild_id))
+            .await
+            .map(Option::unwrap_or_default),
+    };
+    match loaded {
+        Ok(config) => config,
+        Err(e) => {
+            report_error(ctx, "길드 설정 조회", &e).await;
+            GuildConfig::default()
+        }
+    }
+}
+
+// 새 활성화 번호 (길드별로 1부터 증가). 카운터는 설정과 따로 저장하므로 /config 저장과 겹쳐도 안전
+pub async fn next_session_id(ctx: &Context, guild_id: GuildId) -> Result<u64, sqlx::Error> {
+    let Some(pool) = storage::pool(ctx).await else {
+        return Err(sqlx::Error::PoolClosed);
+    };
+    storage::next_session_id(&pool, guild_id).await
+}
+
+// 설정된 감사 로그 채널에 관리 명령 실행 기록 전송 (설정하지 않았으면 무시)
+pub async fn post_audit_log(ctx: &Context, guild_id: GuildId, content: String) {
+    if let Some(channel_id) = get_usable_config(ctx, guild_id).await.audit_channel {
+        notify_or_report(ctx, channel_id, content, "감사 로그 전송").await;
+    }
+}
+
+// 길드 설정 변경 후 저장. 바뀐 항목은 변경 기록에 남기고 채널에 알림. 저장에 실패하면 false
+pub async fn update_guild_config(
+    ctx: &Context,
+    guild_id: GuildId,
+    user_id: UserId,
+    f: impl FnOnce(&mut GuildConfig),
+) -> bool {
+    let Some(pool) = storage::pool(ctx).await else {
+        return false;
+    };
+    let old = get_guild_config(ctx, guild_id).await;
+    let mut config = old.clone();
+    f(&mut config);
+    // 같은 값으로 다시 설정한 경우 저장과 변경 알림을 건너뜀
+    if config == old {
+        return true;
+    }
+    if let Err(e) = storage::upsert_guild_settings(&pool, guild_id, &config).await {
+        report_error(ctx, "길드 설정 저장", &e).await;
+        return false;
+    }
+    if let Some(cache) = storage::settings_cache(ctx).await {
+        cache.set(guild_id, Arc::new(config.clone())).await;
+    }
+    // 바뀐 채널/역할을 바로 검사
+    check_guild(ctx, guild_id).await;
+
+    let changed_at = unix_now();
+    let changes: Vec<ConfigChange> = config_diff(&old, &config)
+        .into_iter()
+        .map(|(key, old_value, new_value)| ConfigChange {
+            user_id,
+            changed_at,
+            key,
+            old_value,
+            new_value,
+        })
+        .collect();
+    if changes.is_empty() {
+        return true;
+    }
+    if let Err(e) = storage::insert_config_changes(&pool, guild_id, &changes).await {
+        report_error(ctx, "설정 변경 기록 저장", &e).await;
+    }
+    post_config_changes(ctx, guild_id, &changes).await;
+    true
+}
+
+// 두 설정 사이에 바뀐 항목: (키, 이전 값, 새 값). 필드를 추가해도 따로 등록할 필요 없음
+fn config_diff(old: &GuildConfig, new: &GuildConfig) -> Vec<(String, String, String)> {
+    let (Ok(Value::Object(old_fields)), Ok(Value::Object(new_fields))) =
+        (serde_json::to_value(old), serde_json::to_value(new))
+    else {
+        return Vec::new();
+    };
+    new_fields
+        .iter()
+        .filter(|(key, value)| old_fields.get(*key) != Some(*value))
+        .map(|(key, value)| {
+            let before = format_field(key, old, old_fields.get(key));
+            let after = format_field(key, new, Some(value));
+            (key.clone(), before, after)
+        })
+        .collect()
+}
+
+// 멘션 대상 목록 표시: "@대상 (적용 범위)"
+fn format_mention_targets(targets: &[ActivationMention], separator: &str) -> String {
+    if targets.is_empty() {
+        return "없음".to_string();
+    }
+    targets
+        .iter()
+        .map(|m| format!("{} ({})", m.target.mention(), mention_scope(m.channel)))
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+fn mention_scope(channel: Option<ChannelId>) -> String {
+    channel.map_or_else(|| "모든 채널".to_string(), |c| format!("<#{}>", c))
+}
+
+// 변경 기록에 남길 값 표시. /config 설정은 /config show와 같은 형식, 나머지는 필드별 형식이나 JSON
+fn format_field(key: &str, config: &GuildConfig, raw: Option<&Value>) -> String {
+    if let Some(spec) = SettingSpec::find(key) {
+        return spec.format(&(spec.get)(config));
+    }
+    match key {
+        "command_permissions" if config.command_permissions.is_empty() => "없음".to_string(),
+        "command_permissions" => config
+            .command_permissions
+            .iter()
+            .map(|p| format!("/{}→<@&{}>", p.command_name, p.required_role))
+            .collect::<Vec<_>>()
+            .join(" "),
+        "disabled_commands" if config.disabled_commands.is_empty() => "없음".to_string(),
+        "disabled_commands" => config
+            .disabled_commands
+            .iter()
+            .map(|c| format!("/{}", c))
+            .collect::<Vec<_>>()
+            .join(" "),
+        "mention_targets" => format_mention_targets(&config.mention_targets, " "),
+        "status_rename_channels" if config.status_rename_channels.is_empty() => "없음".to_string(),
+        "status_rename_channels" => format_channels(&config.status_rename_channels),
+        "join_template" | "leave_template" | "activate_template" | "deactivate_template" => raw
+            .and_then(Value::as_str)
+            .map_or_else(|| "기본 문구".to_string(), |t| format!("`{}`", t)),
+        "streak_min_minutes" => format!("{}분", config.streak_min_minutes),
+        _ => raw.map_or_else(|| "없음".to_string(), Value::to_string),
+    }
+}
+
+fn format_change(change: &ConfigChange) -> String {
+    format!(
+        "**{}**: {} → {}",
+        change.key,
+        truncate(&change.old_value, CHANGE_VALUE_LIMIT),
+        truncate(&change.new_value, CHANGE_VALUE_LIMIT)
+    )
+}
+
+// 설정 변경 한 줄 요약을 감사 로그 채널(없으면 알림 채널)에 전송
+async fn post_config_changes(ctx: &Context, guild_id: GuildId, changes: &[ConfigChange]) {
+    let Some(first) = changes.first() else {
+        return;
+    };
+    let config = get_usable_config(ctx, guild_id).await;
+    let Some(channel_id) = config.audit_channel.or(config.notification_channel) else {
+        return;
+    };
+    let lines = changes.iter().map(format_change).collect::<Vec<_>>().join("\n");
+    let content = truncate(&format!("📝 <@{}> 님이 설정을 바꿨습니다\n{}", first.user_id, lines), MESSAGE_LIMIT);
+    notify_or_report(ctx, channel_id, content, "설정 변경 알림").await;
+}
+
+// 예약 작업: 설정 캐시를 비워 DB에서 다시 읽게 함 (DB를 직접 고친 경우 등 대비)
+pub async fn refresh_settings_cache(ctx: Context) {
+    if let Some(cache) = storage::settings_cache(&ctx).await {
+        cache.clear().await;
+    }
+}
+
+// 예약 작업: 보관 기간이 지났거나 길드별 최대 개수를 넘은 설정 변경 기록 삭제
+pub async fn prune_config_history(ctx: Context) {
+    let Some(pool) = storage::pool(&ctx).await else {
+        return;
+    };
+    let before = unix_now() - CONFIG_HISTORY_RETENTION_DAYS * 86400;
+    if let Err(e) = storage::prune_config_changes(&pool, before, MAX_CONFIG_HISTORY_PER_GUILD).await {
+        report_error(&ctx, "설정 변경 기록 정리", &e).await;
+    }
+}
+
+pub async fn handle_setchannel(ctx: &Context, cmd: &CommandInteraction) {
+    let Some(guild_id) = cmd.guild_id else {
+        return;
+    };
+    let channel_id = cmd
+        .data
+        .options
+        .iter()
+        .find(|o| o.name == "channel")
+        .and_then(|o| o.value.as_channel_id());
+
+    let content = match channel_id {
+        Some(channel_id) => {
+            if update_guild_config(ctx, guild_id, cmd.user.id, |c| c.notification_channel = Some(channel_id)).await {
+                format!("알림 채널을 <#{}> 로 설정했습니다.", channel_id)
+            } else {
+                SAVE_FAILED.to_string()
+            }
+        }
+        None => "채널을 지정하세요.".to_string(),
+    };
+
+    respond(
+        ctx,
+        cmd,
+        CreateInteractionResponseMessage::new()
+            .content(content)
+            .ephemeral(true),
+    )
+    .await;
+}
+
+pub async fn handle_setrole(ctx: &Context, cmd: &CommandInteraction) {
+    let Some(guild_id) = cmd.guild_id else {
+        return;
+    };
+    let role_id = cmd
+        .data
+        .options
+        .iter()
+        .find(|o| o.name == "role")
+        .and_then(|o| match o.value {
+            CommandDataOptionValue::Role(id) => Some(id),
+            _ => None,
+        });
+
+    let content = if !update_guild_config(ctx, guild_id, cmd.user.id, |c| c.mention_role = role_id).await {
+        SAVE_FAILED.to_string()
+    } else {
+        match role_id {
+            Some(role_id) => format!("활성화 알림에서 <@&{}> 역할을 멘션합니다.", role_id),
+            None => "활성화 알림에서 역할을 멘션하지 않습니다.".to_string(),
+        }
+    };
+
+    respond(
+        ctx,
+        cmd,
+        CreateInteractionResponseMessage::new()
+            .content(content)
+            .ephemeral(true),
+    )
+    .await;
+}
+
+// /voiceconfig <subcommand>
+pub async fn handle_voiceconfig(ctx: &Context, cmd: &CommandInteraction) {
+    let Some(guild_id) = cmd.guild_id else {
+        return;
+    };
+    let Some(sub) = cmd.data.options.first() else {
+        return;
+    };
+    let CommandDataOptionValue::SubCommand(args) = &sub.value else {
+        return;
+    };
+
+    if sub.name == "reset" {
+        respond(ctx, cmd, reset_confirmation(cmd).ephemeral(true)).await;
+        return;
+    }
+    if let CommandDataOptionValue::SubCommandGroup(group) = &sub.value {
+        if sub.name == "message" {
+            let content = set_message_template(ctx, cmd, guild_id, group).await;
+            respond(
+                ctx,
+                cmd,
+                CreateInteractionResponseMessage::new()
+                    .content(content)
+                    .allowed_mentions(CreateAllowedMentions::new())
+                    .ephemeral(true),
+            )
+            .await;
+        }
+        return;
+    }
+
+    let content = match sub.name.as_str() {
+        "privacy" => {
+            let level = args
+                .iter()
+                .find(|o| o.name == "level")
+                .and_then(|o| o.value.as_str())
+                .and_then(PrivacyLevel::from_choice);
+            match level {
+                Some(level) => {
+                    if update_guild_config(ctx, guild_id, cmd.user.id, |c| c.voice_stats_privacy = level).await {
+                        format!("보이스 통계 공개 범위를 **{}** 으로 설정했습니다.", level.label())
+                    } else {
+                        SAVE_FAILED.to_string()
+                    }
+                }
+                None => "공개 범위를 선택하세요.".to_string(),
+            }
+        }
+        "notifybots" => {
+            let on = args
+                .iter()
+                .find(|o| o.name == "state")
+                .and_then(|o| o.value.as_str())
+                .is_some_and(|v| v == "on");
+            if !update_guild_config(ctx, guild_id, cmd.user.id, |c| c.notify_bots = on).await {
+                SAVE_FAILED.to_string()
+            } else if on {
+                "다른 봇의 보이스 입장/퇴장도 알립니다.".to_string()
+            } else {
+                "다른 봇의 보이스 입장/퇴장은 알리지 않습니다. (인원 집계에는 반영)".to_string()
+            }
+        }
+        "useembeds" => {
+            let on = args
+                .iter()
+                .find(|o| o.name == "state")
+                .and_then(|o| o.value.as_str())
+                .is_some_and(|v| v == "on");
+            if !update_guild_config(ctx, guild_id, cmd.user.id, |c| c.use_embeds = on).await {
+                SAVE_FAILED.to_string()
+            } else if on {
+                "알림을 임베드로 보냅니다.".to_string()
+            } else {
+                "알림을 일반 텍스트로 보냅니다.".to_string()
+            }
+        }
+        "streak" => {
+            let minutes = args
+                .iter()
+                .find(|o| o.name == "minutes")
+                .and_then(|o| o.value.as_i64())
+                .and_then(|m| u32::try_from(m).ok())
+                .filter(|m| (1..=MAX_STREAK_MINUTES).contains(m));
+            match minutes {
+                Some(minutes) => {
+                    if update_guild_config(ctx, guild_id, cmd.user.id, |c| c.streak_min_minutes = minutes).await {
+                        format!("하루 {}분 이상 보이스 채널에 있으면 연속 기록으로 인정합니다.", minutes)
+                    } else {
+                        SAVE_FAILED.to_string()
+                    }
+                }
+                None => format!("1에서 {} 사이의 분을 입력하세요.", MAX_STREAK_MINUTES),
+            }
+        }
+        "statusname" => {
+            let channel_id = args
+                .iter()
+                .find(|o| o.name == "channel")
+                .and_then(|o| o.value.as_channel_id());
+            let on = args
+                .iter()
+                .find(|o| o.name == "state")
+                .and_then(|o| o.value.as_str())
+                .is_some_and(|v| v == "on");
+            match channel_id {
+                Some(channel_id) => set_status_rename(ctx, guild_id, cmd.user.id, channel_id, on).await,
+                None => "보이스 채널을 선택하세요.".to_string(),
+            }
+        }
+        _ => return,
+    };
+
+    respond(
+        ctx,
+        cmd,
+        CreateInteractionResponseMessage::new()
+            .content(content)
+            .ephemeral(true),
+    )
+    .await;
+}
+
+// /voiceconfig message <join|leave|activate|deactivate> [template]: 알림 문구 변경. 템플릿을 비우면 기본 문구로
+async fn set_message_template(
+    ctx: &Context,
+    cmd: &CommandInteraction,
+    guild_id: GuildId,
+    group: &[CommandDataOption],
+) -> String {
+    let Some(sub) = group.first() else {
+        return "알림 종류를 선택하세요.".to_string();
+    };
+    let (Some(kind), CommandDataOptionValue::SubCommand(args)) = (TemplateKind::from_name(&sub.name), &sub.value) else {
+        return "알림 종류를 선택하세요.".to_string();
+    };
+    let template = args
+        .iter()
+        .find(|o| o.name == "template")
+        .and_then(|o| o.value.as_str())
+        .map(str::trim)
+        .filter(|t| !t.is_empty());
+
+    if let Some(template) = template {
+        if template.chars().count() > MAX_TEMPLATE_LEN {
+            return format!("문구는 최대 {}자까지 쓸 수 있습니다.", MAX_TEMPLATE_LEN);
+        }
+        let unknown = notification::unknown_tokens(template, kind);
+        if !unknown.is_empty() {
+            let allowed: Vec<String> = kind.tokens().iter().map(|t| format!("`{{{}}}`", t)).collect();
+            let unknown: Vec<String> = unknown.iter().map(|t| format!("`{{{}}}`", t)).collect();
+            return format!(
+                "{}에서 쓸 수 없는 토큰이 있습니다: {}\n쓸 수 있는 토큰: {}",
+                kind.label(),
+                unknown.join(", "),
+                allowed.join(", ")
+            );
+        }
+    }
+
+    let value = template.map(str::to_string);
+    let saved = update_guild_config(ctx, guild_id, cmd.user.id, |c| *c.template_slot_mut(kind) = value.clone()).await;
+    if !saved {
+        return SAVE_FAILED.to_string();
+    }
+    match template {
+        Some(template) => {
+            let vars = TEMPLATE_PREVIEW_VARS.iter().copied().collect();
+            format!(
+                "{} 문구를 바꿨습니다.\n미리보기: {}",
+                kind.label(),
+                notification::apply_template(template, &vars)
+            )
+        }
+        None => format!("{} 문구를 기본값으로 되돌렸습니다.", kind.label()),
+    }
+}
+
+// /voiceconfig statusname <channel> <on|off>: 활성화된 동안 채널 이름 앞에 표시를 붙일지
+async fn set_status_rename(
+    ctx: &Context,
+    guild_id: GuildId,
+    user_id: UserId,
+    channel_id: ChannelId,
+    on: bool,
+) -> String {
+    let config = get_guild_config(ctx, guild_id).await;
+    let listed = config.status_rename_channels.contains(&channel_id);
+    if on && !listed && config.status_rename_channels.len() >= MAX_STATUS_RENAME_CHANNELS {
+        return format!(
+            "활성화 표시는 최대 {}개 채널까지 켤 수 있습니다.",
+            MAX_STATUS_RENAME_CHANNELS
+        );
+    }
+    let saved = update_guild_config(ctx, guild_id, user_id, |c| {
+        if on && !listed {
+            c.status_rename_channels.push(channel_id);
+        } else if !on {
+            c.status_rename_channels.retain(|&c| c != channel_id);
+        }
+    })
+    .await;
+    if !saved {
+        return SAVE_FAILED.to_string();
+    }
+    // 지금 활성화된 채널이면 바로 이름을 바꾸거나 되돌림
+    channel_status::refresh(ctx, guild_id, channel_id).await;
+
+    if !on {
+        return format!("<#{}> 채널 이름에 활성화 표시를 붙이지 않습니다.", channel_id);
+    }
+    let can_rename = ctx.cache.guild(guild_id).is_some_and(|g| {
+        let bot = g.members.get(&ctx.cache.current_user().id);
+        let channel = g.channels.get(&channel_id);
+        bot.zip(channel)
+            .is_some_and(|(member, channel)| g.user_permissions_in(channel, member).manage_channels())
+    });
+    let mut content = format!(
+        "<#{}> 채널이 활성화된 동안 이름 앞에 `{}` 를 붙입니다. \
+         디스코드 제한으로 이름 변경은 10분에 2번까지만 반영됩니다.",
+        channel_id,
+        channel_status::ACTIVE_PREFIX.trim_end()
+    );
+    if !can_rename {
+        content.push_str("\n⚠️ 봇에게 이 채널의 채널 관리 권한이 없어 이름을 바꿀 수 없습니다.");
+    }
+    content
+}
+
+fn is_administrator(member: Option<&Member>) -> bool {
+    member.and_then(|m| m.permissions).is_some_and(|p| p.administrator())
+}
+
+// /voiceconfig reset: 모든 설정을 기본값으로 되돌리기 전에 확인 버튼 표시 (관리자 전용)
+fn reset_confirmation(cmd: &CommandInteraction) -> CreateInteractionResponseMessage {
+    if !is_administrator(cmd.member.as_deref()) {
+        return CreateInteractionResponseMessage::new().content("설정 초기화는 관리자 권한이 필요합니다.");
+    }
+    let buttons = CreateActionRow::Buttons(vec![
+        CreateButton::new(format!("{}reset", VOICECONFIG_PREFIX))
+            .label("초기화")
+            .style(ButtonStyle::Danger),
+        CreateButton::new(format!("{}cancel", VOICECONFIG_PREFIX))
+            .label("취소")
+            .style(ButtonStyle::Secondary),
+    ]);
+    CreateInteractionResponseMessage::new()
+        .content(
+            "⚠️ 이 서버의 모든 설정(알림 채널, 역할, 커맨드 권한, 꺼진 커맨드 등)을 기본값으로 되돌릴까요? \
+             되돌린 뒤에는 `/config history` 에서 이전 값을 확인할 수 있습니다.",
+        )
+        .components(vec![buttons])
+}
+
+// voiceconfig: 로 시작하는 버튼 처리. 초기화는 설정 전체를 GuildConfig::default()로 바꾸고 바뀐 항목을 보여줌
+pub async fn handle_voiceconfig_component(ctx: &Context, comp: &ComponentInteraction) {
+    let Some(guild_id) = comp.guild_id else {
+        return;
+    };
+    let Some(action) = comp.data.custom_id.strip_prefix(VOICECONFIG_PREFIX) else {
+        return;
+    };
+    let update = |message: CreateInteractionResponseMessage| {
+        CreateInteractionResponse::UpdateMessage(message.components(Vec::new()))
+    };
+    let response = match action {
+        "cancel" => update(CreateInteractionResponseMessage::new().content("설정 초기화를 취소했습니다.")),
+        "reset" if !is_administrator(comp.member.as_ref()) => CreateInteractionResponse::Message(
+            CreateInteractionResponseMessage::new()
+                .content("설정 초기화는 관리자 권한이 필요합니다.")
+                .ephemeral(true),
+        ),
+        "reset" => update(reset_guild_config(ctx, guild_id, comp.user.id).await),
+        _ => return,
+    };
+    if let Err(e) = event_metrics::phase(Phase::Discord, comp.create_response(&ctx.http, response)).await {
+        report_error(ctx, "설정 초기화 응답", &e).await;
+    }
+}
+
+// 설정 전체를 기본값으로 저장. 초기화 전에 감사 로그 채널이 있었으면 그 채널에 기록
+async fn reset_guild_config(ctx: &Context, guild_id: GuildId, user_id: UserId) -> CreateInteractionResponseMessage {
+    let old = get_guild_config(ctx, guild_id).await;
+    let audit_channel = get_usable_config(ctx, guild_id).await.audit_channel;
+    let defaults = GuildConfig::default();
+    let changes = config_diff(&old, &defaults);
+    if changes.is_empty() {
+        return CreateInteractionResponseMessage::new().content("이미 모든 설정이 기본값입니다.");
+    }
+    let commands_changed = old.disabled_commands != defaults.disabled_commands;
+    if !update_guild_config(ctx, guild_id, user_id, |c| *c = defaults).await {
+        return CreateInteractionResponseMessage::new().content(SAVE_FAILED);
+    }
+    if commands_changed {
+        let sync_ctx = ctx.clone();
+        tokio::spawn(async move { resync_guild_commands(&sync_ctx, guild_id).await });
+    }
+    // 활성화 표시를 붙여 둔 채널은 원래 이름으로 되돌림
+    for &channel_id in &old.status_rename_channels {
+        channel_status::refresh(ctx, guild_id, channel_id).await;
+    }
+
+    let lines = changes
+        .iter()
+        .map(|(key, before, after)| {
+            format!(
+                "**{}**: {} → {}",
+                key,
+                truncate(before, CHANGE_VALUE_LIMIT),
+                truncate(after, CHANGE_VALUE_LIMIT)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    if let Some(channel_id) = audit_channel {
+        let content = truncate(
+            &format!("🧹 <@{}> 님이 서버 설정을 모두 기본값으로 초기화했습니다\n{}", user_id, lines),
+            MESSAGE_LIMIT,
+        );
+        notify_or_report(ctx, channel_id, content, "감사 로그 전송").await;
+    }
+    let embed = CreateEmbed::new()
+        .title("🧹 설정을 초기화했습니다")
+        .description(truncate(&lines, EMBED_DESCRIPTION_LIMIT))
+        .footer(CreateEmbedFooter::new(format!("기본값으로 되돌린 항목 {}개", changes.len())));
+    CreateInteractionResponseMessage::new().content("").embed(embed)
+}
+
+// /config show | set <key> <value> | reset <key>
+pub async fn handle_config(ctx: &Context, cmd: &CommandInteraction) {
+    let Some(guild_id) = cmd.guild_id else {
+        return;
+    };
+    let Some(sub) = cmd.data.options.first() else {
+        return;
+    };
+    if let CommandDataOptionValue::SubCommandGroup(group) = &sub.value {
+        if sub.name == "mention" {
+            let content = edit_mention_targets(ctx, cmd, guild_id, group).await;
+            respond(
+                ctx,
+                cmd,
+                CreateInteractionResponseMessage::new()
+                    .content(content)
+                    .allowed_mentions(CreateAllowedMentions::new())
+                    .ephemeral(true),
+            )
+            .await;
+        }
+        return;
+    }
+    let CommandDataOptionValue::SubCommand(args) = &sub.value else {
+        return;
+    };
+    let arg = |name: &str| {
+        args.iter()
+            .find(|o| o.name == name)
+            .and_then(|o| o.value.as_str())
+            .map(str::to_string)
+    };
+
+    if sub.name == "show" {
+        let config = get_guild_config(ctx, guild_id).await;
+        let defaults = GuildConfig::default();
+        let mut embed = CreateEmbed::new().title("⚙️ 서버 설정");
+        if let Some(scope) = dry_run_scope(ctx, Some(guild_id)).await {
+            embed = embed.description(format!(
+                "🧪 **드라이런 중 ({})**: 알림을 보내지 않고 봇 로그에만 기록합니다.",
+                scope.label()
+            ));
+        }
+        for spec in SETTINGS {
+            embed = embed.field(
+                spec.key,
+                format!(
+                    "{}\n현재: {} · 기본: {}",
+                    spec.description,
+                    spec.format(&(spec.get)(&config)),
+                    spec.format(&(spec.get)(&defaults)),
+                ),
+                false,
+            );
+        }
+        if !config.disabled_commands.is_empty() {
+            let disabled = config
+                .disabled_commands
+                .iter()
+                .map(|c| format!("/{}", c))
+                .collect::<Vec<_>>()
+                .join(" ");
+            embed = embed.field("꺼진 커맨드", format!("{}\n/config enable 로 다시 켤 수 있습니다", disabled), false);
+        }
+        let templates: Vec<String> = [
+            TemplateKind::Join,
+            TemplateKind::Leave,
+            TemplateKind::Activate,
+            TemplateKind::Deactivate,
+        ]
+        .into_iter()
+        .filter_map(|kind| config.template(kind).map(|t| format!("{}: `{}`", kind.label(), t)))
+        .collect();
+        if !templates.is_empty() {
+            embed = embed.field(
+                "알림 문구",
+                truncate(
+                    &format!("{}\n/voiceconfig message 로 바꿀 수 있습니다", templates.join("\n")),
+                    EMBED_FIELD_LIMIT,
+                ),
+                false,
+            );
+        }
+        if !config.status_rename_channels.is_empty() {
+            embed = embed.field(
+                "활성화 표시 채널",
+                format!(
+                    "{}\n/voiceconfig statusname 으로 바꿀 수 있습니다",
+                    format_channels(&config.status_rename_channels)
+                ),
+                false,
+            );
+        }
+        if !config.mention_targets.is_empty() {
+            embed = embed.field(
+                "활성화 알림 멘션",
+                truncate(&format_mention_targets(&config.mention_targets, "\n"), EMBED_FIELD_LIMIT),
+                false,
+            );
+        }
+        respond(
+            ctx,
+            cmd,
+            CreateInteractionResponseMessage::new()
+                .embed(embed)
+                .ephemeral(true),
+        )
+        .await;
+        return;
+    }
+
+    if sub.name == "history" {
+        let key = arg("key");
+        let embed = match storage::pool(ctx).await {
+            Some(pool) => match storage::config_changes(&pool, guild_id, key.as_deref(), CONFIG_HISTORY_LIMIT).await {
+                Ok(changes) => history_embed(key.as_deref(), &changes),
+                Err(e) => {
+                    report_error(ctx, "설정 변경 기록 조회", &e).await;
+                    CreateEmbed::new().description("변경 기록을 불러오지 못했습니다. 잠시 후 다시 시도해주세요.")
+                }
+            },
+            None => return,
+        };
+        respond(
+            ctx,
+            cmd,
+            CreateInteractionResponseMessage::new()
+                .embed(embed)
+                .ephemeral(true),
+        )
+        .await;
+        return;
+    }
+
+    if sub.name == "export" || sub.name == "import" {
+        let administrator = cmd
+            .member
+            .as_ref()
+            .and_then(|m| m.permissions)
+            .is_some_and(|p| p.administrator());
+        let message = if !administrator {
+            CreateInteractionResponseMessage::new().content("설정 내보내기/가져오기는 관리자 권한이 필요합니다.")
+        } else if sub.name == "export" {
+            export_config(ctx, guild_id).await
+        } else {
+            CreateInteractionResponseMessage::new().content(import_config(ctx, cmd, guild_id, args).await)
+        };
+        respond(ctx, cmd, message.ephemeral(true)).await;
+        return;
+    }
+
+    if sub.name == "enable" || sub.name == "disable" {
+        let name = arg("command").unwrap_or_default();
+        let content = set_command_enabled(ctx, cmd, guild_id, &name, sub.name == "enable").await;
+        respond(
+            ctx,
+            cmd,
+            CreateInteractionResponseMessage::new()
+                .content(content)
+                .ephemeral(true),
+        )
+        .await;
+        return;
+    }
+
+    let Some(spec) = arg("key").as_deref().and_then(SettingSpec::find) else {
+        respond(
+            ctx,
+            cmd,
+            CreateInteractionResponseMessage::new()
+                .content("알 수 없는 설정입니다.")
+                .ephemeral(true),
+        )
+        .await;
+        return;
+    };
+
+    let content = match sub.name.as_str() {
+        "set" => match spec.parse(ctx, guild_id, &arg("value").unwrap_or_default()) {
+            Ok(value) => {
+                let shown = spec.format(&value);
+                if update_guild_config(ctx, guild_id, cmd.user.id, |c| (spec.set)(c, value)).await {
+                    format!("**{}** 을(를) {} 로 설정했습니다.", spec.key, shown)
+                } else {
+                    SAVE_FAILED.to_string()
+                }
+            }
+            Err(message) => message,
+        },
+        "reset" => {
+            let value = (spec.get)(&GuildConfig::default());
+            let shown = spec.format(&value);
+            if update_guild_config(ctx, guild_id, cmd.user.id, |c| (spec.set)(c, value)).await {
+                format!(
+                    "**{}** 을(를) 기본값({})으로 되돌렸습니다.",
+                    spec.key,
+                    shown
+                )
+            } else {
+                SAVE_FAILED.to_string()
+            }
+        }
+        _ => return,
+    };
+
+    respond(
+        ctx,
+        cmd,
+        CreateInteractionResponseMessage::new()
+            .content(content)
+            .ephemeral(true),
+    )
+    .await;
+}
+
+// /config mention add|remove <대상> [채널]: 활성화 알림 멘션 대상 추가/삭제
+async fn edit_mention_targets(
+    ctx: &Context,
+    cmd: &CommandInteraction,
+    guild_id: GuildId,
+    group: &[CommandDataOption],
+) -> String {
+    let Some(action) = group.first() else {
+        return String::new();
+    };
+    let CommandDataOptionValue::SubCommand(args) = &action.value else {
+        return String::new();
+    };
+    let Some(target) = args
+        .iter()
+        .find(|o| o.name == "target")
+        .and_then(|o| o.value.as_mentionable())
+        .and_then(|id| resolve_mention_target(cmd, id))
+    else {
+        return "멘션할 역할이나 사용자를 선택하세요.".to_string();
+    };
+    let channel = args
+        .iter()
+        .find(|o| o.name == "channel")
+        .and_then(|o| o.value.as_channel_id());
+    let entry = ActivationMention { target, channel };
+    let config = get_guild_config(ctx, guild_id).await;
+    let registered = config.mention_targets.contains(&entry);
+
+    match action.name.as_str() {
+        "add" if registered => "이미 추가된 멘션 대상입니다.".to_string(),
+        "add" if config.mention_targets.len() >= MAX_MENTION_TARGETS => {
+            format!("멘션 대상은 최대 {}개까지 추가할 수 있습니다.", MAX_MENTION_TARGETS)
+        }
+        "add" => {
+            if update_guild_config(ctx, guild_id, cmd.user.id, |c| c.mention_targets.push(entry)).await {
+                format!(
+                    "{} 을(를) 활성화 알림 멘션에 추가했습니다 ({}).",
+                    target.mention(),
+                    mention_scope(channel)
+                )
+            } else {
+                SAVE_FAILED.to_string()
+            }
+        }
+        "remove" if !registered => "등록되지 않은 멘션 대상입니다. `/config show` 에서 목록을 확인하세요.".to_string(),
+        "remove" => {
+            if update_guild_config(ctx, guild_id, cmd.user.id, |c| c.mention_targets.retain(|m| *m != entry)).await {
+                format!(
+                    "{} 을(를) 활성화 알림 멘션에서 뺐습니다 ({}).",
+                    target.mention(),
+                    mention_scope(channel)
+                )
+            } else {
+                SAVE_FAILED.to_string()
+            }
+        }
+        _ => String::new(),
+    }
+}
+
+// 멘션 가능 옵션의 ID가 역할인지 사용자인지는 함께 온 resolved 데이터로 구분
+fn resolve_mention_target(cmd: &CommandInteraction, id: GenericId) -> Option<MentionTarget> {
+    let resolved = &cmd.data.resolved;
+    if resolved.roles.contains_key(&RoleId::new(id.get())) {
+        Some(MentionTarget::Role(RoleId::new(id.get())))
+    } else if resolved.users.contains_key(&UserId::new(id.get())) {
+        Some(MentionTarget::User(UserId::new(id.get())))
+    } else {
+        None
+    }
+}
+
+// /config export: 채널/역할 ID를 뺀 설정을 JSON 파일로
+async fn export_config(ctx: &Context, guild_id: GuildId) -> CreateInteractionResponseMessage {
+    let config = get_guild_config(ctx, guild_id).await;
+    let Ok(Value::Object(mut fields)) = serde_json::to_value(&config) else {
+        return CreateInteractionResponseMessage::new().content("설정을 내보내지 못했습니다.");
+    };
+    fields.retain(|key, _| !SERVER_SPECIFIC_FIELDS.contains(&key.as_str()));
+    let json = serde_json::to_vec_pretty(&fields).unwrap_or_default();
+    CreateInteractionResponseMessage::new()
+        .content("📦 설정을 내보냈습니다. 새 서버에서 `/config import` 로 가져올 수 있습니다. 채널과 역할은 포함되지 않습니다.")
+        .add_file(CreateAttachment::bytes(json, format!("aurobot-config-{}.json", guild_id)))
+}
+
+// /config import <file>: 내보낸 JSON을 검증해 적용하고 바뀐 항목을 돌려줌. 채널/역할은 비워 둠
+async fn import_config(
+    ctx: &Context,
+    cmd: &CommandInteraction,
+    guild_id: GuildId,
+    args: &[CommandDataOption],
+) -> String {
+    let Some(attachment) = args
+        .iter()
+        .find(|o| o.name == "file")
+        .and_then(|o| o.value.as_attachment_id())
+        .and_then(|id| cmd.data.resolved.attachments.get(&id))
+    else {
+        return "설정 파일을 첨부해주세요.".to_string();
+    };
+    if attachment.size > MAX_IMPORT_BYTES {
+        return format!("설정 파일이 너무 큽니다 (최대 {}KB).", MAX_IMPORT_BYTES / 1024);
+    }
+    let bytes = match attachment.download().await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            report_error(ctx, "설정 파일 다운로드", &e).await;
+            return "첨부 파일을 받지 못했습니다. 잠시 후 다시 시도해주세요.".to_string();
+        }
+    };
+    let Ok(Value::Object(imported)) = serde_json::from_slice::<Value>(&bytes) else {
+        return "JSON 객체 형식의 설정 파일이 아닙니다.".to_string();
+    };
+
+    // 모르는 키가 있으면 다른 프로그램의 파일이거나 오타이므로 적용하지 않음
+    let Ok(Value::Object(mut fields)) = serde_json::to_value(GuildConfig::default()) else {
+        return SAVE_FAILED.to_string();
+    };
+    let mut unknown: Vec<&str> = imported
+        .keys()
+        .map(String::as_str)
+        .filter(|k| !fields.contains_key(*k) || SERVER_SPECIFIC_FIELDS.contains(k))
+        .collect();
+    if !unknown.is_empty() {
+        unknown.sort_unstable();
+        return format!("가져올 수 없는 항목이 있습니다: {}", unknown.join(", "));
+    }
+    let old = get_guild_config(ctx, guild_id).await;
+    fields.extend(imported);
+    let mut new: GuildConfig = match serde_json::from_value(Value::Object(fields)) {
+        Ok(config) => config,
+        Err(e) => return format!("설정 파일 형식이 올바르지 않습니다: {}", e),
+    };
+    new.notification_channel = None;
+    new.mention_role = None;
+    new.audit_channel = None;
+    new.command_channels.clear();
+    new.command_permissions.clear();
+    new.mention_targets.clear();
+    new.status_rename_channels.clear();
+    new.hub_channel = None;
+    new.afk_bypass_role = None;
+    new.voice_stats_role = None;
+    new.disabled_commands.retain(|name| name != "config" && registry().iter().any(|s| s.name == *name));
+
+    let changes = config_diff(&old, &new);
+    if changes.is_empty() {
+        return "가져온 설정이 현재 설정과 같습니다.".to_string();
+    }
+    let commands_changed = old.disabled_commands != new.disabled_commands;
+    if !update_guild_config(ctx, guild_id, cmd.user.id, |c| *c = new).await {
+        return SAVE_FAILED.to_string();
+    }
+    if commands_changed {
+        let sync_ctx = ctx.clone();
+        tokio::spawn(async move { resync_guild_commands(&sync_ctx, guild_id).await });
+    }
+    let lines = changes
+        .iter()
+        .map(|(key, before, after)| {
+            format!(
+                "**{}**: {} → {}",
+                key,
+                truncate(before, CHANGE_VALUE_LIMIT),
+                truncate(after, CHANGE_VALUE_LIMIT)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    truncate(
+        &format!(
+            "📥 설정을 가져왔습니다. 알림 채널과 역할은 `/setup` 으로 다시 정해주세요.\n{}",
+            lines
+        ),
+        MESSAGE_LIMIT,
+    )
+}
+
+fn history_embed(key: Option<&str>, changes: &[ConfigChange]) -> CreateEmbed {
+    let title = match key {
+        Some(key) => format!("📜 설정 변경 기록 · {}", key),
+        None => "📜 설정 변경 기록".to_string(),
+    };
+    let body = if changes.is_empty() {
+        "변경 기록이 없습니다.".to_string()
+    } else {
+        changes
+            .iter()
+            .map(|c| format!("<t:{}:f> <@{}> {}", c.changed_at, c.user_id, format_change(c)))
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+    CreateEmbed::new()
+        .title(title)
+        .description(truncate(&body, EMBED_DESCRIPTION_LIMIT))
+}
+
+// /config enable|disable <command>. 바꾼 뒤 이 서버의 길드 커맨드 목록을 다시 맞춤
+async fn set_command_enabled(
+    ctx: &Context,
+    cmd: &CommandInteraction,
+    guild_id: GuildId,
+    name: &str,
+    enable: bool,
+) -> String {
+    let name = name.trim().trim_start_matches('/').to_lowercase();
+    if !registry().iter().any(|s| s.name == name) {
+        return format!("알 수 없는 커맨드입니다: /{}", name);
+    }
+    // 끄고 나면 다시 켤 방법이 없어짐
+    if !enable && name == "config" {
+        return "/config 는 끌 수 없습니다.".to_string();
+    }
+    let saved = update_guild_config(ctx, guild_id, cmd.user.id, |c| {
+        c.disabled_commands.retain(|c| *c != name);
+        if !enable {
+            c.disabled_commands.push(name.clone());
+        }
+    })
+    .await;
+    if !saved {
+        return SAVE_FAILED.to_string();
+    }
+    // 커맨드 동기화는 오래 걸릴 수 있으므로 응답을 막지 않도록 따로 실행
+    let sync_ctx = ctx.clone();
+    tokio::spawn(async move { resync_guild_commands(&sync_ctx, guild_id).await });
+    let state = if enable { "켰습니다" } else { "껐습니다" };
+    format!("이 서버에서 /{} 을(를) {}.", name, state)
+}
+
+// /permission set <command> <role> | clear <command> | list
+pub async fn handle_permission(ctx: &Context, cmd: &CommandInteraction) {
+    let Some(guild_id) = cmd.guild_id else {
+        return;
+    };
+    let Some(sub) = cmd.data.options.first() else {
+        return;
+    };
+    let CommandDataOptionValue::SubCommand(args) = &sub.value else {
+        return;
+    };
+    let command_name = args
+        .iter()
+        .find(|o| o.name == "command")
+        .and_then(|o| o.value.as_str())
+        .map(|name| name.trim().trim_start_matches('/').to_lowercase());
+    let role_id = args.iter().find(|o| o.name == "role").and_then(|o| match o.value {
+        CommandDataOptionValue::Role(id) => Some(id),
+        _ => None,
+    });
+
+    let content = match (sub.name.as_str(), command_name) {
+        ("list", _) => {
+            let config = get_guild_config(ctx, guild_id).await;
+            if config.command_permissions.is_empty() {
+                "역할 규칙이 없습니다. 모든 커맨드를 디스코드 권한 설정대로 확인합니다.".to_string()
+            } else {
+                config
+                    .command_permissions
+                    .iter()
+                    .map(|p| format!("• /{} → <@&{}>", p.command_name, p.required_role))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            }
+        }
+        (_, Some(name)) if !registry().iter().any(|s| s.name == name && s.allows_role_rule()) => {
+            format!("/{} 에는 역할 규칙을 정할 수 없습니다. (없는 커맨드이거나 봇 소유자 전용/권한 관리 커맨드)", name)
+        }
+        ("set", Some(name)) => {
+            let Some(role_id) = role_id else {
+                return;
+            };
+            let rule = CommandPermission {
+                command_name: name.clone(),
+                required_role: role_id,
+            };
+            let saved = update_guild_config(ctx, guild_id, cmd.user.id, |c| {
+                c.command_permissions.retain(|p| p.command_name != name);
+                c.command_permissions.push(rule);
+            })
+            .await;
+            if saved {
+                format!("이제 <@&{}> 역할이 있어야 /{} 을(를) 사용할 수 있습니다.", role_id, name)
+            } else {
+                SAVE_FAILED.to_string()
+            }
+        }
+        ("clear", Some(name)) => {
+            if update_guild_config(ctx, guild_id, cmd.user.id, |c| c.command_permissions.retain(|p| p.command_name != name)).await {
+                format!("/{} 의 역할 규칙을 지웠습니다. 디스코드 권한 설정대로 확인합니다.", name)
+            } else {
+                SAVE_FAILED.to_string()
+            }
+        }
+        _ => return,
+    };
+
+    respond(
+        ctx,
+        cmd,
+        CreateInteractionResponseMessage::new()
+            .content(content)
+            .ephemeral(true),
+    )
+    .await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn change(key: &str, before: &str, after: &str) -> (String, String, String) {
+        (key.to_string(), before.to_string(), after.to_string())
+    }
+
+    #[test]
+    fn same_config_has_no_changes() {
+        assert!(config_diff(&GuildConfig::default(), &GuildConfig::default()).is_empty());
+    }
+
+    // 바뀐 필드만, 키 순으로, /config show와 같은 표시 형식으로
+    #[test]
+    fn lists_changed_fields() {
+        let new = GuildConfig {
+            notification_channel: Some(ChannelId::new(5)),
+            afk_move_minutes: 45,
+            disabled_commands: vec!["calc".to_string()],
+            ..GuildConfig::default()
+        };
+        assert_eq!(
+            config_diff(&GuildConfig::default(), &new),
+            vec![
+                change("afk_move_minutes", "30분", "45분"),
+                change("disabled_commands", "없음", "/calc"),
+                change("notification_channel", "없음", "<#5>"),
+            ]
+        );
+    }
+
+    // Option 필드는 기본값이 None이고, None과의 차이는 "없음"/"기본 문구"로 표시
+    #[test]
+    fn option_fields_default_to_none() {
+        let defaults = GuildConfig::default();
+        assert_eq!(defaults.notification_channel, None);
+        assert_eq!(defaults.mention_role, None);
+        assert_eq!(defaults.hub_channel, None);
+        assert_eq!(defaults.join_template, None);
+
+        let set = GuildConfig {
+            mention_role: Some(RoleId::new(7)),
+            join_template: Some("{user} 입장".to_string()),
+            ..GuildConfig::default()
+        };
+        assert_eq!(
+            config_diff(&defaults, &set),
+            vec![
+                change("join_template", "기본 문구", "`{user} 입장`"),
+                change("mention_role", "없음", "<@&7>"),
+            ]
+        );
+        // 다시 지우면 반대 방향으로 기록
+        assert_eq!(
+            config_diff(&set, &defaults),
+            vec![
+                change("join_template", "`{user} 입장`", "기본 문구"),
+                change("mention_role", "<@&7>", "없음"),
+            ]
+        );
+    }
+
+    // 저장된 JSON에 없는 필드(새로 추가된 Option 필드 등)는 기본값으로 읽혀 변경으로 잡히지 않음
+    #[test]
+    fn missing_stored_fields_are_defaults() {
+        let stored: GuildConfig = serde_json::from_str(r#"{"notification_channel": "5"}"#).unwrap();
+        assert_eq!(stored.hub_channel, None);
+        assert_eq!(stored.afk_bypass_role, None);
+        let expected = GuildConfig {
+            notification_channel: Some(ChannelId::new(5)),
+            ..GuildConfig::default()
+        };
+        assert!(config_diff(&expected, &stored).is_empty());
+    }
+}
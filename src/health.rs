@@ -0,0 +1,133 @@
+#[cfg(feature = "http-api")]
+use axum::{Json, Router, extract::State, http::StatusCode, routing::get};
+use serde_json::{json, Value};
+use serenity::all::ConnectionStage;
+#[cfg(feature = "http-api")]
+use std::sync::Arc;
+use std::sync::atomic::{AtomicI64, AtomicU32, Ordering};
+use std::time::{Duration, Instant};
+
+use crate::calc_cache::cache_counts;
+use crate::event_metrics;
+use crate::retry::retry_counts;
+use crate::storage::unix_now;
+
+// 기본값: 게이트웨이 연결이 60초 넘게 끊겨 있으면 비정상으로 응답
+pub const DEFAULT_DISCONNECT_THRESHOLD_SECS: u64 = 60;
+
+// /healthz가 보고하는 상태. 이벤트 핸들러에서 갱신
+pub struct HealthState {
+    started_at: Instant,
+    shard_count: AtomicU32,
+    connected_shards: AtomicU32,
+    // 마지막 이벤트 수신 시각 (유닉스 초, 0이면 아직 없음)
+    last_event_at: AtomicI64,
+    // 연결된 샤드가 전체보다 적어진 시각 (0이면 모두 연결됨)
+    disconnected_since: AtomicI64,
+    disconnect_threshold: Duration,
+}
+
+impl HealthState {
+    pub fn new(disconnect_threshold: Duration) -> Self {
+        Self {
+            started_at: Instant::now(),
+            shard_count: AtomicU32::new(0),
+            connected_shards: AtomicU32::new(0),
+            last_event_at: AtomicI64::new(0),
+            disconnected_since: AtomicI64::new(unix_now()),
+            disconnect_threshold,
+        }
+    }
+
+    pub fn set_shard_count(&self, count: u32) {
+        self.shard_count.store(count, Ordering::Relaxed);
+        self.update_disconnected_since();
+    }
+
+    pub fn record_event(&self) {
+        self.last_event_at.store(unix_now(), Ordering::Relaxed);
+    }
+
+    // 샤드 연결 단계 변경 반영
+    pub fn stage_changed(&self, old: ConnectionStage, new: ConnectionStage) {
+        let was_connected = old == ConnectionStage::Connected;
+        let is_connected = new == ConnectionStage::Connected;
+        if is_connected && !was_connected {
+            self.connected_shards.fetch_add(1, Ordering::Relaxed);
+        } else if was_connected && !is_connected {
+            let _ = self
+                .connected_shards
+                .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |n| n.checked_sub(1));
+        }
+        self.update_disconnected_since();
+    }
+
+    fn update_disconnected_since(&self) {
+        let total = self.shard_count.load(Ordering::Relaxed);
+        let connected = self.connected_shards.load(Ordering::Relaxed);
+        if total > 0 && connected >= total {
+            self.disconnected_since.store(0, Ordering::Relaxed);
+        } else {
+            // 이미 끊겨 있던 경우 처음 끊긴 시각 유지
+            let _ = self.disconnected_since.compare_exchange(
+                0,
+                unix_now(),
+                Ordering::Relaxed,
+                Ordering::Relaxed,
+            );
+        }
+    }
+
+    // (정상 여부, 응답 본문). http-api 기능 없이 빌드하면 쓰이지 않음
+    #[cfg_attr(not(feature = "http-api"), allow(dead_code))]
+    fn report(&self) -> (bool, Value) {
+        let disconnected_since = self.disconnected_since.load(Ordering::Relaxed);
+        let disconnected_for = if disconnected_since == 0 {
+            0
+        } else {
+            (unix_now() - disconnected_since).max(0) as u64
+        };
+        let healthy = disconnected_for <= self.disconnect_threshold.as_secs();
+        let last_event_at = self.last_event_at.load(Ordering::Relaxed);
+        let (retries, gave_up) = retry_counts();
+        let (cache_hits, cache_misses) = cache_counts();
+
+        let body = json!({
+            "status": if healthy { "ok" } else { "unavailable" },
+            "gateway": if disconnected_since == 0 { "connected" } else { "disconnected" },
+            "disconnected_secs": disconnected_for,
+            "connected_shards": self.connected_shards.load(Ordering::Relaxed),
+            "shard_count": self.shard_count.load(Ordering::Relaxed),
+            "last_event_at": (last_event_at != 0).then_some(last_event_at),
+            "uptime_secs": self.started_at.elapsed().as_secs(),
+            "discord_retries": { "retries": retries, "gave_up": gave_up },
+            "calc_cache": { "hits": cache_hits, "misses": cache_misses },
+            "event_handlers": event_metrics::report(),
+        });
+        (healthy, body)
+    }
+}
+
+#[cfg(feature = "http-api")]
+async fn healthz(State(health): State<Arc<HealthState>>) -> (StatusCode, Json<Value>) {
+    let (healthy, body) = health.report();
+    let status = if healthy {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (status, Json(body))
+}
+
+// 상태 확인용 HTTP 서버 실행 (GET /healthz). dashboard가 있으면 대시보드 API도 함께 제공
+#[cfg(feature = "http-api")]
+pub async fn serve(port: u16, health: Arc<HealthState>, dashboard: Option<Router>) -> std::io::Result<()> {
+    let mut app = Router::new()
+        .route("/healthz", get(healthz))
+        .with_state(health);
+    if let Some(dashboard) = dashboard {
+        app = app.merge(dashboard);
+    }
+    let listener = tokio::net::TcpListener::bind(("0.0.0.0", port)).await?;
+    axum::serve(listener, app).await
+}
@@ -0,0 +1,198 @@
+use serenity::all::ChannelId;
+use serenity::all::CommandInteraction;
+use serenity::all::CreateEmbed;
+use serenity::all::CreateInteractionResponseMessage;
+use serenity::all::CreateInvite;
+use serenity::all::GuildId;
+use serenity::all::UserId;
+use serenity::prelude::*;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+use crate::commands::respond;
+use crate::error_report::report_error;
+use crate::storage::unix_now;
+
+// /invitecreate 만료 시간 선택지: (값, 초, 표시 이름). 0초는 만료 없음
+pub const EXPIRY_CHOICES: &[(&str, u32, &str)] = &[
+    ("30m", 1800, "30분"),
+    ("1h", 3600, "1시간"),
+    ("6h", 21600, "6시간"),
+    ("24h", 86400, "24시간"),
+    ("never", 0, "만료 없음"),
+];
+
+const DEFAULT_EXPIRY: &str = "24h";
+
+// 봇이 만든 초대 링크
+#[derive(Debug, Clone)]
+pub struct TrackedInvite {
+    pub code: String,
+    pub channel_id: ChannelId,
+    pub creator: UserId,
+    pub max_uses: u8,
+    pub uses: u64,
+    // 만료 시각 (유닉스 초, 만료 없음이면 None)
+    pub expires_at: Option<i64>,
+}
+
+// 길드별로 봇이 만든 초대 링크 목록 (/invitelist 조회 시 갱신)
+pub struct BotInvites;
+
+impl TypeMapKey for BotInvites {
+    type Value = Arc<RwLock<HashMap<GuildId, Vec<TrackedInvite>>>>;
+}
+
+pub fn new_invite_store() -> Arc<RwLock<HashMap<GuildId, Vec<TrackedInvite>>>> {
+    Arc::new(RwLock::new(HashMap::new()))
+}
+
+async fn invite_store(ctx: &Context) -> Option<Arc<RwLock<HashMap<GuildId, Vec<TrackedInvite>>>>> {
+    let data = ctx.data.read().await;
+    data.get::<BotInvites>().cloned()
+}
+
+async fn reply(ctx: &Context, cmd: &CommandInteraction, message: CreateInteractionResponseMessage) {
+    respond(ctx, cmd, message.ephemeral(true)).await;
+}
+
+// /invitecreate <channel> [max_uses] [expires_in]
+// 서버 관리 권한이 없으면 1회용 초대만 만들 수 있음
+pub async fn handle_invitecreate(ctx: &Context, cmd: &CommandInteraction) {
+    let Some(guild_id) = cmd.guild_id else {
+        return;
+    };
+    let options = &cmd.data.options;
+    let Some(channel_id) = options
+        .iter()
+        .find(|o| o.name == "channel")
+        .and_then(|o| o.value.as_channel_id())
+    else {
+        return;
+    };
+    let max_uses = options
+        .iter()
+        .find(|o| o.name == "max_uses")
+        .and_then(|o| o.value.as_i64())
+        .unwrap_or(1)
+        .clamp(1, 100) as u8;
+    let expiry = options
+        .iter()
+        .find(|o| o.name == "expires_in")
+        .and_then(|o| o.value.as_str())
+        .unwrap_or(DEFAULT_EXPIRY);
+    let Some(&(_, max_age, expiry_label)) = EXPIRY_CHOICES.iter().find(|(v, _, _)| *v == expiry) else {
+        return;
+    };
+
+    let can_manage = cmd
+        .member
+        .as_ref()
+        .and_then(|m| m.permissions)
+        .is_some_and(|p| p.administrator() || p.manage_guild());
+    if !can_manage && max_uses > 1 {
+        reply(
+            ctx,
+            cmd,
+            CreateInteractionResponseMessage::new()
+                .content("여러 번 사용할 수 있는 초대 링크는 서버 관리 권한이 필요합니다"),
+        )
+        .await;
+        return;
+    }
+
+    let reason = format!("/invitecreate by {}", cmd.user.id);
+    let builder = CreateInvite::new()
+        .max_age(max_age)
+        .max_uses(max_uses)
+        .unique(true)
+        .audit_log_reason(&reason);
+    let invite = match channel_id.create_invite(&ctx.http, builder).await {
+        Ok(invite) => invite,
+        Err(e) => {
+            report_error(ctx, "초대 링크 생성", &e).await;
+            reply(
+                ctx,
+                cmd,
+                CreateInteractionResponseMessage::new()
+                    .content("초대 링크를 만들지 못했습니다. 봇에 초대 만들기 권한이 있는지 확인해주세요."),
+            )
+            .await;
+            return;
+        }
+    };
+
+    if let Some(store) = invite_store(ctx).await {
+        store.write().await.entry(guild_id).or_default().push(TrackedInvite {
+            code: invite.code.clone(),
+            channel_id,
+            creator: cmd.user.id,
+            max_uses,
+            uses: 0,
+            expires_at: (max_age > 0).then(|| unix_now() + i64::from(max_age)),
+        });
+    }
+
+    let embed = CreateEmbed::new()
+        .title("🔗 보이스 채널 초대 링크")
+        .description(invite.url())
+        .field("채널", format!("<#{}>", channel_id), true)
+        .field("사용 횟수", format!("{}회", max_uses), true)
+        .field("만료", expiry_label, true);
+    reply(ctx, cmd, CreateInteractionResponseMessage::new().embed(embed)).await;
+}
+
+// /invitelist: 봇이 만든 초대 중 아직 유효한 것
+pub async fn handle_invitelist(ctx: &Context, cmd: &CommandInteraction) {
+    let Some(guild_id) = cmd.guild_id else {
+        return;
+    };
+    let Some(store) = invite_store(ctx).await else {
+        return;
+    };
+    let tracked = store.read().await.get(&guild_id).cloned().unwrap_or_default();
+
+    // 디스코드의 현재 초대 목록으로 사용 횟수를 갱신하고 사라진 초대는 제외.
+    // 목록을 볼 권한이 없으면 만료 시각으로만 거름
+    let now = unix_now();
+    let refreshed: Vec<TrackedInvite> = match guild_id.invites(&ctx.http).await {
+        Ok(current) => {
+            let current: HashMap<&str, u64> =
+                current.iter().map(|i| (i.code.as_str(), i.uses)).collect();
+            tracked
+                .into_iter()
+                .filter_map(|mut invite| {
+                    invite.uses = *current.get(invite.code.as_str())?;
+                    Some(invite)
+                })
+                .collect()
+        }
+        Err(_) => tracked
+            .into_iter()
+            .filter(|invite| invite.expires_at.is_none_or(|at| at > now))
+            .collect(),
+    };
+    store.write().await.insert(guild_id, refreshed.clone());
+
+    let body = if refreshed.is_empty() {
+        "봇이 만든 유효한 초대 링크가 없습니다.".to_string()
+    } else {
+        refreshed
+            .iter()
+            .map(|invite| {
+                let expiry = match invite.expires_at {
+                    Some(at) => format!("<t:{}:R> 만료", at),
+                    None => "만료 없음".to_string(),
+                };
+                format!(
+                    "`{}` · <#{}> · {}/{}회 · {} · <@{}>",
+                    invite.code, invite.channel_id, invite.uses, invite.max_uses, expiry, invite.creator
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+    let embed = CreateEmbed::new().title("🔗 초대 링크 목록").description(body);
+    reply(ctx, cmd, CreateInteractionResponseMessage::new().embed(embed)).await;
+}
@@ -0,0 +1,58 @@
+// 봇 본체. 실행 파일(main.rs)은 명령줄을 읽고 여기의 app/calc 등을 호출만 함
+pub mod afk_move;
+pub mod app;
+pub mod calc;
+pub mod calc_buttons;
+pub mod calc_cache;
+pub mod calc_log;
+pub mod calc_session;
+pub mod channel_status;
+#[cfg(feature = "chime")]
+pub mod chime;
+pub mod cli;
+pub mod command_sync;
+pub mod commands;
+pub mod config;
+pub mod config_check;
+#[cfg(feature = "http-api")]
+pub mod dashboard;
+pub mod dry_run;
+pub mod error;
+pub mod error_report;
+pub mod event_metrics;
+pub mod feedback;
+pub mod formulas;
+pub mod guild_config;
+pub mod health;
+pub mod invites;
+pub mod locale;
+pub mod long_message;
+pub mod maintenance;
+pub mod mention;
+pub mod nick;
+pub mod notification;
+pub mod notification_batch;
+pub mod notifier;
+pub mod presence;
+pub mod rate_limit;
+pub mod reminders;
+pub mod retry;
+pub mod roleinfo;
+pub mod scheduled_events;
+pub mod scheduler;
+pub mod setup;
+pub mod shards;
+pub mod slowmode;
+pub mod storage;
+pub mod streaks;
+pub mod telemetry;
+pub mod temp_channels;
+pub mod threads;
+pub mod tts_announce;
+pub mod usage;
+pub mod user_prefs;
+pub mod voice_events;
+pub mod voice_log;
+pub mod voice_stats;
+pub mod voice_tracker;
+pub mod weekly_report;
@@ -0,0 +1,269 @@
+use serde_json::Value;
+use serenity::all::CommandOptionType;
+use serenity::all::CreateCommand;
+use serenity::all::CreateCommandOption;
+
+// 영어 사용자에게 보여줄 로캘. 기본(한국어) 문구는 빌더에 그대로 넣음
+const ENGLISH_LOCALES: &[&str] = &["en-US", "en-GB"];
+
+// 커맨드 설명, 옵션 설명, 선택지 이름의 영어 번역 (한국어 원문 -> 영어)
+const ENGLISH: &[(&str, &str)] = &[
+    ("수식을 PEMDAS 우선순위로 계산합니다", "Evaluate an expression using PEMDAS precedence"),
+    ("계산기가 지원하는 연산자와 함수를 보여줍니다", "Show the operators and functions the calculator supports"),
+    ("계산기 모드를 설정합니다", "Set the calculator mode"),
+    ("보이스 채널 알림을 보낼 텍스트 채널을 설정합니다", "Set the text channel for voice channel notifications"),
+    ("채널 활성화 시 멘션할 역할을 설정합니다 (비우면 멘션 안 함)", "Set the role to mention when a channel becomes active (empty: no mention)"),
+    ("샤드 상태를 확인합니다 (봇 소유자 전용)", "Show shard status (bot owner only)"),
+    ("예약 작업과 다음 실행 시각을 확인합니다 (봇 소유자 전용)", "Show scheduled jobs and their next run (bot owner only)"),
+    ("봇을 안전하게 종료합니다 (봇 소유자 전용)", "Shut the bot down cleanly (bot owner only)"),
+    ("설정 파일을 다시 읽습니다 (봇 소유자 전용)", "Reload the config file (bot owner only)"),
+    ("모든 서버의 알림 채널에 공지를 보냅니다 (봇 소유자 전용)", "Send an announcement to every server's notification channel (bot owner only)"),
+    ("누적 보이스 채널 이용 시간을 확인합니다", "Show total time spent in voice channels"),
+    ("보이스 채널이 활성화된 지 얼마나 됐는지 확인합니다", "Show how long voice channels have been active"),
+    ("확인할 보이스 채널 (비우면 활성화된 채널 전체)", "Voice channel to check (empty: all active channels)"),
+    ("보이스 채널 이용 시간 순위를 확인합니다", "Show the voice time leaderboard"),
+    ("보이스 통계와 알림 설정을 변경합니다", "Change voice stats and notification settings"),
+    ("서버 설정을 확인하거나 변경합니다", "View or change server settings"),
+    ("봇을 통해 닉네임을 바꿉니다", "Change a nickname through the bot"),
+    ("역할의 색상, 위치, 멤버 수, 주요 권한을 확인합니다", "Show a role's colour, position, member count and key permissions"),
+    ("텍스트 채널의 슬로우 모드를 관리합니다", "Manage slow mode for a text channel"),
+    ("보이스 채널 초대 링크를 만듭니다", "Create a voice channel invite link"),
+    ("봇이 만든 유효한 초대 링크를 확인합니다", "List active invite links created by the bot"),
+    ("보이스 채널 입장/퇴장 기록을 확인합니다", "Show the voice channel join/leave log"),
+    ("시간을 표시할 때 사용할 내 시간대를 설정합니다", "Set the time zone used to show times to you"),
+    ("매주 보이스 시간 요약 DM을 받을지 설정합니다", "Choose whether to receive a weekly voice time summary DM"),
+    ("보이스 채널에 들어올 때 내 이름을 TTS로 읽을지 설정합니다", "Choose whether your name is read aloud by TTS when you join voice"),
+    ("정해진 시간 뒤에 알림을 보냅니다", "Send a reminder after a set time"),
+    ("커맨드 사용 통계를 확인합니다", "Show command usage statistics"),
+    ("알림 채널, 멘션 역할 등을 고르는 설정 마법사를 엽니다", "Open the setup wizard for the notification channel, mention role and more"),
+    ("이 서버에 등록된 슬래시 커맨드를 모두 삭제합니다", "Remove every slash command registered in this server"),
+    ("채널 활성화 알림음을 지금 재생해 봅니다", "Play the channel activation chime now"),
+    ("재생할 보이스 채널 (비우면 내가 있는 채널)", "Voice channel to play in (empty: the one you are in)"),
+    ("커맨드별로 필요한 역할을 정합니다", "Set the role required for each command"),
+    ("계산할 수식 (비우면 여러 줄 입력 창)", "Expression to evaluate (empty: open a multi-line input)"),
+    ("계산 과정을 단계별로 설명합니다", "Explain the calculation step by step"),
+    ("복소수 결과 허용 (예: sqrt(-1) = i)", "Allow complex results (e.g. sqrt(-1) = i)"),
+    ("알림 채널", "Notification channel"),
+    ("멘션할 역할", "Role to mention"),
+    ("공지 내용", "Announcement text"),
+    ("조회할 사용자 (비우면 본인)", "User to look up (empty: yourself)"),
+    ("보이스 통계 공개 범위를 설정합니다", "Set who can see voice stats"),
+    ("공개 범위", "Visibility"),
+    ("다른 봇의 보이스 입장/퇴장 알림을 켜거나 끕니다", "Turn voice join/leave notifications for other bots on or off"),
+    ("알림 여부", "Notifications"),
+    ("채널 활성화 알림에서 멘션할 역할/사용자를 관리합니다", "Manage the roles/users mentioned in channel activation notifications"),
+    ("활성화 알림 멘션 대상을 추가합니다", "Add a mention target for activation notifications"),
+    ("활성화 알림 멘션 대상을 뺍니다", "Remove a mention target from activation notifications"),
+    ("멘션할 역할 또는 사용자", "Role or user to mention"),
+    ("이 보이스 채널에만 적용 (비우면 모든 채널)", "Apply only to this voice channel (leave empty for all channels)"),
+    ("채널이 활성화된 동안 이름 앞에 표시를 붙일지 정합니다", "Choose whether to prefix a channel's name while it is active"),
+    ("대상 보이스 채널", "Voice channel"),
+    ("표시 여부", "Show indicator"),
+    ("알림을 임베드로 보낼지 일반 텍스트로 보낼지 정합니다", "Choose whether notifications are sent as embeds or plain text"),
+    ("임베드 사용 여부", "Use embeds"),
+    ("연속 보이스 기록에 하루로 인정할 최소 접속 시간을 정합니다", "Set the minimum voice time that counts as a day for streaks"),
+    ("하루 최소 접속 시간 (분)", "Minimum voice time per day (minutes)"),
+    ("활성화 번호로 보이스 채널 활성화 기록을 확인합니다", "Look up a voice channel activation by its number"),
+    ("서버의 활성화 번호 (1부터)", "Activation number in this server (starting at 1)"),
+    ("최근 /calc 계산 기록을 봅니다 (DM에서는 내 기록)", "View recent /calc evaluations (your own in DMs)"),
+    ("이 서버에서 /calc 자동 완성으로 쓸 수식을 관리합니다", "Manage the formulas suggested by /calc autocomplete in this server"),
+    ("수식을 이름을 붙여 저장합니다 (서버 관리 권한)", "Save a formula under a name (Manage Server)"),
+    ("수식 이름", "Formula name"),
+    ("저장할 수식", "Formula to save"),
+    ("저장한 수식을 지웁니다 (서버 관리 권한)", "Delete a saved formula (Manage Server)"),
+    ("지울 수식 이름", "Name of the formula to delete"),
+    ("저장한 수식 목록을 봅니다", "List the saved formulas"),
+    ("최근 계산 10개를 봅니다 (서버: 모든 사용자, DM: 나)", "Show the last 10 evaluations (server: all users, DM: yours)"),
+    ("이 서버의 /calc 계산을 기록할지 정합니다 (서버 관리 권한 필요)", "Choose whether to log /calc evaluations in this server (requires Manage Server)"),
+    ("기록 여부", "Logging"),
+    ("이번 달 보이스 채널을 가장 많이 활성화한 멤버를 확인합니다", "See who started the most voice channel sessions this month"),
+    ("기록된 누적 보이스 시간을 보정합니다", "Correct a member's recorded voice time"),
+    ("누적 보이스 시간을 더합니다", "Add to the recorded voice time"),
+    ("누적 보이스 시간을 뺍니다 (0 밑으로는 줄지 않음)", "Subtract from the recorded voice time (never below zero)"),
+    ("누적 보이스 시간을 0으로 되돌립니다", "Reset the recorded voice time to zero"),
+    ("기간 (예: 45m, 2h30m, 1d)", "Duration (e.g. 45m, 2h30m, 1d)"),
+    ("사유 (감사 로그에 기록)", "Reason (recorded in the audit log)"),
+    ("연속 보이스 활동 기록을 확인합니다", "Check your voice activity streak"),
+    ("내 연속 보이스 활동 일수를 확인합니다", "Show your current and best voice streak"),
+    ("연속 기록이 끊기기 전 저녁에 DM으로 알려줄지 정합니다", "Choose whether to get an evening DM before your streak breaks"),
+    ("알림 문구를 바꿉니다", "Change notification wording"),
+    ("입장 알림 문구를 바꿉니다 ({user}, {channel}, {count})", "Change the join notification ({user}, {channel}, {count})"),
+    ("퇴장 알림 문구를 바꿉니다 ({user}, {channel}, {count})", "Change the leave notification ({user}, {channel}, {count})"),
+    ("활성화 알림 문구를 바꿉니다 ({user}, {channel}, {count})", "Change the activation notification ({user}, {channel}, {count})"),
+    ("비활성화 알림 문구를 바꿉니다 ({channel}, {duration})", "Change the deactivation notification ({channel}, {duration})"),
+    ("새 문구 (비우면 기본 문구로)", "New wording (leave empty for the default)"),
+    ("서버 설정 전체를 기본값으로 되돌립니다 (관리자 전용)", "Reset all server settings to their defaults (administrators only)"),
+    ("설정 이름", "Setting name"),
+    ("모든 설정의 현재 값과 기본값을 표시합니다", "Show the current and default value of every setting"),
+    ("설정 값을 변경합니다", "Change a setting"),
+    ("새 값 (#채널, 여러 #채널, @역할, on/off, 분, 선택지, none으로 비우기)", "New value (#channel, several #channels, @role, on/off, minutes, a choice, or none to clear)"),
+    ("설정을 기본값으로 되돌립니다", "Reset a setting to its default"),
+    ("최근 설정 변경 기록 20개", "Show the 20 most recent setting changes"),
+    ("이 설정만 보기", "Only show this setting"),
+    ("채널/역할을 뺀 설정을 JSON 파일로 내보냅니다 (관리자 전용)", "Export settings without channels and roles as JSON (admin only)"),
+    ("내보낸 설정 파일을 가져옵니다 (관리자 전용)", "Import an exported settings file (admin only)"),
+    ("/config export 로 만든 JSON 파일", "JSON file created by /config export"),
+    ("꺼 둔 커맨드를 다시 켭니다", "Re-enable a disabled command"),
+    ("커맨드 이름 (예: calc)", "Command name (e.g. calc)"),
+    ("이 서버에서 커맨드를 끕니다", "Disable a command in this server"),
+    ("새 닉네임", "New nickname"),
+    ("닉네임을 바꿀 멤버 (닉네임 관리 권한 필요)", "Member whose nickname to change (requires Manage Nicknames)"),
+    ("확인할 역할", "Role to inspect"),
+    ("대상 채널 (비우면 현재 채널)", "Target channel (empty: this channel)"),
+    ("슬로우 모드를 설정합니다", "Set slow mode"),
+    ("메시지 간 대기 시간 (초, 0이면 끔)", "Delay between messages in seconds (0 turns it off)"),
+    ("현재 슬로우 모드를 확인합니다", "Show the current slow mode"),
+    ("만료 시간 (기본 24시간)", "Expires after (default 24 hours)"),
+    ("초대할 보이스 채널", "Voice channel to invite to"),
+    ("사용 가능 횟수 (기본 1회, 2회 이상은 서버 관리 권한 필요)", "Max uses (default 1; more than 1 requires Manage Server)"),
+    ("사용자로 거르기", "Filter by user"),
+    ("채널로 거르기", "Filter by channel"),
+    ("날짜 (YYYY-MM-DD, UTC, 비우면 오늘, 최근 7일까지)", "Date (YYYY-MM-DD, UTC; empty: today; last 7 days only)"),
+    ("페이지", "Page"),
+    ("시간대를 설정합니다", "Set your time zone"),
+    ("IANA 시간대 이름 (예: Asia/Seoul)", "IANA time zone name (e.g. Asia/Seoul)"),
+    ("시간대 설정을 지우고 UTC로 표시합니다", "Clear your time zone and show times in UTC"),
+    ("매주 일요일에 지난 7일 요약을 DM으로 받습니다", "Receive a summary of the last 7 days by DM every Sunday"),
+    ("주간 요약 DM을 그만 받습니다", "Stop receiving the weekly summary DM"),
+    ("서버가 입장 안내를 켜 두었다면 내 이름도 읽습니다", "Read your name when the server has join announcements on"),
+    ("내 이름은 읽지 않습니다", "Never read your name"),
+    ("리마인더를 예약합니다", "Schedule a reminder"),
+    ("기간 (예: 10m, 1h30m, 2d)", "Duration (e.g. 10m, 1h30m, 2d)"),
+    ("알림 내용", "Reminder text"),
+    ("이 채널 대신 DM으로 받습니다", "Receive it by DM instead of in this channel"),
+    ("리마인더를 취소합니다", "Cancel a reminder"),
+    ("리마인더 ID (/remind list)", "Reminder ID (see /remind list)"),
+    ("대기 중인 리마인더를 확인합니다", "List pending reminders"),
+    ("이 서버의 최근 7일 통계", "Statistics for this server over the last 7 days"),
+    ("모든 서버의 최근 7일 통계 (봇 소유자 전용)", "Statistics for all servers over the last 7 days (bot owner only)"),
+    ("서버 커맨드 대신 글로벌 커맨드를 삭제 (봇 소유자 전용)", "Remove global commands instead of this server's (bot owner only)"),
+    ("커맨드에 필요한 역할 지정", "Set the role a command requires"),
+    ("커맨드 이름 (예: voicetop)", "Command name (e.g. voicetop)"),
+    ("필요한 역할", "Required role"),
+    ("역할 규칙 삭제", "Remove a role rule"),
+    ("커맨드 이름", "Command name"),
+    ("역할 규칙 목록", "List role rules"),
+    ("봇 개발자에게 버그 제보나 제안을 보냅니다", "Send a bug report or suggestion to the bot developer"),
+    ("피드백을 보냅니다", "Send feedback"),
+    ("버그 제보나 제안 내용", "Bug report or suggestion"),
+    ("사용자의 피드백을 막습니다 (봇 소유자 전용)", "Block a user's feedback (bot owner only)"),
+    ("피드백 차단을 해제합니다 (봇 소유자 전용)", "Unblock a user's feedback (bot owner only)"),
+    ("대상 사용자", "Target user"),
+    ("모두 공개", "Everyone"),
+    ("서버 멤버만", "Server members only"),
+    ("관리자만", "Admins only"),
+    ("30분", "30 minutes"),
+    ("1시간", "1 hour"),
+    ("6시간", "6 hours"),
+    ("24시간", "24 hours"),
+    ("만료 없음", "Never"),
+];
+
+fn english(korean: &str) -> Option<&'static str> {
+    ENGLISH.iter().find(|(ko, _)| *ko == korean).map(|(_, en)| *en)
+}
+
+// 번역이 없으면 한국어 그대로 (check_localizations가 시작할 때 알려줌)
+fn english_locales(korean: &str) -> Vec<(&'static str, &str)> {
+    english(korean)
+        .map(|en| ENGLISH_LOCALES.iter().map(|&locale| (locale, en)).collect())
+        .unwrap_or_default()
+}
+
+// 한국어/영어 설명을 함께 넣은 커맨드
+pub fn command(name: &str, description: &str) -> CreateCommand {
+    english_locales(description).into_iter().fold(
+        CreateCommand::new(name).description(description),
+        |cmd, (locale, en)| cmd.description_localized(locale, en),
+    )
+}
+
+// 한국어/영어 설명을 함께 넣은 옵션
+pub fn option(kind: CommandOptionType, name: &str, description: &str) -> CreateCommandOption {
+    english_locales(description).into_iter().fold(
+        CreateCommandOption::new(kind, name, description),
+        |option, (locale, en)| option.description_localized(locale, en),
+    )
+}
+
+// 이름이 한국어인 문자열 선택지
+pub fn string_choice(option: CreateCommandOption, name: &str, value: &str) -> CreateCommandOption {
+    option.add_string_choice_localized(name, value, english_locales(name))
+}
+
+// 영어 번역이 빠진 설명/선택지 목록 ("/커맨드 옵션: 원문"). 새 커맨드를 반만 번역한 채 올리지 않도록 시작할 때 확인
+pub fn check_localizations(commands: &[CreateCommand]) -> Vec<String> {
+    let mut missing = Vec::new();
+    for command in commands {
+        let Ok(value) = serde_json::to_value(command) else {
+            continue;
+        };
+        let name = format!("/{}", value["name"].as_str().unwrap_or_default());
+        collect_missing(&name, &value, &mut missing);
+    }
+    missing
+}
+
+fn collect_missing(path: &str, value: &Value, missing: &mut Vec<String>) {
+    if !has_english(value, "description_localizations") {
+        missing.push(format!("{}: {}", path, value["description"].as_str().unwrap_or_default()));
+    }
+    // 선택지는 영어/숫자 값 그대로 쓰는 것(on/off, 설정 키)이 많아 한국어가 들어간 이름만 확인
+    for choice in value["choices"].as_array().into_iter().flatten() {
+        let name = choice["name"].as_str().unwrap_or_default();
+        if !name.is_ascii() && !has_english(choice, "name_localizations") {
+            missing.push(format!("{} 선택지: {}", path, name));
+        }
+    }
+    for option in value["options"].as_array().into_iter().flatten() {
+        let path = format!("{} {}", path, option["name"].as_str().unwrap_or_default());
+        collect_missing(&path, option, missing);
+    }
+}
+
+fn has_english(value: &Value, field: &str) -> bool {
+    ENGLISH_LOCALES.iter().all(|locale| value[field][*locale].is_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::registry;
+
+    // 번역이 없는 문구는 로캘 없이 한국어 원문만 (디스코드가 기본 문구를 보여줌)
+    #[test]
+    fn missing_translation_falls_back_to_korean() {
+        assert!(english_locales("번역하지 않은 설명").is_empty());
+        let value = serde_json::to_value(command("test", "번역하지 않은 설명")).unwrap();
+        assert_eq!(value["description"], "번역하지 않은 설명");
+        assert!(value["description_localizations"].as_object().is_none_or(|m| m.is_empty()));
+        assert_eq!(
+            check_localizations(&[command("test", "번역하지 않은 설명")]),
+            vec!["/test: 번역하지 않은 설명".to_string()]
+        );
+    }
+
+    // 번역이 있으면 모든 영어 로캘에 같은 문구
+    #[test]
+    fn translation_is_set_for_every_locale() {
+        let value = serde_json::to_value(command("calc", "수식을 PEMDAS 우선순위로 계산합니다")).unwrap();
+        for locale in ENGLISH_LOCALES {
+            assert_eq!(value["description_localizations"][*locale], "Evaluate an expression using PEMDAS precedence");
+        }
+    }
+
+    // 등록하는 모든 커맨드의 설명/옵션/선택지가 모든 영어 로캘에 번역돼 있어야 함
+    #[test]
+    fn every_registered_string_is_translated() {
+        let commands: Vec<CreateCommand> = registry().iter().map(|spec| spec.create()).collect();
+        assert_eq!(check_localizations(&commands), Vec::<String>::new());
+    }
+
+    // 같은 원문이 두 번 있으면 뒤의 번역은 쓰이지 않음
+    #[test]
+    fn no_duplicate_source_strings() {
+        for (i, (korean, _)) in ENGLISH.iter().enumerate() {
+            assert!(!ENGLISH[..i].iter().any(|(ko, _)| ko == korean), "중복: {}", korean);
+        }
+    }
+}
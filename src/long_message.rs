@@ -0,0 +1,155 @@
+// 디스코드 메시지/임베드 길이 제한 (문자 수)
+pub const MESSAGE_LIMIT: usize = 2000;
+pub const EMBED_DESCRIPTION_LIMIT: usize = 4096;
+pub const EMBED_FIELD_LIMIT: usize = 1024;
+
+// 긴 응답을 나눠 보낼 때 최대 메시지 수 (나머지는 잘라냄)
+pub const MAX_CHUNKS: usize = 5;
+
+// 제한을 넘으면 잘라내고 끝에 …를 붙임 (문자 단위라 글자가 깨지지 않음)
+pub fn truncate(text: &str, limit: usize) -> String {
+    if text.chars().count() <= limit {
+        return text.to_string();
+    }
+    let mut out: String = text.chars().take(limit.saturating_sub(1)).collect();
+    out.push('…');
+    out
+}
+
+// 본문을 limit 이하 조각으로 나눔. 코드 블록(```) 안에서 끊기면 조각 끝에서 닫고
+// 다음 조각을 같은 언어로 다시 열어, 조각마다 따로 보여도 코드 블록이 유지됨
+pub fn split_content(text: &str, limit: usize) -> Vec<String> {
+    let longest_info = text
+        .lines()
+        .filter_map(|line| line.trim_start().strip_prefix("```"))
+        .map(|info| info.trim().chars().count())
+        .max();
+    let Some(longest_info) = longest_info else {
+        return split_plain(text, limit);
+    };
+    // 다시 여는 "```언어\n"과 닫는 "\n```" 자리를 비워 둠
+    let reserved = 3 + longest_info + 1 + 4;
+    let mut open: Option<String> = None;
+    split_plain(text, limit.saturating_sub(reserved).max(1))
+        .into_iter()
+        .map(|chunk| {
+            let mut out = match &open {
+                Some(info) => format!("```{}\n{}", info, chunk),
+                None => chunk.clone(),
+            };
+            for line in chunk.lines() {
+                if let Some(info) = line.trim_start().strip_prefix("```") {
+                    open = match open {
+                        Some(_) => None,
+                        None => Some(info.trim().to_string()),
+                    };
+                }
+            }
+            if open.is_some() {
+                out.push_str("\n```");
+            }
+            out
+        })
+        .collect()
+}
+
+// 줄바꿈 > 공백 순으로 끊을 곳을 찾고, 그래도 없으면(아주 긴 숫자 등)
+// 숫자 사이가 아닌 곳, 마지막으로 제한 위치에서 끊음
+fn split_plain(text: &str, limit: usize) -> Vec<String> {
+    let mut chunks = Vec::new();
+    let mut rest = text;
+    while rest.chars().count() > limit {
+        let head_end = rest.char_indices().nth(limit).map_or(rest.len(), |(i, _)| i);
+        let head = &rest[..head_end];
+        let cut = head
+            .rfind('\n')
+            .or_else(|| head.rfind(' '))
+            .filter(|&i| i > 0)
+            .or_else(|| {
+                head.char_indices()
+                    .rev()
+                    .find(|&(i, c)| i > 0 && !c.is_ascii_digit() && c != '.')
+                    .map(|(i, c)| i + c.len_utf8())
+                    .filter(|&i| i < head_end)
+            })
+            .unwrap_or(head_end);
+        let (chunk, tail) = rest.split_at(cut);
+        chunks.push(chunk.trim_end().to_string());
+        rest = tail.trim_start_matches(['\n', ' ']);
+    }
+    if !rest.is_empty() || chunks.is_empty() {
+        chunks.push(rest.to_string());
+    }
+    chunks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lengths(chunks: &[String]) -> Vec<usize> {
+        chunks.iter().map(|c| c.chars().count()).collect()
+    }
+
+    // 딱 제한 길이면 나누지 않고, 한 글자 넘으면 나눔
+    #[test]
+    fn exact_limit_is_one_chunk() {
+        let text = "가".repeat(MESSAGE_LIMIT);
+        assert_eq!(split_content(&text, MESSAGE_LIMIT), vec![text.clone()]);
+        let over = "가".repeat(MESSAGE_LIMIT + 1);
+        assert_eq!(lengths(&split_content(&over, MESSAGE_LIMIT)), vec![MESSAGE_LIMIT, 1]);
+        assert_eq!(split_content("", MESSAGE_LIMIT), vec![String::new()]);
+    }
+
+    // 제한 안에서 마지막 줄바꿈에서 끊고, 줄바꿈은 어느 조각에도 남기지 않음
+    #[test]
+    fn splits_on_newline_boundary() {
+        let line = "a".repeat(900);
+        let text = format!("{line}\n{line}\n{line}");
+        let chunks = split_content(&text, MESSAGE_LIMIT);
+        assert_eq!(chunks, vec![format!("{line}\n{line}"), line.clone()]);
+    }
+
+    // 줄바꿈도 공백도 없는 2000자 넘는 한 줄은 제한 위치에서 자름
+    #[test]
+    fn single_long_line_is_hard_split() {
+        let text = "x".repeat(MESSAGE_LIMIT * 2 + 10);
+        let chunks = split_content(&text, MESSAGE_LIMIT);
+        assert_eq!(lengths(&chunks), vec![MESSAGE_LIMIT, MESSAGE_LIMIT, 10]);
+        assert_eq!(chunks.concat(), text);
+    }
+
+    // 긴 숫자는 숫자 사이에서 끊지 않음
+    #[test]
+    fn long_number_is_not_split_mid_digits() {
+        let text = format!("결과={}", "9".repeat(30));
+        let chunks = split_content(&text, 20);
+        assert_eq!(chunks[0], "결과=");
+        assert!(chunks[1..].iter().all(|c| c.chars().all(|ch| ch.is_ascii_digit())));
+    }
+
+    // 코드 블록 중간에서 끊긴 조각은 닫고, 다음 조각은 같은 언어로 다시 엶
+    #[test]
+    fn code_fence_is_carried_across_chunks() {
+        let body = (0..300).map(|i| format!("let x{} = {};", i, i)).collect::<Vec<_>>().join("\n");
+        let text = format!("결과:\n```rust\n{}\n```\n끝", body);
+        let chunks = split_content(&text, MESSAGE_LIMIT);
+        assert!(chunks.len() > 1);
+        for chunk in &chunks {
+            assert!(chunk.chars().count() <= MESSAGE_LIMIT, "{}", chunk.chars().count());
+            // 조각마다 펜스가 짝이 맞음
+            assert_eq!(chunk.lines().filter(|l| l.starts_with("```")).count() % 2, 0, "{}", chunk);
+        }
+        assert!(chunks[0].starts_with("결과:\n```rust\n"));
+        assert!(chunks[0].ends_with("\n```"));
+        assert!(chunks[1].starts_with("```rust\n"));
+        assert!(chunks.last().unwrap().ends_with("```\n끝"));
+    }
+
+    // 코드 블록이 없으면 펜스를 붙이지 않음
+    #[test]
+    fn plain_text_gets_no_fences() {
+        let text = "word ".repeat(1000);
+        assert!(split_content(&text, MESSAGE_LIMIT).iter().all(|c| !c.contains("```")));
+    }
+}
@@ -1,32 +1,62 @@
-use serenity::Client;
-use serenity::all::GatewayIntents;
-use std::collections::HashMap;
-use std::sync::Arc;
-use tokio::sync::RwLock;
-
-mod voice_tracker;
-mod calc;
-use crate::voice_tracker::{ChannelActivityTracker, VoiceHandler};
+use aurobot::app;
+use aurobot::calc;
+use aurobot::calc_session::UserCalcSession;
+use aurobot::cli::{Cli, Command};
+use aurobot::telemetry;
+use clap::{CommandFactory, Parser};
+use clap::error::ErrorKind;
+use serenity::all::GuildId;
+use std::io::BufRead;
 
 #[tokio::main]
 async fn main() {
+    // 명령줄 옵션이 없으면 .env / 환경 변수 값을 사용
     dotenv::dotenv().ok();
-    
-    let token = std::env::var("DISCORD_TOKEN")
-        .expect("DISCORD_TOKEN이 .env 파일에 설정되어야 합니다");
-
-    let intents = GatewayIntents::GUILDS 
-        | GatewayIntents::GUILD_VOICE_STATES;
+    let cli = Cli::parse();
+    if let Err(message) = cli.validate() {
+        Cli::command().error(ErrorKind::ValueValidation, message).exit();
+    }
+    let _telemetry = telemetry::init(cli.log_level);
 
-    let mut client = Client::builder(&token, intents)
-        .event_handler(VoiceHandler)
-        .type_map_insert::<ChannelActivityTracker>(Arc::new(RwLock::new(HashMap::new())))
-        .await
-        .expect("클라이언트 생성 실패");
+    match cli.command.clone() {
+        Some(Command::Calc { expression }) => run_calc(&expression),
+        Some(Command::ValidateConfig) => app::validate_config(&cli),
+        Some(Command::RegisterCommands { guild }) => app::register_commands(&cli, guild.map(GuildId::new)).await,
+        // `cargo run -- repl` / `--repl`: 디스코드 없이 계산기만 테스트
+        Some(Command::Repl) => run_repl(),
+        None | Some(Command::Run) if cli.repl => run_repl(),
+        None | Some(Command::Run) => app::run_bot(cli).await,
+    }
+}
 
-    println!("봇을 시작합니다...");
+// `aurobot calc "<식>"`: 결과는 표준 출력, 오류는 표준 오류로 내고 종료 코드 1
+fn run_calc(expression: &str) {
+    match calc::evaluate(expression) {
+        Ok(v) => println!("{}", v),
+        Err(e) => {
+            eprintln!("오류: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
 
-    if let Err(why) = client.start().await {
-        println!("클라이언트 에러: {:?}", why);
+// 표준 입력에서 한 줄씩 읽어 계산 결과를 출력 (EOF까지 반복)
+fn run_repl() {
+    let stdin = std::io::stdin();
+    // 직전 결과 (ans, _)
+    let mut session = UserCalcSession::default();
+    for line in stdin.lock().lines() {
+        let line = match line {
+            Ok(line) => line,
+            Err(_) => break,
+        };
+        let expr = line.trim();
+        if expr.is_empty() {
+            continue;
+        }
+        match session.evaluate(expr) {
+            Ok(v) => println!("{}", v),
+            Err(e) => println!("오류: {}", e),
+        }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,299 @@
+use serenity::all::ButtonStyle;
+use serenity::all::CommandInteraction;
+use serenity::all::ComponentInteraction;
+use serenity::all::CreateActionRow;
+use serenity::all::CreateAllowedMentions;
+use serenity::all::CreateButton;
+use serenity::all::CreateEmbed;
+use serenity::all::CreateInteractionResponse;
+use serenity::all::CreateInteractionResponseMessage;
+use serenity::all::CreateMessage;
+use serenity::all::EditInteractionResponse;
+use serenity::prelude::*;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+
+use crate::commands::respond;
+use crate::config::{modified_time, ConfigFile, FileConfig, HOT_RELOAD_KEYS};
+use crate::dry_run::{self, GlobalDryRun};
+use crate::error::{report_bot_error, require, BotError, ErrorContext};
+use crate::error_report::{report_error, ErrorReportState, ErrorReporter};
+use crate::event_metrics;
+use crate::config_check::get_usable_config;
+use crate::presence::{PresenceConfig, PresenceSettings};
+use crate::rate_limit::{RateLimitState, RateLimiter};
+use crate::shards::ShardManagerKey;
+
+pub const ANNOUNCE_PREFIX: &str = "announce:";
+
+// 확인 버튼을 누르지 않은 공지는 이 시간이 지나면 폐기
+const ANNOUNCE_TTL: Duration = Duration::from_secs(15 * 60);
+
+// 확인 대기 중인 공지: 커맨드 인터랙션 ID -> (내용, 작성 시각)
+pub struct PendingAnnouncements;
+
+impl TypeMapKey for PendingAnnouncements {
+    type Value = Arc<Mutex<HashMap<u64, (String, Instant)>>>;
+}
+
+pub fn new_announcement_store() -> Arc<Mutex<HashMap<u64, (String, Instant)>>> {
+    Arc::new(Mutex::new(HashMap::new()))
+}
+
+async fn reply(ctx: &Context, cmd: &CommandInteraction, content: String) {
+    respond(
+        ctx,
+        cmd,
+        CreateInteractionResponseMessage::new()
+            .content(content)
+            .ephemeral(true),
+    )
+    .await;
+}
+
+// /shutdown: 모든 샤드 연결을 닫음. main은 start_autosharded가 끝나면 나머지 작업을 정리하고 종료
+pub async fn handle_shutdown(ctx: &Context, cmd: &CommandInteraction) {
+    let shard_manager = {
+        let data = ctx.data.read().await;
+        data.get::<ShardManagerKey>().cloned()
+    };
+    let Some(shard_manager) = shard_manager else {
+        reply(ctx, cmd, "샤드 관리자를 찾을 수 없어 종료하지 못했습니다.".to_string()).await;
+        return;
+    };
+    reply(ctx, cmd, "👋 봇을 종료합니다.".to_string()).await;
+    tracing::info!("{} 님의 요청으로 봇을 종료합니다", cmd.user.name);
+    shard_manager.shutdown_all().await;
+}
+
+// /reloadconfig: 설정 파일을 다시 읽어 바로 적용할 수 있는 값은 적용하고, 나머지는 재시작이 필요하다고 안내
+pub async fn handle_reloadconfig(ctx: &Context, cmd: &CommandInteraction) {
+    let (path, changed) = match reload_config(ctx).await {
+        Ok(reloaded) => reloaded,
+        Err(BotError::Config(message)) => {
+            reply(ctx, cmd, message).await;
+            return;
+        }
+        Err(e) => {
+            report_bot_error(ctx, ErrorContext::new("설정 다시 읽기"), &e).await;
+            reply(ctx, cmd, "설정을 다시 읽지 못했습니다.".to_string()).await;
+            return;
+        }
+    };
+
+    let (applied, restart): (Vec<&String>, Vec<&String>) = changed
+        .iter()
+        .partition(|key| HOT_RELOAD_KEYS.contains(&key.as_str()));
+    let list = |keys: &[&String]| {
+        if keys.is_empty() {
+            "없음".to_string()
+        } else {
+            keys.iter().map(|k| format!("`{}`", k)).collect::<Vec<_>>().join(", ")
+        }
+    };
+    let embed = CreateEmbed::new()
+        .title("🔄 설정을 다시 읽었습니다")
+        .description(format!("`{}`", path.display()))
+        .field("바로 적용됨", list(&applied), false)
+        .field("재시작 후 적용", list(&restart), false);
+    respond(
+        ctx,
+        cmd,
+        CreateInteractionResponseMessage::new()
+            .embed(embed)
+            .ephemeral(true),
+    )
+    .await;
+}
+
+// 설정 파일을 다시 읽어 TypeMap의 설정 값들을 교체. 바뀐 키 목록을 돌려줌
+async fn reload_config(ctx: &Context) -> Result<(PathBuf, Vec<String>), BotError> {
+    let loaded = require::<ConfigFile>(ctx).await?;
+    let path = loaded.read().await.path.clone().ok_or_else(|| {
+        BotError::Config(
+            "설정 파일 없이 실행 중입니다. `--config-path` 또는 `AUROBOT_CONFIG` 로 지정한 경우에만 다시 읽을 수 있습니다."
+                .to_string(),
+        )
+    })?;
+    let modified = modified_time(&path);
+    let file = FileConfig::load(&path).map_err(|e| BotError::Config(format!("설정 파일을 읽지 못했습니다: {}", e)))?;
+    let unknown = file.unknown_keys();
+    if !unknown.is_empty() {
+        return Err(BotError::Config(format!(
+            "알 수 없는 설정 키가 있어 적용하지 않았습니다: {}",
+            unknown.join(", ")
+        )));
+    }
+
+    let changed = {
+        let mut loaded = loaded.write().await;
+        loaded.modified = modified;
+        let changed = loaded.file.changed_keys(&file).into_iter().map(str::to_string).collect();
+        loaded.file = file.clone();
+        changed
+    };
+    {
+        let mut data = ctx.data.write().await;
+        data.insert::<PresenceSettings>(Arc::new(PresenceConfig::from_config(&file)));
+        data.insert::<RateLimiter>(Arc::new(RateLimitState::from_config(&file)));
+        data.insert::<ErrorReporter>(Arc::new(ErrorReportState::from_config(&file)));
+        data.insert::<GlobalDryRun>(dry_run::from_config(&file));
+    }
+    event_metrics::apply_config(&file);
+    Ok((path, changed))
+}
+
+// 주기 작업: 설정 파일이 바뀌었으면 다시 읽음. 잘못된 파일이면 기록만 하고 이전 설정을 유지
+pub async fn watch_config_file(ctx: Context) {
+    let Ok(loaded) = require::<ConfigFile>(&ctx).await else {
+        return;
+    };
+    // 잘못된 파일이어도 시각은 먼저 기록해 같은 파일을 반복해서 읽지 않음
+    let path = {
+        let mut loaded = loaded.write().await;
+        let Some(path) = loaded.path.clone() else {
+            return;
+        };
+        let modified = modified_time(&path);
+        if modified.is_none() || modified == loaded.modified {
+            return;
+        }
+        loaded.modified = modified;
+        path
+    };
+
+    match reload_config(&ctx).await {
+        Ok((_, changed)) if changed.is_empty() => {}
+        Ok((path, changed)) => {
+            let (applied, restart): (Vec<String>, Vec<String>) = changed
+                .into_iter()
+                .partition(|key| HOT_RELOAD_KEYS.contains(&key.as_str()));
+            tracing::info!("설정 파일이 바뀌어 다시 읽었습니다: {}", path.display());
+            if !applied.is_empty() {
+                tracing::info!("  바로 적용됨: {}", applied.join(", "));
+            }
+            if !restart.is_empty() {
+                tracing::info!("  재시작 후 적용: {}", restart.join(", "));
+            }
+        }
+        Err(e) => {
+            tracing::warn!("설정 파일 변경을 적용하지 않았습니다 ({}): {}", path.display(), e);
+        }
+    }
+}
+
+// /announce <text>: 확인 버튼을 누르면 모든 서버의 알림 채널에 전송
+pub async fn handle_announce(ctx: &Context, cmd: &CommandInteraction) {
+    let Some(text) = cmd
+        .data
+        .options
+        .iter()
+        .find(|o| o.name == "text")
+        .and_then(|o| o.value.as_str())
+    else {
+        return;
+    };
+    let store = match require::<PendingAnnouncements>(ctx).await {
+        Ok(store) => store,
+        Err(e) => {
+            report_bot_error(ctx, ErrorContext::new("/announce"), &e).await;
+            reply(ctx, cmd, "공지를 준비하지 못했습니다.".to_string()).await;
+            return;
+        }
+    };
+    {
+        let mut store = store.lock().await;
+        let now = Instant::now();
+        store.retain(|_, (_, saved)| now.duration_since(*saved) < ANNOUNCE_TTL);
+        store.insert(cmd.id.get(), (text.to_string(), now));
+    }
+
+    let targets = ctx.cache.guilds().len();
+    let buttons = CreateActionRow::Buttons(vec![
+        CreateButton::new(format!("{}send:{}", ANNOUNCE_PREFIX, cmd.id))
+            .label("전송")
+            .style(ButtonStyle::Danger),
+        CreateButton::new(format!("{}cancel:{}", ANNOUNCE_PREFIX, cmd.id))
+            .label("취소")
+            .style(ButtonStyle::Secondary),
+    ]);
+    respond(
+        ctx,
+        cmd,
+        CreateInteractionResponseMessage::new()
+            .content(format!(
+                "아래 공지를 알림 채널이 설정된 서버에 전송할까요? (참가한 서버 {}개)\n>>> {}",
+                targets, text
+            ))
+            .components(vec![buttons])
+            .ephemeral(true),
+    )
+    .await;
+}
+
+// announce: 로 시작하는 버튼 처리 (소유자 확인은 dispatch_component에서)
+pub async fn handle_announce_component(ctx: &Context, comp: &ComponentInteraction) {
+    let Some(rest) = comp.data.custom_id.strip_prefix(ANNOUNCE_PREFIX) else {
+        return;
+    };
+    let Some((action, id)) = rest.split_once(':') else {
+        return;
+    };
+    let Ok(id) = id.parse::<u64>() else {
+        return;
+    };
+    let store = match require::<PendingAnnouncements>(ctx).await {
+        Ok(store) => store,
+        Err(e) => {
+            report_bot_error(ctx, ErrorContext::new("공지 버튼"), &e).await;
+            return;
+        }
+    };
+    let text = store.lock().await.remove(&id).map(|(text, _)| text);
+
+    let update = |content: String| {
+        CreateInteractionResponse::UpdateMessage(
+            CreateInteractionResponseMessage::new()
+                .content(content)
+                .components(Vec::new()),
+        )
+    };
+    let (response, text) = match (action, text) {
+        ("send", Some(text)) => (update("📢 공지를 전송하는 중입니다…".to_string()), text),
+        ("cancel", _) => (update("공지를 취소했습니다.".to_string()), String::new()),
+        _ => (update("만료되었거나 이미 처리된 공지입니다.".to_string()), String::new()),
+    };
+    if let Err(e) = comp.create_response(&ctx.http, response).await {
+        report_error(ctx, "공지 버튼 응답", &e).await;
+    }
+    if text.is_empty() {
+        return;
+    }
+
+    let (mut sent, mut failed) = (0, 0);
+    for guild_id in ctx.cache.guilds() {
+        let Some(channel_id) = get_usable_config(ctx, guild_id).await.notification_channel else {
+            continue;
+        };
+        let message = CreateMessage::new()
+            .content(format!("📢 {}", text))
+            .allowed_mentions(CreateAllowedMentions::new());
+        match channel_id.send_message(&ctx.http, message).await {
+            Ok(_) => sent += 1,
+            Err(e) => {
+                failed += 1;
+                report_error(ctx, &format!("공지 전송 ({})", guild_id), &e).await;
+            }
+        }
+    }
+    let result = format!("📢 공지를 전송했습니다. 성공 {}개 · 실패 {}개", sent, failed);
+    if let Err(e) = comp
+        .edit_response(&ctx.http, EditInteractionResponse::new().content(result))
+        .await
+    {
+        report_error(ctx, "공지 결과 표시", &e).await;
+    }
+}
@@ -0,0 +1,134 @@
+use serenity::all::CreateAllowedMentions;
+use serenity::all::CreateMessage;
+use serenity::all::Message;
+use serenity::all::UserId;
+use serenity::prelude::*;
+
+use crate::calc_cache::evaluate_cached;
+use crate::calc_session::get_session;
+use crate::commands::{calchelp_embed, validate_text_input, MAX_EXPRESSION_LEN};
+use crate::error_report::report_error;
+use crate::guild_config::{check_command_channel, get_guild_config};
+use crate::long_message::{truncate, MESSAGE_LIMIT};
+use crate::rate_limit::RateLimiter;
+use crate::usage;
+
+// 메시지가 봇 멘션(<@id> 또는 <@!id>)으로 시작하는지
+pub fn is_bot_mention(msg: &Message, bot_id: UserId) -> bool {
+    strip_bot_mention(&msg.content, bot_id).is_some()
+}
+
+// 앞의 봇 멘션을 떼어낸 나머지
+fn strip_bot_mention(content: &str, bot_id: UserId) -> Option<&str> {
+    let content = content.trim_start();
+    [format!("<@{}>", bot_id), format!("<@!{}>", bot_id)]
+        .iter()
+        .find_map(|mention| content.strip_prefix(mention.as_str()))
+        .map(str::trim)
+}
+
+// 멘션 뒤의 "calc 2+3"을 (소문자 커맨드 이름, 인자)로. 이름 앞의 /는 무시
+fn parse_command(rest: &str) -> (String, &str) {
+    let (name, args) = match rest.split_once(char::is_whitespace) {
+        Some((name, args)) => (name, args.trim()),
+        None => (rest, ""),
+    };
+    (name.trim_start_matches('/').to_lowercase(), args)
+}
+
+// `@봇 calc 2+3` 같은 멘션 명령 처리. 슬래시 커맨드와 같은 결과를 답장으로 보냄
+pub async fn handle_bot_mention(ctx: &Context, msg: &Message) {
+    if msg.author.bot {
+        return;
+    }
+    let bot_id = ctx.cache.current_user().id;
+    let Some(rest) = strip_bot_mention(&msg.content, bot_id) else {
+        return;
+    };
+    let (name, args) = parse_command(rest);
+
+    // /calc와 같은 채널 제한 (허용되지 않은 채널이면 무시)
+    if let Some(guild_id) = msg.guild_id {
+        let config = get_guild_config(ctx, guild_id).await;
+        let parent_id = ctx
+            .cache
+            .guild(guild_id)
+            .and_then(|g| g.threads.iter().find(|t| t.id == msg.channel_id).and_then(|t| t.parent_id));
+        if check_command_channel(&config, msg.channel_id, parent_id).is_err() || config.is_disabled(&name) {
+            return;
+        }
+    }
+
+    // 슬래시 커맨드와 같은 실행 빈도 제한 (초과하면 조용히 무시)
+    let limiter = {
+        let data = ctx.data.read().await;
+        data.get::<RateLimiter>().cloned()
+    };
+    if let Some(limiter) = limiter
+        && limiter.check(msg.author.id, &name).await.is_err()
+    {
+        return;
+    }
+
+    let reply = match name.as_str() {
+        "calc" if !args.is_empty() => {
+            let content = match validate_text_input(args, MAX_EXPRESSION_LEN) {
+                Err(message) => message,
+                Ok(()) => {
+                    let mode = get_session(ctx, msg.author.id).await.mode;
+                    match evaluate_cached(ctx, msg.author.id, args, mode).await {
+                        Ok(v) => format!("{} = {}", args, v),
+                        Err(e) => {
+                            usage::record_calc_error(ctx, msg.guild_id, &e).await;
+                            format!("{} -> 오류: {}", args, e)
+                        }
+                    }
+                }
+            };
+            CreateMessage::new().content(truncate(&content, MESSAGE_LIMIT))
+        }
+        "calchelp" => CreateMessage::new().embed(calchelp_embed()),
+        _ => CreateMessage::new().content(
+            "슬래시 커맨드를 사용해주세요. 예: `/calc 2+3`, `/voicestats`\n멘션으로는 `calc <수식>`, `calchelp` 만 사용할 수 있습니다.",
+        ),
+    };
+    let reply = reply
+        .reference_message(msg)
+        .allowed_mentions(CreateAllowedMentions::new());
+    if let Err(e) = msg.channel_id.send_message(&ctx.http, reply).await {
+        report_error(ctx, "멘션 명령 응답", &e).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BOT: UserId = UserId::new(42);
+
+    // <@id>와 닉네임 멘션 <@!id> 모두 떼어내고 앞뒤 공백 제거
+    #[test]
+    fn strips_both_mention_forms() {
+        assert_eq!(strip_bot_mention("<@42> calc 2+3", BOT), Some("calc 2+3"));
+        assert_eq!(strip_bot_mention("<@!42>   calchelp  ", BOT), Some("calchelp"));
+        assert_eq!(strip_bot_mention("  <@42>calc 1", BOT), Some("calc 1"));
+        assert_eq!(strip_bot_mention("<@42>", BOT), Some(""));
+    }
+
+    // 다른 사용자 멘션이나 중간에 있는 멘션은 명령이 아님
+    #[test]
+    fn ignores_other_mentions() {
+        assert_eq!(strip_bot_mention("<@43> calc 2+3", BOT), None);
+        assert_eq!(strip_bot_mention("<@420> calc 2+3", BOT), None);
+        assert_eq!(strip_bot_mention("hi <@42> calc 2+3", BOT), None);
+        assert_eq!(strip_bot_mention("calc 2+3", BOT), None);
+    }
+
+    #[test]
+    fn parses_name_and_arguments() {
+        assert_eq!(parse_command("calc 2 + 3"), ("calc".to_string(), "2 + 3"));
+        assert_eq!(parse_command("/CALC  1/0 "), ("calc".to_string(), "1/0"));
+        assert_eq!(parse_command("calchelp"), ("calchelp".to_string(), ""));
+        assert_eq!(parse_command(""), (String::new(), ""));
+    }
+}
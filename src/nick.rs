@@ -0,0 +1,96 @@
+use serenity::all::CommandInteraction;
+use serenity::all::CreateInteractionResponseMessage;
+use serenity::all::EditMember;
+use serenity::prelude::*;
+
+use crate::commands::respond;
+use crate::error_report::report_error;
+use crate::guild_config::{get_guild_config, post_audit_log};
+
+// 디스코드 닉네임 최대 길이 (문자 수)
+pub const MAX_NICK_LEN: u16 = 32;
+
+// 닉네임 검사: 앞뒤 공백을 뗀 값을 돌려주거나, 문제가 있으면 사용자에게 보여줄 문구.
+// 목록 맨 위로 올라가도록 특수 문자로 시작하는 이름과 전체 멘션/코드 블록은 거부
+fn validate_nickname(name: &str) -> Result<&str, String> {
+    let name = name.trim();
+    let Some(first) = name.chars().next() else {
+        return Err("닉네임을 입력하세요.".to_string());
+    };
+    if name.chars().count() > usize::from(MAX_NICK_LEN) {
+        return Err(format!("닉네임은 {}자까지 입력할 수 있습니다.", MAX_NICK_LEN));
+    }
+    if name.chars().any(char::is_control) {
+        return Err("닉네임에 제어 문자를 넣을 수 없습니다.".to_string());
+    }
+    if !first.is_alphanumeric() {
+        return Err("닉네임은 글자나 숫자로 시작해야 합니다.".to_string());
+    }
+    if name.contains("@everyone") || name.contains("@here") || name.contains("```") {
+        return Err("닉네임에 @everyone, @here, ``` 를 넣을 수 없습니다.".to_string());
+    }
+    Ok(name)
+}
+
+// /nick <name> [user]: 서버가 allow_self_nick을 켜 두면 자신의 닉네임을, 닉네임 관리 권한이 있으면 다른 멤버의 닉네임도 변경
+pub async fn handle_nick(ctx: &Context, cmd: &CommandInteraction) {
+    let Some(guild_id) = cmd.guild_id else {
+        return;
+    };
+    let name = cmd
+        .data
+        .options
+        .iter()
+        .find(|o| o.name == "name")
+        .and_then(|o| o.value.as_str())
+        .unwrap_or_default();
+    let target = cmd
+        .data
+        .options
+        .iter()
+        .find(|o| o.name == "user")
+        .and_then(|o| o.value.as_user_id())
+        .unwrap_or(cmd.user.id);
+
+    let can_manage = cmd
+        .member
+        .as_ref()
+        .and_then(|m| m.permissions)
+        .is_some_and(|p| p.administrator() || p.manage_nicknames());
+    let content = if target != cmd.user.id && !can_manage {
+        "다른 멤버의 닉네임은 닉네임 관리 권한이 있어야 바꿀 수 있습니다.".to_string()
+    } else if !can_manage && !get_guild_config(ctx, guild_id).await.allow_self_nick {
+        "이 서버에서는 봇으로 닉네임을 바꿀 수 없습니다. (관리자가 allow_self_nick을 켜야 합니다)".to_string()
+    } else {
+        match validate_nickname(name) {
+            Err(message) => message,
+            Ok(name) => match guild_id
+                .edit_member(&ctx.http, target, EditMember::new().nickname(name))
+                .await
+            {
+                Ok(_) => {
+                    post_audit_log(
+                        ctx,
+                        guild_id,
+                        format!("🏷️ <@{}> 님이 <@{}> 의 닉네임을 **{}** 으로 변경했습니다.", cmd.user.id, target, name),
+                    )
+                    .await;
+                    format!("<@{}> 의 닉네임을 **{}** 으로 변경했습니다.", target, name)
+                }
+                Err(e) => {
+                    report_error(ctx, "닉네임 변경", &e).await;
+                    "닉네임을 바꾸지 못했습니다. 봇에 닉네임 관리 권한이 있고, 봇의 역할이 대상보다 위에 있는지 확인해주세요. (서버 소유자의 닉네임은 바꿀 수 없습니다)".to_string()
+                }
+            },
+        }
+    };
+
+    respond(
+        ctx,
+        cmd,
+        CreateInteractionResponseMessage::new()
+            .content(content)
+            .ephemeral(true),
+    )
+    .await;
+}
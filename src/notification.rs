@@ -0,0 +1,647 @@
+use serenity::all::ChannelId;
+use serenity::all::CreateEmbed;
+use serenity::all::CreateMessage;
+use serenity::all::GuildId;
+use serenity::all::UserId;
+use serenity::prelude::*;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Arc;
+use tokio::sync::{mpsc, Mutex};
+use tracing::Instrument;
+
+use crate::dry_run;
+use crate::error_report::send_or_report;
+use crate::guild_config::{get_guild_config, MentionTarget};
+use crate::long_message::{truncate, MESSAGE_LIMIT};
+use crate::scheduled_events::LiveEvent;
+use crate::voice_tracker::format_duration;
+
+// 알림 임베드 색상
+pub const COLOUR_ACTIVATE: u32 = 0x2ecc71;
+pub const COLOUR_JOIN: u32 = 0x3498db;
+pub const COLOUR_LEAVE: u32 = 0x95a5a6;
+
+// 메시지 하나에 넣을 최대 멘션 수 (디스코드 allowed_mentions의 역할/사용자 목록 제한)
+const MAX_MENTIONS_PER_MESSAGE: usize = 100;
+
+// 보낼 알림 내용. 만드는 함수는 디스코드 API를 쓰지 않고, 전송은 send_notification이 담당
+#[derive(Debug, Clone)]
+pub enum NotificationMessage {
+    PlainText(String),
+    Embed(CreateEmbed),
+    // 역할 멘션은 임베드 안에서는 알림이 가지 않으므로 본문에 따로 담음
+    EmbedWithText { text: String, embed: CreateEmbed },
+}
+
+impl NotificationMessage {
+    pub fn into_message(self) -> CreateMessage {
+        match self {
+            NotificationMessage::PlainText(text) => CreateMessage::new().content(text),
+            NotificationMessage::Embed(embed) => CreateMessage::new().embed(embed),
+            NotificationMessage::EmbedWithText { text, embed } => {
+                CreateMessage::new().content(text).embed(embed)
+            }
+        }
+    }
+
+    // 임베드를 끈 서버(use_embeds)용 일반 텍스트. 임베드의 본문과 필드를 줄로 이어 붙임
+    pub fn into_plain_text(self) -> NotificationMessage {
+        let embed_text = |embed: &CreateEmbed| {
+            let Ok(value) = serde_json::to_value(embed) else {
+                return String::new();
+            };
+            let mut lines: Vec<String> = value
+                .get("description")
+                .and_then(|d| d.as_str())
+                .map(str::to_string)
+                .into_iter()
+                .collect();
+            for field in value.get("fields").and_then(|f| f.as_array()).into_iter().flatten() {
+                let name = field.get("name").and_then(|n| n.as_str()).unwrap_or_default();
+                let value = field.get("value").and_then(|v| v.as_str()).unwrap_or_default();
+                lines.push(format!("**{}**: {}", name, value));
+            }
+            lines.join("\n")
+        };
+        let text = match self {
+            NotificationMessage::PlainText(text) => text,
+            NotificationMessage::Embed(embed) => embed_text(&embed),
+            NotificationMessage::EmbedWithText { text, embed } => format!("{}\n{}", text, embed_text(&embed)),
+        };
+        NotificationMessage::PlainText(truncate(&text, MESSAGE_LIMIT))
+    }
+
+    // 드라이런 로그에 남길 내용 (임베드는 본문만)
+    pub fn preview(&self) -> String {
+        let embed_text = |embed: &CreateEmbed| {
+            serde_json::to_value(embed)
+                .ok()
+                .and_then(|v| v.get("description").and_then(|d| d.as_str()).map(str::to_string))
+                .unwrap_or_default()
+        };
+        match self {
+            NotificationMessage::PlainText(text) => text.clone(),
+            NotificationMessage::Embed(embed) => embed_text(embed),
+            NotificationMessage::EmbedWithText { text, embed } => format!("{} {}", text, embed_text(embed)),
+        }
+    }
+}
+
+// 서버가 바꿀 수 있는 알림 문구 (/voiceconfig message). 설정하지 않으면 기본 문구
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TemplateKind {
+    Join,
+    Leave,
+    Activate,
+    Deactivate,
+}
+
+impl TemplateKind {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "join" => Some(Self::Join),
+            "leave" => Some(Self::Leave),
+            "activate" => Some(Self::Activate),
+            "deactivate" => Some(Self::Deactivate),
+            _ => None,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Join => "입장 알림",
+            Self::Leave => "퇴장 알림",
+            Self::Activate => "활성화 알림",
+            Self::Deactivate => "비활성화 알림",
+        }
+    }
+
+    // 이 알림에서 쓸 수 있는 치환 토큰
+    pub fn tokens(self) -> &'static [&'static str] {
+        match self {
+            Self::Join | Self::Leave | Self::Activate => &["user", "channel", "count"],
+            Self::Deactivate => &["channel", "duration"],
+        }
+    }
+}
+
+// 템플릿의 {이름}을 vars 값으로 바꿈. 모르는 이름이나 닫히지 않은 중괄호는 그대로 둠
+pub fn apply_template(template: &str, vars: &HashMap<&str, &str>) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        match after.find('}').and_then(|end| vars.get(&after[..end]).map(|value| (end, value))) {
+            Some((end, value)) => {
+                out.push_str(value);
+                rest = &after[end + 1..];
+            }
+            None => {
+                out.push('{');
+                rest = after;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+// 템플릿에 들어 있는 {이름} 중 이 알림에서 쓸 수 없는 것 (설정할 때 오타 확인용)
+pub fn unknown_tokens(template: &str, kind: TemplateKind) -> Vec<String> {
+    let mut unknown = Vec::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let after = &rest[start + 1..];
+        let Some(end) = after.find('}') else {
+            break;
+        };
+        let name = &after[..end];
+        if !kind.tokens().contains(&name) && !unknown.iter().any(|u| u == name) {
+            unknown.push(name.to_string());
+        }
+        rest = &after[end + 1..];
+    }
+    unknown
+}
+
+// 알림 임베드에 표시할 보이스 채널 부가 정보
+#[derive(Debug, Default, PartialEq)]
+pub struct ChannelDetails {
+    pub topic: Option<String>,
+    pub user_limit: Option<u32>,
+}
+
+impl ChannelDetails {
+    // 공백뿐인 주제와 0(제한 없음)인 정원은 설정되지 않은 것으로
+    pub fn new(topic: Option<&str>, user_limit: Option<u32>) -> Self {
+        Self {
+            topic: topic.filter(|t| !t.trim().is_empty()).map(str::to_string),
+            user_limit: user_limit.filter(|&limit| limit > 0),
+        }
+    }
+}
+
+// 입장/퇴장/활성화 알림 임베드. 주제와 정원 필드는 채널에 설정된 경우에만 추가
+fn notification_embed(
+    description: String,
+    colour: u32,
+    details: &ChannelDetails,
+    member_count: usize,
+) -> CreateEmbed {
+    let mut embed = CreateEmbed::new().description(description).colour(colour);
+    if let Some(topic) = &details.topic {
+        embed = embed.field("주제", topic, false);
+    }
+    if let Some(limit) = details.user_limit {
+        embed = embed.field("정원", format!("{}/{}", member_count, limit), true);
+    }
+    embed
+}
+
+// 멘션을 중복 없이 이어 붙이고, 메시지 길이와 멘션 수 제한을 넘으면 여러 덩어리로 나눔
+pub fn mention_chunks(targets: &[MentionTarget]) -> Vec<String> {
+    let mut seen = Vec::new();
+    let mut chunks: Vec<String> = Vec::new();
+    let mut count = 0;
+    for target in targets {
+        if seen.contains(target) {
+            continue;
+        }
+        seen.push(*target);
+        let mention = target.mention();
+        match chunks.last_mut() {
+            Some(chunk) if count < MAX_MENTIONS_PER_MESSAGE && chunk.len() + 1 + mention.len() <= MESSAGE_LIMIT => {
+                chunk.push(' ');
+                chunk.push_str(&mention);
+                count += 1;
+            }
+            _ => {
+                chunks.push(mention);
+                count = 1;
+            }
+        }
+    }
+    chunks
+}
+
+// 채널 활성화 알림. 채널에서 예약 이벤트가 진행 중이면 이벤트 링크를 붙임. 멘션은 첫 메시지 본문에 넣고, 한 메시지에 다 들어가지 않으면 이어서 따로 보냄
+pub fn activated(
+    template: Option<&str>,
+    user_name: &str,
+    channel_name: &str,
+    details: &ChannelDetails,
+    members: usize,
+    mentions: &[MentionTarget],
+    event: Option<&LiveEvent>,
+) -> Vec<NotificationMessage> {
+    let mut description = match template {
+        Some(template) => apply_template(
+            template,
+            &HashMap::from([("user", user_name), ("channel", channel_name), ("count", &members.to_string())]),
+        ),
+        None => format!("🟢 **#{}** 방이 활성화되었습니다.", channel_name),
+    };
+    if let Some(event) = event {
+        description.push_str(&format!(" — 이벤트: [{}]({})", event.name, event.url));
+    }
+    let embed = notification_embed(description, COLOUR_ACTIVATE, details, members);
+    let mut chunks = mention_chunks(mentions).into_iter();
+    let first = match chunks.next() {
+        Some(text) => NotificationMessage::EmbedWithText { text, embed },
+        None => NotificationMessage::Embed(embed),
+    };
+    std::iter::once(first).chain(chunks.map(NotificationMessage::PlainText)).collect()
+}
+
+// 입장/퇴장 템플릿 치환 값
+fn member_vars<'a>(user_name: &'a str, channel_name: &'a str, count: &'a str) -> HashMap<&'static str, &'a str> {
+    HashMap::from([("user", user_name), ("channel", channel_name), ("count", count)])
+}
+
+pub fn join_line(template: Option<&str>, user_name: &str, channel_name: &str, members: usize) -> String {
+    match template {
+        Some(template) => apply_template(template, &member_vars(user_name, channel_name, &members.to_string())),
+        None => format!("➡️ {} 님이 **#{}** 에 입장했습니다.", user_name, channel_name),
+    }
+}
+
+pub fn leave_line(template: Option<&str>, user_name: &str, channel_name: &str, members: usize) -> String {
+    match template {
+        Some(template) => apply_template(template, &member_vars(user_name, channel_name, &members.to_string())),
+        None => format!("⬅️ {} 님이 **#{}** 방에서 퇴장했습니다.", user_name, channel_name),
+    }
+}
+
+// 입장 알림 하나 (line은 join_line으로 만든 문구)
+pub fn joined(line: String, details: &ChannelDetails, members: usize) -> NotificationMessage {
+    NotificationMessage::Embed(notification_embed(line, COLOUR_JOIN, details, members))
+}
+
+pub fn left(line: String, details: &ChannelDetails, members: usize) -> NotificationMessage {
+    NotificationMessage::Embed(notification_embed(line, COLOUR_LEAVE, details, members))
+}
+
+// 채널 비활성화 알림. recovered: 연결이 끊긴 동안 채널이 비어 재연결 후 정리한 경우
+pub fn deactivated(
+    template: Option<&str>,
+    channel_name: &str,
+    duration_secs: u64,
+    recovered: bool,
+    starter_name: Option<&str>,
+) -> NotificationMessage {
+    let mut suffix = if recovered { " (연결 복구 중 종료됨)" } else { "" }.to_string();
+    // 멘션하면 시작한 사람에게 알림이 가므로 이름으로만 표시
+    if let Some(name) = starter_name {
+        suffix.push_str(&format!("\n시작한 사람: {}", name));
+    }
+    let duration = format_duration(duration_secs);
+    let text = match template {
+        Some(template) => apply_template(
+            template,
+            &HashMap::from([("channel", channel_name), ("duration", duration.as_str())]),
+        ),
+        None => format!("🔴 **#{}** 방이 비활성화되었습니다. 활성화 시간: {}", channel_name, duration),
+    };
+    NotificationMessage::PlainText(format!("{}{}", text, suffix))
+}
+
+// 혼자 스피커를 끈 채 오래 있던 멤버를 잠수 채널로 옮겼을 때 (알림 채널 또는 DM)
+pub fn moved_to_afk(user_id: UserId, from: ChannelId, minutes: u32) -> NotificationMessage {
+    NotificationMessage::PlainText(format!(
+        "💤 <@{}> 님이 <#{}> 채널에 혼자 스피커를 끈 채 {}분 넘게 있어 잠수 채널로 옮겼습니다",
+        user_id, from, minutes
+    ))
+}
+
+// 짧은 시간 동안 모인 입장/퇴장 알림을 한 임베드로
+pub fn batched(lines: &[&str], joins: usize, leaves: usize) -> NotificationMessage {
+    NotificationMessage::Embed(
+        CreateEmbed::new()
+            .description(format!("{}\n\n입장 {}명 · 퇴장 {}명", lines.join("\n"), joins, leaves))
+            .colour(if joins >= leaves { COLOUR_JOIN } else { COLOUR_LEAVE }),
+    )
+}
+
+// 새 길드에 참가했을 때 시스템 채널에 보내는 안내
+// 알림 전송 (실패하거나 시간이 초과되면 오류 보고). 서버가 임베드를 껐으면 일반 텍스트로, 드라이런 중이면 로그만 남김
+pub async fn send_notification(
+    ctx: &Context,
+    guild_id: GuildId,
+    channel_id: ChannelId,
+    message: NotificationMessage,
+    operation: &str,
+) {
+    let message = if get_guild_config(ctx, guild_id).await.use_embeds {
+        message
+    } else {
+        message.into_plain_text()
+    };
+    let detail = format!("<#{}> {}", channel_id, message.preview());
+    if dry_run::suppressed(ctx, Some(guild_id), operation, detail).await {
+        return;
+    }
+    send_or_report(ctx, channel_id, message.into_message(), operation).await;
+}
+
+// (채널, 보낼 알림들, 넣은 이벤트의 스팬)
+type NotificationJob = (ChannelId, Vec<(NotificationMessage, &'static str)>, tracing::Span);
+type NotificationSender = mpsc::UnboundedSender<NotificationJob>;
+
+// 길드별 알림 전송 대기열. 길드마다 작업 하나가 넣은 순서대로 전송하므로
+// 이벤트가 연달아 와도 같은 채널의 알림 순서가 바뀌지 않음
+pub struct NotificationQueues;
+
+impl TypeMapKey for NotificationQueues {
+    type Value = Arc<Mutex<HashMap<GuildId, NotificationSender>>>;
+}
+
+pub fn new_notification_queues() -> Arc<Mutex<HashMap<GuildId, NotificationSender>>> {
+    Arc::new(Mutex::new(HashMap::new()))
+}
+
+// 이벤트 처리를 막지 않도록 길드 대기열에 넣고 바로 반환
+pub async fn queue_send(
+    ctx: &Context,
+    guild_id: GuildId,
+    channel_id: ChannelId,
+    messages: Vec<(NotificationMessage, &'static str)>,
+) {
+    if messages.is_empty() {
+        return;
+    }
+    let queues = {
+        let data = ctx.data.read().await;
+        data.get::<NotificationQueues>().cloned()
+    };
+    let Some(queues) = queues else {
+        return;
+    };
+    // 전송도 이벤트 스팬 아래에 기록되도록 현재 스팬을 함께 넘김
+    let job = (channel_id, messages, tracing::Span::current());
+    let mut queues = queues.lock().await;
+    let sender = queues
+        .entry(guild_id)
+        .or_insert_with(|| spawn_worker(ctx.clone(), guild_id));
+    // 작업이 끝나 있으면(패닉 등) 새로 시작
+    if let Err(mpsc::error::SendError(job)) = sender.send(job) {
+        let sender = spawn_worker(ctx.clone(), guild_id);
+        let _ = sender.send(job);
+        queues.insert(guild_id, sender);
+    }
+}
+
+fn spawn_worker(ctx: Context, guild_id: GuildId) -> NotificationSender {
+    run_queue(move |channel_id, message, operation| {
+        let ctx = ctx.clone();
+        async move { send_notification(&ctx, guild_id, channel_id, message, operation).await }
+    })
+}
+
+// 받은 알림을 하나씩 전송하는 작업을 띄움. 앞 전송이 끝나야(또는 시간 초과) 다음 전송
+fn run_queue<F, Fut>(send: F) -> NotificationSender
+where
+    F: Fn(ChannelId, NotificationMessage, &'static str) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = ()> + Send,
+{
+    let (sender, mut receiver) = mpsc::unbounded_channel::<NotificationJob>();
+    tokio::spawn(async move {
+        while let Some((channel_id, messages, span)) = receiver.recv().await {
+            async {
+                for (message, operation) in messages {
+                    send(channel_id, message, operation).await;
+                }
+            }
+            .instrument(span)
+            .await;
+        }
+    });
+    sender
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notifier::{NotifyTarget, Notifier, RecordingNotifier};
+    use crate::voice_events::{transition, VoiceAction, VoiceEvent};
+    use std::time::Duration;
+
+    fn job(channel: u64, texts: &[&str]) -> NotificationJob {
+        let messages = texts
+            .iter()
+            .map(|text| (NotificationMessage::PlainText(text.to_string()), "테스트"))
+            .collect();
+        (ChannelId::new(channel), messages, tracing::Span::none())
+    }
+
+    fn text(message: &NotificationMessage) -> String {
+        match message {
+            NotificationMessage::PlainText(text) => text.clone(),
+            _ => unreachable!(),
+        }
+    }
+
+    // 첫 전송이 늦어져도 뒤에 넣은 알림이 앞지르지 않음
+    #[tokio::test(start_paused = true)]
+    async fn queue_keeps_order_when_send_is_slow() {
+        let sent = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let record = sent.clone();
+        let sender = run_queue(move |channel_id, message, _| {
+            let record = record.clone();
+            async move {
+                let text = text(&message);
+                if text == "입장" {
+                    tokio::time::sleep(Duration::from_secs(3)).await;
+                }
+                record.lock().unwrap().push((channel_id.get(), text));
+            }
+        });
+        sender.send(job(1, &["입장"])).unwrap();
+        sender.send(job(1, &["퇴장", "종료"])).unwrap();
+        tokio::time::sleep(Duration::from_secs(4)).await;
+        let sent = sent.lock().unwrap().clone();
+        assert_eq!(
+            sent,
+            vec![(1, "입장".to_string()), (1, "퇴장".to_string()), (1, "종료".to_string())]
+        );
+    }
+
+    // 전송이 멈춰 있어도 대기열에 넣는 쪽(이벤트 처리)은 기다리지 않음
+    #[tokio::test(start_paused = true)]
+    async fn hung_send_does_not_block_enqueue() {
+        let sender = run_queue(|_, _, _| std::future::pending::<()>());
+        let started = tokio::time::Instant::now();
+        for _ in 0..10 {
+            sender.send(job(1, &["입장"])).unwrap();
+        }
+        assert_eq!(started.elapsed(), Duration::ZERO);
+    }
+
+    // 한 길드의 전송이 멈춰도 다른 길드 대기열은 계속 전송
+    #[tokio::test(start_paused = true)]
+    async fn stuck_guild_does_not_delay_other_guild() {
+        let sent = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let stuck = run_queue(|_, _, _| std::future::pending::<()>());
+        let record = sent.clone();
+        let other = run_queue(move |_, message, _| {
+            let record = record.clone();
+            async move { record.lock().unwrap().push(text(&message)) }
+        });
+        stuck.send(job(1, &["입장"])).unwrap();
+        other.send(job(2, &["입장"])).unwrap();
+        tokio::time::sleep(Duration::from_millis(1)).await;
+        assert_eq!(*sent.lock().unwrap(), vec!["입장".to_string()]);
+    }
+
+    const ALICE: UserId = UserId::new(1);
+    const BOB: UserId = UserId::new(2);
+    const LOBBY: ChannelId = ChannelId::new(10);
+    const NOTIFY_CHANNEL: ChannelId = ChannelId::new(99);
+
+    // 보이스 이벤트를 실제 핸들러처럼 작업으로 바꾸고, 이벤트마다 만든 알림을 대기열을 거쳐
+    // 기록용 Notifier로 보냄. 이름과 채널 정보는 고정값 사용
+    async fn play_scenario(events: &[(u64, VoiceEvent)]) -> Vec<(NotifyTarget, String)> {
+        let notifier = Arc::new(RecordingNotifier::default());
+        let sink = notifier.clone();
+        let queue = run_queue(move |channel_id, message, _| {
+            let sink = sink.clone();
+            async move {
+                sink.send(NotifyTarget::Channel(channel_id), message.into_message())
+                    .await
+                    .unwrap();
+            }
+        });
+        let name = |user: UserId| if user == ALICE { "앨리스" } else { "밥" };
+        let details = ChannelDetails::default();
+        let start = std::time::Instant::now();
+        let mut sessions = HashMap::new();
+        for &(secs, event) in events {
+            let now = start + Duration::from_secs(secs);
+            let mut messages = Vec::new();
+            for action in transition(&mut sessions, event, now) {
+                match action {
+                    VoiceAction::AnnounceActivate { members, .. } => {
+                        messages.extend(activated(None, "앨리스", "로비", &details, members, &[], None));
+                    }
+                    VoiceAction::AnnounceJoin { user, members, .. } => {
+                        messages.push(joined(join_line(None, name(user), "로비", members), &details, members));
+                    }
+                    VoiceAction::AnnounceLeave { user, members, .. } => {
+                        messages.push(left(leave_line(None, name(user), "로비", members), &details, members));
+                    }
+                    VoiceAction::EndSession { duration, .. } => {
+                        messages.push(deactivated(None, "로비", duration.as_secs(), false, None));
+                    }
+                    _ => {}
+                }
+            }
+            let job = (NOTIFY_CHANNEL, messages.into_iter().map(|m| (m, "테스트")).collect(), tracing::Span::none());
+            queue.send(job).unwrap();
+        }
+        // 보낸 쪽을 닫고 대기열이 빌 때까지 기다림
+        drop(queue);
+        while Arc::strong_count(&notifier) > 1 {
+            tokio::task::yield_now().await;
+        }
+        notifier.sent()
+    }
+
+    fn texts(sent: &[(NotifyTarget, String)]) -> Vec<&str> {
+        sent.iter().map(|(_, text)| text.as_str()).collect()
+    }
+
+    // 첫 입장 → 두 번째 입장 → 모두 퇴장: 활성화, 입장 두 번, 퇴장 두 번, 비활성화 순서로 전송
+    #[tokio::test]
+    async fn first_join_second_join_everyone_leaves() {
+        let sent = play_scenario(&[
+            (0, VoiceEvent::Join { user: ALICE, channel: LOBBY, members: 1 }),
+            (60, VoiceEvent::Join { user: BOB, channel: LOBBY, members: 2 }),
+            (600, VoiceEvent::Leave { user: ALICE, channel: LOBBY, members: 1 }),
+            (900, VoiceEvent::Leave { user: BOB, channel: LOBBY, members: 0 }),
+        ])
+        .await;
+        assert!(sent.iter().all(|(target, _)| *target == NotifyTarget::Channel(NOTIFY_CHANNEL)));
+        assert_eq!(
+            texts(&sent),
+            vec![
+                "🟢 **#로비** 방이 활성화되었습니다.",
+                "➡️ 앨리스 님이 **#로비** 에 입장했습니다.",
+                "➡️ 밥 님이 **#로비** 에 입장했습니다.",
+                "⬅️ 앨리스 님이 **#로비** 방에서 퇴장했습니다.",
+                "⬅️ 밥 님이 **#로비** 방에서 퇴장했습니다.",
+                &format!("🔴 **#로비** 방이 비활성화되었습니다. 활성화 시간: {}", format_duration(900)),
+            ]
+        );
+    }
+
+    // 두 번째 입장은 이미 활성화된 채널이므로 활성화 알림을 다시 보내지 않음
+    #[tokio::test]
+    async fn second_join_does_not_reactivate() {
+        let sent = play_scenario(&[
+            (0, VoiceEvent::Join { user: ALICE, channel: LOBBY, members: 1 }),
+            (60, VoiceEvent::Join { user: BOB, channel: LOBBY, members: 2 }),
+        ])
+        .await;
+        let activations = texts(&sent).iter().filter(|text| text.contains("활성화되었습니다")).count();
+        assert_eq!(activations, 1);
+        assert_eq!(sent.len(), 3);
+    }
+
+    // 한 명만 나가면 채널은 계속 활성 상태
+    #[tokio::test]
+    async fn partial_leave_keeps_session() {
+        let sent = play_scenario(&[
+            (0, VoiceEvent::Join { user: ALICE, channel: LOBBY, members: 1 }),
+            (60, VoiceEvent::Join { user: BOB, channel: LOBBY, members: 2 }),
+            (120, VoiceEvent::Leave { user: BOB, channel: LOBBY, members: 1 }),
+        ])
+        .await;
+        assert!(!texts(&sent).iter().any(|text| text.contains("비활성화")));
+        assert_eq!(texts(&sent).last(), Some(&"⬅️ 밥 님이 **#로비** 방에서 퇴장했습니다."));
+    }
+
+    // 임베드의 (이름, 값) 필드 목록
+    fn embed_fields(embed: &CreateEmbed) -> Vec<(String, String)> {
+        let value = serde_json::to_value(embed).unwrap();
+        value["fields"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .map(|f| (f["name"].as_str().unwrap().to_string(), f["value"].as_str().unwrap().to_string()))
+            .collect()
+    }
+
+    // 주제와 정원이 모두 있으면 두 필드, 정원은 "현재/제한"
+    #[test]
+    fn embed_shows_topic_and_capacity() {
+        let details = ChannelDetails::new(Some("스터디"), Some(5));
+        let embed = notification_embed("입장".to_string(), COLOUR_ACTIVATE, &details, 3);
+        assert_eq!(
+            embed_fields(&embed),
+            vec![("주제".to_string(), "스터디".to_string()), ("정원".to_string(), "3/5".to_string())]
+        );
+    }
+
+    // 설정되지 않은 정보는 필드를 만들지 않음
+    #[test]
+    fn embed_omits_missing_details() {
+        let embed = notification_embed("입장".to_string(), COLOUR_ACTIVATE, &ChannelDetails::default(), 3);
+        assert!(embed_fields(&embed).is_empty());
+        let only_limit = notification_embed("입장".to_string(), COLOUR_ACTIVATE, &ChannelDetails::new(None, Some(2)), 2);
+        assert_eq!(embed_fields(&only_limit), vec![("정원".to_string(), "2/2".to_string())]);
+    }
+
+    // 공백뿐인 주제와 정원 0(제한 없음)은 없는 것으로
+    #[test]
+    fn blank_topic_and_zero_limit_are_unset() {
+        assert_eq!(ChannelDetails::new(Some("   "), Some(0)), ChannelDetails::default());
+        assert_eq!(
+            ChannelDetails::new(Some("공지"), None),
+            ChannelDetails {
+                topic: Some("공지".to_string()),
+                user_limit: None
+            }
+        );
+    }
+}
@@ -0,0 +1,100 @@
+use serenity::all::ChannelId;
+use serenity::all::GuildId;
+use serenity::prelude::*;
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+use crate::notification::{self, send_notification, NotificationMessage};
+
+// 첫 이벤트 후 이 시간 동안 들어온 입장/퇴장 알림을 한 메시지로 묶음
+pub const BATCH_WINDOW: Duration = Duration::from_millis(2000);
+
+// 묶음에 들어가는 입장/퇴장 알림 하나
+#[derive(Debug, Clone)]
+pub struct BatchEntry {
+    // 알림이 가리키는 보이스 채널
+    pub channel_id: ChannelId,
+    pub joined: bool,
+    // 묶음 임베드에 들어갈 한 줄
+    pub line: String,
+    // 묶음에 이 알림 하나뿐일 때 그대로 보낼 알림
+    pub single: NotificationMessage,
+}
+
+// 길드별로 전송을 기다리는 알림 묶음
+#[derive(Debug)]
+pub struct NotificationBatch {
+    notification_channel: ChannelId,
+    entries: Vec<BatchEntry>,
+}
+
+pub struct NotificationBatches;
+
+impl TypeMapKey for NotificationBatches {
+    type Value = Arc<Mutex<HashMap<GuildId, NotificationBatch>>>;
+}
+
+pub fn new_batch_store() -> Arc<Mutex<HashMap<GuildId, NotificationBatch>>> {
+    Arc::new(Mutex::new(HashMap::new()))
+}
+
+async fn batch_store(ctx: &Context) -> Option<Arc<Mutex<HashMap<GuildId, NotificationBatch>>>> {
+    let data = ctx.data.read().await;
+    data.get::<NotificationBatches>().cloned()
+}
+
+// 알림을 묶음에 추가. 묶음의 첫 알림이면 BATCH_WINDOW 뒤에 전송하는 타이머 시작
+pub async fn push(ctx: &Context, guild_id: GuildId, notification_channel: ChannelId, entry: BatchEntry) {
+    let Some(store) = batch_store(ctx).await else {
+        return;
+    };
+    match store.lock().await.entry(guild_id) {
+        Entry::Occupied(mut batch) => batch.get_mut().entries.push(entry),
+        Entry::Vacant(slot) => {
+            slot.insert(NotificationBatch {
+                notification_channel,
+                entries: vec![entry],
+            });
+            let ctx = ctx.clone();
+            let store = store.clone();
+            tokio::spawn(async move {
+                tokio::time::sleep(BATCH_WINDOW).await;
+                let batch = store.lock().await.remove(&guild_id);
+                if let Some(batch) = batch {
+                    flush(&ctx, guild_id, batch).await;
+                }
+            });
+        }
+    }
+}
+
+// 채널이 다시 비었으면 그 채널의 대기 중인 알림은 보내지 않음
+pub async fn cancel_channel(ctx: &Context, guild_id: GuildId, channel_id: ChannelId) {
+    let Some(store) = batch_store(ctx).await else {
+        return;
+    };
+    if let Some(batch) = store.lock().await.get_mut(&guild_id) {
+        batch.entries.retain(|e| e.channel_id != channel_id);
+    }
+}
+
+async fn flush(ctx: &Context, guild_id: GuildId, mut batch: NotificationBatch) {
+    let operation = if batch.entries.len() > 1 {
+        "입장/퇴장 묶음 알림 전송"
+    } else {
+        "입장/퇴장 알림 전송"
+    };
+    let message = match batch.entries.len() {
+        0 => return,
+        1 => batch.entries.remove(0).single,
+        count => {
+            let joins = batch.entries.iter().filter(|e| e.joined).count();
+            let lines: Vec<&str> = batch.entries.iter().map(|e| e.line.as_str()).collect();
+            notification::batched(&lines, joins, count - joins)
+        }
+    };
+    send_notification(ctx, guild_id, batch.notification_channel, message, operation).await;
+}
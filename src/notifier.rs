@@ -0,0 +1,117 @@
+use serenity::all::ChannelId;
+use serenity::all::CreateMessage;
+use serenity::all::Http;
+use serenity::all::MessageId;
+use serenity::all::UserId;
+use serenity::async_trait;
+use serenity::prelude::*;
+use std::sync::Arc;
+
+// 알림을 보낼 곳
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotifyTarget {
+    Channel(ChannelId),
+    Dm(UserId),
+}
+
+// 보낸 메시지 위치 (수정/삭제할 때 사용)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MessageRef {
+    pub channel_id: ChannelId,
+    pub message_id: MessageId,
+}
+
+// 봇이 스스로 보내는 메시지(알림, TTS 안내, DM)의 전송 경로.
+// 기능 코드는 디스코드 HTTP를 직접 쓰지 않고 TypeMap의 Notifier를 거치므로 다른 구현으로 바꿔 끼울 수 있음
+#[async_trait]
+pub trait Notifier: Send + Sync {
+    async fn send(&self, target: NotifyTarget, message: CreateMessage) -> Result<MessageRef, serenity::Error>;
+}
+
+// 디스코드 HTTP로 실제 전송하는 기본 구현
+pub struct HttpNotifier {
+    http: Arc<Http>,
+}
+
+impl HttpNotifier {
+    pub fn new(http: Arc<Http>) -> Self {
+        Self { http }
+    }
+}
+
+#[async_trait]
+impl Notifier for HttpNotifier {
+    async fn send(&self, target: NotifyTarget, message: CreateMessage) -> Result<MessageRef, serenity::Error> {
+        let channel_id = match target {
+            NotifyTarget::Channel(channel_id) => channel_id,
+            NotifyTarget::Dm(user_id) => user_id.create_dm_channel(&self.http).await?.id,
+        };
+        let sent = channel_id.send_message(&self.http, message).await?;
+        Ok(MessageRef {
+            channel_id: sent.channel_id,
+            message_id: sent.id,
+        })
+    }
+}
+
+pub struct Notifiers;
+
+impl TypeMapKey for Notifiers {
+    type Value = Arc<dyn Notifier>;
+}
+
+// 등록된 Notifier. 클라이언트 생성 직후 등록하기 전이면 HTTP 구현을 바로 씀
+pub async fn notifier(ctx: &Context) -> Arc<dyn Notifier> {
+    let data = ctx.data.read().await;
+    data.get::<Notifiers>()
+        .cloned()
+        .unwrap_or_else(|| Arc::new(HttpNotifier::new(ctx.http.clone())))
+}
+
+// 보낸 메시지를 순서대로 모아 두는 테스트용 Notifier (디스코드에 연결하지 않음)
+#[cfg(test)]
+#[derive(Default)]
+pub struct RecordingNotifier {
+    sent: std::sync::Mutex<Vec<(NotifyTarget, serde_json::Value)>>,
+}
+
+#[cfg(test)]
+impl RecordingNotifier {
+    // (보낸 곳, 본문). 본문은 메시지 내용과 임베드 설명을 줄로 이은 것
+    pub fn sent(&self) -> Vec<(NotifyTarget, String)> {
+        let text = |message: &serde_json::Value| {
+            let content = message.get("content").and_then(|c| c.as_str());
+            let descriptions = message
+                .get("embeds")
+                .and_then(|e| e.as_array())
+                .into_iter()
+                .flatten()
+                .filter_map(|embed| embed.get("description").and_then(|d| d.as_str()));
+            content.into_iter().chain(descriptions).collect::<Vec<_>>().join("\n")
+        };
+        self.sent
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(target, message)| (*target, text(message)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+#[async_trait]
+impl Notifier for RecordingNotifier {
+    async fn send(&self, target: NotifyTarget, message: CreateMessage) -> Result<MessageRef, serenity::Error> {
+        let message = serde_json::to_value(&message)?;
+        let mut sent = self.sent.lock().unwrap();
+        sent.push((target, message));
+        let channel_id = match target {
+            NotifyTarget::Channel(channel_id) => channel_id,
+            NotifyTarget::Dm(user_id) => ChannelId::new(user_id.get()),
+        };
+        Ok(MessageRef {
+            channel_id,
+            message_id: MessageId::new(sent.len() as u64),
+        })
+    }
+}
@@ -0,0 +1,127 @@
+use serenity::all::ActivityData;
+use serenity::all::ShardId;
+use serenity::prelude::*;
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+use crate::config::FileConfig;
+use crate::voice_tracker::ChannelActivityTracker;
+
+const DEFAULT_FORMAT: &str = "🎧 {users}명이 {channels}개 채널에서 대화 중";
+const DEFAULT_INTERVAL_SECS: u64 = 300;
+
+// 봇 상태 메시지 설정
+#[derive(Debug, Clone)]
+pub struct PresenceConfig {
+    pub enabled: bool,
+    // {channels}: 활성 보이스 채널 수, {users}: 보이스 채널 접속자 수
+    pub format: String,
+    pub interval: Duration,
+}
+
+impl PresenceConfig {
+    // 설정 파일 [presence] 섹션 또는 PRESENCE_ENABLED, PRESENCE_FORMAT, PRESENCE_INTERVAL_SECS 환경 변수에서 읽음
+    pub fn from_config(file: &FileConfig) -> Self {
+        let enabled = file
+            .value("presence.enabled", "PRESENCE_ENABLED")
+            .map(|v| !matches!(v.trim().to_ascii_lowercase().as_str(), "0" | "false" | "off"))
+            .unwrap_or(true);
+        let format = file
+            .value("presence.format", "PRESENCE_FORMAT")
+            .filter(|v| !v.trim().is_empty())
+            .unwrap_or_else(|| DEFAULT_FORMAT.to_string());
+        let interval_secs = file
+            .value("presence.interval_secs", "PRESENCE_INTERVAL_SECS")
+            .and_then(|v| v.trim().parse::<u64>().ok())
+            .filter(|&secs| secs >= 30)
+            .unwrap_or(DEFAULT_INTERVAL_SECS);
+        Self {
+            enabled,
+            format,
+            interval: Duration::from_secs(interval_secs),
+        }
+    }
+}
+
+pub struct PresenceSettings;
+
+impl TypeMapKey for PresenceSettings {
+    type Value = Arc<PresenceConfig>;
+}
+
+// 상태 갱신 작업이 이미 시작된 샤드 (재연결 시 ready가 다시 와도 중복 실행 방지)
+pub struct PresenceTasks;
+
+impl TypeMapKey for PresenceTasks {
+    type Value = Arc<Mutex<HashSet<ShardId>>>;
+}
+
+pub fn new_presence_tasks() -> Arc<Mutex<HashSet<ShardId>>> {
+    Arc::new(Mutex::new(HashSet::new()))
+}
+
+// ready에서 호출: 이 샤드의 상태 메시지를 주기적으로 갱신하는 작업 시작
+pub async fn start_presence_task(ctx: &Context) {
+    let (config, tasks) = {
+        let data = ctx.data.read().await;
+        (
+            data.get::<PresenceSettings>().cloned(),
+            data.get::<PresenceTasks>().cloned(),
+        )
+    };
+    let (Some(config), Some(tasks)) = (config, tasks) else {
+        return;
+    };
+    if !tasks.lock().await.insert(ctx.shard_id) {
+        return;
+    }
+
+    // 갱신 주기는 시작할 때 값으로 고정. 켜짐 여부와 형식은 /reloadconfig 후 다음 갱신부터 반영
+    let ctx = ctx.clone();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(config.interval);
+        loop {
+            interval.tick().await;
+            let config = {
+                let data = ctx.data.read().await;
+                data.get::<PresenceSettings>().cloned()
+            };
+            match config {
+                Some(config) if config.enabled => {
+                    let status = render_status(&ctx, &config.format).await;
+                    ctx.set_activity(Some(ActivityData::custom(status)));
+                }
+                Some(_) => ctx.set_activity(None),
+                // TypeMap이 새 클라이언트로 넘어감 (새 ready에서 작업을 다시 시작)
+                None => return,
+            }
+        }
+    });
+}
+
+async fn render_status(ctx: &Context, format: &str) -> String {
+    let tracker = {
+        let data = ctx.data.read().await;
+        data.get::<ChannelActivityTracker>().cloned()
+    };
+    let active_channels = match tracker {
+        Some(tracker) => tracker.active_channels().await,
+        None => 0,
+    };
+    let users_in_voice: usize = ctx
+        .cache
+        .guilds()
+        .into_iter()
+        .filter_map(|guild_id| {
+            ctx.cache
+                .guild(guild_id)
+                .map(|g| g.voice_states.values().filter(|vs| vs.channel_id.is_some()).count())
+        })
+        .sum();
+
+    format
+        .replace("{channels}", &active_channels.to_string())
+        .replace("{users}", &users_in_voice.to_string())
+}
@@ -0,0 +1,144 @@
+use serenity::all::UserId;
+use serenity::prelude::*;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+
+use crate::config::FileConfig;
+
+const DEFAULT_CAPACITY: u32 = 5;
+const DEFAULT_WINDOW_SECS: u64 = 20;
+
+// (사용자, 커맨드)별 토큰 버킷
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+// 커맨드 실행 빈도 제한: window 동안 capacity번까지 허용하고 그 비율로 다시 채움
+pub struct RateLimitState {
+    capacity: f64,
+    window: Duration,
+    buckets: Mutex<HashMap<(UserId, String), Bucket>>,
+    last_sweep: Mutex<Instant>,
+}
+
+impl RateLimitState {
+    pub fn new(capacity: u32, window: Duration) -> Self {
+        Self {
+            capacity: f64::from(capacity.max(1)),
+            window,
+            buckets: Mutex::new(HashMap::new()),
+            last_sweep: Mutex::new(Instant::now()),
+        }
+    }
+
+    // 설정 파일 [rate_limit] 섹션 또는 RATE_LIMIT_CAPACITY, RATE_LIMIT_WINDOW_SECS 환경 변수에서 읽음
+    pub fn from_config(file: &FileConfig) -> Self {
+        let capacity = file
+            .value("rate_limit.capacity", "RATE_LIMIT_CAPACITY")
+            .and_then(|v| v.trim().parse::<u32>().ok())
+            .filter(|&n| n > 0)
+            .unwrap_or(DEFAULT_CAPACITY);
+        let window_secs = file
+            .value("rate_limit.window_secs", "RATE_LIMIT_WINDOW_SECS")
+            .and_then(|v| v.trim().parse::<u64>().ok())
+            .filter(|&secs| secs > 0)
+            .unwrap_or(DEFAULT_WINDOW_SECS);
+        Self::new(capacity, Duration::from_secs(window_secs))
+    }
+
+    // 토큰 하나를 사용. 남은 토큰이 없으면 다음 토큰까지 기다려야 하는 시간 반환
+    pub async fn check(&self, user_id: UserId, command: &str) -> Result<(), Duration> {
+        let now = Instant::now();
+        let refill_per_sec = self.capacity / self.window.as_secs_f64();
+        self.sweep(now).await;
+
+        let mut buckets = self.buckets.lock().await;
+        let bucket = buckets
+            .entry((user_id, command.to_string()))
+            .or_insert(Bucket {
+                tokens: self.capacity,
+                updated: now,
+            });
+        let elapsed = now.duration_since(bucket.updated).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * refill_per_sec).min(self.capacity);
+        bucket.updated = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - bucket.tokens) / refill_per_sec))
+        }
+    }
+
+    // window 동안 쓰이지 않은 버킷은 이미 가득 찼으므로 삭제 (메모리 제한)
+    async fn sweep(&self, now: Instant) {
+        let mut last_sweep = self.last_sweep.lock().await;
+        if now.duration_since(*last_sweep) < self.window {
+            return;
+        }
+        *last_sweep = now;
+        self.buckets
+            .lock()
+            .await
+            .retain(|_, bucket| now.duration_since(bucket.updated) < self.window);
+    }
+}
+
+pub struct RateLimiter;
+
+impl TypeMapKey for RateLimiter {
+    type Value = Arc<RateLimitState>;
+}
+
+// 커맨드 쿨다운 적용 범위
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CooldownScope {
+    User,
+    Guild,
+}
+
+// 쿨다운이 걸린 대상: (커맨드, 사용자 또는 길드 ID)
+type CooldownKey = (&'static str, CooldownScope, u64);
+
+// 실행 비용이 큰 커맨드의 쿨다운 (메모리에만 보관, 재시작 시 초기화)
+#[derive(Default)]
+pub struct CooldownState {
+    until: Mutex<HashMap<CooldownKey, Instant>>,
+}
+
+impl CooldownState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // 쿨다운 중이 아니면 지금부터 duration 동안 쿨다운을 걸고 Ok, 쿨다운 중이면 남은 시간 반환.
+    // 확인과 기록을 한 번의 잠금 안에서 처리해 동시에 호출돼도 한 번만 통과
+    pub async fn try_start(
+        &self,
+        command: &'static str,
+        scope: CooldownScope,
+        id: u64,
+        duration: Duration,
+    ) -> Result<(), Duration> {
+        let now = Instant::now();
+        let mut until = self.until.lock().await;
+        until.retain(|_, end| *end > now);
+        match until.get(&(command, scope, id)) {
+            Some(end) => Err(end.duration_since(now)),
+            None => {
+                until.insert((command, scope, id), now + duration);
+                Ok(())
+            }
+        }
+    }
+}
+
+pub struct Cooldowns;
+
+impl TypeMapKey for Cooldowns {
+    type Value = Arc<CooldownState>;
+}
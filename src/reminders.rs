@@ -0,0 +1,266 @@
+use serenity::all::CommandDataOptionValue;
+use serenity::all::CommandInteraction;
+use serenity::all::CreateAllowedMentions;
+use serenity::all::CreateEmbed;
+use serenity::all::CreateInteractionResponseMessage;
+use serenity::all::CreateMessage;
+use serenity::prelude::*;
+use sqlx::SqlitePool;
+use std::time::Duration;
+
+use crate::calc::parse_duration_literal;
+use crate::commands::respond;
+use crate::error_report::{report_error, send_or_report};
+use crate::storage::{self, Reminder, unix_now};
+use crate::user_prefs::{format_timestamp, get_user_prefs};
+use crate::voice_tracker::format_duration;
+
+// 사용자당 대기 중인 리마인더 최대 개수
+pub const MAX_PENDING_PER_USER: usize = 25;
+// 예약할 수 있는 최대 기간 (1년)
+pub const MAX_REMIND_SECS: u64 = 365 * 86400;
+// 리마인더 메시지 최대 길이
+pub const MAX_MESSAGE_LEN: u16 = 1000;
+// 예정 시각보다 이만큼 늦게 보내면 지연 안내를 덧붙임
+const LATE_NOTE_THRESHOLD_SECS: i64 = 60;
+
+// 예정 시각까지 기다렸다가 전송. 그 사이 취소되면 (DB에서 지워졌으면) 보내지 않음
+pub fn schedule(ctx: &Context, pool: SqlitePool, reminder: Reminder) {
+    let ctx = ctx.clone();
+    tokio::spawn(async move {
+        let wait = (reminder.due_at - unix_now()).max(0) as u64;
+        if wait > 0 {
+            tokio::time::sleep(Duration::from_secs(wait)).await;
+        }
+        match storage::delete_reminder(&pool, reminder.id, None).await {
+            Ok(true) => fire(&ctx, &reminder).await,
+            Ok(false) => {}
+            Err(e) => report_error(&ctx, "리마인더 삭제", &e).await,
+        }
+    });
+}
+
+async fn fire(ctx: &Context, reminder: &Reminder) {
+    let late = unix_now() - reminder.due_at;
+    let mut content = format!("⏰ <@{}> 리마인더: {}", reminder.user_id, reminder.message);
+    if late > LATE_NOTE_THRESHOLD_SECS {
+        content.push_str(&format!(
+            "\n-# 봇이 꺼져 있어 {} 늦게 전달되었습니다 (예정: <t:{}:f>)",
+            format_duration(late as u64),
+            reminder.due_at
+        ));
+    }
+    let channel_id = if reminder.dm {
+        match reminder.user_id.create_dm_channel(&ctx.http).await {
+            Ok(channel) => channel.id,
+            Err(e) => {
+                report_error(ctx, "리마인더 DM 채널 열기", &e).await;
+                return;
+            }
+        }
+    } else {
+        reminder.channel_id
+    };
+    // 메시지 안의 @everyone 등은 무시하고 본인만 멘션
+    let message = CreateMessage::new()
+        .content(content)
+        .allowed_mentions(CreateAllowedMentions::new().users(vec![reminder.user_id]));
+    send_or_report(ctx, channel_id, message, "리마인더 전송").await;
+}
+
+// 시작 시 저장된 리마인더를 다시 예약 (지난 것은 바로 전송)
+pub async fn restore_reminders(ctx: &Context, pool: &SqlitePool) {
+    let reminders = match storage::load_reminders(pool).await {
+        Ok(reminders) => reminders,
+        Err(e) => {
+            report_error(ctx, "리마인더 불러오기", &e).await;
+            return;
+        }
+    };
+    if !reminders.is_empty() {
+        tracing::info!("대기 중인 리마인더 {}개를 다시 예약했습니다", reminders.len());
+    }
+    for reminder in reminders {
+        schedule(ctx, pool.clone(), reminder);
+    }
+}
+
+async fn reply(ctx: &Context, cmd: &CommandInteraction, content: String) {
+    respond(
+        ctx,
+        cmd,
+        CreateInteractionResponseMessage::new()
+            .content(content)
+            .ephemeral(true),
+    )
+    .await;
+}
+
+// /remind set <in> <message> [dm] | cancel <id> | list
+pub async fn handle_remind(ctx: &Context, cmd: &CommandInteraction) {
+    let Some(sub) = cmd.data.options.first() else {
+        return;
+    };
+    let CommandDataOptionValue::SubCommand(args) = &sub.value else {
+        return;
+    };
+    let Some(pool) = storage::pool(ctx).await else {
+        return;
+    };
+    // 예정 시각은 사용자가 /timezone으로 설정한 시간대로도 함께 표시
+    let timezone = get_user_prefs(ctx, cmd.user.id).await.timezone;
+
+    match sub.name.as_str() {
+        "set" => {
+            let duration = args.iter().find(|o| o.name == "in").and_then(|o| o.value.as_str());
+            let message = args
+                .iter()
+                .find(|o| o.name == "message")
+                .and_then(|o| o.value.as_str());
+            let (Some(duration), Some(message)) = (duration, message) else {
+                return;
+            };
+            let dm = args
+                .iter()
+                .find(|o| o.name == "dm")
+                .and_then(|o| o.value.as_bool())
+                .unwrap_or(false);
+
+            let secs = match parse_duration_literal(duration) {
+                Ok(0) => {
+                    reply(ctx, cmd, "기간은 1초 이상이어야 합니다.".to_string()).await;
+                    return;
+                }
+                Ok(secs) if secs > MAX_REMIND_SECS => {
+                    reply(ctx, cmd, "최대 365일 뒤까지만 예약할 수 있습니다.".to_string()).await;
+                    return;
+                }
+                Ok(secs) => secs,
+                Err(e) => {
+                    reply(ctx, cmd, format!("{} (예: `10m`, `1h30m`, `2d`)", e)).await;
+                    return;
+                }
+            };
+
+            match storage::user_reminders(&pool, cmd.user.id).await {
+                Ok(pending) if pending.len() >= MAX_PENDING_PER_USER => {
+                    reply(
+                        ctx,
+                        cmd,
+                        format!(
+                            "대기 중인 리마인더는 최대 {}개까지 만들 수 있습니다. `/remind cancel` 로 정리해주세요.",
+                            MAX_PENDING_PER_USER
+                        ),
+                    )
+                    .await;
+                    return;
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    report_error(ctx, "리마인더 조회", &e).await;
+                    return;
+                }
+            }
+
+            let due_at = unix_now() + secs as i64;
+            let id = match storage::insert_reminder(&pool, cmd.user.id, cmd.channel_id, dm, message, due_at).await {
+                Ok(id) => id,
+                Err(e) => {
+                    report_error(ctx, "리마인더 저장", &e).await;
+                    reply(ctx, cmd, "리마인더를 저장하지 못했습니다.".to_string()).await;
+                    return;
+                }
+            };
+            schedule(
+                ctx,
+                pool,
+                Reminder {
+                    id,
+                    user_id: cmd.user.id,
+                    channel_id: cmd.channel_id,
+                    dm,
+                    message: message.to_string(),
+                    due_at,
+                },
+            );
+
+            let target = if dm { "DM으로" } else { "이 채널에서" };
+            respond(
+                ctx,
+                cmd,
+                CreateInteractionResponseMessage::new()
+                    .content(format!(
+                        "⏰ <t:{}:R> ({})에 {} 알려드릴게요. (ID `{}`)",
+                        due_at,
+                        format_timestamp(due_at, &timezone),
+                        target,
+                        id
+                    ))
+                    .allowed_mentions(CreateAllowedMentions::new()),
+            )
+            .await;
+        }
+        "cancel" => {
+            let Some(id) = args.iter().find(|o| o.name == "id").and_then(|o| o.value.as_i64()) else {
+                return;
+            };
+            let content = match storage::delete_reminder(&pool, id, Some(cmd.user.id)).await {
+                Ok(true) => format!("리마인더 `{}` 를 취소했습니다.", id),
+                Ok(false) => format!("ID `{}` 인 대기 중인 리마인더가 없습니다.", id),
+                Err(e) => {
+                    report_error(ctx, "리마인더 취소", &e).await;
+                    "리마인더를 취소하지 못했습니다.".to_string()
+                }
+            };
+            reply(ctx, cmd, content).await;
+        }
+        "list" => {
+            let pending = match storage::user_reminders(&pool, cmd.user.id).await {
+                Ok(pending) => pending,
+                Err(e) => {
+                    report_error(ctx, "리마인더 조회", &e).await;
+                    return;
+                }
+            };
+            let body = if pending.is_empty() {
+                "대기 중인 리마인더가 없습니다.".to_string()
+            } else {
+                pending
+                    .iter()
+                    .map(|r| {
+                        let target = if r.dm {
+                            "DM".to_string()
+                        } else {
+                            format!("<#{}>", r.channel_id)
+                        };
+                        let mut message: String = r.message.chars().take(80).collect();
+                        if message.len() < r.message.len() {
+                            message.push('…');
+                        }
+                        format!(
+                            "`{}` · <t:{}:R> ({}) · {} · {}",
+                            r.id,
+                            r.due_at,
+                            format_timestamp(r.due_at, &timezone),
+                            target,
+                            message
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            };
+            let embed = CreateEmbed::new()
+                .title(format!("⏰ 리마인더 ({}/{})", pending.len(), MAX_PENDING_PER_USER))
+                .description(body);
+            respond(
+                ctx,
+                cmd,
+                CreateInteractionResponseMessage::new()
+                    .embed(embed)
+                    .ephemeral(true),
+            )
+            .await;
+        }
+        _ => {}
+    }
+}
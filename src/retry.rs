@@ -0,0 +1,155 @@
+use serenity::all::HttpError;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+// 테스트에서 시계를 멈출 수 있도록 tokio 시계를 사용
+use tokio::time::Instant;
+
+// 지금까지의 재시도 횟수와, 재시도할 수 있는 오류였지만 끝내 실패한 호출 수 (/healthz에 표시)
+static RETRIES: AtomicU64 = AtomicU64::new(0);
+static GAVE_UP: AtomicU64 = AtomicU64::new(0);
+
+pub fn retry_counts() -> (u64, u64) {
+    (RETRIES.load(Ordering::Relaxed), GAVE_UP.load(Ordering::Relaxed))
+}
+
+// 재시도 정책. deadline은 첫 시도부터 마지막 시도가 끝날 때까지의 전체 제한 시간
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+    pub deadline: Duration,
+}
+
+impl RetryPolicy {
+    // 알림 전송: 보이스 이벤트 처리가 오래 붙잡히지 않도록 짧게
+    pub const NOTIFICATION: Self = Self {
+        max_attempts: 3,
+        base_delay: Duration::from_millis(250),
+        max_delay: Duration::from_secs(2),
+        deadline: Duration::from_secs(5),
+    };
+
+    // 커맨드 등록: 시작 시 한 번이므로 조금 더 기다림
+    pub const REGISTRATION: Self = Self {
+        max_attempts: 4,
+        base_delay: Duration::from_millis(500),
+        max_delay: Duration::from_secs(5),
+        deadline: Duration::from_secs(30),
+    };
+
+    // 게이트웨이 클라이언트 재시작 (main의 감시 루프). 횟수 제한은 --max-restarts
+    pub const CLIENT_RESTART: Self = Self {
+        max_attempts: 5,
+        base_delay: Duration::from_secs(5),
+        max_delay: Duration::from_secs(300),
+        deadline: Duration::MAX,
+    };
+
+    // n번째 재시도 전 대기 시간: 지수 증가 + 0~50% 지터
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let exp = self.base_delay.saturating_mul(1 << retry.min(16)).min(self.max_delay);
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.subsec_nanos())
+            .unwrap_or(0);
+        exp + exp.mul_f64(f64::from(nanos % 1000) / 2000.0)
+    }
+}
+
+#[derive(Debug)]
+pub enum RetryError {
+    // 재시도할 수 없는 오류이거나 재시도를 다 쓴 경우의 마지막 오류
+    Failed(serenity::Error),
+    // 전체 제한 시간 안에 응답을 받지 못함
+    TimedOut,
+}
+
+// 일시적인 오류만 재시도: 5xx, 429, 요청 시간 초과/연결 실패. 권한 등 나머지 4xx는 바로 실패
+fn is_retryable(error: &serenity::Error) -> bool {
+    match error {
+        serenity::Error::Http(HttpError::UnsuccessfulRequest(response)) => {
+            response.status_code.is_server_error() || response.status_code.as_u16() == 429
+        }
+        serenity::Error::Http(HttpError::Request(e)) => e.is_timeout() || e.is_connect(),
+        _ => false,
+    }
+}
+
+// 디스코드 API 호출을 정책에 따라 재시도. 재시도할 때마다 로그를 남김
+pub async fn retrying<T, F, Fut>(operation: &str, policy: RetryPolicy, mut call: F) -> Result<T, RetryError>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, serenity::Error>>,
+{
+    let started = Instant::now();
+    let mut attempt = 1;
+    loop {
+        let remaining = policy.deadline.saturating_sub(started.elapsed());
+        let error = match tokio::time::timeout(remaining, call()).await {
+            Ok(Ok(value)) => return Ok(value),
+            Ok(Err(e)) => e,
+            Err(_) => return Err(RetryError::TimedOut),
+        };
+        if !is_retryable(&error) {
+            return Err(RetryError::Failed(error));
+        }
+        let delay = policy.backoff(attempt - 1);
+        if attempt >= policy.max_attempts || started.elapsed() + delay >= policy.deadline {
+            GAVE_UP.fetch_add(1, Ordering::Relaxed);
+            return Err(RetryError::Failed(error));
+        }
+        RETRIES.fetch_add(1, Ordering::Relaxed);
+        tracing::warn!(
+            "{} 재시도 {}/{} ({}ms 후): {}",
+            operation,
+            attempt,
+            policy.max_attempts - 1,
+            delay.as_millis(),
+            error
+        );
+        tokio::time::sleep(delay).await;
+        attempt += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 응답 없는 호출은 전체 제한 시간이 지나면 포기 (시계를 멈춰 실제로 기다리지 않음)
+    #[tokio::test(start_paused = true)]
+    async fn hung_call_times_out_at_deadline() {
+        let started = Instant::now();
+        let result: Result<(), _> = retrying("테스트", RetryPolicy::NOTIFICATION, || {
+            std::future::pending::<Result<(), serenity::Error>>()
+        })
+        .await;
+        assert!(matches!(result, Err(RetryError::TimedOut)));
+        assert_eq!(started.elapsed(), RetryPolicy::NOTIFICATION.deadline);
+    }
+
+    // 재시도할 수 없는 오류는 바로 실패
+    #[tokio::test(start_paused = true)]
+    async fn non_retryable_error_fails_immediately() {
+        let mut calls = 0;
+        let result: Result<(), _> = retrying("테스트", RetryPolicy::NOTIFICATION, || {
+            calls += 1;
+            std::future::ready(Err(serenity::Error::Other("권한 없음")))
+        })
+        .await;
+        assert!(matches!(result, Err(RetryError::Failed(_))));
+        assert_eq!(calls, 1);
+    }
+
+    #[test]
+    fn backoff_stays_within_jitter() {
+        let policy = RetryPolicy::NOTIFICATION;
+        for retry in 0..5 {
+            let exp = policy.base_delay.saturating_mul(1 << retry).min(policy.max_delay);
+            let delay = policy.backoff(retry);
+            assert!(delay >= exp && delay <= exp + exp / 2, "{}: {:?}", retry, delay);
+        }
+    }
+}
@@ -0,0 +1,137 @@
+use serenity::all::CommandInteraction;
+use serenity::all::CreateEmbed;
+use serenity::all::CreateEmbedFooter;
+use serenity::all::CreateInteractionResponseMessage;
+use serenity::all::Permissions;
+use serenity::prelude::*;
+
+use crate::commands::respond;
+use crate::guild_config::get_guild_config;
+
+// 임베드에 보여줄 최대 권한 수
+const MAX_PERMISSIONS_SHOWN: usize = 10;
+
+// 영향이 큰 순서로 나열한 권한과 표시 이름
+const SIGNIFICANT_PERMISSIONS: &[(Permissions, &str)] = &[
+    (Permissions::ADMINISTRATOR, "관리자"),
+    (Permissions::MANAGE_GUILD, "서버 관리"),
+    (Permissions::MANAGE_ROLES, "역할 관리"),
+    (Permissions::MANAGE_CHANNELS, "채널 관리"),
+    (Permissions::BAN_MEMBERS, "멤버 차단"),
+    (Permissions::KICK_MEMBERS, "멤버 추방"),
+    (Permissions::MODERATE_MEMBERS, "멤버 타임아웃"),
+    (Permissions::MANAGE_WEBHOOKS, "웹후크 관리"),
+    (Permissions::MANAGE_MESSAGES, "메시지 관리"),
+    (Permissions::MENTION_EVERYONE, "@everyone 멘션"),
+    (Permissions::VIEW_AUDIT_LOG, "감사 로그 보기"),
+    (Permissions::MANAGE_NICKNAMES, "별명 관리"),
+    (Permissions::MANAGE_GUILD_EXPRESSIONS, "이모지/스티커 관리"),
+    (Permissions::MANAGE_EVENTS, "이벤트 관리"),
+    (Permissions::MANAGE_THREADS, "스레드 관리"),
+    (Permissions::MOVE_MEMBERS, "음성 멤버 이동"),
+    (Permissions::MUTE_MEMBERS, "음성 멤버 음소거"),
+    (Permissions::DEAFEN_MEMBERS, "음성 멤버 소리 끄기"),
+    (Permissions::PRIORITY_SPEAKER, "우선 발언권"),
+    (Permissions::VIEW_GUILD_INSIGHTS, "서버 인사이트 보기"),
+];
+
+fn yes_no(value: bool) -> &'static str {
+    if value { "예" } else { "아니요" }
+}
+
+// 켜진 권한 중 영향이 큰 것부터 최대 MAX_PERMISSIONS_SHOWN개
+fn significant_permissions(permissions: Permissions) -> String {
+    if permissions.administrator() {
+        return "관리자 (모든 권한)".to_string();
+    }
+    let names: Vec<&str> = SIGNIFICANT_PERMISSIONS
+        .iter()
+        .filter(|(p, _)| permissions.contains(*p))
+        .map(|(_, name)| *name)
+        .collect();
+    if names.is_empty() {
+        return "주요 권한 없음".to_string();
+    }
+    let mut text = names
+        .iter()
+        .take(MAX_PERMISSIONS_SHOWN)
+        .map(|name| format!("• {}", name))
+        .collect::<Vec<_>>()
+        .join("\n");
+    if names.len() > MAX_PERMISSIONS_SHOWN {
+        text.push_str(&format!("\n외 {}개", names.len() - MAX_PERMISSIONS_SHOWN));
+    }
+    text
+}
+
+// /roleinfo <role>: 역할의 색상, 위치, 멤버 수, 주요 권한 등을 표시.
+// 서버가 roleinfo_requires_insights를 켜 두면 서버 인사이트 보기 권한이 있어야 함
+pub async fn handle_roleinfo(ctx: &Context, cmd: &CommandInteraction) {
+    let Some(guild_id) = cmd.guild_id else {
+        return;
+    };
+    let Some(role_id) = cmd
+        .data
+        .options
+        .iter()
+        .find(|o| o.name == "role")
+        .and_then(|o| o.value.as_role_id())
+    else {
+        return;
+    };
+
+    let restricted = get_guild_config(ctx, guild_id).await.roleinfo_requires_insights;
+    let allowed = !restricted
+        || cmd
+            .member
+            .as_ref()
+            .and_then(|m| m.permissions)
+            .is_some_and(|p| p.administrator() || p.view_guild_insights());
+    if !allowed {
+        respond(
+            ctx,
+            cmd,
+            CreateInteractionResponseMessage::new()
+                .content("이 서버에서는 서버 인사이트 보기 권한이 있어야 역할 정보를 볼 수 있습니다.")
+                .ephemeral(true),
+        )
+        .await;
+        return;
+    }
+
+    // 멤버 수는 캐시에 있는 멤버 기준 (GUILD_MEMBERS 인텐트 없이 실행하므로 전체보다 적을 수 있음)
+    let embed = ctx.cache.guild(guild_id).and_then(|guild| {
+        let role = guild.roles.get(&role_id)?;
+        let rank = guild.roles.values().filter(|r| r.position > role.position).count() + 1;
+        let members = if role_id.get() == guild_id.get() {
+            guild.members.len()
+        } else {
+            guild.members.values().filter(|m| m.roles.contains(&role_id)).count()
+        };
+        let colour = if role.colour.0 == 0 {
+            "기본 (없음)".to_string()
+        } else {
+            format!("#{}", role.colour.hex())
+        };
+        Some(
+            CreateEmbed::new()
+                .title(format!("🏷️ {}", role.name))
+                .colour(role.colour)
+                .field("색상", colour, true)
+                .field("위치", format!("{} / {}번째", rank, guild.roles.len()), true)
+                .field("멤버 수", format!("{}명", members), true)
+                .field("생성일", format!("<t:{}:D>", role_id.created_at().unix_timestamp()), true)
+                .field("멘션 가능", yes_no(role.mentionable), true)
+                .field("따로 표시", yes_no(role.hoist), true)
+                .field("연동 역할", yes_no(role.managed), true)
+                .field("주요 권한", significant_permissions(role.permissions), false)
+                .footer(CreateEmbedFooter::new(format!("역할 ID: {} · 멤버 수는 봇이 본 멤버 기준", role_id))),
+        )
+    });
+
+    let message = match embed {
+        Some(embed) => CreateInteractionResponseMessage::new().embed(embed),
+        None => CreateInteractionResponseMessage::new().content("이 서버의 역할이 아닙니다."),
+    };
+    respond(ctx, cmd, message.ephemeral(true)).await;
+}
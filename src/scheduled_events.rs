@@ -0,0 +1,118 @@
+use serenity::all::ChannelId;
+use serenity::all::GuildId;
+use serenity::all::ScheduledEvent;
+use serenity::all::ScheduledEventId;
+use serenity::all::ScheduledEventStatus;
+use serenity::prelude::*;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+
+use crate::guild_config::MentionTarget;
+use crate::storage::unix_now;
+
+// 길드별 예약 이벤트 목록을 다시 조회하기 전까지 재사용하는 시간
+const EVENT_CACHE_TTL: Duration = Duration::from_secs(300);
+// 활성화 알림이 늦어지지 않도록 이벤트 조회를 기다리는 최대 시간
+const FETCH_TIMEOUT: Duration = Duration::from_secs(3);
+// 시작 예정 시각 이만큼 전부터 (아직 시작 처리하지 않은 이벤트도) 진행 중으로 봄
+const EARLY_START_SECS: i64 = 15 * 60;
+// 끝나는 시각이 없는 이벤트는 시작 후 이 시간까지만 진행 중으로 봄
+const DEFAULT_EVENT_SECS: i64 = 3 * 3600;
+// 관심 멤버 멘션 최대 수 (디스코드 한 번 조회 한도)
+const MAX_INTERESTED_USERS: u64 = 100;
+
+// 조회한 시각과 이벤트 목록
+type CachedEvents = (Instant, Vec<ScheduledEvent>);
+
+pub struct ScheduledEventCache;
+
+impl TypeMapKey for ScheduledEventCache {
+    type Value = Arc<Mutex<HashMap<GuildId, CachedEvents>>>;
+}
+
+pub fn new_event_cache() -> Arc<Mutex<HashMap<GuildId, CachedEvents>>> {
+    Arc::new(Mutex::new(HashMap::new()))
+}
+
+// 활성화 알림에 표시할 진행 중인 이벤트
+#[derive(Debug, Clone)]
+pub struct LiveEvent {
+    pub id: ScheduledEventId,
+    pub name: String,
+    pub url: String,
+}
+
+// 이 보이스 채널에서 지금 진행 중인 예약 이벤트. 이벤트가 없거나 조회에 실패하면 None
+pub async fn live_event(ctx: &Context, guild_id: GuildId, channel_id: ChannelId) -> Option<LiveEvent> {
+    let events = guild_events(ctx, guild_id).await?;
+    let now = unix_now();
+    events
+        .iter()
+        .filter(|e| e.channel_id == Some(channel_id) && is_live(e, now))
+        // 이미 시작 처리된 이벤트를 먼저
+        .min_by_key(|e| (e.status != ScheduledEventStatus::Active, e.start_time.unix_timestamp()))
+        .map(|e| LiveEvent {
+            id: e.id,
+            name: e.name.clone(),
+            url: format!("https://discord.com/events/{}/{}", guild_id, e.id),
+        })
+}
+
+fn is_live(event: &ScheduledEvent, now: i64) -> bool {
+    match event.status {
+        ScheduledEventStatus::Active => true,
+        ScheduledEventStatus::Scheduled => {
+            let start = event.start_time.unix_timestamp();
+            let end = event
+                .end_time
+                .map_or(start + DEFAULT_EVENT_SECS, |t| t.unix_timestamp());
+            start - EARLY_START_SECS <= now && now <= end
+        }
+        _ => false,
+    }
+}
+
+// 길드 예약 이벤트 목록 (EVENT_CACHE_TTL 동안 캐시). 실패도 캐시해 활성화마다 다시 요청하지 않음
+async fn guild_events(ctx: &Context, guild_id: GuildId) -> Option<Vec<ScheduledEvent>> {
+    let cache = {
+        let data = ctx.data.read().await;
+        data.get::<ScheduledEventCache>().cloned()
+    }?;
+    if let Some((fetched_at, events)) = cache.lock().await.get(&guild_id)
+        && fetched_at.elapsed() < EVENT_CACHE_TTL
+    {
+        return Some(events.clone());
+    }
+    let events = match tokio::time::timeout(FETCH_TIMEOUT, guild_id.scheduled_events(&ctx.http, false)).await {
+        Ok(Ok(events)) => events,
+        Ok(Err(e)) => {
+            tracing::warn!("예약 이벤트 조회 실패 (길드 {}): {}", guild_id, e);
+            Vec::new()
+        }
+        Err(_) => {
+            tracing::warn!("예약 이벤트 조회 시간 초과 (길드 {})", guild_id);
+            Vec::new()
+        }
+    };
+    cache.lock().await.insert(guild_id, (Instant::now(), events.clone()));
+    Some(events)
+}
+
+// 이벤트에 관심 표시한 멤버 (봇 제외). 조회에 실패하면 빈 목록
+pub async fn interested_users(ctx: &Context, guild_id: GuildId, event_id: ScheduledEventId) -> Vec<MentionTarget> {
+    let users = guild_id.scheduled_event_users(&ctx.http, event_id, Some(MAX_INTERESTED_USERS));
+    match tokio::time::timeout(FETCH_TIMEOUT, users).await {
+        Ok(Ok(users)) => users
+            .into_iter()
+            .filter(|u| !u.user.bot)
+            .map(|u| MentionTarget::User(u.user.id))
+            .collect(),
+        Ok(Err(e)) => {
+            tracing::warn!("예약 이벤트 관심 멤버 조회 실패 (길드 {}): {}", guild_id, e);
+            Vec::new()
+        }
+        Err(_) => Vec::new(),
+    }
+}
@@ -0,0 +1,233 @@
+use serenity::all::CommandInteraction;
+use serenity::all::CreateEmbed;
+use serenity::all::CreateInteractionResponseMessage;
+use serenity::prelude::*;
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use tokio::sync::{Mutex, Notify};
+
+use crate::commands::respond;
+use crate::storage::unix_now;
+
+const SECS_PER_DAY: i64 = 86400;
+
+// 작업 실행 주기
+#[derive(Debug, Clone, Copy)]
+pub enum Schedule {
+    // 일정 간격마다 (시작 직후 첫 실행은 한 간격 뒤)
+    Every(Duration),
+    // 매일 UTC 기준 hour:minute
+    DailyAt { hour: u8, minute: u8 },
+}
+
+impl Schedule {
+    // now(유닉스 초) 이후 처음 실행할 시각
+    fn next_after(self, now: i64) -> i64 {
+        match self {
+            Schedule::Every(interval) => now + (interval.as_secs() as i64).max(1),
+            Schedule::DailyAt { hour, minute } => {
+                let today = now.div_euclid(SECS_PER_DAY) * SECS_PER_DAY
+                    + i64::from(hour) * 3600
+                    + i64::from(minute) * 60;
+                if today > now { today } else { today + SECS_PER_DAY }
+            }
+        }
+    }
+
+    fn describe(self) -> String {
+        match self {
+            Schedule::Every(interval) => format!("{}초마다", interval.as_secs()),
+            Schedule::DailyAt { hour, minute } => format!("매일 {:02}:{:02} (UTC)", hour, minute),
+        }
+    }
+}
+
+type JobFuture = Pin<Box<dyn Future<Output = ()> + Send>>;
+type JobFn = Arc<dyn Fn(Context) -> JobFuture + Send + Sync>;
+
+struct Job {
+    name: &'static str,
+    schedule: Schedule,
+    run: JobFn,
+}
+
+// /jobs에 표시하는 작업 상태
+#[derive(Debug, Clone, Default)]
+struct JobStatus {
+    next_run: i64,
+    last_run: Option<i64>,
+    last_panicked: bool,
+}
+
+// 주기 작업 스케줄러. main에서 작업을 등록하고 ready에서 한 번 시작
+#[derive(Default)]
+pub struct Scheduler {
+    jobs: Vec<Job>,
+    status: Mutex<HashMap<&'static str, JobStatus>>,
+    started: AtomicBool,
+    shutdown: Notify,
+}
+
+impl Scheduler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // 작업 등록. 작업은 Context 복사본(http, cache, TypeMap 접근)을 받음
+    pub fn job<F, Fut>(mut self, name: &'static str, schedule: Schedule, run: F) -> Self
+    where
+        F: Fn(Context) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        self.jobs.push(Job {
+            name,
+            schedule,
+            run: Arc::new(move |ctx| Box::pin(run(ctx))),
+        });
+        self
+    }
+
+    // 실행 중인 루프를 멈춤 (실행 중인 작업은 끝까지 기다림)
+    pub fn shutdown(&self) {
+        // 시작 전에 알리면 나중에 시작하자마자 멈추므로 실행 중일 때만
+        if self.started.load(Ordering::SeqCst) {
+            self.shutdown.notify_one();
+        }
+    }
+
+    // 하나의 tokio 작업에서 모든 작업을 순서대로 실행. 작업이 패닉해도 루프는 계속됨
+    async fn run(self: Arc<Self>, ctx: Context) {
+        let now = unix_now();
+        {
+            let mut status = self.status.lock().await;
+            for job in &self.jobs {
+                status.insert(
+                    job.name,
+                    JobStatus {
+                        next_run: job.schedule.next_after(now),
+                        ..JobStatus::default()
+                    },
+                );
+            }
+        }
+
+        loop {
+            let next = {
+                let status = self.status.lock().await;
+                status.values().map(|s| s.next_run).min()
+            };
+            let Some(next) = next else {
+                return;
+            };
+            let wait = Duration::from_secs((next - unix_now()).max(0) as u64);
+            tokio::select! {
+                _ = tokio::time::sleep(wait) => {}
+                _ = self.shutdown.notified() => {
+                    tracing::info!("스케줄러를 종료합니다");
+                    // 클라이언트를 다시 만들면 새 ready에서 다시 시작
+                    self.started.store(false, Ordering::SeqCst);
+                    return;
+                }
+            }
+
+            let now = unix_now();
+            for job in &self.jobs {
+                let due = self
+                    .status
+                    .lock()
+                    .await
+                    .get(job.name)
+                    .is_some_and(|s| s.next_run <= now);
+                if !due {
+                    continue;
+                }
+                let result = tokio::spawn((job.run)(ctx.clone())).await;
+                let panicked = matches!(&result, Err(e) if e.is_panic());
+                if panicked {
+                    tracing::error!("예약 작업 {} 실행 중 패닉이 발생했습니다", job.name);
+                }
+                let finished = unix_now();
+                if let Some(status) = self.status.lock().await.get_mut(job.name) {
+                    status.last_run = Some(finished);
+                    status.last_panicked = panicked;
+                    status.next_run = job.schedule.next_after(finished);
+                }
+            }
+        }
+    }
+}
+
+pub struct SchedulerKey;
+
+impl TypeMapKey for SchedulerKey {
+    type Value = Arc<Scheduler>;
+}
+
+// ready에서 호출: 스케줄러 루프를 프로세스당 한 번만 시작
+pub async fn start_scheduler(ctx: &Context) {
+    let scheduler = {
+        let data = ctx.data.read().await;
+        data.get::<SchedulerKey>().cloned()
+    };
+    let Some(scheduler) = scheduler else {
+        return;
+    };
+    if scheduler.started.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    tokio::spawn(scheduler.run(ctx.clone()));
+}
+
+// /jobs: 등록된 작업과 다음 실행 시각 (소유자 전용)
+pub async fn handle_jobs(ctx: &Context, cmd: &CommandInteraction) {
+    let scheduler = {
+        let data = ctx.data.read().await;
+        data.get::<SchedulerKey>().cloned()
+    };
+    let Some(scheduler) = scheduler else {
+        return;
+    };
+    let status = scheduler.status.lock().await.clone();
+
+    let body = if scheduler.jobs.is_empty() {
+        "등록된 작업이 없습니다.".to_string()
+    } else {
+        scheduler
+            .jobs
+            .iter()
+            .map(|job| {
+                let status = status.get(job.name);
+                let next = match status {
+                    Some(s) => format!("<t:{}:R>", s.next_run),
+                    None => "시작 전".to_string(),
+                };
+                let last = match status.and_then(|s| s.last_run.map(|at| (at, s.last_panicked))) {
+                    Some((at, false)) => format!("<t:{}:R>", at),
+                    Some((at, true)) => format!("<t:{}:R> ⚠️ 패닉", at),
+                    None => "없음".to_string(),
+                };
+                format!(
+                    "**{}** · {}\n다음 실행: {} · 마지막 실행: {}",
+                    job.name,
+                    job.schedule.describe(),
+                    next,
+                    last
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    };
+    let embed = CreateEmbed::new().title("⏱ 예약 작업").description(body);
+    respond(
+        ctx,
+        cmd,
+        CreateInteractionResponseMessage::new()
+            .embed(embed)
+            .ephemeral(true),
+    )
+    .await;
+}
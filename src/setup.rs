@@ -0,0 +1,214 @@
+use serenity::all::ButtonStyle;
+use serenity::all::ChannelType;
+use serenity::all::CommandInteraction;
+use serenity::all::ComponentInteraction;
+use serenity::all::ComponentInteractionDataKind;
+use serenity::all::CreateActionRow;
+use serenity::all::CreateButton;
+use serenity::all::CreateEmbed;
+use serenity::all::CreateEmbedFooter;
+use serenity::all::CreateInteractionResponse;
+use serenity::all::CreateInteractionResponseMessage;
+use serenity::all::CreateMessage;
+use serenity::all::CreateSelectMenu;
+use serenity::all::CreateSelectMenuKind;
+use serenity::all::Guild;
+use serenity::all::GuildId;
+use serenity::all::Permissions;
+use serenity::prelude::*;
+
+use crate::commands::respond;
+use crate::error_report::{report_error, send_or_report};
+use crate::event_metrics::{self, Phase};
+use crate::guild_config::{get_guild_config, update_guild_config};
+
+// 설정 마법사 컴포넌트의 custom_id 접두사: setup:<channel|role|joinleave|done>
+pub const SETUP_PREFIX: &str = "setup:";
+
+const WELCOME: &str = "👋 안녕하세요! 보이스 채널 입장/퇴장 알림과 `/calc` 계산기를 제공합니다.\n\
+                       아래에서 기본 설정을 골라주세요. 나중에 `/setup` 으로 다시 열 수 있습니다.";
+
+// 현재 설정을 보여주는 안내와 선택 메뉴/버튼. 이 서버에 없는 채널/역할은 기본 선택으로 넣지 않음
+async fn wizard(ctx: &Context, guild_id: GuildId) -> (CreateEmbed, Vec<CreateActionRow>) {
+    let config = get_guild_config(ctx, guild_id).await;
+    let (channel, role) = ctx
+        .cache
+        .guild(guild_id)
+        .map(|g| {
+            (
+                config.notification_channel.filter(|c| g.channels.contains_key(c)),
+                config.mention_role.filter(|r| g.roles.contains_key(r)),
+            )
+        })
+        .unwrap_or_default();
+
+    let embed = CreateEmbed::new()
+        .title("⚙️ AuroBOT 설정")
+        .field("알림 채널", channel.map_or("없음".to_string(), |c| format!("<#{}>", c)), true)
+        .field("멘션 역할", role.map_or("없음".to_string(), |r| format!("<@&{}>", r)), true)
+        .field(
+            "입장/퇴장 알림",
+            if config.notify_join_leave { "켜짐" } else { "꺼짐" },
+            true,
+        )
+        .footer(CreateEmbedFooter::new("서버 관리 권한이 있는 멤버만 바꿀 수 있습니다"));
+
+    let channel_menu = CreateSelectMenu::new(
+        format!("{}channel", SETUP_PREFIX),
+        CreateSelectMenuKind::Channel {
+            channel_types: Some(vec![ChannelType::Text]),
+            default_channels: channel.map(|c| vec![c]),
+        },
+    )
+    .placeholder("알림을 보낼 채널");
+    let role_menu = CreateSelectMenu::new(
+        format!("{}role", SETUP_PREFIX),
+        CreateSelectMenuKind::Role {
+            default_roles: role.map(|r| vec![r]),
+        },
+    )
+    .placeholder("활성화 시 멘션할 역할 (비우면 멘션 안 함)")
+    .min_values(0)
+    .max_values(1);
+    let buttons = CreateActionRow::Buttons(vec![
+        CreateButton::new(format!("{}joinleave", SETUP_PREFIX))
+            .label(if config.notify_join_leave { "입장/퇴장 알림 끄기" } else { "입장/퇴장 알림 켜기" })
+            .style(ButtonStyle::Secondary),
+        CreateButton::new(format!("{}done", SETUP_PREFIX))
+            .label("완료")
+            .style(ButtonStyle::Success),
+    ]);
+    (
+        embed,
+        vec![
+            CreateActionRow::SelectMenu(channel_menu),
+            CreateActionRow::SelectMenu(role_menu),
+            buttons,
+        ],
+    )
+}
+
+// 새로 참가한 길드에 설정 마법사 게시: 시스템 채널, 안 되면 봇이 쓸 수 있는 첫 텍스트 채널
+pub async fn post_setup_wizard(ctx: &Context, guild: &Guild) {
+    let bot_id = ctx.cache.current_user().id;
+    let Some(member) = guild.members.get(&bot_id) else {
+        return;
+    };
+    let writable = |channel_id| {
+        guild.channels.get(&channel_id).is_some_and(|c| {
+            c.kind == ChannelType::Text
+                && guild
+                    .user_permissions_in(c, member)
+                    .contains(Permissions::VIEW_CHANNEL | Permissions::SEND_MESSAGES)
+        })
+    };
+    let mut text_channels: Vec<_> = guild.channels.values().filter(|c| writable(c.id)).collect();
+    text_channels.sort_by_key(|c| c.position);
+    let Some(channel_id) = guild
+        .system_channel_id
+        .filter(|&c| writable(c))
+        .or_else(|| text_channels.first().map(|c| c.id))
+    else {
+        return;
+    };
+
+    let (embed, components) = wizard(ctx, guild.id).await;
+    let message = CreateMessage::new()
+        .content(WELCOME)
+        .embed(embed)
+        .components(components);
+    send_or_report(ctx, channel_id, message, "설정 마법사 전송").await;
+}
+
+// /setup: 설정 마법사 다시 열기
+pub async fn handle_setup(ctx: &Context, cmd: &CommandInteraction) {
+    let Some(guild_id) = cmd.guild_id else {
+        return;
+    };
+    let (embed, components) = wizard(ctx, guild_id).await;
+    respond(
+        ctx,
+        cmd,
+        CreateInteractionResponseMessage::new()
+            .embed(embed)
+            .components(components)
+            .ephemeral(true),
+    )
+    .await;
+}
+
+async fn respond_component(ctx: &Context, comp: &ComponentInteraction, response: CreateInteractionResponse) {
+    if let Err(e) = event_metrics::phase(Phase::Discord, comp.create_response(&ctx.http, response)).await {
+        report_error(ctx, "설정 마법사 응답", &e).await;
+    }
+}
+
+async fn reply_ephemeral(ctx: &Context, comp: &ComponentInteraction, content: &str) {
+    respond_component(
+        ctx,
+        comp,
+        CreateInteractionResponse::Message(
+            CreateInteractionResponseMessage::new()
+                .content(content)
+                .ephemeral(true),
+        ),
+    )
+    .await;
+}
+
+// 마법사에서 고른 값을 바로 저장하고 메시지를 새 설정으로 갱신
+pub async fn handle_setup_component(ctx: &Context, comp: &ComponentInteraction) {
+    let Some(guild_id) = comp.guild_id else {
+        return;
+    };
+    let Some(action) = comp.data.custom_id.strip_prefix(SETUP_PREFIX) else {
+        return;
+    };
+    // 마법사는 채널에 공개로 게시되므로 누르는 사람의 권한을 확인
+    let permitted = comp
+        .member
+        .as_ref()
+        .and_then(|m| m.permissions)
+        .is_some_and(|p| p.administrator() || p.manage_guild());
+    if !permitted {
+        reply_ephemeral(ctx, comp, "서버 관리 권한이 있어야 설정을 바꿀 수 있습니다").await;
+        return;
+    }
+
+    let user_id = comp.user.id;
+    let saved = match (action, &comp.data.kind) {
+        ("channel", ComponentInteractionDataKind::ChannelSelect { values }) => {
+            let Some(&channel_id) = values.first() else {
+                return;
+            };
+            update_guild_config(ctx, guild_id, user_id, |c| c.notification_channel = Some(channel_id)).await
+        }
+        ("role", ComponentInteractionDataKind::RoleSelect { values }) => {
+            let role_id = values.first().copied();
+            update_guild_config(ctx, guild_id, user_id, |c| c.mention_role = role_id).await
+        }
+        ("joinleave", _) => {
+            update_guild_config(ctx, guild_id, user_id, |c| c.notify_join_leave = !c.notify_join_leave).await
+        }
+        ("done", _) => {
+            let (embed, _) = wizard(ctx, guild_id).await;
+            let message = CreateInteractionResponseMessage::new()
+                .content("✅ 설정을 마쳤습니다. 바꾸려면 `/setup` 또는 `/config` 를 사용하세요.")
+                .embed(embed)
+                .components(Vec::new());
+            respond_component(ctx, comp, CreateInteractionResponse::UpdateMessage(message)).await;
+            return;
+        }
+        _ => return,
+    };
+    if !saved {
+        reply_ephemeral(ctx, comp, "설정을 저장하지 못했습니다. 잠시 후 다시 시도해주세요.").await;
+        return;
+    }
+
+    let (embed, components) = wizard(ctx, guild_id).await;
+    let message = CreateInteractionResponseMessage::new()
+        .embed(embed)
+        .components(components);
+    respond_component(ctx, comp, CreateInteractionResponse::UpdateMessage(message)).await;
+}
@@ -0,0 +1,114 @@
+use serenity::all::CommandInteraction;
+use serenity::all::CreateEmbed;
+use serenity::all::CreateInteractionResponseMessage;
+use serenity::all::ShardId;
+use serenity::all::ShardManager;
+use serenity::prelude::*;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+use crate::commands::respond;
+
+// 샤드 상태 조회용 ShardManager 핸들
+pub struct ShardManagerKey;
+
+impl TypeMapKey for ShardManagerKey {
+    type Value = Arc<ShardManager>;
+}
+
+// 샤드별로 처리한 이벤트 수
+pub struct ShardEventCounters;
+
+impl TypeMapKey for ShardEventCounters {
+    type Value = Arc<Mutex<HashMap<ShardId, u64>>>;
+}
+
+pub fn new_event_counters() -> Arc<Mutex<HashMap<ShardId, u64>>> {
+    Arc::new(Mutex::new(HashMap::new()))
+}
+
+// 이벤트를 받은 샤드의 카운터 증가
+pub async fn record_event(ctx: &Context) {
+    let counters = {
+        let data = ctx.data.read().await;
+        match data.get::<ShardEventCounters>() {
+            Some(counters) => counters.clone(),
+            None => return,
+        }
+    };
+    *counters.lock().await.entry(ctx.shard_id).or_insert(0) += 1;
+}
+
+// 길드가 속한 샤드 번호 (디스코드 샤딩 공식)
+pub fn shard_of(guild_id: u64, shard_count: u32) -> u32 {
+    ((guild_id >> 22) % u64::from(shard_count.max(1))) as u32
+}
+
+pub async fn handle_shards(ctx: &Context, cmd: &CommandInteraction) {
+    let (manager, counters) = {
+        let data = ctx.data.read().await;
+        (
+            data.get::<ShardManagerKey>().cloned(),
+            data.get::<ShardEventCounters>().cloned(),
+        )
+    };
+    let Some(manager) = manager else {
+        respond(
+            ctx,
+            cmd,
+            CreateInteractionResponseMessage::new()
+                .content("샤드 정보를 찾을 수 없습니다.")
+                .ephemeral(true),
+        )
+        .await;
+        return;
+    };
+
+    let shard_count = ctx.cache.shard_count();
+    let mut guild_counts: HashMap<u32, usize> = HashMap::new();
+    for guild_id in ctx.cache.guilds() {
+        *guild_counts.entry(shard_of(guild_id.get(), shard_count)).or_insert(0) += 1;
+    }
+    let event_counts = match counters {
+        Some(counters) => counters.lock().await.clone(),
+        None => HashMap::new(),
+    };
+
+    let mut lines = Vec::new();
+    {
+        let runners = manager.runners.lock().await;
+        let mut ids: Vec<&ShardId> = runners.keys().collect();
+        ids.sort_by_key(|id| id.0);
+        for id in ids {
+            let info = &runners[id];
+            let latency = info
+                .latency
+                .map(|l| format!("{}ms", l.as_millis()))
+                .unwrap_or_else(|| "-".to_string());
+            lines.push(format!(
+                "**#{}** {} · 지연 {} · 길드 {}개 · 이벤트 {}개",
+                id.0,
+                info.stage,
+                latency,
+                guild_counts.get(&id.0).copied().unwrap_or(0),
+                event_counts.get(id).copied().unwrap_or(0),
+            ));
+        }
+    }
+    if lines.is_empty() {
+        lines.push("실행 중인 샤드가 없습니다.".to_string());
+    }
+
+    let embed = CreateEmbed::new()
+        .title(format!("샤드 상태 (총 {}개)", shard_count))
+        .description(lines.join("\n"));
+    respond(
+        ctx,
+        cmd,
+        CreateInteractionResponseMessage::new()
+            .embed(embed)
+            .ephemeral(true),
+    )
+    .await;
+}
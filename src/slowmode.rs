@@ -0,0 +1,113 @@
+use serenity::all::ChannelId;
+use serenity::all::CommandDataOption;
+use serenity::all::CommandDataOptionValue;
+use serenity::all::CommandInteraction;
+use serenity::all::CreateInteractionResponseMessage;
+use serenity::all::EditChannel;
+use serenity::prelude::*;
+
+use crate::commands::respond;
+use crate::error_report::report_error;
+use crate::guild_config::post_audit_log;
+
+// 디스코드가 허용하는 최대 슬로우 모드 (6시간)
+pub const MAX_SLOWMODE_SECS: u16 = 21600;
+
+fn describe(secs: u16) -> String {
+    if secs == 0 {
+        "꺼짐".to_string()
+    } else {
+        format!("{}초", secs)
+    }
+}
+
+// /slowmode set <seconds> [channel] | status [channel]
+pub async fn handle_slowmode(ctx: &Context, cmd: &CommandInteraction) {
+    let Some(guild_id) = cmd.guild_id else {
+        return;
+    };
+    let Some(sub) = cmd.data.options.first() else {
+        return;
+    };
+    let CommandDataOptionValue::SubCommand(args) = &sub.value else {
+        return;
+    };
+    // 채널을 지정하지 않으면 명령을 실행한 채널
+    let channel_id = args
+        .iter()
+        .find(|o| o.name == "channel")
+        .and_then(|o| o.value.as_channel_id())
+        .unwrap_or(cmd.channel_id);
+
+    let content = match sub.name.as_str() {
+        "set" => set_slowmode(ctx, cmd, args, channel_id).await,
+        "status" => {
+            let current = ctx
+                .cache
+                .guild(guild_id)
+                .and_then(|g| g.channels.get(&channel_id).map(|c| c.rate_limit_per_user));
+            match current {
+                Some(secs) => format!(
+                    "<#{}> 의 슬로우 모드: **{}**",
+                    channel_id,
+                    describe(secs.unwrap_or(0))
+                ),
+                None => "이 서버의 채널이 아닙니다.".to_string(),
+            }
+        }
+        _ => return,
+    };
+
+    respond(
+        ctx,
+        cmd,
+        CreateInteractionResponseMessage::new()
+            .content(content)
+            .ephemeral(true),
+    )
+    .await;
+}
+
+async fn set_slowmode(
+    ctx: &Context,
+    cmd: &CommandInteraction,
+    args: &[CommandDataOption],
+    channel_id: ChannelId,
+) -> String {
+    let Some(guild_id) = cmd.guild_id else {
+        return String::new();
+    };
+    let secs = args
+        .iter()
+        .find(|o| o.name == "seconds")
+        .and_then(|o| o.value.as_i64())
+        .and_then(|v| u16::try_from(v).ok())
+        .filter(|&v| v <= MAX_SLOWMODE_SECS);
+    let Some(secs) = secs else {
+        return format!("0에서 {} 사이의 초를 입력하세요.", MAX_SLOWMODE_SECS);
+    };
+
+    match channel_id
+        .edit(&ctx.http, EditChannel::new().rate_limit_per_user(secs))
+        .await
+    {
+        Ok(_) => {
+            post_audit_log(
+                ctx,
+                guild_id,
+                format!(
+                    "🐢 <@{}> 님이 <#{}> 의 슬로우 모드를 **{}** 으로 변경했습니다.",
+                    cmd.user.id,
+                    channel_id,
+                    describe(secs)
+                ),
+            )
+            .await;
+            format!("<#{}> 의 슬로우 모드를 **{}** 으로 설정했습니다.", channel_id, describe(secs))
+        }
+        Err(e) => {
+            report_error(ctx, "슬로우 모드 변경", &e).await;
+            "슬로우 모드를 변경하지 못했습니다. 봇에 채널 관리 권한이 있는지 확인해주세요.".to_string()
+        }
+    }
+}
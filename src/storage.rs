@@ -0,0 +1,1696 @@
+use serenity::all::ChannelId;
+use serenity::all::GuildId;
+use serenity::all::UserId;
+use serenity::prelude::*;
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePool, SqlitePoolOptions};
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::sync::RwLock;
+
+use crate::channel_status::RenamedChannel;
+use crate::event_metrics::{self, Phase};
+use crate::guild_config::GuildConfig;
+use crate::streaks::VoiceStreak;
+use crate::temp_channels::TempChannel;
+use crate::user_prefs::UserPrefs;
+
+pub const DEFAULT_DATABASE_URL: &str = "sqlite://aurobot.db";
+
+// 모든 기능이 공유하는 SQLite 커넥션 풀
+pub struct Storage;
+
+impl TypeMapKey for Storage {
+    type Value = SqlitePool;
+}
+
+
+// 종료된 보이스 채널 활성화 기록
+#[derive(Debug, Clone)]
+pub struct VoiceSession {
+    pub guild_id: GuildId,
+    // 길드별 활성화 번호 (1부터, guild_config::next_session_id)
+    pub session_id: u64,
+    pub channel_id: ChannelId,
+    pub started_at: i64,
+    pub ended_at: i64,
+    // 활성화 중 최대 동시 접속자 수와 활동 점수 (voice_events::activity_score)
+    pub peak_members: i64,
+    pub activity_score: i64,
+    // 활성화를 시작한 사람 (첫 사람 입장, 봇 제외). 알 수 없으면 None
+    pub starter_id: Option<UserId>,
+}
+
+// 아직 보내지 않은 리마인더
+#[derive(Debug, Clone)]
+pub struct Reminder {
+    pub id: i64,
+    pub user_id: UserId,
+    pub channel_id: ChannelId,
+    // 채널 대신 DM으로 보낼지 여부
+    pub dm: bool,
+    pub message: String,
+    pub due_at: i64,
+}
+
+type ReminderRow = (i64, i64, i64, bool, String, i64);
+
+fn reminder_from_row((id, user_id, channel_id, dm, message, due_at): ReminderRow) -> Reminder {
+    Reminder {
+        id,
+        user_id: UserId::new(from_db(user_id)),
+        channel_id: ChannelId::new(from_db(channel_id)),
+        dm,
+        message,
+        due_at,
+    }
+}
+
+pub fn unix_now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+// 디스코드 ID는 u64이지만 SQLite 정수는 i64이므로 비트 그대로 변환
+fn to_db(id: u64) -> i64 {
+    id as i64
+}
+
+fn from_db(id: i64) -> u64 {
+    id as u64
+}
+
+// 데이터베이스 연결 (파일이 없으면 생성). 스키마는 run_migrations로 맞춤
+pub async fn connect(url: &str) -> Result<SqlitePool, sqlx::Error> {
+    let options = SqliteConnectOptions::from_str(url)?.create_if_missing(true);
+    SqlitePoolOptions::new()
+        .max_connections(4)
+        .connect_with(options)
+        .await
+}
+
+// migrations/ 의 SQL 파일 중 아직 적용하지 않은 것을 순서대로 적용.
+// 파일마다 트랜잭션 안에서 실행되고 적용 기록은 _sqlx_migrations 테이블에 남음
+pub async fn run_migrations(pool: &SqlitePool) -> Result<(), sqlx::Error> {
+    sqlx::migrate!("./migrations").run(pool).await?;
+    Ok(())
+}
+
+// TypeMap에서 커넥션 풀 조회
+pub async fn pool(ctx: &Context) -> Option<SqlitePool> {
+    let data = ctx.data.read().await;
+    data.get::<Storage>().cloned()
+}
+
+// 길드 설정 읽기 캐시. 보이스 이벤트마다 DB를 읽지 않도록 처음 읽을 때 채우고,
+// 설정을 저장하면 새 값으로 바꿈. 예약 작업이 주기적으로 비워 DB와 다시 맞춤
+pub struct GuildSettingsCache;
+
+impl TypeMapKey for GuildSettingsCache {
+    type Value = Arc<SettingsCache>;
+}
+
+#[derive(Default)]
+pub struct SettingsCache {
+    entries: RwLock<HashMap<GuildId, Arc<GuildConfig>>>,
+    // 저장/비우기마다 증가. DB를 읽는 동안 값이 바뀌었으면 읽은 (이전) 값을 캐시에 넣지 않음
+    generation: AtomicU64,
+}
+
+impl SettingsCache {
+    pub async fn get(&self, guild_id: GuildId) -> Option<Arc<GuildConfig>> {
+        self.entries.read().await.get(&guild_id).cloned()
+    }
+
+    // DB를 읽기 전에 받아 두고 fill에 넘김
+    pub fn generation(&self) -> u64 {
+        self.generation.load(Ordering::Acquire)
+    }
+
+    // DB에서 읽은 값을 넣음. 읽기 시작한 뒤 저장/비우기가 있었으면 버림
+    pub async fn fill(&self, guild_id: GuildId, config: Arc<GuildConfig>, generation: u64) {
+        let mut entries = self.entries.write().await;
+        if self.generation() == generation {
+            entries.insert(guild_id, config);
+        }
+    }
+
+    // 저장한 설정으로 교체
+    pub async fn set(&self, guild_id: GuildId, config: Arc<GuildConfig>) {
+        let mut entries = self.entries.write().await;
+        self.generation.fetch_add(1, Ordering::AcqRel);
+        entries.insert(guild_id, config);
+    }
+
+    // 캐시에 있으면 DB를 읽지 않고 그대로, 없으면 DB에서 읽어 채움 (저장된 설정이 없으면 기본값)
+    pub async fn get_or_load(&self, pool: &SqlitePool, guild_id: GuildId) -> Result<Arc<GuildConfig>, sqlx::Error> {
+        if let Some(config) = event_metrics::phase(Phase::Cache, self.get(guild_id)).await {
+            return Ok(config);
+        }
+        let generation = self.generation();
+        let stored = event_metrics::phase(Phase::Storage, get_guild_settings(pool, guild_id)).await?;
+        let config = Arc::new(stored.unwrap_or_default());
+        self.fill(guild_id, config.clone(), generation).await;
+        Ok(config)
+    }
+
+    pub async fn clear(&self) {
+        let mut entries = self.entries.write().await;
+        self.generation.fetch_add(1, Ordering::AcqRel);
+        entries.clear();
+    }
+}
+
+pub async fn settings_cache(ctx: &Context) -> Option<Arc<SettingsCache>> {
+    let data = ctx.data.read().await;
+    data.get::<GuildSettingsCache>().cloned()
+}
+
+pub async fn get_guild_settings(
+    pool: &SqlitePool,
+    guild_id: GuildId,
+) -> Result<Option<GuildConfig>, sqlx::Error> {
+    let row: Option<(String,)> = sqlx::query_as("SELECT config FROM guild_config WHERE guild_id = ?")
+        .bind(to_db(guild_id.get()))
+        .fetch_optional(pool)
+        .await?;
+    match row {
+        Some((json,)) => serde_json::from_str(&json)
+            .map(Some)
+            .map_err(|e| sqlx::Error::Decode(Box::new(e))),
+        None => Ok(None),
+    }
+}
+
+pub async fn upsert_guild_settings(
+    pool: &SqlitePool,
+    guild_id: GuildId,
+    config: &GuildConfig,
+) -> Result<(), sqlx::Error> {
+    let json = serde_json::to_string(config).map_err(|e| sqlx::Error::Encode(Box::new(e)))?;
+    sqlx::query(
+        "INSERT INTO guild_config (guild_id, config) VALUES (?, ?)
+         ON CONFLICT (guild_id) DO UPDATE SET config = excluded.config",
+    )
+    .bind(to_db(guild_id.get()))
+    .bind(json)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+pub async fn insert_session(pool: &SqlitePool, session: &VoiceSession) -> Result<i64, sqlx::Error> {
+    let result = sqlx::query(
+        "INSERT INTO voice_sessions (guild_id, session_id, channel_id, started_at, ended_at, peak_members, activity_score,
+         starter_id)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(to_db(session.guild_id.get()))
+    .bind(to_db(session.session_id))
+    .bind(to_db(session.channel_id.get()))
+    .bind(session.started_at)
+    .bind(session.ended_at)
+    .bind(session.peak_members)
+    .bind(session.activity_score)
+    .bind(session.starter_id.map(|id| to_db(id.get())))
+    .execute(pool)
+    .await?;
+    Ok(result.last_insert_rowid())
+}
+
+// 누적 시간과 오늘(UTC) 날짜의 일별 시간에 함께 더함
+pub async fn add_user_time(
+    pool: &SqlitePool,
+    guild_id: GuildId,
+    user_id: UserId,
+    secs: i64,
+) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    sqlx::query(
+        "INSERT INTO user_voice_stats (guild_id, user_id, total_secs) VALUES (?, ?, ?)
+         ON CONFLICT (guild_id, user_id) DO UPDATE SET total_secs = total_secs + excluded.total_secs",
+    )
+    .bind(to_db(guild_id.get()))
+    .bind(to_db(user_id.get()))
+    .bind(secs)
+    .execute(&mut *tx)
+    .await?;
+    sqlx::query(
+        "INSERT INTO user_voice_daily (day, guild_id, user_id, secs) VALUES (?, ?, ?, ?)
+         ON CONFLICT (day, guild_id, user_id) DO UPDATE SET secs = secs + excluded.secs",
+    )
+    .bind(unix_now().div_euclid(86400))
+    .bind(to_db(guild_id.get()))
+    .bind(to_db(user_id.get()))
+    .bind(secs)
+    .execute(&mut *tx)
+    .await?;
+    tx.commit().await
+}
+
+// 관리자 보정 (/adjust): 누적 시간을 f로 바꿔 저장 (음수는 0으로). (보정 전, 보정 후) 반환
+pub async fn update_user_time(
+    pool: &SqlitePool,
+    guild_id: GuildId,
+    user_id: UserId,
+    f: impl FnOnce(i64) -> i64,
+) -> Result<(i64, i64), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    let before: i64 = sqlx::query_scalar(
+        "SELECT total_secs FROM user_voice_stats WHERE guild_id = ? AND user_id = ?",
+    )
+    .bind(to_db(guild_id.get()))
+    .bind(to_db(user_id.get()))
+    .fetch_optional(&mut *tx)
+    .await?
+    .unwrap_or(0);
+    let after = f(before).max(0);
+    sqlx::query(
+        "INSERT INTO user_voice_stats (guild_id, user_id, total_secs) VALUES (?, ?, ?)
+         ON CONFLICT (guild_id, user_id) DO UPDATE SET total_secs = excluded.total_secs",
+    )
+    .bind(to_db(guild_id.get()))
+    .bind(to_db(user_id.get()))
+    .bind(after)
+    .execute(&mut *tx)
+    .await?;
+    tx.commit().await?;
+    Ok((before, after))
+}
+
+// since_day 이후 사용자의 길드별 보이스 시간 (많은 순)
+pub async fn user_time_since(
+    pool: &SqlitePool,
+    user_id: UserId,
+    since_day: i64,
+) -> Result<Vec<(GuildId, i64)>, sqlx::Error> {
+    let rows: Vec<(i64, i64)> = sqlx::query_as(
+        "SELECT guild_id, SUM(secs) AS total FROM user_voice_daily
+         WHERE user_id = ? AND day >= ?
+         GROUP BY guild_id HAVING total > 0 ORDER BY total DESC",
+    )
+    .bind(to_db(user_id.get()))
+    .bind(since_day)
+    .fetch_all(pool)
+    .await?;
+    Ok(rows
+        .into_iter()
+        .map(|(guild_id, secs)| (GuildId::new(from_db(guild_id)), secs))
+        .collect())
+}
+
+// since_day 이후 모든 길드 합산 보이스 시간이 secs보다 많은 사용자 수 + 1 (전체 순위)
+pub async fn global_time_rank(pool: &SqlitePool, since_day: i64, secs: i64) -> Result<i64, sqlx::Error> {
+    let (above,): (i64,) = sqlx::query_as(
+        "SELECT COUNT(*) FROM (
+             SELECT SUM(secs) AS total FROM user_voice_daily WHERE day >= ? GROUP BY user_id
+         ) WHERE total > ?",
+    )
+    .bind(since_day)
+    .bind(secs)
+    .fetch_one(pool)
+    .await?;
+    Ok(above + 1)
+}
+
+pub async fn prune_user_voice_daily(pool: &SqlitePool, before_day: i64) -> Result<(), sqlx::Error> {
+    sqlx::query("DELETE FROM user_voice_daily WHERE day < ?")
+        .bind(before_day)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+// 사용자의 누적 보이스 시간 (초)
+pub async fn get_user_time(
+    pool: &SqlitePool,
+    guild_id: GuildId,
+    user_id: UserId,
+) -> Result<i64, sqlx::Error> {
+    let row: Option<(i64,)> = sqlx::query_as(
+        "SELECT total_secs FROM user_voice_stats WHERE guild_id = ? AND user_id = ?",
+    )
+    .bind(to_db(guild_id.get()))
+    .bind(to_db(user_id.get()))
+    .fetch_optional(pool)
+    .await?;
+    Ok(row.map(|(secs,)| secs).unwrap_or(0))
+}
+
+// 길드 내 누적 보이스 시간 상위 사용자
+pub async fn top_users(
+    pool: &SqlitePool,
+    guild_id: GuildId,
+    limit: i64,
+) -> Result<Vec<(UserId, i64)>, sqlx::Error> {
+    let rows: Vec<(i64, i64)> = sqlx::query_as(
+        "SELECT user_id, total_secs FROM user_voice_stats
+         WHERE guild_id = ? AND total_secs > 0
+         ORDER BY total_secs DESC LIMIT ?",
+    )
+    .bind(to_db(guild_id.get()))
+    .bind(limit)
+    .fetch_all(pool)
+    .await?;
+    Ok(rows
+        .into_iter()
+        .map(|(user_id, secs)| (UserId::new(from_db(user_id)), secs))
+        .collect())
+}
+
+// since_day 이후 길드 내 보이스 시간 상위 사용자 (일별 기록 기준). 대시보드 API용
+#[cfg_attr(not(feature = "http-api"), allow(dead_code))]
+pub async fn top_users_since(
+    pool: &SqlitePool,
+    guild_id: GuildId,
+    since_day: i64,
+    limit: i64,
+) -> Result<Vec<(UserId, i64)>, sqlx::Error> {
+    let rows: Vec<(i64, i64)> = sqlx::query_as(
+        "SELECT user_id, SUM(secs) AS total FROM user_voice_daily
+         WHERE guild_id = ? AND day >= ?
+         GROUP BY user_id HAVING total > 0 ORDER BY total DESC LIMIT ?",
+    )
+    .bind(to_db(guild_id.get()))
+    .bind(since_day)
+    .bind(limit)
+    .fetch_all(pool)
+    .await?;
+    Ok(rows
+        .into_iter()
+        .map(|(user_id, secs)| (UserId::new(from_db(user_id)), secs))
+        .collect())
+}
+
+// id, session_id, channel_id, started_at, ended_at, peak_members, activity_score, starter_id
+type SessionRow = (i64, i64, i64, i64, i64, i64, i64, Option<i64>);
+
+// 길드의 활성화 번호로 종료된 활성화 기록 조회 (/session)
+pub async fn get_session(
+    pool: &SqlitePool,
+    guild_id: GuildId,
+    session_id: u64,
+) -> Result<Option<VoiceSession>, sqlx::Error> {
+    let row: Option<(i64, i64, i64, i64, i64, Option<i64>)> = sqlx::query_as(
+        "SELECT channel_id, started_at, ended_at, peak_members, activity_score, starter_id FROM voice_sessions
+         WHERE guild_id = ? AND session_id = ?",
+    )
+    .bind(to_db(guild_id.get()))
+    .bind(to_db(session_id))
+    .fetch_optional(pool)
+    .await?;
+    Ok(row.map(
+        |(channel_id, started_at, ended_at, peak_members, activity_score, starter_id)| VoiceSession {
+            guild_id,
+            session_id,
+            channel_id: ChannelId::new(from_db(channel_id)),
+            started_at,
+            ended_at,
+            peak_members,
+            activity_score,
+            starter_id: starter_id.map(|id| UserId::new(from_db(id))),
+        },
+    ))
+}
+
+// 길드에서 이미 쓴 가장 큰 활성화 번호 (종료된 기록과 진행 중인 활성화 모두)
+pub async fn max_session_id(pool: &SqlitePool, guild_id: GuildId) -> Result<u64, sqlx::Error> {
+    let max: Option<i64> = sqlx::query_scalar(
+        "SELECT MAX(session_id) FROM (
+             SELECT session_id FROM voice_sessions WHERE guild_id = ?
+             UNION ALL SELECT session_id FROM active_channels WHERE guild_id = ?
+         )",
+    )
+    .bind(to_db(guild_id.get()))
+    .bind(to_db(guild_id.get()))
+    .fetch_one(pool)
+    .await?;
+    Ok(max.map_or(0, from_db))
+}
+
+// 길드의 다음 활성화 번호를 한 문장으로 올리고 돌려줌 (동시에 불러도 번호가 겹치지 않음).
+// 카운터가 없으면 이미 쓴 가장 큰 번호 다음부터 시작
+pub async fn next_session_id(pool: &SqlitePool, guild_id: GuildId) -> Result<u64, sqlx::Error> {
+    let next: i64 = sqlx::query_scalar(
+        "INSERT INTO session_counters (guild_id, last_session_id)
+         VALUES (?1, (
+             SELECT COALESCE(MAX(session_id), 0) FROM (
+                 SELECT session_id FROM voice_sessions WHERE guild_id = ?1
+                 UNION ALL SELECT session_id FROM active_channels WHERE guild_id = ?1
+             )
+         ) + 1)
+         ON CONFLICT (guild_id) DO UPDATE SET last_session_id = last_session_id + 1
+         RETURNING last_session_id",
+    )
+    .bind(to_db(guild_id.get()))
+    .fetch_one(pool)
+    .await?;
+    Ok(from_db(next))
+}
+
+// since 이후 시작한 종료된 활성화 기록 (id 순). after_id보다 큰 id만 돌려주므로 마지막 id로 다음 페이지 조회
+#[cfg_attr(not(feature = "http-api"), allow(dead_code))]
+pub async fn sessions_since(
+    pool: &SqlitePool,
+    guild_id: GuildId,
+    since: i64,
+    after_id: i64,
+    limit: i64,
+) -> Result<Vec<(i64, VoiceSession)>, sqlx::Error> {
+    let rows: Vec<SessionRow> = sqlx::query_as(
+        "SELECT id, session_id, channel_id, started_at, ended_at, peak_members, activity_score, starter_id
+         FROM voice_sessions
+         WHERE guild_id = ? AND started_at >= ? AND id > ?
+         ORDER BY id LIMIT ?",
+    )
+    .bind(to_db(guild_id.get()))
+    .bind(since)
+    .bind(after_id)
+    .bind(limit)
+    .fetch_all(pool)
+    .await?;
+    Ok(rows
+        .into_iter()
+        .map(|(id, session_id, channel_id, started_at, ended_at, peak_members, activity_score, starter_id)| {
+            let session = VoiceSession {
+                guild_id,
+                session_id: from_db(session_id),
+                channel_id: ChannelId::new(from_db(channel_id)),
+                started_at,
+                ended_at,
+                peak_members,
+                activity_score,
+                starter_id: starter_id.map(|id| UserId::new(from_db(id))),
+            };
+            (id, session)
+        })
+        .collect())
+}
+
+// 활성화 표시를 붙인 채널의 원래 이름 저장
+pub async fn save_channel_rename(
+    pool: &SqlitePool,
+    guild_id: GuildId,
+    channel_id: ChannelId,
+    renamed: &RenamedChannel,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO channel_renames (channel_id, guild_id, original_name, renamed_to) VALUES (?, ?, ?, ?)
+         ON CONFLICT (channel_id) DO UPDATE SET original_name = excluded.original_name, renamed_to = excluded.renamed_to",
+    )
+    .bind(to_db(channel_id.get()))
+    .bind(to_db(guild_id.get()))
+    .bind(&renamed.original)
+    .bind(&renamed.name)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+pub async fn delete_channel_rename(pool: &SqlitePool, channel_id: ChannelId) -> Result<(), sqlx::Error> {
+    sqlx::query("DELETE FROM channel_renames WHERE channel_id = ?")
+        .bind(to_db(channel_id.get()))
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+pub async fn load_channel_renames(
+    pool: &SqlitePool,
+) -> Result<Vec<(GuildId, ChannelId, RenamedChannel)>, sqlx::Error> {
+    let rows: Vec<(i64, i64, String, String)> =
+        sqlx::query_as("SELECT guild_id, channel_id, original_name, renamed_to FROM channel_renames")
+            .fetch_all(pool)
+            .await?;
+    Ok(rows
+        .into_iter()
+        .map(|(guild_id, channel_id, original, name)| {
+            (
+                GuildId::new(from_db(guild_id)),
+                ChannelId::new(from_db(channel_id)),
+                RenamedChannel { original, name },
+            )
+        })
+        .collect())
+}
+
+// 서버에 수식 저장 (같은 이름이면 덮어씀)
+pub async fn save_formula(
+    pool: &SqlitePool,
+    guild_id: GuildId,
+    name: &str,
+    expression: &str,
+    created_by: UserId,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO calc_formulas (guild_id, name, expression, created_by) VALUES (?, ?, ?, ?)
+         ON CONFLICT (guild_id, name) DO UPDATE SET expression = excluded.expression, created_by = excluded.created_by",
+    )
+    .bind(to_db(guild_id.get()))
+    .bind(name)
+    .bind(expression)
+    .bind(to_db(created_by.get()))
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+// 저장한 수식 삭제. 있었으면 true
+pub async fn delete_formula(pool: &SqlitePool, guild_id: GuildId, name: &str) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query("DELETE FROM calc_formulas WHERE guild_id = ? AND name = ?")
+        .bind(to_db(guild_id.get()))
+        .bind(name)
+        .execute(pool)
+        .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+// 서버에 저장한 수식 (이름, 수식), 이름 순
+pub async fn guild_formulas(pool: &SqlitePool, guild_id: GuildId) -> Result<Vec<(String, String)>, sqlx::Error> {
+    sqlx::query_as("SELECT name, expression FROM calc_formulas WHERE guild_id = ? ORDER BY name")
+        .bind(to_db(guild_id.get()))
+        .fetch_all(pool)
+        .await
+}
+
+// 사용자 설정 저장 (있으면 덮어씀)
+pub async fn save_user_prefs(pool: &SqlitePool, user_id: UserId, prefs: &UserPrefs) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO user_prefs (user_id, timezone, weekly_dm_summary, tts_opt_out) VALUES (?, ?, ?, ?)
+         ON CONFLICT (user_id) DO UPDATE SET timezone = excluded.timezone,
+             weekly_dm_summary = excluded.weekly_dm_summary, tts_opt_out = excluded.tts_opt_out",
+    )
+    .bind(to_db(user_id.get()))
+    .bind(&prefs.timezone)
+    .bind(prefs.weekly_dm_summary)
+    .bind(prefs.tts_opt_out)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+pub async fn load_user_prefs(pool: &SqlitePool) -> Result<Vec<(UserId, UserPrefs)>, sqlx::Error> {
+    let rows: Vec<(i64, String, bool, bool)> =
+        sqlx::query_as("SELECT user_id, timezone, weekly_dm_summary, tts_opt_out FROM user_prefs")
+            .fetch_all(pool)
+            .await?;
+    Ok(rows
+        .into_iter()
+        .map(|(user_id, timezone, weekly_dm_summary, tts_opt_out)| {
+            (
+                UserId::new(from_db(user_id)),
+                UserPrefs {
+                    timezone,
+                    weekly_dm_summary,
+                    tts_opt_out,
+                },
+            )
+        })
+        .collect())
+}
+
+pub async fn insert_temp_channel(
+    pool: &SqlitePool,
+    guild_id: GuildId,
+    channel_id: ChannelId,
+    owner_id: UserId,
+    created_at: i64,
+) -> Result<(), sqlx::Error> {
+    sqlx::query("INSERT OR REPLACE INTO temp_channels (channel_id, guild_id, owner_id, created_at) VALUES (?, ?, ?, ?)")
+        .bind(to_db(channel_id.get()))
+        .bind(to_db(guild_id.get()))
+        .bind(to_db(owner_id.get()))
+        .bind(created_at)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+pub async fn delete_temp_channel(pool: &SqlitePool, channel_id: ChannelId) -> Result<(), sqlx::Error> {
+    sqlx::query("DELETE FROM temp_channels WHERE channel_id = ?")
+        .bind(to_db(channel_id.get()))
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+pub async fn load_temp_channels(pool: &SqlitePool) -> Result<Vec<(ChannelId, TempChannel)>, sqlx::Error> {
+    let rows: Vec<(i64, i64, i64)> = sqlx::query_as("SELECT channel_id, guild_id, owner_id FROM temp_channels")
+        .fetch_all(pool)
+        .await?;
+    Ok(rows
+        .into_iter()
+        .map(|(channel_id, guild_id, owner_id)| {
+            (
+                ChannelId::new(from_db(channel_id)),
+                TempChannel {
+                    guild_id: GuildId::new(from_db(guild_id)),
+                    owner_id: UserId::new(from_db(owner_id)),
+                },
+            )
+        })
+        .collect())
+}
+
+type StreakRow = (i64, i64, i64, i64, i64, i64, i64);
+// guild_id, user_id + StreakRow
+type GuildStreakRow = (i64, i64, i64, i64, i64, i64, i64, i64, i64);
+
+fn streak_from_row((current, best, last_day, day, day_secs, remind, reminded_day): StreakRow) -> VoiceStreak {
+    VoiceStreak {
+        current,
+        best,
+        last_day,
+        day,
+        day_secs,
+        remind: remind != 0,
+        reminded_day,
+    }
+}
+
+pub async fn load_streak(
+    pool: &SqlitePool,
+    guild_id: GuildId,
+    user_id: UserId,
+) -> Result<Option<VoiceStreak>, sqlx::Error> {
+    let row: Option<StreakRow> = sqlx::query_as(
+        "SELECT current, best, last_day, day, day_secs, remind, reminded_day \
+         FROM voice_streaks WHERE guild_id = ? AND user_id = ?",
+    )
+    .bind(to_db(guild_id.get()))
+    .bind(to_db(user_id.get()))
+    .fetch_optional(pool)
+    .await?;
+    Ok(row.map(streak_from_row))
+}
+
+// 연속 기록 저장 (알림 설정은 set_streak_reminder로만 바꿈)
+pub async fn save_streak(
+    pool: &SqlitePool,
+    guild_id: GuildId,
+    user_id: UserId,
+    streak: &VoiceStreak,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO voice_streaks (guild_id, user_id, current, best, last_day, day, day_secs) \
+         VALUES (?, ?, ?, ?, ?, ?, ?) \
+         ON CONFLICT (guild_id, user_id) DO UPDATE SET current = excluded.current, best = excluded.best, \
+         last_day = excluded.last_day, day = excluded.day, day_secs = excluded.day_secs",
+    )
+    .bind(to_db(guild_id.get()))
+    .bind(to_db(user_id.get()))
+    .bind(streak.current)
+    .bind(streak.best)
+    .bind(streak.last_day)
+    .bind(streak.day)
+    .bind(streak.day_secs)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+pub async fn set_streak_reminder(
+    pool: &SqlitePool,
+    guild_id: GuildId,
+    user_id: UserId,
+    remind: bool,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO voice_streaks (guild_id, user_id, remind) VALUES (?, ?, ?) \
+         ON CONFLICT (guild_id, user_id) DO UPDATE SET remind = excluded.remind",
+    )
+    .bind(to_db(guild_id.get()))
+    .bind(to_db(user_id.get()))
+    .bind(remind)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+pub async fn mark_streak_reminded(
+    pool: &SqlitePool,
+    guild_id: GuildId,
+    user_id: UserId,
+    day: i64,
+) -> Result<(), sqlx::Error> {
+    sqlx::query("UPDATE voice_streaks SET reminded_day = ? WHERE guild_id = ? AND user_id = ?")
+        .bind(day)
+        .bind(to_db(guild_id.get()))
+        .bind(to_db(user_id.get()))
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+// 끊기기 전 알림을 신청했고 이어지는 기록이 있는 사용자 (날짜 조건은 서버 시간대로 호출하는 쪽에서 확인)
+pub async fn streak_reminders(pool: &SqlitePool) -> Result<Vec<(GuildId, UserId, VoiceStreak)>, sqlx::Error> {
+    let rows: Vec<GuildStreakRow> = sqlx::query_as(
+        "SELECT guild_id, user_id, current, best, last_day, day, day_secs, remind, reminded_day \
+         FROM voice_streaks WHERE remind = 1 AND current > 0",
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(rows
+        .into_iter()
+        .map(|(guild_id, user_id, current, best, last_day, day, day_secs, remind, reminded_day)| {
+            (
+                GuildId::new(from_db(guild_id)),
+                UserId::new(from_db(user_id)),
+                streak_from_row((current, best, last_day, day, day_secs, remind, reminded_day)),
+            )
+        })
+        .collect())
+}
+
+// since 이후 시작한 활성화를 가장 많이 시작한 사용자 (횟수 순)
+pub async fn top_starters(
+    pool: &SqlitePool,
+    guild_id: GuildId,
+    since: i64,
+    limit: i64,
+) -> Result<Vec<(UserId, i64)>, sqlx::Error> {
+    let rows: Vec<(i64, i64)> = sqlx::query_as(
+        "SELECT starter_id, COUNT(*) AS started FROM voice_sessions
+         WHERE guild_id = ? AND started_at >= ? AND starter_id IS NOT NULL
+         GROUP BY starter_id ORDER BY started DESC, MIN(started_at) LIMIT ?",
+    )
+    .bind(to_db(guild_id.get()))
+    .bind(since)
+    .bind(limit)
+    .fetch_all(pool)
+    .await?;
+    Ok(rows
+        .into_iter()
+        .map(|(user_id, count)| (UserId::new(from_db(user_id)), count))
+        .collect())
+}
+
+// 채널 활성화 시작 기록 (재시작 후 복원용)
+pub async fn save_active_channel(
+    pool: &SqlitePool,
+    guild_id: GuildId,
+    channel_id: ChannelId,
+    session_id: u64,
+    started_at: i64,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO active_channels (channel_id, guild_id, session_id, started_at) VALUES (?, ?, ?, ?)
+         ON CONFLICT (channel_id) DO UPDATE SET guild_id = excluded.guild_id, session_id = excluded.session_id,
+         started_at = excluded.started_at, starter_id = NULL",
+    )
+    .bind(to_db(channel_id.get()))
+    .bind(to_db(guild_id.get()))
+    .bind(to_db(session_id))
+    .bind(started_at)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+// 진행 중인 활성화를 시작한 사람 기록 (재시작 후에도 유지)
+pub async fn set_active_channel_starter(
+    pool: &SqlitePool,
+    channel_id: ChannelId,
+    starter_id: UserId,
+) -> Result<(), sqlx::Error> {
+    sqlx::query("UPDATE active_channels SET starter_id = ? WHERE channel_id = ?")
+        .bind(to_db(starter_id.get()))
+        .bind(to_db(channel_id.get()))
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+pub async fn remove_active_channel(pool: &SqlitePool, channel_id: ChannelId) -> Result<(), sqlx::Error> {
+    sqlx::query("DELETE FROM active_channels WHERE channel_id = ?")
+        .bind(to_db(channel_id.get()))
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+// 진행 중이던 채널 활성화 목록: (길드, 채널, 활성화 번호, 시작 시각, 시작한 사람)
+pub async fn load_active_channels(
+    pool: &SqlitePool,
+) -> Result<Vec<(GuildId, ChannelId, u64, i64, Option<UserId>)>, sqlx::Error> {
+    let rows: Vec<(i64, i64, i64, i64, Option<i64>)> =
+        sqlx::query_as("SELECT guild_id, channel_id, session_id, started_at, starter_id FROM active_channels")
+            .fetch_all(pool)
+            .await?;
+    Ok(rows
+        .into_iter()
+        .map(|(guild_id, channel_id, session_id, started_at, starter_id)| {
+            (
+                GuildId::new(from_db(guild_id)),
+                ChannelId::new(from_db(channel_id)),
+                from_db(session_id),
+                started_at,
+                starter_id.map(|id| UserId::new(from_db(id))),
+            )
+        })
+        .collect())
+}
+
+// 리마인더 저장 후 ID 반환
+pub async fn insert_reminder(
+    pool: &SqlitePool,
+    user_id: UserId,
+    channel_id: ChannelId,
+    dm: bool,
+    message: &str,
+    due_at: i64,
+) -> Result<i64, sqlx::Error> {
+    let result = sqlx::query(
+        "INSERT INTO reminders (user_id, channel_id, dm, message, due_at, created_at) VALUES (?, ?, ?, ?, ?, ?)",
+    )
+    .bind(to_db(user_id.get()))
+    .bind(to_db(channel_id.get()))
+    .bind(dm)
+    .bind(message)
+    .bind(due_at)
+    .bind(unix_now())
+    .execute(pool)
+    .await?;
+    Ok(result.last_insert_rowid())
+}
+
+// 리마인더 삭제. user_id를 주면 그 사용자의 것만 삭제. 실제로 삭제됐는지 반환
+pub async fn delete_reminder(
+    pool: &SqlitePool,
+    id: i64,
+    user_id: Option<UserId>,
+) -> Result<bool, sqlx::Error> {
+    let result = match user_id {
+        Some(user_id) => {
+            sqlx::query("DELETE FROM reminders WHERE id = ? AND user_id = ?")
+                .bind(id)
+                .bind(to_db(user_id.get()))
+                .execute(pool)
+                .await?
+        }
+        None => {
+            sqlx::query("DELETE FROM reminders WHERE id = ?")
+                .bind(id)
+                .execute(pool)
+                .await?
+        }
+    };
+    Ok(result.rows_affected() > 0)
+}
+
+// 사용자의 대기 중인 리마인더 (예정 시각 순)
+pub async fn user_reminders(pool: &SqlitePool, user_id: UserId) -> Result<Vec<Reminder>, sqlx::Error> {
+    let rows: Vec<ReminderRow> = sqlx::query_as(
+        "SELECT id, user_id, channel_id, dm, message, due_at FROM reminders
+         WHERE user_id = ? ORDER BY due_at",
+    )
+    .bind(to_db(user_id.get()))
+    .fetch_all(pool)
+    .await?;
+    Ok(rows.into_iter().map(reminder_from_row).collect())
+}
+
+// 모든 대기 중인 리마인더 (시작 시 다시 예약)
+pub async fn load_reminders(pool: &SqlitePool) -> Result<Vec<Reminder>, sqlx::Error> {
+    let rows: Vec<ReminderRow> = sqlx::query_as(
+        "SELECT id, user_id, channel_id, dm, message, due_at FROM reminders ORDER BY due_at",
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(rows.into_iter().map(reminder_from_row).collect())
+}
+
+// 하루 동안의 커맨드 사용 통계 한 줄
+#[derive(Debug, Clone)]
+pub struct CommandUsageRow {
+    pub day: i64,
+    pub guild_id: Option<GuildId>,
+    pub command: String,
+    pub count: i64,
+    pub errors: i64,
+    pub latency: Vec<i64>,
+}
+
+fn guild_to_db(guild_id: Option<GuildId>) -> i64 {
+    guild_id.map(|g| to_db(g.get())).unwrap_or(0)
+}
+
+fn guild_from_db(guild_id: i64) -> Option<GuildId> {
+    (guild_id != 0).then(|| GuildId::new(from_db(guild_id)))
+}
+
+// 사용 통계를 기존 값에 더함 (응답 시간 구간도 구간별로 합산). 한 트랜잭션에서 처리
+pub async fn add_command_usage(
+    pool: &SqlitePool,
+    rows: &[CommandUsageRow],
+    calc_errors: &[(i64, Option<GuildId>, String, i64)],
+) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    for row in rows {
+        let existing: Option<(String,)> = sqlx::query_as(
+            "SELECT latency FROM command_usage WHERE day = ? AND guild_id = ? AND command = ?",
+        )
+        .bind(row.day)
+        .bind(guild_to_db(row.guild_id))
+        .bind(&row.command)
+        .fetch_optional(&mut *tx)
+        .await?;
+        let mut latency: Vec<i64> = existing
+            .and_then(|(json,)| serde_json::from_str(&json).ok())
+            .unwrap_or_default();
+        if latency.len() < row.latency.len() {
+            latency.resize(row.latency.len(), 0);
+        }
+        for (total, add) in latency.iter_mut().zip(&row.latency) {
+            *total += add;
+        }
+        let latency = serde_json::to_string(&latency).map_err(|e| sqlx::Error::Encode(Box::new(e)))?;
+        sqlx::query(
+            "INSERT INTO command_usage (day, guild_id, command, count, errors, latency) VALUES (?, ?, ?, ?, ?, ?)
+             ON CONFLICT (day, guild_id, command) DO UPDATE SET
+                 count = count + excluded.count,
+                 errors = errors + excluded.errors,
+                 latency = excluded.latency",
+        )
+        .bind(row.day)
+        .bind(guild_to_db(row.guild_id))
+        .bind(&row.command)
+        .bind(row.count)
+        .bind(row.errors)
+        .bind(latency)
+        .execute(&mut *tx)
+        .await?;
+    }
+    for (day, guild_id, kind, count) in calc_errors {
+        sqlx::query(
+            "INSERT INTO calc_errors (day, guild_id, kind, count) VALUES (?, ?, ?, ?)
+             ON CONFLICT (day, guild_id, kind) DO UPDATE SET count = count + excluded.count",
+        )
+        .bind(day)
+        .bind(guild_to_db(*guild_id))
+        .bind(kind)
+        .bind(count)
+        .execute(&mut *tx)
+        .await?;
+    }
+    tx.commit().await
+}
+
+// since_day 이후의 커맨드 사용 통계. guild_id가 None이면 모든 길드
+pub async fn command_usage_since(
+    pool: &SqlitePool,
+    since_day: i64,
+    guild_id: Option<GuildId>,
+) -> Result<Vec<CommandUsageRow>, sqlx::Error> {
+    let rows: Vec<(i64, i64, String, i64, i64, String)> = sqlx::query_as(
+        "SELECT day, guild_id, command, count, errors, latency FROM command_usage
+         WHERE day >= ? AND (? IS NULL OR guild_id = ?)",
+    )
+    .bind(since_day)
+    .bind(guild_id.map(|g| to_db(g.get())))
+    .bind(guild_id.map(|g| to_db(g.get())))
+    .fetch_all(pool)
+    .await?;
+    Ok(rows
+        .into_iter()
+        .map(|(day, guild_id, command, count, errors, latency)| CommandUsageRow {
+            day,
+            guild_id: guild_from_db(guild_id),
+            command,
+            count,
+            errors,
+            latency: serde_json::from_str(&latency).unwrap_or_default(),
+        })
+        .collect())
+}
+
+// since_day 이후의 계산기 오류 종류별 횟수 (많은 순)
+pub async fn calc_errors_since(
+    pool: &SqlitePool,
+    since_day: i64,
+    guild_id: Option<GuildId>,
+) -> Result<Vec<(String, i64)>, sqlx::Error> {
+    sqlx::query_as(
+        "SELECT kind, SUM(count) AS total FROM calc_errors
+         WHERE day >= ? AND (? IS NULL OR guild_id = ?)
+         GROUP BY kind ORDER BY total DESC",
+    )
+    .bind(since_day)
+    .bind(guild_id.map(|g| to_db(g.get())))
+    .bind(guild_id.map(|g| to_db(g.get())))
+    .fetch_all(pool)
+    .await
+}
+
+// 길드 설정 변경 기록 한 건
+#[derive(Debug, Clone)]
+pub struct ConfigChange {
+    pub user_id: UserId,
+    pub changed_at: i64,
+    pub key: String,
+    pub old_value: String,
+    pub new_value: String,
+}
+
+// 한 번의 설정 변경에서 바뀐 항목들을 함께 기록
+pub async fn insert_config_changes(
+    pool: &SqlitePool,
+    guild_id: GuildId,
+    changes: &[ConfigChange],
+) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    for change in changes {
+        sqlx::query(
+            "INSERT INTO config_changes (guild_id, user_id, changed_at, key, old_value, new_value)
+             VALUES (?, ?, ?, ?, ?, ?)",
+        )
+        .bind(to_db(guild_id.get()))
+        .bind(to_db(change.user_id.get()))
+        .bind(change.changed_at)
+        .bind(&change.key)
+        .bind(&change.old_value)
+        .bind(&change.new_value)
+        .execute(&mut *tx)
+        .await?;
+    }
+    tx.commit().await
+}
+
+// 최근 설정 변경 기록 (최신순). key가 있으면 그 설정만
+pub async fn config_changes(
+    pool: &SqlitePool,
+    guild_id: GuildId,
+    key: Option<&str>,
+    limit: i64,
+) -> Result<Vec<ConfigChange>, sqlx::Error> {
+    let rows: Vec<(i64, i64, String, String, String)> = sqlx::query_as(
+        "SELECT user_id, changed_at, key, old_value, new_value FROM config_changes
+         WHERE guild_id = ? AND (? IS NULL OR key = ?)
+         ORDER BY id DESC LIMIT ?",
+    )
+    .bind(to_db(guild_id.get()))
+    .bind(key)
+    .bind(key)
+    .bind(limit)
+    .fetch_all(pool)
+    .await?;
+    Ok(rows
+        .into_iter()
+        .map(|(user_id, changed_at, key, old_value, new_value)| ConfigChange {
+            user_id: UserId::new(from_db(user_id)),
+            changed_at,
+            key,
+            old_value,
+            new_value,
+        })
+        .collect())
+}
+
+// before보다 오래된 기록과, 길드마다 최근 keep개를 넘는 기록 삭제. 삭제한 수 반환
+pub async fn prune_config_changes(pool: &SqlitePool, before: i64, keep: i64) -> Result<u64, sqlx::Error> {
+    let old = sqlx::query("DELETE FROM config_changes WHERE changed_at < ?")
+        .bind(before)
+        .execute(pool)
+        .await?;
+    let excess = sqlx::query(
+        "DELETE FROM config_changes WHERE id IN (
+             SELECT id FROM (
+                 SELECT id, ROW_NUMBER() OVER (PARTITION BY guild_id ORDER BY id DESC) AS n
+                 FROM config_changes
+             ) WHERE n > ?
+         )",
+    )
+    .bind(keep)
+    .execute(pool)
+    .await?;
+    Ok(old.rows_affected() + excess.rows_affected())
+}
+
+// 받은 피드백 기록
+pub async fn insert_feedback(
+    pool: &SqlitePool,
+    user_id: UserId,
+    guild_id: Option<GuildId>,
+    channel_id: ChannelId,
+    content: &str,
+    created_at: i64,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO feedback (user_id, guild_id, channel_id, content, created_at) VALUES (?, ?, ?, ?, ?)",
+    )
+    .bind(to_db(user_id.get()))
+    .bind(guild_id.map(|g| to_db(g.get())))
+    .bind(to_db(channel_id.get()))
+    .bind(content)
+    .bind(created_at)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+// since 이후 이 사용자가 보낸 피드백 수
+pub async fn feedback_count_since(pool: &SqlitePool, user_id: UserId, since: i64) -> Result<i64, sqlx::Error> {
+    sqlx::query_scalar("SELECT COUNT(*) FROM feedback WHERE user_id = ? AND created_at >= ?")
+        .bind(to_db(user_id.get()))
+        .bind(since)
+        .fetch_one(pool)
+        .await
+}
+
+pub async fn is_feedback_blocked(pool: &SqlitePool, user_id: UserId) -> Result<bool, sqlx::Error> {
+    let row: Option<i64> = sqlx::query_scalar("SELECT 1 FROM feedback_blocks WHERE user_id = ?")
+        .bind(to_db(user_id.get()))
+        .fetch_optional(pool)
+        .await?;
+    Ok(row.is_some())
+}
+
+// 피드백 차단/해제. 상태가 실제로 바뀌었으면 true
+pub async fn set_feedback_blocked(pool: &SqlitePool, user_id: UserId, blocked: bool) -> Result<bool, sqlx::Error> {
+    let result = if blocked {
+        sqlx::query("INSERT OR IGNORE INTO feedback_blocks (user_id, blocked_at) VALUES (?, ?)")
+            .bind(to_db(user_id.get()))
+            .bind(unix_now())
+            .execute(pool)
+            .await?
+    } else {
+        sqlx::query("DELETE FROM feedback_blocks WHERE user_id = ?")
+            .bind(to_db(user_id.get()))
+            .execute(pool)
+            .await?
+    };
+    Ok(result.rows_affected() > 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GUILD: GuildId = GuildId::new(1);
+    const OTHER_GUILD: GuildId = GuildId::new(2);
+    const ALICE: UserId = UserId::new(10);
+    const BOB: UserId = UserId::new(11);
+    const CHANNEL: ChannelId = ChannelId::new(100);
+    const OTHER_CHANNEL: ChannelId = ChannelId::new(101);
+
+    // 메모리 DB는 연결마다 따로 생기므로 연결 하나만 쓰는 풀
+    async fn memory_pool() -> SqlitePool {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .idle_timeout(None)
+            .max_lifetime(None)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        run_migrations(&pool).await.unwrap();
+        pool
+    }
+
+    fn session(session_id: u64, started_at: i64, starter_id: Option<UserId>) -> VoiceSession {
+        VoiceSession {
+            guild_id: GUILD,
+            session_id,
+            channel_id: CHANNEL,
+            started_at,
+            ended_at: started_at + 600,
+            peak_members: 3,
+            activity_score: 42,
+            starter_id,
+        }
+    }
+
+    fn today() -> i64 {
+        unix_now().div_euclid(86400)
+    }
+
+    #[tokio::test]
+    async fn migrations_are_idempotent() {
+        let pool = memory_pool().await;
+        run_migrations(&pool).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn guild_settings_round_trip() {
+        let pool = memory_pool().await;
+        assert_eq!(get_guild_settings(&pool, GUILD).await.unwrap(), None);
+
+        let mut config = GuildConfig {
+            notification_channel: Some(CHANNEL),
+            ..GuildConfig::default()
+        };
+        upsert_guild_settings(&pool, GUILD, &config).await.unwrap();
+        assert_eq!(get_guild_settings(&pool, GUILD).await.unwrap(), Some(config.clone()));
+
+        // 다시 저장하면 덮어씀
+        config.enable_voice_log = !config.enable_voice_log;
+        upsert_guild_settings(&pool, GUILD, &config).await.unwrap();
+        assert_eq!(get_guild_settings(&pool, GUILD).await.unwrap(), Some(config));
+        assert_eq!(get_guild_settings(&pool, OTHER_GUILD).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn sessions_round_trip() {
+        let pool = memory_pool().await;
+        let first = insert_session(&pool, &session(1, 1000, Some(ALICE))).await.unwrap();
+        let second = insert_session(&pool, &session(2, 2000, None)).await.unwrap();
+        assert!(second > first);
+
+        let found = get_session(&pool, GUILD, 1).await.unwrap().unwrap();
+        assert_eq!(found.channel_id, CHANNEL);
+        assert_eq!((found.started_at, found.ended_at), (1000, 1600));
+        assert_eq!((found.peak_members, found.activity_score), (3, 42));
+        assert_eq!(found.starter_id, Some(ALICE));
+        assert!(get_session(&pool, GUILD, 3).await.unwrap().is_none());
+        assert!(get_session(&pool, OTHER_GUILD, 1).await.unwrap().is_none());
+
+        // since와 after_id로 페이지 나누기
+        let page = sessions_since(&pool, GUILD, 0, 0, 1).await.unwrap();
+        assert_eq!(page.len(), 1);
+        assert_eq!(page[0].1.session_id, 1);
+        let next = sessions_since(&pool, GUILD, 0, page[0].0, 10).await.unwrap();
+        assert_eq!(next.iter().map(|(_, s)| s.session_id).collect::<Vec<_>>(), vec![2]);
+        assert_eq!(next[0].1.starter_id, None);
+        assert_eq!(sessions_since(&pool, GUILD, 1500, 0, 10).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn max_session_id_counts_active_channels() {
+        let pool = memory_pool().await;
+        assert_eq!(max_session_id(&pool, GUILD).await.unwrap(), 0);
+        insert_session(&pool, &session(4, 1000, None)).await.unwrap();
+        assert_eq!(max_session_id(&pool, GUILD).await.unwrap(), 4);
+        save_active_channel(&pool, GUILD, OTHER_CHANNEL, 7, 2000).await.unwrap();
+        assert_eq!(max_session_id(&pool, GUILD).await.unwrap(), 7);
+        assert_eq!(max_session_id(&pool, OTHER_GUILD).await.unwrap(), 0);
+    }
+
+    // 활성화 번호는 길드마다 따로 1씩 늘어나고, 설정을 저장해도 되돌아가지 않음
+    #[tokio::test]
+    async fn next_session_id_is_monotonic_per_guild() {
+        let pool = memory_pool().await;
+        for expected in 1..=3 {
+            assert_eq!(next_session_id(&pool, GUILD).await.unwrap(), expected);
+        }
+        assert_eq!(next_session_id(&pool, OTHER_GUILD).await.unwrap(), 1);
+        upsert_guild_settings(&pool, GUILD, &GuildConfig::default()).await.unwrap();
+        assert_eq!(next_session_id(&pool, GUILD).await.unwrap(), 4);
+        assert_eq!(next_session_id(&pool, OTHER_GUILD).await.unwrap(), 2);
+    }
+
+    // 동시에 요청해도 번호가 겹치지 않음
+    #[tokio::test]
+    async fn next_session_id_concurrent_calls_are_unique() {
+        let pool = memory_pool().await;
+        let mut calls = tokio::task::JoinSet::new();
+        for _ in 0..10 {
+            let pool = pool.clone();
+            calls.spawn(async move { next_session_id(&pool, GUILD).await.unwrap() });
+        }
+        let mut ids = calls.join_all().await;
+        ids.sort_unstable();
+        assert_eq!(ids, (1..=10).collect::<Vec<_>>());
+    }
+
+    // 카운터가 없던 길드는 이미 쓴 가장 큰 번호 다음부터
+    #[tokio::test]
+    async fn next_session_id_continues_after_used_ids() {
+        let pool = memory_pool().await;
+        insert_session(&pool, &session(4, 1000, None)).await.unwrap();
+        save_active_channel(&pool, GUILD, OTHER_CHANNEL, 7, 2000).await.unwrap();
+        assert_eq!(next_session_id(&pool, GUILD).await.unwrap(), 8);
+        assert_eq!(next_session_id(&pool, GUILD).await.unwrap(), 9);
+    }
+
+    // 수식은 서버별로 저장되고 같은 이름은 덮어씀
+    #[tokio::test]
+    async fn formulas_round_trip() {
+        let pool = memory_pool().await;
+        save_formula(&pool, GUILD, "원넓이", "pi*2^2", ALICE).await.unwrap();
+        save_formula(&pool, GUILD, "area", "pi*3^2", ALICE).await.unwrap();
+        save_formula(&pool, GUILD, "area", "pi*4^2", BOB).await.unwrap();
+        save_formula(&pool, OTHER_GUILD, "tax", "100*0.1", BOB).await.unwrap();
+
+        let formulas = guild_formulas(&pool, GUILD).await.unwrap();
+        assert_eq!(
+            formulas,
+            vec![
+                ("area".to_string(), "pi*4^2".to_string()),
+                ("원넓이".to_string(), "pi*2^2".to_string()),
+            ]
+        );
+        assert!(delete_formula(&pool, GUILD, "area").await.unwrap());
+        assert!(!delete_formula(&pool, GUILD, "area").await.unwrap());
+        assert_eq!(guild_formulas(&pool, GUILD).await.unwrap().len(), 1);
+        assert_eq!(guild_formulas(&pool, OTHER_GUILD).await.unwrap().len(), 1);
+    }
+
+    fn config_with_channel(channel: u64) -> GuildConfig {
+        GuildConfig {
+            notification_channel: Some(ChannelId::new(channel)),
+            ..GuildConfig::default()
+        }
+    }
+
+    // 한 번 읽은 뒤에는 DB를 읽지 않음: DB를 바꾸고 닫아도 캐시 값 그대로
+    #[tokio::test]
+    async fn settings_cache_skips_db_after_warm_up() {
+        let pool = memory_pool().await;
+        upsert_guild_settings(&pool, GUILD, &config_with_channel(1)).await.unwrap();
+        let cache = SettingsCache::default();
+        assert_eq!(*cache.get_or_load(&pool, GUILD).await.unwrap(), config_with_channel(1));
+
+        upsert_guild_settings(&pool, GUILD, &config_with_channel(2)).await.unwrap();
+        pool.close().await;
+        for _ in 0..3 {
+            assert_eq!(*cache.get_or_load(&pool, GUILD).await.unwrap(), config_with_channel(1));
+        }
+        // 캐시에 없는 길드는 DB를 읽어야 하므로 닫힌 풀에서는 실패
+        assert!(cache.get_or_load(&pool, OTHER_GUILD).await.is_err());
+    }
+
+    // 저장된 설정이 없는 길드는 기본값으로 채움
+    #[tokio::test]
+    async fn settings_cache_fills_defaults() {
+        let pool = memory_pool().await;
+        let cache = SettingsCache::default();
+        assert_eq!(*cache.get_or_load(&pool, GUILD).await.unwrap(), GuildConfig::default());
+        assert!(cache.get(GUILD).await.is_some());
+    }
+
+    // 읽는 도중 저장/비우기가 있었으면 읽은 (이전) 값은 캐시에 넣지 않음
+    #[tokio::test]
+    async fn stale_fill_is_dropped_after_invalidation() {
+        let cache = SettingsCache::default();
+        let generation = cache.generation();
+        cache.set(GUILD, Arc::new(config_with_channel(2))).await;
+        cache.fill(GUILD, Arc::new(config_with_channel(1)), generation).await;
+        assert_eq!(*cache.get(GUILD).await.unwrap(), config_with_channel(2));
+
+        let generation = cache.generation();
+        cache.clear().await;
+        cache.fill(GUILD, Arc::new(config_with_channel(1)), generation).await;
+        assert!(cache.get(GUILD).await.is_none());
+    }
+
+    // 읽기와 저장이 동시에 일어나도 저장한 값이 남음. DB의 이전 값을 읽은 작업이 덮어쓰지 않음
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn concurrent_loads_do_not_overwrite_saved_config() {
+        let pool = memory_pool().await;
+        upsert_guild_settings(&pool, GUILD, &config_with_channel(1)).await.unwrap();
+        let cache = Arc::new(SettingsCache::default());
+        let tasks: Vec<_> = (0..16)
+            .map(|_| {
+                let (cache, pool) = (cache.clone(), pool.clone());
+                tokio::spawn(async move { cache.get_or_load(&pool, GUILD).await.unwrap() })
+            })
+            .collect();
+        cache.set(GUILD, Arc::new(config_with_channel(2))).await;
+        for task in tasks {
+            task.await.unwrap();
+        }
+        assert_eq!(*cache.get(GUILD).await.unwrap(), config_with_channel(2));
+    }
+
+    // 사용자 설정은 저장한 그대로 다시 읽히고, 다시 저장하면 덮어씀
+    #[tokio::test]
+    async fn user_prefs_round_trip() {
+        let pool = memory_pool().await;
+        assert!(load_user_prefs(&pool).await.unwrap().is_empty());
+        let prefs = UserPrefs {
+            timezone: "America/New_York".to_string(),
+            weekly_dm_summary: true,
+            tts_opt_out: false,
+        };
+        save_user_prefs(&pool, ALICE, &prefs).await.unwrap();
+        save_user_prefs(&pool, BOB, &UserPrefs::default()).await.unwrap();
+        save_user_prefs(&pool, BOB, &UserPrefs { tts_opt_out: true, ..UserPrefs::default() }).await.unwrap();
+
+        let mut loaded = load_user_prefs(&pool).await.unwrap();
+        loaded.sort_by_key(|(user_id, _)| *user_id);
+        assert_eq!(
+            loaded,
+            vec![(ALICE, prefs), (BOB, UserPrefs { tts_opt_out: true, ..UserPrefs::default() })]
+        );
+    }
+
+    #[tokio::test]
+    async fn user_time_accumulates() {
+        let pool = memory_pool().await;
+        assert_eq!(get_user_time(&pool, GUILD, ALICE).await.unwrap(), 0);
+        add_user_time(&pool, GUILD, ALICE, 60).await.unwrap();
+        add_user_time(&pool, GUILD, ALICE, 30).await.unwrap();
+        add_user_time(&pool, GUILD, BOB, 120).await.unwrap();
+        add_user_time(&pool, OTHER_GUILD, ALICE, 500).await.unwrap();
+        assert_eq!(get_user_time(&pool, GUILD, ALICE).await.unwrap(), 90);
+
+        assert_eq!(top_users(&pool, GUILD, 10).await.unwrap(), vec![(BOB, 120), (ALICE, 90)]);
+        assert_eq!(top_users(&pool, GUILD, 1).await.unwrap(), vec![(BOB, 120)]);
+        assert_eq!(top_users_since(&pool, GUILD, today(), 10).await.unwrap(), vec![(BOB, 120), (ALICE, 90)]);
+        assert!(top_users_since(&pool, GUILD, today() + 1, 10).await.unwrap().is_empty());
+        assert_eq!(
+            user_time_since(&pool, ALICE, today()).await.unwrap(),
+            vec![(OTHER_GUILD, 500), (GUILD, 90)]
+        );
+
+        // ALICE는 모든 길드 합산 590초로 1위, BOB은 2위
+        assert_eq!(global_time_rank(&pool, today(), 590).await.unwrap(), 1);
+        assert_eq!(global_time_rank(&pool, today(), 120).await.unwrap(), 2);
+
+        prune_user_voice_daily(&pool, today() + 1).await.unwrap();
+        assert!(user_time_since(&pool, ALICE, 0).await.unwrap().is_empty());
+        // 누적 시간은 일별 기록을 지워도 유지
+        assert_eq!(get_user_time(&pool, GUILD, ALICE).await.unwrap(), 90);
+    }
+
+    #[tokio::test]
+    async fn update_user_time_clamps_at_zero() {
+        let pool = memory_pool().await;
+        assert_eq!(update_user_time(&pool, GUILD, ALICE, |secs| secs + 300).await.unwrap(), (0, 300));
+        assert_eq!(update_user_time(&pool, GUILD, ALICE, |secs| secs - 1000).await.unwrap(), (300, 0));
+        assert_eq!(get_user_time(&pool, GUILD, ALICE).await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn channel_renames_round_trip() {
+        let pool = memory_pool().await;
+        let renamed = RenamedChannel {
+            original: "로비".to_string(),
+            name: "🔴 로비".to_string(),
+        };
+        save_channel_rename(&pool, GUILD, CHANNEL, &renamed).await.unwrap();
+        let updated = RenamedChannel {
+            original: "로비".to_string(),
+            name: "🟢 로비".to_string(),
+        };
+        save_channel_rename(&pool, GUILD, CHANNEL, &updated).await.unwrap();
+        assert_eq!(load_channel_renames(&pool).await.unwrap(), vec![(GUILD, CHANNEL, updated)]);
+
+        delete_channel_rename(&pool, CHANNEL).await.unwrap();
+        assert!(load_channel_renames(&pool).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn temp_channels_round_trip() {
+        let pool = memory_pool().await;
+        insert_temp_channel(&pool, GUILD, CHANNEL, ALICE, 1000).await.unwrap();
+        insert_temp_channel(&pool, GUILD, CHANNEL, BOB, 2000).await.unwrap();
+        let owner = TempChannel {
+            guild_id: GUILD,
+            owner_id: BOB,
+        };
+        assert_eq!(load_temp_channels(&pool).await.unwrap(), vec![(CHANNEL, owner)]);
+
+        delete_temp_channel(&pool, CHANNEL).await.unwrap();
+        assert!(load_temp_channels(&pool).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn streaks_round_trip() {
+        let pool = memory_pool().await;
+        assert_eq!(load_streak(&pool, GUILD, ALICE).await.unwrap(), None);
+
+        let streak = VoiceStreak {
+            current: 3,
+            best: 5,
+            last_day: 100,
+            day: 100,
+            day_secs: 1800,
+            ..VoiceStreak::default()
+        };
+        save_streak(&pool, GUILD, ALICE, &streak).await.unwrap();
+        assert_eq!(load_streak(&pool, GUILD, ALICE).await.unwrap(), Some(streak.clone()));
+        assert!(streak_reminders(&pool).await.unwrap().is_empty());
+
+        // 알림 설정은 save_streak으로 덮어쓰지 않음
+        set_streak_reminder(&pool, GUILD, ALICE, true).await.unwrap();
+        save_streak(&pool, GUILD, ALICE, &streak).await.unwrap();
+        mark_streak_reminded(&pool, GUILD, ALICE, 101).await.unwrap();
+        let expected = VoiceStreak {
+            remind: true,
+            reminded_day: 101,
+            ..streak
+        };
+        assert_eq!(load_streak(&pool, GUILD, ALICE).await.unwrap(), Some(expected.clone()));
+        assert_eq!(streak_reminders(&pool).await.unwrap(), vec![(GUILD, ALICE, expected)]);
+
+        // 기록 없이 알림만 신청한 사용자는 보낼 대상이 아님
+        set_streak_reminder(&pool, GUILD, BOB, true).await.unwrap();
+        assert_eq!(streak_reminders(&pool).await.unwrap().len(), 1);
+        set_streak_reminder(&pool, GUILD, ALICE, false).await.unwrap();
+        assert!(streak_reminders(&pool).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn top_starters_counts_sessions() {
+        let pool = memory_pool().await;
+        insert_session(&pool, &session(1, 1000, Some(ALICE))).await.unwrap();
+        insert_session(&pool, &session(2, 2000, Some(BOB))).await.unwrap();
+        insert_session(&pool, &session(3, 3000, Some(BOB))).await.unwrap();
+        insert_session(&pool, &session(4, 4000, None)).await.unwrap();
+        assert_eq!(top_starters(&pool, GUILD, 0, 10).await.unwrap(), vec![(BOB, 2), (ALICE, 1)]);
+        assert_eq!(top_starters(&pool, GUILD, 1500, 10).await.unwrap(), vec![(BOB, 2)]);
+        assert!(top_starters(&pool, OTHER_GUILD, 0, 10).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn active_channels_round_trip() {
+        let pool = memory_pool().await;
+        save_active_channel(&pool, GUILD, CHANNEL, 1, 1000).await.unwrap();
+        set_active_channel_starter(&pool, CHANNEL, ALICE).await.unwrap();
+        assert_eq!(load_active_channels(&pool).await.unwrap(), vec![(GUILD, CHANNEL, 1, 1000, Some(ALICE))]);
+
+        // 같은 채널의 새 활성화는 시작한 사람을 지움
+        save_active_channel(&pool, GUILD, CHANNEL, 2, 2000).await.unwrap();
+        assert_eq!(load_active_channels(&pool).await.unwrap(), vec![(GUILD, CHANNEL, 2, 2000, None)]);
+
+        remove_active_channel(&pool, CHANNEL).await.unwrap();
+        assert!(load_active_channels(&pool).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn reminders_round_trip() {
+        let pool = memory_pool().await;
+        let later = insert_reminder(&pool, ALICE, CHANNEL, false, "나중", 2000).await.unwrap();
+        let sooner = insert_reminder(&pool, ALICE, CHANNEL, true, "먼저", 1000).await.unwrap();
+        let other = insert_reminder(&pool, BOB, CHANNEL, false, "BOB", 1500).await.unwrap();
+
+        let mine = user_reminders(&pool, ALICE).await.unwrap();
+        assert_eq!(mine.iter().map(|r| r.id).collect::<Vec<_>>(), vec![sooner, later]);
+        assert!(mine[0].dm);
+        assert_eq!(mine[0].message, "먼저");
+        assert_eq!(
+            load_reminders(&pool).await.unwrap().iter().map(|r| r.id).collect::<Vec<_>>(),
+            vec![sooner, other, later]
+        );
+
+        // 다른 사용자의 리마인더는 지울 수 없음
+        assert!(!delete_reminder(&pool, other, Some(ALICE)).await.unwrap());
+        assert!(delete_reminder(&pool, sooner, Some(ALICE)).await.unwrap());
+        assert!(delete_reminder(&pool, other, None).await.unwrap());
+        assert!(!delete_reminder(&pool, other, None).await.unwrap());
+        assert_eq!(load_reminders(&pool).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn command_usage_accumulates() {
+        let pool = memory_pool().await;
+        let row = |guild_id, count, latency: Vec<i64>| CommandUsageRow {
+            day: 10,
+            guild_id,
+            command: "calc".to_string(),
+            count,
+            errors: 1,
+            latency,
+        };
+        let errors = [(10, Some(GUILD), "0으로 나눔".to_string(), 2)];
+        add_command_usage(&pool, &[row(Some(GUILD), 3, vec![1, 2])], &errors).await.unwrap();
+        add_command_usage(&pool, &[row(Some(GUILD), 2, vec![0, 1, 5])], &errors).await.unwrap();
+        add_command_usage(&pool, &[row(None, 1, vec![1])], &[(10, None, "구문".to_string(), 1)])
+            .await
+            .unwrap();
+
+        let guild = command_usage_since(&pool, 10, Some(GUILD)).await.unwrap();
+        assert_eq!(guild.len(), 1);
+        assert_eq!((guild[0].count, guild[0].errors), (5, 2));
+        assert_eq!(guild[0].latency, vec![1, 3, 5]);
+        // 길드를 지정하지 않으면 DM 사용도 포함
+        let all = command_usage_since(&pool, 10, None).await.unwrap();
+        assert_eq!(all.len(), 2);
+        assert!(all.iter().any(|row| row.guild_id.is_none()));
+        assert!(command_usage_since(&pool, 11, None).await.unwrap().is_empty());
+
+        assert_eq!(
+            calc_errors_since(&pool, 10, None).await.unwrap(),
+            vec![("0으로 나눔".to_string(), 4), ("구문".to_string(), 1)]
+        );
+        assert_eq!(calc_errors_since(&pool, 10, Some(GUILD)).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn config_changes_round_trip() {
+        let pool = memory_pool().await;
+        let change = |changed_at, key: &str| ConfigChange {
+            user_id: ALICE,
+            changed_at,
+            key: key.to_string(),
+            old_value: "없음".to_string(),
+            new_value: "#일반".to_string(),
+        };
+        insert_config_changes(&pool, GUILD, &[change(100, "notification_channel"), change(100, "mention_role")])
+            .await
+            .unwrap();
+        insert_config_changes(&pool, GUILD, &[change(200, "notification_channel")]).await.unwrap();
+        insert_config_changes(&pool, OTHER_GUILD, &[change(300, "audit_channel")]).await.unwrap();
+
+        let recent = config_changes(&pool, GUILD, None, 10).await.unwrap();
+        assert_eq!(recent.iter().map(|c| c.changed_at).collect::<Vec<_>>(), vec![200, 100, 100]);
+        assert_eq!(config_changes(&pool, GUILD, Some("notification_channel"), 10).await.unwrap().len(), 2);
+        assert_eq!(config_changes(&pool, GUILD, None, 1).await.unwrap()[0].changed_at, 200);
+
+        // 150 이전 기록 2건, 길드마다 최근 1건을 넘는 기록은 없음
+        assert_eq!(prune_config_changes(&pool, 150, 1).await.unwrap(), 2);
+        assert_eq!(config_changes(&pool, GUILD, None, 10).await.unwrap().len(), 1);
+        assert_eq!(config_changes(&pool, OTHER_GUILD, None, 10).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn prune_config_changes_keeps_latest() {
+        let pool = memory_pool().await;
+        for changed_at in 1..=5 {
+            let change = ConfigChange {
+                user_id: ALICE,
+                changed_at,
+                key: "mention_role".to_string(),
+                old_value: String::new(),
+                new_value: String::new(),
+            };
+            insert_config_changes(&pool, GUILD, &[change]).await.unwrap();
+        }
+        assert_eq!(prune_config_changes(&pool, 0, 2).await.unwrap(), 3);
+        let left = config_changes(&pool, GUILD, None, 10).await.unwrap();
+        assert_eq!(left.iter().map(|c| c.changed_at).collect::<Vec<_>>(), vec![5, 4]);
+    }
+
+    #[tokio::test]
+    async fn feedback_round_trip() {
+        let pool = memory_pool().await;
+        insert_feedback(&pool, ALICE, Some(GUILD), CHANNEL, "좋아요", 100).await.unwrap();
+        insert_feedback(&pool, ALICE, None, CHANNEL, "DM에서", 200).await.unwrap();
+        assert_eq!(feedback_count_since(&pool, ALICE, 0).await.unwrap(), 2);
+        assert_eq!(feedback_count_since(&pool, ALICE, 150).await.unwrap(), 1);
+        assert_eq!(feedback_count_since(&pool, BOB, 0).await.unwrap(), 0);
+
+        assert!(!is_feedback_blocked(&pool, ALICE).await.unwrap());
+        assert!(set_feedback_blocked(&pool, ALICE, true).await.unwrap());
+        // 이미 차단된 상태면 바뀐 것이 없음
+        assert!(!set_feedback_blocked(&pool, ALICE, true).await.unwrap());
+        assert!(is_feedback_blocked(&pool, ALICE).await.unwrap());
+        assert!(set_feedback_blocked(&pool, ALICE, false).await.unwrap());
+        assert!(!set_feedback_blocked(&pool, ALICE, false).await.unwrap());
+        assert!(!is_feedback_blocked(&pool, ALICE).await.unwrap());
+    }
+}
@@ -0,0 +1,242 @@
+use serenity::all::CommandDataOptionValue;
+use serenity::all::CommandInteraction;
+use serenity::all::CreateInteractionResponseMessage;
+use serenity::all::CreateMessage;
+use serenity::all::GuildId;
+use serenity::all::UserId;
+use serenity::prelude::*;
+use sqlx::SqlitePool;
+
+use crate::commands::respond;
+use crate::config_check::get_usable_config;
+use crate::dry_run;
+use crate::error::{report_bot_error, require, BotError, ErrorContext};
+use crate::error_report::report_error;
+use crate::guild_config::get_guild_config;
+use crate::notification::{send_notification, NotificationMessage};
+use crate::notifier::{notifier, NotifyTarget};
+use crate::storage::{self, unix_now, Storage};
+use crate::user_prefs::local_day_hour;
+
+// 알림 채널에 축하를 보내는 연속 일수
+const MILESTONES: &[i64] = &[7, 30, 100];
+// 연속 기록이 끊기기 전 알림을 보내는 시각 (서버 시간대 기준 시)
+const REMIND_HOUR: i64 = 20;
+
+// 서버별 사용자의 연속 보이스 활동 기록. 날짜는 서버 시간대 기준 (1970-01-01부터의 일수)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VoiceStreak {
+    pub current: i64,
+    pub best: i64,
+    // 기준 시간(streak_min_minutes)을 채운 마지막 날 (0이면 없음)
+    pub last_day: i64,
+    // day에 쌓은 보이스 시간
+    pub day: i64,
+    pub day_secs: i64,
+    // 끊기기 전 DM 알림을 받을지 (/streak remind)
+    pub remind: bool,
+    pub reminded_day: i64,
+}
+
+impl VoiceStreak {
+    // today 기준 이어지고 있는 연속 일수 (어제나 오늘 채우지 않았으면 끊긴 것)
+    pub fn current_on(&self, today: i64) -> i64 {
+        if self.last_day >= today - 1 { self.current } else { 0 }
+    }
+
+    // day에 secs만큼 보이스 시간을 더함. 이번에 그날 기준을 처음 채웠으면 true
+    fn add_time(&mut self, day: i64, secs: i64, min_secs: i64) -> bool {
+        if self.day != day {
+            self.day = day;
+            self.day_secs = 0;
+        }
+        self.day_secs += secs;
+        if self.day_secs < min_secs || self.last_day >= day {
+            return false;
+        }
+        self.current = if self.last_day == day - 1 { self.current + 1 } else { 1 };
+        self.best = self.best.max(self.current);
+        self.last_day = day;
+        true
+    }
+}
+
+// 사용자가 보이스 채널에서 나갈 때 호출: 접속 시간을 나간 날(서버 시간대)에 더하고 연속 기록 갱신
+pub async fn record_voice_time(ctx: &Context, guild_id: GuildId, user_id: UserId, secs: i64) {
+    if secs <= 0 || ctx.cache.user(user_id).is_some_and(|u| u.bot) {
+        return;
+    }
+    let Some(pool) = storage::pool(ctx).await else {
+        return;
+    };
+    let config = get_guild_config(ctx, guild_id).await;
+    let (today, _) = local_day_hour(unix_now(), &config.timezone);
+    let min_secs = i64::from(config.streak_min_minutes) * 60;
+
+    let result = async {
+        let mut streak = storage::load_streak(&pool, guild_id, user_id).await?.unwrap_or_default();
+        let extended = streak.add_time(today, secs, min_secs);
+        storage::save_streak(&pool, guild_id, user_id, &streak).await?;
+        Ok::<_, sqlx::Error>(extended.then_some(streak.current))
+    }
+    .await;
+    match result {
+        Ok(Some(days)) if MILESTONES.contains(&days) => announce_milestone(ctx, guild_id, user_id, days).await,
+        Ok(_) => {}
+        Err(e) => report_error(ctx, "연속 보이스 기록 저장", &e).await,
+    }
+}
+
+async fn announce_milestone(ctx: &Context, guild_id: GuildId, user_id: UserId, days: i64) {
+    let Some(channel_id) = get_usable_config(ctx, guild_id).await.notification_channel else {
+        return;
+    };
+    let message = NotificationMessage::PlainText(format!(
+        "🔥 <@{}> 님이 {}일 연속 보이스 활동을 달성했습니다!",
+        user_id, days
+    ));
+    send_notification(ctx, guild_id, channel_id, message, "연속 보이스 기록 축하 전송").await;
+}
+
+// /voicestats에 붙일 연속 기록 한 줄
+pub async fn streak_line(pool: &SqlitePool, guild_id: GuildId, user_id: UserId, timezone: &str) -> Option<String> {
+    let streak = storage::load_streak(pool, guild_id, user_id).await.ok()??;
+    let (today, _) = local_day_hour(unix_now(), timezone);
+    Some(format!(
+        "🔥 연속 보이스 활동: **{}일** (최고 {}일)",
+        streak.current_on(today),
+        streak.best
+    ))
+}
+
+// /streak show | remind <on|off>
+pub async fn handle_streak(ctx: &Context, cmd: &CommandInteraction) {
+    let Some(guild_id) = cmd.guild_id else {
+        return;
+    };
+    let Some(sub) = cmd.data.options.first() else {
+        return;
+    };
+    let CommandDataOptionValue::SubCommand(args) = &sub.value else {
+        return;
+    };
+    let Some(pool) = storage::pool(ctx).await else {
+        return;
+    };
+    let config = get_guild_config(ctx, guild_id).await;
+
+    let content = match sub.name.as_str() {
+        "show" => match storage::load_streak(&pool, guild_id, cmd.user.id).await {
+            Ok(streak) => {
+                let streak = streak.unwrap_or_default();
+                let (today, _) = local_day_hour(unix_now(), &config.timezone);
+                let today_secs = if streak.day == today { streak.day_secs } else { 0 };
+                let min_secs = i64::from(config.streak_min_minutes) * 60;
+                let status = if streak.last_day == today {
+                    "오늘 기록을 채웠습니다 ✅".to_string()
+                } else {
+                    format!(
+                        "오늘 {}분 더 보이스 채널에 있으면 이어집니다",
+                        ((min_secs - today_secs).max(0) as u64).div_ceil(60)
+                    )
+                };
+                format!(
+                    "🔥 연속 보이스 활동: **{}일** (최고 {}일)\n{}\n하루 {}분 이상 ({} 기준 날짜)",
+                    streak.current_on(today),
+                    streak.best,
+                    status,
+                    config.streak_min_minutes,
+                    config.timezone
+                )
+            }
+            Err(e) => {
+                report_error(ctx, "연속 보이스 기록 조회", &e).await;
+                "기록을 불러오지 못했습니다.".to_string()
+            }
+        },
+        "remind" => {
+            let on = args
+                .iter()
+                .find(|o| o.name == "state")
+                .and_then(|o| o.value.as_str())
+                .is_some_and(|v| v == "on");
+            match storage::set_streak_reminder(&pool, guild_id, cmd.user.id, on).await {
+                Ok(()) if on => format!(
+                    "연속 기록이 끊기기 전 ({}시, 서버 시간대 {}) 아직 보이스 채널에 들어오지 않았으면 DM으로 알려드립니다.",
+                    REMIND_HOUR, config.timezone
+                ),
+                Ok(()) => "연속 기록 알림을 보내지 않습니다.".to_string(),
+                Err(e) => {
+                    report_error(ctx, "연속 기록 알림 설정", &e).await;
+                    "설정을 저장하지 못했습니다. 잠시 후 다시 시도해주세요.".to_string()
+                }
+            }
+        }
+        _ => return,
+    };
+    respond(
+        ctx,
+        cmd,
+        CreateInteractionResponseMessage::new()
+            .content(content)
+            .ephemeral(true),
+    )
+    .await;
+}
+
+// 예약 작업 (매시): 알림을 신청한 사용자 중 서버 시간대로 저녁인데 오늘 아직 기준을 채우지 않아
+// 연속 기록이 끊길 사용자에게 하루 한 번 DM
+pub async fn send_streak_reminders(ctx: Context) {
+    if let Err(e) = run_streak_reminders(&ctx).await {
+        report_bot_error(&ctx, ErrorContext::new("연속 기록 알림 DM"), &e).await;
+    }
+}
+
+async fn run_streak_reminders(ctx: &Context) -> Result<(), BotError> {
+    let pool = require::<Storage>(ctx).await?;
+    let now = unix_now();
+    for (guild_id, user_id, streak) in storage::streak_reminders(&pool).await? {
+        if !ctx.cache.guilds().contains(&guild_id) {
+            continue;
+        }
+        let config = get_guild_config(ctx, guild_id).await;
+        let (today, hour) = local_day_hour(now, &config.timezone);
+        // 어제까지 이어졌고 오늘 아직 채우지 않은 경우만 (지금 보이스 채널에 있으면 곧 채울 것이므로 제외)
+        if hour < REMIND_HOUR || streak.reminded_day == today || streak.last_day != today - 1 {
+            continue;
+        }
+        let in_voice = ctx
+            .cache
+            .guild(guild_id)
+            .is_some_and(|g| g.voice_states.get(&user_id).is_some_and(|v| v.channel_id.is_some()));
+        if in_voice {
+            continue;
+        }
+        storage::mark_streak_reminded(&pool, guild_id, user_id, today).await?;
+
+        let guild_name = ctx
+            .cache
+            .guild(guild_id)
+            .map(|g| g.name.clone())
+            .unwrap_or_else(|| format!("서버 {}", guild_id));
+        let content = format!(
+            "🔥 **{}** 에서 {}일 연속 보이스 활동 중입니다. 오늘 {}분 이상 보이스 채널에 있으면 기록이 이어집니다.\n\
+             (`/streak remind off` 로 그만 받을 수 있습니다)",
+            guild_name, streak.current, config.streak_min_minutes
+        );
+        let detail = format!("사용자 {}: {}일", user_id, streak.current);
+        if dry_run::suppressed(ctx, Some(guild_id), "연속 기록 알림 DM", detail).await {
+            continue;
+        }
+        // DM을 막아 둔 사용자는 알림을 해제해 매일 실패하지 않도록
+        let sent = notifier(ctx)
+            .await
+            .send(NotifyTarget::Dm(user_id), CreateMessage::new().content(content))
+            .await;
+        if let Err(e) = sent {
+            tracing::warn!("연속 기록 알림 DM 실패 ({}), 알림을 해제합니다: {}", user_id, e);
+            storage::set_streak_reminder(&pool, guild_id, user_id, false).await?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,73 @@
+use tracing_subscriber::EnvFilter;
+use tracing_subscriber::fmt;
+use tracing_subscriber::layer::SubscriberExt;
+
+use crate::cli::LogLevel;
+
+// 종료할 때 내보내지 못한 스팬을 마저 보냄 (main이 끝날 때까지 들고 있어야 함)
+pub struct Telemetry {
+    #[cfg(feature = "otlp")]
+    provider: Option<opentelemetry_sdk::trace::SdkTracerProvider>,
+}
+
+impl Drop for Telemetry {
+    fn drop(&mut self) {
+        #[cfg(feature = "otlp")]
+        if let Some(provider) = self.provider.take()
+            && let Err(e) = provider.shutdown()
+        {
+            eprintln!("OTLP 스팬 내보내기 종료 실패: {}", e);
+        }
+    }
+}
+
+// 로그 출력 설정. 봇 로그는 --log-level 수준까지, 라이브러리(serenity, sqlx 등)는 경고 이상만 출력.
+// RUST_LOG 환경 변수가 있으면 그 필터를 그대로 사용 (예: RUST_LOG=serenity=debug,aurobot=trace)
+pub fn init(level: LogLevel) -> Telemetry {
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(default_directive(level)));
+    // 표준 출력은 calc 등 서브커맨드 결과용으로 남겨 둠
+    let subscriber = tracing_subscriber::registry()
+        .with(filter)
+        .with(fmt::layer().with_writer(std::io::stderr));
+
+    #[cfg(feature = "otlp")]
+    {
+        let provider = otlp_provider();
+        let layer = provider.as_ref().map(|provider| {
+            use opentelemetry::trace::TracerProvider;
+            tracing_opentelemetry::layer().with_tracer(provider.tracer("aurobot"))
+        });
+        // 이미 설치되어 있으면 (테스트 등) 그대로 둠
+        let _ = tracing::subscriber::set_global_default(subscriber.with(layer));
+        Telemetry { provider }
+    }
+
+    #[cfg(not(feature = "otlp"))]
+    {
+        let _ = tracing::subscriber::set_global_default(subscriber);
+        Telemetry {}
+    }
+}
+
+fn default_directive(level: LogLevel) -> String {
+    format!("warn,aurobot={}", level)
+}
+
+// OTLP(HTTP) 스팬 내보내기. 주소는 OTEL_EXPORTER_OTLP_ENDPOINT (기본 http://localhost:4318)
+#[cfg(feature = "otlp")]
+fn otlp_provider() -> Option<opentelemetry_sdk::trace::SdkTracerProvider> {
+    let exporter = match opentelemetry_otlp::SpanExporter::builder().with_http().build() {
+        Ok(exporter) => exporter,
+        Err(e) => {
+            eprintln!("OTLP 스팬 내보내기를 설정하지 못했습니다: {}", e);
+            return None;
+        }
+    };
+    let resource = opentelemetry_sdk::Resource::builder().with_service_name("aurobot").build();
+    Some(
+        opentelemetry_sdk::trace::SdkTracerProvider::builder()
+            .with_batch_exporter(exporter)
+            .with_resource(resource)
+            .build(),
+    )
+}
@@ -0,0 +1,206 @@
+use serenity::all::ChannelId;
+use serenity::all::ChannelType;
+use serenity::all::CreateChannel;
+use serenity::all::GuildId;
+use serenity::all::PermissionOverwrite;
+use serenity::all::PermissionOverwriteType;
+use serenity::all::Permissions;
+use serenity::all::UserId;
+use serenity::prelude::*;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::Instrument;
+
+use crate::dry_run;
+use crate::error_report::report_error;
+use crate::storage::{self, unix_now};
+
+// 서버마다 동시에 둘 수 있는 임시 채널 수
+const MAX_TEMP_CHANNELS_PER_GUILD: usize = 10;
+// 디스코드 채널 이름 최대 길이 (글자 수)
+const MAX_CHANNEL_NAME: usize = 100;
+
+// 허브 채널(/config set hub_channel)에 들어온 멤버에게 만들어 준 보이스 채널
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TempChannel {
+    pub guild_id: GuildId,
+    pub owner_id: UserId,
+}
+
+pub struct TempChannels;
+
+impl TypeMapKey for TempChannels {
+    type Value = Arc<RwLock<HashMap<ChannelId, TempChannel>>>;
+}
+
+pub fn new_temp_channel_store() -> Arc<RwLock<HashMap<ChannelId, TempChannel>>> {
+    Arc::new(RwLock::new(HashMap::new()))
+}
+
+async fn temp_store(ctx: &Context) -> Option<Arc<RwLock<HashMap<ChannelId, TempChannel>>>> {
+    let data = ctx.data.read().await;
+    data.get::<TempChannels>().cloned()
+}
+
+// 허브 채널에 들어온 멤버의 임시 채널을 만들고 옮김. 이벤트 처리를 막지 않도록 별도 작업에서 실행
+pub fn spawn_create(ctx: &Context, guild_id: GuildId, hub: ChannelId, user_id: UserId, name: String) {
+    let ctx = ctx.clone();
+    tokio::spawn(async move { create_for(&ctx, guild_id, hub, user_id, &name).await }.in_current_span());
+}
+
+async fn create_for(ctx: &Context, guild_id: GuildId, hub: ChannelId, user_id: UserId, name: &str) {
+    let Some(store) = temp_store(ctx).await else {
+        return;
+    };
+    // 이미 만들어 준 채널이 있으면 새로 만들지 않고 그 채널로 옮김
+    let (existing, count) = {
+        let store = store.read().await;
+        let existing = store
+            .iter()
+            .find(|(_, t)| t.guild_id == guild_id && t.owner_id == user_id)
+            .map(|(id, _)| *id);
+        (existing, store.values().filter(|t| t.guild_id == guild_id).count())
+    };
+    if let Some(channel_id) = existing {
+        if let Err(e) = guild_id.move_member(&ctx.http, user_id, channel_id).await {
+            report_error(ctx, "임시 채널로 이동", &e).await;
+        }
+        return;
+    }
+    if count >= MAX_TEMP_CHANNELS_PER_GUILD {
+        tracing::info!(
+            "임시 채널이 이미 {}개라 새로 만들지 않습니다 (길드 {})",
+            MAX_TEMP_CHANNELS_PER_GUILD, guild_id
+        );
+        return;
+    }
+
+    let channel_name: String = format!("{}의 방", name).chars().take(MAX_CHANNEL_NAME).collect();
+    if dry_run::suppressed(ctx, Some(guild_id), "임시 채널 생성", &channel_name).await {
+        return;
+    }
+    // 허브와 같은 카테고리에 만들고, 만든 사람에게 채널 관리 권한을 줌
+    let parent = ctx
+        .cache
+        .guild(guild_id)
+        .and_then(|g| g.channels.get(&hub).and_then(|c| c.parent_id));
+    let owner_permissions = PermissionOverwrite {
+        allow: Permissions::MANAGE_CHANNELS
+            | Permissions::MOVE_MEMBERS
+            | Permissions::MUTE_MEMBERS
+            | Permissions::DEAFEN_MEMBERS,
+        deny: Permissions::empty(),
+        kind: PermissionOverwriteType::Member(user_id),
+    };
+    let mut builder = CreateChannel::new(channel_name)
+        .kind(ChannelType::Voice)
+        .permissions(vec![owner_permissions]);
+    if let Some(parent) = parent {
+        builder = builder.category(parent);
+    }
+    let channel = match guild_id.create_channel(&ctx.http, builder).await {
+        Ok(channel) => channel,
+        Err(e) => {
+            report_error(ctx, "임시 채널 생성", &e).await;
+            return;
+        }
+    };
+
+    store.write().await.insert(
+        channel.id,
+        TempChannel {
+            guild_id,
+            owner_id: user_id,
+        },
+    );
+    if let Some(pool) = storage::pool(ctx).await
+        && let Err(e) = storage::insert_temp_channel(&pool, guild_id, channel.id, user_id, unix_now()).await
+    {
+        report_error(ctx, "임시 채널 기록 저장", &e).await;
+    }
+    // 그 사이 허브에서 나갔으면 옮길 수 없으므로 빈 채널을 바로 지움
+    if let Err(e) = guild_id.move_member(&ctx.http, user_id, channel.id).await {
+        report_error(ctx, "임시 채널로 이동", &e).await;
+        delete(ctx, guild_id, channel.id).await;
+    }
+}
+
+// 채널이 비었을 때 호출. 임시 채널이면 삭제
+pub async fn channel_emptied(ctx: &Context, guild_id: GuildId, channel_id: ChannelId) {
+    let Some(store) = temp_store(ctx).await else {
+        return;
+    };
+    if !store.read().await.contains_key(&channel_id) {
+        return;
+    }
+    let ctx = ctx.clone();
+    tokio::spawn(async move { delete(&ctx, guild_id, channel_id).await }.in_current_span());
+}
+
+// 임시 채널을 삭제하고 기록을 지움. 삭제에 실패했는데 채널이 남아 있으면 기록을 남겨 재시작 때 다시 시도
+async fn delete(ctx: &Context, guild_id: GuildId, channel_id: ChannelId) {
+    if dry_run::suppressed(ctx, Some(guild_id), "임시 채널 삭제", channel_id).await {
+        return;
+    }
+    if let Err(e) = channel_id.delete(&ctx.http).await {
+        let exists = ctx
+            .cache
+            .guild(guild_id)
+            .is_some_and(|g| g.channels.contains_key(&channel_id));
+        if exists {
+            report_error(ctx, "임시 채널 삭제", &e).await;
+            return;
+        }
+    }
+    forget(ctx, channel_id).await;
+}
+
+// 임시 채널 기록 삭제 (삭제했거나 누군가 직접 지운 경우)
+pub async fn forget(ctx: &Context, channel_id: ChannelId) {
+    let Some(store) = temp_store(ctx).await else {
+        return;
+    };
+    if store.write().await.remove(&channel_id).is_none() {
+        return;
+    }
+    if let Some(pool) = storage::pool(ctx).await
+        && let Err(e) = storage::delete_temp_channel(&pool, channel_id).await
+    {
+        report_error(ctx, "임시 채널 기록 삭제", &e).await;
+    }
+}
+
+// 재시작 후 정리: 저장된 임시 채널을 불러오고, 이미 없어졌거나 비어 있는 채널은 삭제
+pub async fn recover(ctx: &Context, guild_ids: &[GuildId]) {
+    let (Some(pool), Some(store)) = (storage::pool(ctx).await, temp_store(ctx).await) else {
+        return;
+    };
+    let saved = match storage::load_temp_channels(&pool).await {
+        Ok(saved) => saved,
+        Err(e) => {
+            report_error(ctx, "임시 채널 기록 조회", &e).await;
+            return;
+        }
+    };
+    for (channel_id, temp) in saved {
+        if !guild_ids.contains(&temp.guild_id) {
+            continue;
+        }
+        store.write().await.insert(channel_id, temp);
+        let occupied = ctx.cache.guild(temp.guild_id).map(|g| {
+            g.channels.contains_key(&channel_id).then(|| {
+                g.voice_states
+                    .values()
+                    .any(|v| v.channel_id == Some(channel_id))
+            })
+        });
+        match occupied {
+            Some(Some(true)) => {}
+            Some(Some(false)) => delete(ctx, temp.guild_id, channel_id).await,
+            // 채널이 이미 없음
+            Some(None) => forget(ctx, channel_id).await,
+            None => {}
+        }
+    }
+}
@@ -0,0 +1,73 @@
+use serenity::all::ChannelId;
+use serenity::all::CreateEmbed;
+use serenity::all::GuildId;
+use serenity::prelude::*;
+
+use crate::notification::{queue_send, NotificationMessage};
+use crate::config_check::get_usable_config;
+
+const COLOUR_THREAD: u32 = 0x9b59b6;
+
+// 스레드 이벤트 종류
+#[derive(Debug, Clone, Copy)]
+pub enum ThreadEvent {
+    Created,
+    Archived,
+    Deleted,
+}
+
+impl ThreadEvent {
+    fn label(self) -> &'static str {
+        match self {
+            ThreadEvent::Created => "🧵 스레드가 생성되었습니다",
+            ThreadEvent::Archived => "📦 스레드가 보관되었습니다",
+            ThreadEvent::Deleted => "🗑️ 스레드가 삭제되었습니다",
+        }
+    }
+}
+
+// 스레드 이벤트 기록 후, 길드에서 켜 두었으면 (notify_thread_events) 알림 채널에 간단한 임베드 전송
+pub async fn announce_thread_event(
+    ctx: &Context,
+    guild_id: GuildId,
+    thread_id: ChannelId,
+    parent_id: Option<ChannelId>,
+    name: Option<&str>,
+    event: ThreadEvent,
+) {
+    tracing::info!(
+        "스레드 이벤트 {:?}: 길드 {}, 스레드 {} ({}), 상위 채널 {:?}",
+        event,
+        guild_id,
+        thread_id,
+        name.unwrap_or("-"),
+        parent_id.map(|p| p.get())
+    );
+
+    let config = get_usable_config(ctx, guild_id).await;
+    if !config.notify_thread_events {
+        return;
+    }
+    let Some(notification_channel) = config.notification_channel else {
+        return;
+    };
+
+    // 삭제된 스레드는 멘션이 깨지므로 이름으로 표시
+    let title = match (event, name) {
+        (ThreadEvent::Deleted, Some(name)) => format!("**{}**", name),
+        (ThreadEvent::Deleted, None) => format!("`{}`", thread_id),
+        _ => format!("<#{}>", thread_id),
+    };
+    let mut description = format!("{}: {}", event.label(), title);
+    if let Some(parent_id) = parent_id {
+        description.push_str(&format!(" (<#{}>)", parent_id));
+    }
+    let embed = CreateEmbed::new().description(description).colour(COLOUR_THREAD);
+    queue_send(
+        ctx,
+        guild_id,
+        notification_channel,
+        vec![(NotificationMessage::Embed(embed), "스레드 알림 전송")],
+    )
+    .await;
+}
@@ -0,0 +1,131 @@
+use serenity::all::ChannelId;
+use serenity::all::CommandDataOptionValue;
+use serenity::all::CommandInteraction;
+use serenity::all::CreateAllowedMentions;
+use serenity::all::CreateInteractionResponseMessage;
+use serenity::all::CreateMessage;
+use serenity::all::GuildId;
+use serenity::prelude::*;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{mpsc, Mutex};
+
+use crate::commands::respond;
+use crate::dry_run;
+use crate::error_report::report_error;
+use crate::guild_config::SAVE_FAILED;
+use crate::notifier::{notifier, NotifyTarget};
+use crate::user_prefs::update_user_prefs;
+
+// 같이 들어온 사람을 한 번에 읽도록 첫 안내 뒤 이만큼 모아서 보냄
+const COALESCE_WINDOW: Duration = Duration::from_secs(1);
+// 앞 안내를 읽는 동안 다음 안내가 겹치지 않도록 두는 간격
+const ANNOUNCE_GAP: Duration = Duration::from_secs(3);
+// 한 번에 읽을 최대 이름 수 (나머지는 "외 N명")
+const MAX_NAMES: usize = 5;
+
+type TtsSender = mpsc::UnboundedSender<(ChannelId, String)>;
+
+// 길드별 TTS 안내 대기열. 길드마다 작업 하나가 순서대로 전송
+pub struct TtsQueues;
+
+impl TypeMapKey for TtsQueues {
+    type Value = Arc<Mutex<HashMap<GuildId, TtsSender>>>;
+}
+
+pub fn new_tts_queues() -> Arc<Mutex<HashMap<GuildId, TtsSender>>> {
+    Arc::new(Mutex::new(HashMap::new()))
+}
+
+// 보이스 채널 채팅에 "{이름} 님이 들어왔습니다"를 TTS 메시지로 읽도록 대기열에 추가
+pub async fn announce_join(ctx: &Context, guild_id: GuildId, channel_id: ChannelId, name: &str) {
+    let queues = {
+        let data = ctx.data.read().await;
+        data.get::<TtsQueues>().cloned()
+    };
+    let Some(queues) = queues else {
+        return;
+    };
+    let mut queues = queues.lock().await;
+    let sender = queues
+        .entry(guild_id)
+        .or_insert_with(|| spawn_worker(ctx.clone(), guild_id));
+    // 작업이 끝나 있으면(패닉 등) 새로 시작
+    if let Err(mpsc::error::SendError(item)) = sender.send((channel_id, name.to_string())) {
+        let sender = spawn_worker(ctx.clone(), guild_id);
+        let _ = sender.send(item);
+        queues.insert(guild_id, sender);
+    }
+}
+
+fn spawn_worker(ctx: Context, guild_id: GuildId) -> TtsSender {
+    let (sender, mut receiver) = mpsc::unbounded_channel::<(ChannelId, String)>();
+    tokio::spawn(async move {
+        while let Some(first) = receiver.recv().await {
+            tokio::time::sleep(COALESCE_WINDOW).await;
+            // 기다리는 동안 들어온 안내를 채널별로 묶음 (들어온 순서 유지)
+            let mut batches: Vec<(ChannelId, Vec<String>)> = Vec::new();
+            for (channel_id, name) in std::iter::once(first).chain(std::iter::from_fn(|| receiver.try_recv().ok())) {
+                match batches.iter_mut().find(|(c, _)| *c == channel_id) {
+                    Some((_, names)) => names.push(name),
+                    None => batches.push((channel_id, vec![name])),
+                }
+            }
+            for (channel_id, names) in batches {
+                let text = utterance(&names);
+                let detail = format!("<#{}> {}", channel_id, text);
+                if dry_run::suppressed(&ctx, Some(guild_id), "입장 TTS 안내", detail).await {
+                    continue;
+                }
+                let message = CreateMessage::new()
+                    .content(text)
+                    .tts(true)
+                    .allowed_mentions(CreateAllowedMentions::new());
+                if let Err(e) = notifier(&ctx).await.send(NotifyTarget::Channel(channel_id), message).await {
+                    report_error(&ctx, "입장 TTS 안내", &e).await;
+                }
+                tokio::time::sleep(ANNOUNCE_GAP).await;
+            }
+        }
+    });
+    sender
+}
+
+fn utterance(names: &[String]) -> String {
+    let shown = names.iter().take(MAX_NAMES).cloned().collect::<Vec<_>>().join(", ");
+    match names.len().saturating_sub(MAX_NAMES) {
+        0 => format!("{} 님이 들어왔습니다", shown),
+        rest => format!("{} 님 외 {}명이 들어왔습니다", shown, rest),
+    }
+}
+
+// /joinannounce opt-in | opt-out: 내가 들어올 때 TTS로 읽을지
+pub async fn handle_joinannounce(ctx: &Context, cmd: &CommandInteraction) {
+    let Some(sub) = cmd.data.options.first() else {
+        return;
+    };
+    if !matches!(sub.value, CommandDataOptionValue::SubCommand(_)) {
+        return;
+    }
+    let opt_out = match sub.name.as_str() {
+        "opt-out" => true,
+        "opt-in" => false,
+        _ => return,
+    };
+    let content = if !update_user_prefs(ctx, cmd.user.id, |p| p.tts_opt_out = opt_out).await {
+        SAVE_FAILED
+    } else if opt_out {
+        "보이스 채널에 들어와도 이름을 읽지 않습니다."
+    } else {
+        "서버가 입장 TTS 안내를 켜 두었다면 들어올 때 이름을 읽습니다."
+    };
+    respond(
+        ctx,
+        cmd,
+        CreateInteractionResponseMessage::new()
+            .content(content)
+            .ephemeral(true),
+    )
+    .await;
+}
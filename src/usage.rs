@@ -0,0 +1,308 @@
+use serenity::all::CommandInteraction;
+use serenity::all::CreateEmbed;
+use serenity::all::CreateInteractionResponseMessage;
+use serenity::all::GuildId;
+use serenity::prelude::*;
+use sqlx::SqlitePool;
+use std::cell::Cell;
+use std::collections::{BTreeMap, HashMap};
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::sync::Mutex;
+
+use crate::commands::{is_owner, respond};
+use crate::error::{report_bot_error, require, BotError, ErrorContext};
+use crate::error_report::report_error;
+use crate::long_message::{truncate, EMBED_DESCRIPTION_LIMIT, EMBED_FIELD_LIMIT};
+use crate::storage::{self, unix_now, CommandUsageRow, Storage};
+
+// 응답 시간 구간의 상한 (밀리초). 마지막 구간은 그 이상 전부
+pub const LATENCY_BUCKETS_MS: &[u64] = &[25, 50, 100, 250, 500, 1000, 2500, 5000, 10000, u64::MAX];
+
+// 저장 전까지 모아 두는 계산기 오류 종류 수 (넘으면 "기타"로 합침)
+const MAX_CALC_ERROR_KINDS: usize = 50;
+// /usage가 보여주는 기간 (오늘 포함)
+const REPORT_DAYS: i64 = 7;
+const SECS_PER_DAY: i64 = 86400;
+
+tokio::task_local! {
+    // 커맨드 실행 중 report_error가 호출됐는지
+    static COMMAND_FAILED: Cell<bool>;
+}
+
+// 커맨드 하나의 사용 통계
+#[derive(Debug, Clone, Default)]
+struct CommandUsage {
+    count: i64,
+    errors: i64,
+    latency: [i64; LATENCY_BUCKETS_MS.len()],
+}
+
+impl CommandUsage {
+    fn merge_row(&mut self, row: &CommandUsageRow) {
+        self.count += row.count;
+        self.errors += row.errors;
+        for (total, add) in self.latency.iter_mut().zip(&row.latency) {
+            *total += add;
+        }
+    }
+
+    // 백분위수가 속한 구간의 상한 (표본이 없으면 None)
+    fn percentile(&self, p: f64) -> Option<u64> {
+        let total: i64 = self.latency.iter().sum();
+        if total == 0 {
+            return None;
+        }
+        let rank = ((total as f64) * p).ceil().max(1.0) as i64;
+        let mut seen = 0;
+        for (count, bound) in self.latency.iter().zip(LATENCY_BUCKETS_MS) {
+            seen += count;
+            if seen >= rank {
+                return Some(*bound);
+            }
+        }
+        None
+    }
+}
+
+// (일자, 길드, 커맨드) -> 통계
+type UsageKey = (i64, Option<GuildId>, &'static str);
+// (일자, 길드, 오류 종류) -> 횟수
+type CalcErrorKey = (i64, Option<GuildId>, String);
+
+#[derive(Default)]
+struct Pending {
+    commands: HashMap<UsageKey, CommandUsage>,
+    calc_errors: HashMap<CalcErrorKey, i64>,
+}
+
+// 아직 저장하지 않은 사용 통계. usage_rollup 작업과 종료 시 저장소로 옮김
+#[derive(Default)]
+pub struct UsageState {
+    pending: Mutex<Pending>,
+}
+
+impl UsageState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    async fn record(&self, guild_id: Option<GuildId>, command: &'static str, elapsed_ms: u64, failed: bool) {
+        let bucket = LATENCY_BUCKETS_MS
+            .iter()
+            .position(|&bound| elapsed_ms <= bound)
+            .unwrap_or(LATENCY_BUCKETS_MS.len() - 1);
+        let day = unix_now().div_euclid(SECS_PER_DAY);
+        let mut pending = self.pending.lock().await;
+        let usage = pending.commands.entry((day, guild_id, command)).or_default();
+        usage.count += 1;
+        usage.errors += i64::from(failed);
+        usage.latency[bucket] += 1;
+    }
+
+    async fn record_calc_error(&self, guild_id: Option<GuildId>, kind: &str) {
+        let day = unix_now().div_euclid(SECS_PER_DAY);
+        let mut pending = self.pending.lock().await;
+        let known = pending.calc_errors.contains_key(&(day, guild_id, kind.to_string()));
+        let kind = if known || pending.calc_errors.len() < MAX_CALC_ERROR_KINDS {
+            kind
+        } else {
+            "기타"
+        };
+        *pending.calc_errors.entry((day, guild_id, kind.to_string())).or_default() += 1;
+    }
+
+    // 모아 둔 통계를 저장소에 더함. 실패하면 다음 저장 때 다시 시도하도록 되돌려 놓음
+    pub async fn flush(&self, pool: &SqlitePool) -> Result<(), sqlx::Error> {
+        let taken = std::mem::take(&mut *self.pending.lock().await);
+        if taken.commands.is_empty() && taken.calc_errors.is_empty() {
+            return Ok(());
+        }
+        let rows: Vec<CommandUsageRow> = taken
+            .commands
+            .iter()
+            .map(|(&(day, guild_id, command), usage)| CommandUsageRow {
+                day,
+                guild_id,
+                command: command.to_string(),
+                count: usage.count,
+                errors: usage.errors,
+                latency: usage.latency.to_vec(),
+            })
+            .collect();
+        let calc_errors: Vec<(i64, Option<GuildId>, String, i64)> = taken
+            .calc_errors
+            .iter()
+            .map(|((day, guild_id, kind), count)| (*day, *guild_id, kind.clone(), *count))
+            .collect();
+        if let Err(e) = storage::add_command_usage(pool, &rows, &calc_errors).await {
+            let mut pending = self.pending.lock().await;
+            for (key, usage) in taken.commands {
+                let merged = pending.commands.entry(key).or_default();
+                merged.count += usage.count;
+                merged.errors += usage.errors;
+                for (total, add) in merged.latency.iter_mut().zip(usage.latency) {
+                    *total += add;
+                }
+            }
+            for (key, count) in taken.calc_errors {
+                *pending.calc_errors.entry(key).or_default() += count;
+            }
+            return Err(e);
+        }
+        Ok(())
+    }
+}
+
+pub struct UsageStats;
+
+impl TypeMapKey for UsageStats {
+    type Value = Arc<UsageState>;
+}
+
+async fn usage_state(ctx: &Context) -> Option<Arc<UsageState>> {
+    let data = ctx.data.read().await;
+    data.get::<UsageStats>().cloned()
+}
+
+// 커맨드 실행을 감싸 호출 수, 오류 여부(report_error 호출), 응답 시간을 기록
+pub async fn track<F>(ctx: &Context, guild_id: Option<GuildId>, command: &'static str, run: F)
+where
+    F: Future<Output = ()>,
+{
+    let started = Instant::now();
+    let failed = COMMAND_FAILED
+        .scope(Cell::new(false), async {
+            run.await;
+            COMMAND_FAILED.with(Cell::get)
+        })
+        .await;
+    if let Some(state) = usage_state(ctx).await {
+        let elapsed_ms = started.elapsed().as_millis() as u64;
+        state.record(guild_id, command, elapsed_ms, failed).await;
+    }
+}
+
+// report_error에서 호출: 실행 중인 커맨드가 있으면 실패로 표시
+pub fn note_error() {
+    let _ = COMMAND_FAILED.try_with(|failed| failed.set(true));
+}
+
+// 계산기 오류를 종류별로 집계 ("알 수 없는 함수: foo" -> "알 수 없는 함수")
+pub async fn record_calc_error(ctx: &Context, guild_id: Option<GuildId>, message: &str) {
+    let kind = message.split(':').next().unwrap_or(message).trim();
+    if let Some(state) = usage_state(ctx).await {
+        state.record_calc_error(guild_id, kind).await;
+    }
+}
+
+// 예약 작업: 모아 둔 사용 통계를 저장소로 옮김
+pub async fn rollup(ctx: Context) {
+    if let Err(e) = flush_usage(&ctx).await {
+        report_bot_error(&ctx, ErrorContext::new("사용 통계 저장"), &e).await;
+    }
+}
+
+async fn flush_usage(ctx: &Context) -> Result<(), BotError> {
+    let state = require::<UsageStats>(ctx).await?;
+    let pool = require::<Storage>(ctx).await?;
+    state.flush(&pool).await?;
+    Ok(())
+}
+
+// /usage [global]: 최근 7일 커맨드 사용 통계와 자주 나오는 계산기 오류
+pub async fn handle_usage(ctx: &Context, cmd: &CommandInteraction) {
+    let global = cmd.data.options.first().is_some_and(|o| o.name == "global");
+    if global && !is_owner(ctx, cmd.user.id).await {
+        respond(
+            ctx,
+            cmd,
+            CreateInteractionResponseMessage::new()
+                .content("전체 통계는 봇 소유자만 볼 수 있습니다")
+                .ephemeral(true),
+        )
+        .await;
+        return;
+    }
+    let guild_id = if global {
+        None
+    } else {
+        match cmd.guild_id {
+            Some(guild_id) => Some(guild_id),
+            None => return,
+        }
+    };
+    let (Some(state), Some(pool)) = (usage_state(ctx).await, storage::pool(ctx).await) else {
+        return;
+    };
+
+    // 지금까지 모은 값을 먼저 저장한 뒤 저장소에서 조회
+    if let Err(e) = state.flush(&pool).await {
+        report_error(ctx, "사용 통계 저장", &e).await;
+    }
+    let since_day = unix_now().div_euclid(SECS_PER_DAY) - (REPORT_DAYS - 1);
+    let (rows, calc_errors) = match (
+        storage::command_usage_since(&pool, since_day, guild_id).await,
+        storage::calc_errors_since(&pool, since_day, guild_id).await,
+    ) {
+        (Ok(rows), Ok(calc_errors)) => (rows, calc_errors),
+        (Err(e), _) | (_, Err(e)) => {
+            report_error(ctx, "사용 통계 조회", &e).await;
+            return;
+        }
+    };
+
+    let mut by_command: BTreeMap<String, CommandUsage> = BTreeMap::new();
+    for row in &rows {
+        by_command.entry(row.command.clone()).or_default().merge_row(row);
+    }
+    let mut commands: Vec<(String, CommandUsage)> = by_command.into_iter().collect();
+    commands.sort_by_key(|(_, usage)| std::cmp::Reverse(usage.count));
+
+    let latency = |ms: Option<u64>| match ms {
+        Some(u64::MAX) => ">10s".to_string(),
+        Some(ms) => format!("≤{}ms", ms),
+        None => "-".to_string(),
+    };
+    let table = if commands.is_empty() {
+        "기록된 사용 내역이 없습니다.".to_string()
+    } else {
+        let mut table = format!("{:<14} {:>6} {:>5} {:>8} {:>8}\n", "커맨드", "호출", "오류", "p50", "p95");
+        for (name, usage) in &commands {
+            table.push_str(&format!(
+                "/{:<13} {:>6} {:>5} {:>8} {:>8}\n",
+                name,
+                usage.count,
+                usage.errors,
+                latency(usage.percentile(0.5)),
+                latency(usage.percentile(0.95)),
+            ));
+        }
+        format!("```\n{}```", table)
+    };
+    let top_errors = if calc_errors.is_empty() {
+        "없음".to_string()
+    } else {
+        calc_errors
+            .iter()
+            .take(5)
+            .map(|(kind, count)| format!("{} · {}회", kind, count))
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    let scope = if global { "전체 서버" } else { "이 서버" };
+    let embed = CreateEmbed::new()
+        .title(format!("📊 커맨드 사용 통계 · {} (최근 {}일)", scope, REPORT_DAYS))
+        .description(truncate(&table, EMBED_DESCRIPTION_LIMIT))
+        .field("자주 나오는 계산기 오류", truncate(&top_errors, EMBED_FIELD_LIMIT), false);
+    respond(
+        ctx,
+        cmd,
+        CreateInteractionResponseMessage::new()
+            .embed(embed)
+            .ephemeral(true),
+    )
+    .await;
+}
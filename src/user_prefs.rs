@@ -0,0 +1,246 @@
+use chrono::{DateTime, Datelike, NaiveDate, TimeDelta, TimeZone, Timelike};
+use chrono_tz::Tz;
+use serenity::all::CommandDataOptionValue;
+use serenity::all::CommandInteraction;
+use serenity::all::CreateInteractionResponseMessage;
+use serenity::all::UserId;
+use serenity::prelude::*;
+use sqlx::sqlite::SqlitePool;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+use crate::commands::respond;
+use crate::guild_config::SAVE_FAILED;
+use crate::storage;
+
+// /timezone 안내와 잘못된 입력 오류에 보여줄 시간대 예시
+pub const TIMEZONE_EXAMPLES: &[&str] = &[
+    "Asia/Seoul",
+    "Asia/Tokyo",
+    "UTC",
+    "Europe/London",
+    "America/New_York",
+    "America/Los_Angeles",
+];
+
+const SECS_PER_DAY: i64 = 86400;
+
+// 사용자별 설정. timezone은 IANA 시간대 이름 (find_timezone이 찾을 수 있는 이름)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UserPrefs {
+    pub timezone: String,
+    // 매주 일요일 보이스 시간 요약 DM을 받을지 (/weeklyreport)
+    pub weekly_dm_summary: bool,
+    // 보이스 채널 입장 TTS 안내에서 이름을 읽지 않음 (/joinannounce)
+    pub tts_opt_out: bool,
+}
+
+impl Default for UserPrefs {
+    fn default() -> Self {
+        Self {
+            timezone: "UTC".to_string(),
+            weekly_dm_summary: false,
+            tts_opt_out: false,
+        }
+    }
+}
+
+pub struct UserPreferences;
+
+impl TypeMapKey for UserPreferences {
+    type Value = Arc<RwLock<HashMap<UserId, UserPrefs>>>;
+}
+
+pub fn new_prefs_store() -> Arc<RwLock<HashMap<UserId, UserPrefs>>> {
+    Arc::new(RwLock::new(HashMap::new()))
+}
+
+pub async fn prefs_store(ctx: &Context) -> Option<Arc<RwLock<HashMap<UserId, UserPrefs>>>> {
+    let data = ctx.data.read().await;
+    data.get::<UserPreferences>().cloned()
+}
+
+// 사용자 설정 조회 (없으면 기본값: UTC)
+pub async fn get_user_prefs(ctx: &Context, user_id: UserId) -> UserPrefs {
+    match prefs_store(ctx).await {
+        Some(store) => store.read().await.get(&user_id).cloned().unwrap_or_default(),
+        None => UserPrefs::default(),
+    }
+}
+
+// 사용자 설정을 바꾸고 저장. 저장하지 못하면 false (메모리의 값은 바꾸지 않음)
+pub async fn update_user_prefs(ctx: &Context, user_id: UserId, f: impl FnOnce(&mut UserPrefs)) -> bool {
+    let (Some(store), Some(pool)) = (prefs_store(ctx).await, storage::pool(ctx).await) else {
+        return false;
+    };
+    let mut store = store.write().await;
+    let mut prefs = store.get(&user_id).cloned().unwrap_or_default();
+    f(&mut prefs);
+    if store.get(&user_id).unwrap_or(&UserPrefs::default()) == &prefs {
+        return true;
+    }
+    if let Err(e) = storage::save_user_prefs(&pool, user_id, &prefs).await {
+        tracing::warn!("사용자 설정 저장 실패 ({}): {}", user_id, e);
+        return false;
+    }
+    store.insert(user_id, prefs);
+    true
+}
+
+// 저장해 둔 사용자 설정을 읽어 시작 시 채움 (읽지 못하면 빈 목록으로 시작)
+pub async fn load_prefs_store(pool: &SqlitePool) -> Arc<RwLock<HashMap<UserId, UserPrefs>>> {
+    let store = new_prefs_store();
+    match storage::load_user_prefs(pool).await {
+        Ok(rows) => store.write().await.extend(rows),
+        Err(e) => tracing::error!("사용자 설정 복원 실패: {}", e),
+    }
+    store
+}
+
+// IANA 시간대 이름 찾기 (대소문자 무시). 서머타임은 날짜마다 chrono-tz가 반영
+pub fn find_timezone(name: &str) -> Option<Tz> {
+    Tz::from_str_insensitive(name.trim()).ok()
+}
+
+// 모르는 시간대는 UTC
+fn timezone_or_utc(name: &str) -> Tz {
+    find_timezone(name).unwrap_or(Tz::UTC)
+}
+
+// 유닉스 시간을 시간대의 현지 시각으로 (표현할 수 없는 시간은 None)
+fn local_time(ts: i64, timezone: &str) -> Option<DateTime<Tz>> {
+    DateTime::from_timestamp(ts, 0).map(|t| t.with_timezone(&timezone_or_utc(timezone)))
+}
+
+// 유닉스 시간을 시간대 기준 "YYYY-MM-DD HH:MM 약칭"으로 (모르는 시간대는 UTC)
+pub fn format_timestamp(ts: i64, timezone: &str) -> String {
+    match local_time(ts, timezone) {
+        Some(local) => local.format("%Y-%m-%d %H:%M %Z").to_string(),
+        None => format!("<t:{}:f>", ts),
+    }
+}
+
+// 시간대 기준 날짜 (1970-01-01부터의 일수)와 시 (모르는 시간대는 UTC)
+pub fn local_day_hour(ts: i64, timezone: &str) -> (i64, i64) {
+    let Some(local) = local_time(ts, timezone) else {
+        return (ts.div_euclid(SECS_PER_DAY), ts.rem_euclid(SECS_PER_DAY) / 3600);
+    };
+    let wall = local.naive_local().and_utc().timestamp();
+    (wall.div_euclid(SECS_PER_DAY), i64::from(local.hour()))
+}
+
+// 시간대 기준 이번 달 1일 0시의 유닉스 시간과 (연, 월)
+pub fn local_month_start(ts: i64, timezone: &str) -> (i64, i64, u32) {
+    let tz = timezone_or_utc(timezone);
+    let Some(local) = local_time(ts, timezone) else {
+        return (ts, 1970, 1);
+    };
+    let (year, month) = (local.year(), local.month());
+    let midnight = NaiveDate::from_ymd_opt(year, month, 1)
+        .and_then(|d| d.and_hms_opt(0, 0, 0))
+        .expect("1일 0시는 항상 있는 날짜");
+    // 0시가 서머타임으로 건너뛰어지는 시간대면 1시 (서머타임 이동은 한 시간)
+    let start = tz
+        .from_local_datetime(&midnight)
+        .earliest()
+        .or_else(|| tz.from_local_datetime(&(midnight + TimeDelta::hours(1))).earliest())
+        .map_or_else(|| midnight.and_utc().timestamp(), |t| t.timestamp());
+    (start, i64::from(year), month)
+}
+
+// /timezone set <name> | clear
+pub async fn handle_timezone(ctx: &Context, cmd: &CommandInteraction) {
+    let Some(sub) = cmd.data.options.first() else {
+        return;
+    };
+    let CommandDataOptionValue::SubCommand(args) = &sub.value else {
+        return;
+    };
+    let content = match sub.name.as_str() {
+        "set" => {
+            let Some(name) = args.iter().find(|o| o.name == "name").and_then(|o| o.value.as_str()) else {
+                return;
+            };
+            match find_timezone(name) {
+                Some(tz) if !update_user_prefs(ctx, cmd.user.id, |p| p.timezone = tz.name().to_string()).await => {
+                    SAVE_FAILED.to_string()
+                }
+                Some(tz) => format!(
+                    "시간대를 `{}` 로 설정했습니다. 지금: {}",
+                    tz.name(),
+                    format_timestamp(storage::unix_now(), tz.name())
+                ),
+                None => format!(
+                    "알 수 없는 시간대입니다: `{}`\nIANA 시간대 이름을 입력해주세요. 예: {}",
+                    name,
+                    TIMEZONE_EXAMPLES
+                        .iter()
+                        .map(|tz| format!("`{}`", tz))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+            }
+        }
+        // 다른 설정(주간 요약 등)은 그대로 두고 시간대만 기본값으로
+        "clear" if !update_user_prefs(ctx, cmd.user.id, |p| p.timezone = UserPrefs::default().timezone).await => {
+            SAVE_FAILED.to_string()
+        }
+        "clear" => "시간대 설정을 지웠습니다. 이제 UTC로 표시합니다.".to_string(),
+        _ => return,
+    };
+    respond(
+        ctx,
+        cmd,
+        CreateInteractionResponseMessage::new()
+            .content(content)
+            .ephemeral(true),
+    )
+    .await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 2024-03-10 06:59:59 UTC: 뉴욕 서머타임 시작 직전 (01:59:59 EST)
+    const BEFORE_DST: i64 = 1_710_053_999;
+
+    // IANA 이름은 대소문자를 무시하고 표준 표기로, 모르는 이름은 None
+    #[test]
+    fn finds_iana_names() {
+        assert_eq!(find_timezone("asia/seoul").map(|tz| tz.name()), Some("Asia/Seoul"));
+        assert_eq!(find_timezone(" America/New_York ").map(|tz| tz.name()), Some("America/New_York"));
+        assert_eq!(find_timezone("utc"), Some(Tz::UTC));
+        assert!(find_timezone("Mars/Olympus_Mons").is_none());
+        assert!(find_timezone("KST").is_none());
+        assert!(find_timezone("").is_none());
+        assert!(TIMEZONE_EXAMPLES.iter().all(|tz| find_timezone(tz).is_some()));
+    }
+
+    // 서머타임이 있는 시간대도 그 날짜의 오프셋과 약칭으로 표시
+    #[test]
+    fn formats_with_daylight_saving() {
+        assert_eq!(format_timestamp(0, "Asia/Seoul"), "1970-01-01 09:00 KST");
+        assert_eq!(format_timestamp(BEFORE_DST, "America/New_York"), "2024-03-10 01:59 EST");
+        assert_eq!(format_timestamp(BEFORE_DST + 1, "America/New_York"), "2024-03-10 03:00 EDT");
+        // 모르는 시간대는 UTC
+        assert_eq!(format_timestamp(0, "Nowhere/City"), "1970-01-01 00:00 UTC");
+    }
+
+    #[test]
+    fn local_day_and_hour() {
+        // 1970-01-01 15:00 UTC = 1970-01-02 00:00 KST
+        assert_eq!(local_day_hour(15 * 3600, "Asia/Seoul"), (1, 0));
+        assert_eq!(local_day_hour(15 * 3600, "UTC"), (0, 15));
+        // 1970-01-01 03:00 UTC = 1969-12-31 22:00 EST
+        assert_eq!(local_day_hour(3 * 3600, "America/New_York"), (-1, 22));
+    }
+
+    // 이번 달 1일 0시는 그때의 오프셋으로 (3월 1일 뉴욕은 아직 EST)
+    #[test]
+    fn month_start_uses_local_offset() {
+        assert_eq!(local_month_start(BEFORE_DST, "America/New_York"), (1_709_269_200, 2024, 3));
+        assert_eq!(local_month_start(BEFORE_DST, "UTC"), (1_709_251_200, 2024, 3));
+    }
+}
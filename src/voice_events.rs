@@ -0,0 +1,318 @@
+use serenity::all::ChannelId;
+use serenity::all::UserId;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+// 보이스 상태 변경 하나. 인원 수는 이벤트가 반영된 뒤 각 채널의 접속자 수
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VoiceEvent {
+    Join {
+        user: UserId,
+        channel: ChannelId,
+        members: usize,
+    },
+    Leave {
+        user: UserId,
+        channel: ChannelId,
+        members: usize,
+    },
+    Move {
+        user: UserId,
+        from: ChannelId,
+        from_members: usize,
+        to: ChannelId,
+        to_members: usize,
+    },
+}
+
+impl VoiceEvent {
+    // 트레이싱 스팬에 남길 이벤트 종류
+    pub fn name(&self) -> &'static str {
+        match self {
+            VoiceEvent::Join { .. } => "join",
+            VoiceEvent::Leave { .. } => "leave",
+            VoiceEvent::Move { .. } => "move",
+        }
+    }
+
+    // 사용자가 새로 들어간 채널과 인원 (입장 또는 이동)
+    pub fn joined_channel(&self) -> Option<(ChannelId, usize)> {
+        match *self {
+            VoiceEvent::Join { channel, members, .. } => Some((channel, members)),
+            VoiceEvent::Move { to, to_members, .. } => Some((to, to_members)),
+            VoiceEvent::Leave { .. } => None,
+        }
+    }
+
+    // 사용자가 나간 채널과 남은 인원 (퇴장 또는 이동)
+    pub fn left_channel(&self) -> Option<(ChannelId, usize)> {
+        match *self {
+            VoiceEvent::Leave { channel, members, .. } => Some((channel, members)),
+            VoiceEvent::Move { from, from_members, .. } => Some((from, from_members)),
+            VoiceEvent::Join { .. } => None,
+        }
+    }
+}
+
+// 활동 점수 = 최대 동시 접속자 수 × 활성화 시간(초). 시간만으로는 알 수 없는 활성화의 규모를 함께 반영
+pub fn activity_score(peak_members: usize, duration_secs: u64) -> i64 {
+    (peak_members as i64).saturating_mul(duration_secs.min(i64::MAX as u64) as i64)
+}
+
+// 이벤트 처리 결과로 핸들러가 실행할 작업 (순서대로 실행)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VoiceAction {
+    // 사용자 누적 시간 측정 시작/종료
+    MemberJoined { user: UserId },
+    MemberLeft { user: UserId },
+    StartSession { channel: ChannelId },
+    AnnounceActivate { channel: ChannelId, members: usize },
+    AnnounceJoin { user: UserId, channel: ChannelId, members: usize },
+    AnnounceLeave { user: UserId, channel: ChannelId, members: usize },
+    EndSession { channel: ChannelId, duration: Duration },
+}
+
+// 채널 활성화 상태(채널 ID -> 시작 시각)에 이벤트를 적용하고 실행할 작업 목록을 반환.
+// 디스코드 API나 시계를 직접 쓰지 않으므로 같은 입력이면 항상 같은 결과
+pub fn transition(
+    sessions: &mut HashMap<u64, Instant>,
+    event: VoiceEvent,
+    now: Instant,
+) -> Vec<VoiceAction> {
+    let mut actions = Vec::new();
+    match event {
+        VoiceEvent::Join { user, channel, members } => {
+            actions.push(VoiceAction::MemberJoined { user });
+            enter(sessions, &mut actions, user, channel, members, now);
+        }
+        VoiceEvent::Leave { user, channel, members } => {
+            actions.push(VoiceAction::MemberLeft { user });
+            exit(sessions, &mut actions, user, channel, members, now);
+        }
+        // 채널 이동: 접속 시간은 이어서 측정하고, 이전 채널 퇴장과 새 채널 입장을 모두 처리
+        VoiceEvent::Move {
+            user,
+            from,
+            from_members,
+            to,
+            to_members,
+        } => {
+            exit(sessions, &mut actions, user, from, from_members, now);
+            enter(sessions, &mut actions, user, to, to_members, now);
+        }
+    }
+    actions
+}
+
+fn enter(
+    sessions: &mut HashMap<u64, Instant>,
+    actions: &mut Vec<VoiceAction>,
+    user: UserId,
+    channel: ChannelId,
+    members: usize,
+    now: Instant,
+) {
+    // 이미 활성화된 채널에 다시 입장 이벤트가 와도 세션을 새로 시작하지 않음
+    if members == 1 && !sessions.contains_key(&channel.get()) {
+        sessions.insert(channel.get(), now);
+        actions.push(VoiceAction::StartSession { channel });
+        actions.push(VoiceAction::AnnounceActivate { channel, members });
+    }
+    actions.push(VoiceAction::AnnounceJoin { user, channel, members });
+}
+
+fn exit(
+    sessions: &mut HashMap<u64, Instant>,
+    actions: &mut Vec<VoiceAction>,
+    user: UserId,
+    channel: ChannelId,
+    members: usize,
+    now: Instant,
+) {
+    actions.push(VoiceAction::AnnounceLeave { user, channel, members });
+    if members == 0
+        && let Some(started) = sessions.remove(&channel.get())
+    {
+        actions.push(VoiceAction::EndSession {
+            channel,
+            duration: now.saturating_duration_since(started),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALICE: UserId = UserId::new(1);
+    const BOB: UserId = UserId::new(2);
+    const LOBBY: ChannelId = ChannelId::new(10);
+    const GAMES: ChannelId = ChannelId::new(20);
+
+    // 이벤트를 (시작 후 경과 초, 이벤트) 순서대로 적용하고 각 이벤트의 작업 목록을 돌려줌
+    fn replay(events: &[(u64, VoiceEvent)]) -> (Vec<Vec<VoiceAction>>, HashMap<u64, Instant>) {
+        let start = Instant::now();
+        let mut sessions = HashMap::new();
+        let actions = events
+            .iter()
+            .map(|&(secs, event)| transition(&mut sessions, event, start + Duration::from_secs(secs)))
+            .collect();
+        (actions, sessions)
+    }
+
+    fn join(user: UserId, channel: ChannelId, members: usize) -> VoiceEvent {
+        VoiceEvent::Join { user, channel, members }
+    }
+
+    fn leave(user: UserId, channel: ChannelId, members: usize) -> VoiceEvent {
+        VoiceEvent::Leave { user, channel, members }
+    }
+
+    #[test]
+    fn first_join_starts_session() {
+        let (actions, sessions) = replay(&[(0, join(ALICE, LOBBY, 1))]);
+        assert_eq!(
+            actions[0],
+            vec![
+                VoiceAction::MemberJoined { user: ALICE },
+                VoiceAction::StartSession { channel: LOBBY },
+                VoiceAction::AnnounceActivate { channel: LOBBY, members: 1 },
+                VoiceAction::AnnounceJoin { user: ALICE, channel: LOBBY, members: 1 },
+            ]
+        );
+        assert!(sessions.contains_key(&LOBBY.get()));
+    }
+
+    #[test]
+    fn second_join_only_announces() {
+        let (actions, _) = replay(&[(0, join(ALICE, LOBBY, 1)), (5, join(BOB, LOBBY, 2))]);
+        assert_eq!(
+            actions[1],
+            vec![
+                VoiceAction::MemberJoined { user: BOB },
+                VoiceAction::AnnounceJoin { user: BOB, channel: LOBBY, members: 2 },
+            ]
+        );
+    }
+
+    #[test]
+    fn last_leave_ends_session_with_duration() {
+        let (actions, sessions) = replay(&[
+            (0, join(ALICE, LOBBY, 1)),
+            (5, join(BOB, LOBBY, 2)),
+            (60, leave(ALICE, LOBBY, 1)),
+            (90, leave(BOB, LOBBY, 0)),
+        ]);
+        assert_eq!(
+            actions[2],
+            vec![
+                VoiceAction::MemberLeft { user: ALICE },
+                VoiceAction::AnnounceLeave { user: ALICE, channel: LOBBY, members: 1 },
+            ]
+        );
+        assert_eq!(
+            actions[3],
+            vec![
+                VoiceAction::MemberLeft { user: BOB },
+                VoiceAction::AnnounceLeave { user: BOB, channel: LOBBY, members: 0 },
+                VoiceAction::EndSession { channel: LOBBY, duration: Duration::from_secs(90) },
+            ]
+        );
+        assert!(sessions.is_empty());
+    }
+
+    #[test]
+    fn move_ends_old_session_and_starts_new_one() {
+        let (actions, sessions) = replay(&[
+            (0, join(ALICE, LOBBY, 1)),
+            (
+                30,
+                VoiceEvent::Move {
+                    user: ALICE,
+                    from: LOBBY,
+                    from_members: 0,
+                    to: GAMES,
+                    to_members: 1,
+                },
+            ),
+        ]);
+        // 이동은 접속 시간을 이어서 재므로 MemberLeft/MemberJoined가 없음
+        assert_eq!(
+            actions[1],
+            vec![
+                VoiceAction::AnnounceLeave { user: ALICE, channel: LOBBY, members: 0 },
+                VoiceAction::EndSession { channel: LOBBY, duration: Duration::from_secs(30) },
+                VoiceAction::StartSession { channel: GAMES },
+                VoiceAction::AnnounceActivate { channel: GAMES, members: 1 },
+                VoiceAction::AnnounceJoin { user: ALICE, channel: GAMES, members: 1 },
+            ]
+        );
+        assert_eq!(sessions.len(), 1);
+        assert!(sessions.contains_key(&GAMES.get()));
+    }
+
+    #[test]
+    fn move_into_populated_channel_keeps_its_session() {
+        let (actions, _) = replay(&[
+            (0, join(ALICE, LOBBY, 1)),
+            (1, join(BOB, GAMES, 1)),
+            (
+                10,
+                VoiceEvent::Move {
+                    user: ALICE,
+                    from: LOBBY,
+                    from_members: 0,
+                    to: GAMES,
+                    to_members: 2,
+                },
+            ),
+        ]);
+        assert_eq!(
+            actions[2],
+            vec![
+                VoiceAction::AnnounceLeave { user: ALICE, channel: LOBBY, members: 0 },
+                VoiceAction::EndSession { channel: LOBBY, duration: Duration::from_secs(10) },
+                VoiceAction::AnnounceJoin { user: ALICE, channel: GAMES, members: 2 },
+            ]
+        );
+    }
+
+    #[test]
+    fn double_join_does_not_restart_session() {
+        let (actions, _) = replay(&[
+            (0, join(ALICE, LOBBY, 1)),
+            (3, join(ALICE, LOBBY, 1)),
+            (50, leave(ALICE, LOBBY, 0)),
+        ]);
+        assert_eq!(
+            actions[1],
+            vec![
+                VoiceAction::MemberJoined { user: ALICE },
+                VoiceAction::AnnounceJoin { user: ALICE, channel: LOBBY, members: 1 },
+            ]
+        );
+        // 세션 시간은 첫 입장부터 셈
+        assert_eq!(
+            actions[2].last(),
+            Some(&VoiceAction::EndSession { channel: LOBBY, duration: Duration::from_secs(50) })
+        );
+    }
+
+    #[test]
+    fn leave_from_untracked_channel_has_no_session_end() {
+        let (actions, _) = replay(&[(0, leave(ALICE, LOBBY, 0))]);
+        assert_eq!(
+            actions[0],
+            vec![
+                VoiceAction::MemberLeft { user: ALICE },
+                VoiceAction::AnnounceLeave { user: ALICE, channel: LOBBY, members: 0 },
+            ]
+        );
+    }
+
+    #[test]
+    fn activity_score_saturates() {
+        assert_eq!(activity_score(3, 60), 180);
+        assert_eq!(activity_score(2, u64::MAX), i64::MAX);
+    }
+}
@@ -0,0 +1,233 @@
+use serenity::all::ChannelId;
+use serenity::all::CommandDataOptionValue;
+use serenity::all::CommandInteraction;
+use serenity::all::CreateEmbed;
+use serenity::all::CreateEmbedFooter;
+use serenity::all::CreateInteractionResponseMessage;
+use serenity::all::GuildId;
+use serenity::all::UserId;
+use serenity::prelude::*;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+use crate::commands::respond;
+use crate::guild_config::get_guild_config;
+use crate::storage::unix_now;
+
+// 하루에 보관하는 최대 이벤트 수 (넘으면 오래된 것부터 삭제)
+const DAILY_CAPACITY: usize = 500;
+// 보관하는 날 수 (오늘 포함)
+const RETAINED_DAYS: i64 = 7;
+const PAGE_SIZE: usize = 20;
+const SECS_PER_DAY: i64 = 86400;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventKind {
+    Join,
+    Leave,
+    Move,
+}
+
+// 입장/퇴장 기록 한 줄
+#[derive(Debug, Clone)]
+pub struct VoiceLogEntry {
+    pub ts: i64,
+    pub user_id: UserId,
+    pub user_name: String,
+    pub event: EventKind,
+    pub channel_id: ChannelId,
+    pub channel: String,
+}
+
+// 일자(UTC 기준 1970-01-01부터의 일 수)별 이벤트 기록
+type GuildVoiceLog = BTreeMap<i64, VecDeque<VoiceLogEntry>>;
+
+pub struct DailyVoiceLog;
+
+impl TypeMapKey for DailyVoiceLog {
+    type Value = Arc<RwLock<HashMap<GuildId, GuildVoiceLog>>>;
+}
+
+pub fn new_voice_log() -> Arc<RwLock<HashMap<GuildId, GuildVoiceLog>>> {
+    Arc::new(RwLock::new(HashMap::new()))
+}
+
+// 이벤트 기록 (길드에서 enable_voice_log를 켠 경우에만 호출)
+pub async fn record(ctx: &Context, guild_id: GuildId, entry: VoiceLogEntry) {
+    let log = {
+        let data = ctx.data.read().await;
+        data.get::<DailyVoiceLog>().cloned()
+    };
+    let Some(log) = log else {
+        return;
+    };
+    let day = entry.ts.div_euclid(SECS_PER_DAY);
+    let mut log = log.write().await;
+    let days = log.entry(guild_id).or_default();
+    // 날짜가 바뀌면 보관 기간이 지난 날 삭제
+    days.retain(|&d, _| d > day - RETAINED_DAYS);
+    let events = days.entry(day).or_default();
+    if events.len() >= DAILY_CAPACITY {
+        events.pop_front();
+    }
+    events.push_back(entry);
+}
+
+// 예약 작업: 이벤트가 없어 record에서 정리되지 않은 길드의 오래된 기록 삭제
+pub async fn prune(ctx: Context) {
+    let log = {
+        let data = ctx.data.read().await;
+        data.get::<DailyVoiceLog>().cloned()
+    };
+    let Some(log) = log else {
+        return;
+    };
+    let today = unix_now().div_euclid(SECS_PER_DAY);
+    let mut log = log.write().await;
+    for days in log.values_mut() {
+        days.retain(|&d, _| d > today - RETAINED_DAYS);
+    }
+    log.retain(|_, days| !days.is_empty());
+}
+
+// "YYYY-MM-DD" -> 1970-01-01부터의 일 수
+fn parse_date(input: &str) -> Option<i64> {
+    let mut parts = input.trim().splitn(3, '-');
+    let y: i64 = parts.next()?.parse().ok()?;
+    let m: i64 = parts.next()?.parse().ok()?;
+    let d: i64 = parts.next()?.parse().ok()?;
+    if !(1..=12).contains(&m) || !(1..=31).contains(&d) {
+        return None;
+    }
+    // 그레고리력 날짜 -> 일 수 (Howard Hinnant의 days_from_civil)
+    let y = if m <= 2 { y - 1 } else { y };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let mp = (m + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + d - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    Some(era * 146097 + doe - 719468)
+}
+
+fn format_date(day: i64) -> String {
+    // days_from_civil의 역변환
+    let z = day + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = doy - (153 * mp + 2) / 5 + 1;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 };
+    let y = yoe + era * 400 + if m <= 2 { 1 } else { 0 };
+    format!("{:04}-{:02}-{:02}", y, m, d)
+}
+
+// /voicelog [user] [channel] [date] [page]
+pub async fn handle_voicelog(ctx: &Context, cmd: &CommandInteraction) {
+    let Some(guild_id) = cmd.guild_id else {
+        return;
+    };
+    let reply = |content: String| CreateInteractionResponseMessage::new().content(content).ephemeral(true);
+
+    if !get_guild_config(ctx, guild_id).await.enable_voice_log {
+        respond(
+            ctx,
+            cmd,
+            reply("보이스 기록이 꺼져 있습니다. `/config set enable_voice_log on` 으로 켤 수 있습니다.".to_string()),
+        )
+        .await;
+        return;
+    }
+
+    let options = &cmd.data.options;
+    let user = options.iter().find(|o| o.name == "user").and_then(|o| match o.value {
+        CommandDataOptionValue::User(id) => Some(id),
+        _ => None,
+    });
+    let channel = options
+        .iter()
+        .find(|o| o.name == "channel")
+        .and_then(|o| o.value.as_channel_id());
+    let page = options
+        .iter()
+        .find(|o| o.name == "page")
+        .and_then(|o| o.value.as_i64())
+        .unwrap_or(1)
+        .max(1) as usize;
+    let day = match options.iter().find(|o| o.name == "date").and_then(|o| o.value.as_str()) {
+        Some(date) => match parse_date(date) {
+            Some(day) => day,
+            None => {
+                respond(ctx, cmd, reply("날짜는 YYYY-MM-DD 형식으로 입력하세요.".to_string())).await;
+                return;
+            }
+        },
+        None => unix_now().div_euclid(SECS_PER_DAY),
+    };
+
+    let log = {
+        let data = ctx.data.read().await;
+        data.get::<DailyVoiceLog>().cloned()
+    };
+    let Some(log) = log else {
+        return;
+    };
+    let entries: Vec<VoiceLogEntry> = {
+        let log = log.read().await;
+        log.get(&guild_id)
+            .and_then(|days| days.get(&day))
+            .map(|events| {
+                events
+                    .iter()
+                    .filter(|e| user.is_none_or(|u| e.user_id == u))
+                    .filter(|e| channel.is_none_or(|c| e.channel_id == c))
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+
+    let pages = entries.len().div_ceil(PAGE_SIZE).max(1);
+    let page = page.min(pages);
+    let body = if entries.is_empty() {
+        "기록된 이벤트가 없습니다.".to_string()
+    } else {
+        entries
+            .iter()
+            .skip((page - 1) * PAGE_SIZE)
+            .take(PAGE_SIZE)
+            .map(|e| {
+                let action = match e.event {
+                    EventKind::Join => "입장",
+                    EventKind::Leave => "퇴장",
+                    EventKind::Move => "이동",
+                };
+                format!(
+                    "<t:{}:T> **{}** (<@{}>) {} · #{}",
+                    e.ts, e.user_name, e.user_id, action, e.channel
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    let embed = CreateEmbed::new()
+        .title(format!("📜 보이스 기록 · {} (UTC)", format_date(day)))
+        .description(body)
+        .footer(CreateEmbedFooter::new(format!(
+            "{} / {} 페이지 · 총 {}건",
+            page,
+            pages,
+            entries.len()
+        )));
+    respond(
+        ctx,
+        cmd,
+        CreateInteractionResponseMessage::new()
+            .embed(embed)
+            .ephemeral(true),
+    )
+    .await;
+}
@@ -0,0 +1,577 @@
+use serenity::all::ButtonStyle;
+use serenity::all::ChannelId;
+use serenity::all::CommandDataOptionValue;
+use serenity::all::CommandInteraction;
+use serenity::all::ComponentInteraction;
+use serenity::all::CreateActionRow;
+use serenity::all::CreateButton;
+use serenity::all::CreateEmbed;
+use serenity::all::CreateEmbedFooter;
+use serenity::all::CreateInteractionResponse;
+use serenity::all::CreateInteractionResponseMessage;
+use serenity::all::GuildId;
+use serenity::all::Member;
+use serenity::all::RoleId;
+use serenity::all::UserId;
+use serenity::prelude::*;
+use std::time::Duration;
+
+use crate::calc::parse_duration_literal;
+use crate::commands::{respond, GUILD_ONLY};
+use crate::error_report::report_error;
+use crate::guild_config::{get_guild_config, post_audit_log, PrivacyLevel};
+use crate::long_message::{truncate, MESSAGE_LIMIT};
+use crate::rate_limit::{CooldownScope, Cooldowns};
+use crate::storage::{self, unix_now};
+use crate::streaks;
+use crate::user_prefs::local_month_start;
+use crate::voice_events::activity_score;
+use crate::voice_tracker::{format_duration, ChannelActivityTracker};
+
+const TOP_LIMIT: i64 = 10;
+// /adjust로 한 번에 더하거나 뺄 수 있는 최대 시간 (365일)
+const MAX_ADJUST_SECS: u64 = 365 * 86400;
+// /voiceduration에 함께 보여주는 활동 점수 설명
+const SCORE_HELP: &str = "ℹ️ 활동 점수 = 최대 동시 접속자 수 × 활성화 시간(초)";
+
+// 순위 새로고침 버튼의 custom_id: voicetop_refresh:<guild_id>
+pub const VOICETOP_REFRESH_PREFIX: &str = "voicetop_refresh:";
+// 같은 사용자가 새로고침을 연달아 누를 때 최소 간격
+const REFRESH_DEBOUNCE: Duration = Duration::from_secs(1);
+
+// 길드의 공개 범위 설정에 따라 통계 조회 가능 여부 확인. 거부 시 응답할 메시지 반환
+pub async fn check_privacy(ctx: &Context, member: Option<&Member>, guild_id: GuildId) -> Result<(), &'static str> {
+    let config = get_guild_config(ctx, guild_id).await;
+    privacy_allows(config.voice_stats_privacy, config.voice_stats_role, member)
+}
+
+fn privacy_allows(level: PrivacyLevel, role: Option<RoleId>, member: Option<&Member>) -> Result<(), &'static str> {
+    let is_admin = member
+        .and_then(|m| m.permissions)
+        .is_some_and(|p| p.administrator() || p.manage_guild());
+    match level {
+        PrivacyLevel::Public => Ok(()),
+        PrivacyLevel::MembersOnly => match member {
+            _ if is_admin => Ok(()),
+            // 길드 안에서 실행된 경우에만 멤버 정보가 함께 전달됨.
+            // 멤버 심사(규칙 동의)를 마치지 않은 멤버는 아직 서버 멤버로 보지 않음
+            None => Err("이 서버의 보이스 통계는 서버 멤버만 볼 수 있습니다"),
+            Some(member) if member.pending => Err("서버 규칙 동의를 마친 멤버만 보이스 통계를 볼 수 있습니다"),
+            Some(member) => match role {
+                Some(role) if !member.roles.contains(&role) => {
+                    Err("이 서버의 보이스 통계는 지정된 역할이 있는 멤버만 볼 수 있습니다")
+                }
+                _ => Ok(()),
+            },
+        },
+        PrivacyLevel::AdminOnly if is_admin => Ok(()),
+        PrivacyLevel::AdminOnly => Err("이 서버의 보이스 통계는 서버 관리 권한이 있어야 볼 수 있습니다"),
+    }
+}
+
+async fn deny(ctx: &Context, cmd: &CommandInteraction, message: &str) {
+    respond(
+        ctx,
+        cmd,
+        CreateInteractionResponseMessage::new()
+            .content(message)
+            .ephemeral(true),
+    )
+    .await;
+}
+
+// /voicestats [user]: 누적 보이스 시간
+pub async fn handle_voicestats(ctx: &Context, cmd: &CommandInteraction) {
+    let Some(guild_id) = cmd.guild_id else {
+        deny(ctx, cmd, GUILD_ONLY).await;
+        return;
+    };
+    if let Err(message) = check_privacy(ctx, cmd.member.as_deref(), guild_id).await {
+        deny(ctx, cmd, message).await;
+        return;
+    }
+    let Some(pool) = storage::pool(ctx).await else {
+        return;
+    };
+
+    let user_id = cmd
+        .data
+        .options
+        .iter()
+        .find(|o| o.name == "user")
+        .and_then(|o| match o.value {
+            CommandDataOptionValue::User(id) => Some(id),
+            _ => None,
+        })
+        .unwrap_or(cmd.user.id);
+
+    let content = match storage::get_user_time(&pool, guild_id, user_id).await {
+        Ok(secs) => {
+            let mut content = format!(
+                "🎧 <@{}> 님의 누적 보이스 시간: **{}**",
+                user_id,
+                format_duration(secs.max(0) as u64)
+            );
+            let timezone = get_guild_config(ctx, guild_id).await.timezone;
+            if let Some(line) = streaks::streak_line(&pool, guild_id, user_id, &timezone).await {
+                content.push('\n');
+                content.push_str(&line);
+            }
+            content
+        }
+        Err(e) => {
+            report_error(ctx, "보이스 통계 조회", &e).await;
+            "통계를 불러오지 못했습니다.".to_string()
+        }
+    };
+    respond(ctx, cmd, CreateInteractionResponseMessage::new().content(content)).await;
+}
+
+// /adjust add|remove <user> <duration> [reason] | reset <user> [reason]: 누적 보이스 시간 보정 (다운타임, 테스트 등)
+pub async fn handle_adjust(ctx: &Context, cmd: &CommandInteraction) {
+    let Some(guild_id) = cmd.guild_id else {
+        deny(ctx, cmd, GUILD_ONLY).await;
+        return;
+    };
+    let Some(sub) = cmd.data.options.first() else {
+        return;
+    };
+    let CommandDataOptionValue::SubCommand(args) = &sub.value else {
+        return;
+    };
+    let Some(pool) = storage::pool(ctx).await else {
+        return;
+    };
+    let user_id = args.iter().find(|o| o.name == "user").and_then(|o| match o.value {
+        CommandDataOptionValue::User(id) => Some(id),
+        _ => None,
+    });
+    let Some(user_id) = user_id else {
+        return;
+    };
+    let reason = args
+        .iter()
+        .find(|o| o.name == "reason")
+        .and_then(|o| o.value.as_str())
+        .map(str::trim)
+        .filter(|r| !r.is_empty());
+
+    // 더하거나 뺄 시간 (초). reset이면 None
+    let delta = match sub.name.as_str() {
+        "add" | "remove" => {
+            let duration = args.iter().find(|o| o.name == "duration").and_then(|o| o.value.as_str());
+            let secs = match duration.map(parse_duration_literal) {
+                Some(Ok(secs)) if (1..=MAX_ADJUST_SECS).contains(&secs) => secs as i64,
+                Some(Ok(_)) => {
+                    deny(ctx, cmd, "기간은 1초 이상 365일 이하여야 합니다.").await;
+                    return;
+                }
+                Some(Err(e)) => {
+                    deny(ctx, cmd, &format!("{} (예: `45m`, `2h30m`)", e)).await;
+                    return;
+                }
+                None => return,
+            };
+            Some(if sub.name == "add" { secs } else { -secs })
+        }
+        "reset" => None,
+        _ => return,
+    };
+
+    let result = storage::update_user_time(&pool, guild_id, user_id, |total| match delta {
+        Some(delta) => total.saturating_add(delta),
+        None => 0,
+    })
+    .await;
+    let (before, after) = match result {
+        Ok(times) => times,
+        Err(e) => {
+            report_error(ctx, "보이스 시간 보정", &e).await;
+            deny(ctx, cmd, "보이스 시간을 보정하지 못했습니다.").await;
+            return;
+        }
+    };
+
+    let change = format_signed_duration(after - before);
+    let reason = reason.map(|r| format!(" (사유: {})", r)).unwrap_or_default();
+    post_audit_log(
+        ctx,
+        guild_id,
+        format!(
+            "⏱️ <@{}> 님이 <@{}> 님의 누적 보이스 시간을 보정했습니다: {} → {} ({}){}",
+            cmd.user.id,
+            user_id,
+            format_duration(before as u64),
+            format_duration(after as u64),
+            change,
+            reason
+        ),
+    )
+    .await;
+    let mut content = format!(
+        "<@{}> 님의 누적 보이스 시간: {} → **{}** ({})",
+        user_id,
+        format_duration(before as u64),
+        format_duration(after as u64),
+        change
+    );
+    if delta.is_some_and(|d| before + d < 0) {
+        content.push_str("\n누적 시간은 0보다 작아질 수 없어 0으로 맞췄습니다.");
+    }
+    respond(
+        ctx,
+        cmd,
+        CreateInteractionResponseMessage::new()
+            .content(content)
+            .ephemeral(true),
+    )
+    .await;
+}
+
+fn format_signed_duration(secs: i64) -> String {
+    let sign = if secs < 0 { '-' } else { '+' };
+    format!("{}{}", sign, format_duration(secs.unsigned_abs()))
+}
+
+// /voicetop: 길드 누적 보이스 시간 순위
+pub async fn handle_voicetop(ctx: &Context, cmd: &CommandInteraction) {
+    let Some(guild_id) = cmd.guild_id else {
+        deny(ctx, cmd, GUILD_ONLY).await;
+        return;
+    };
+    if let Err(message) = check_privacy(ctx, cmd.member.as_deref(), guild_id).await {
+        deny(ctx, cmd, message).await;
+        return;
+    }
+    let Some(pool) = storage::pool(ctx).await else {
+        return;
+    };
+
+    let rows = match storage::top_users(&pool, guild_id, TOP_LIMIT).await {
+        Ok(rows) => rows,
+        Err(e) => {
+            report_error(ctx, "보이스 순위 조회", &e).await;
+            deny(ctx, cmd, "순위를 불러오지 못했습니다.").await;
+            return;
+        }
+    };
+    respond(
+        ctx,
+        cmd,
+        CreateInteractionResponseMessage::new()
+            .embed(leaderboard_embed(&rows))
+            .components(vec![refresh_button(guild_id)]),
+    )
+    .await;
+}
+
+// /starters: 이번 달 (서버 시간대 기준) 채널 활성화를 가장 많이 시작한 멤버
+pub async fn handle_starters(ctx: &Context, cmd: &CommandInteraction) {
+    let Some(guild_id) = cmd.guild_id else {
+        deny(ctx, cmd, GUILD_ONLY).await;
+        return;
+    };
+    if let Err(message) = check_privacy(ctx, cmd.member.as_deref(), guild_id).await {
+        deny(ctx, cmd, message).await;
+        return;
+    }
+    let Some(pool) = storage::pool(ctx).await else {
+        return;
+    };
+
+    let timezone = get_guild_config(ctx, guild_id).await.timezone;
+    let (since, year, month) = local_month_start(unix_now(), &timezone);
+    let rows = match storage::top_starters(&pool, guild_id, since, TOP_LIMIT).await {
+        Ok(rows) => rows,
+        Err(e) => {
+            report_error(ctx, "활성화 시작 순위 조회", &e).await;
+            deny(ctx, cmd, "순위를 불러오지 못했습니다.").await;
+            return;
+        }
+    };
+    let body = if rows.is_empty() {
+        "이번 달에는 아직 기록이 없습니다.".to_string()
+    } else {
+        rows.iter()
+            .enumerate()
+            .map(|(i, (user_id, count))| format!("{}. <@{}> — {}번", i + 1, user_id, count))
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+    let embed = CreateEmbed::new()
+        .title(format!("🎉 {}년 {}월 채널을 가장 많이 연 사람", year, month))
+        .description(body)
+        .footer(CreateEmbedFooter::new(format!("보이스 채널에 처음 들어와 활성화한 횟수 ({} 기준)", timezone)));
+    respond(ctx, cmd, CreateInteractionResponseMessage::new().embed(embed)).await;
+}
+
+fn refresh_button(guild_id: GuildId) -> CreateActionRow {
+    CreateActionRow::Buttons(vec![CreateButton::new(format!("{}{}", VOICETOP_REFRESH_PREFIX, guild_id))
+        .label("🔄 새로고침")
+        .style(ButtonStyle::Secondary)])
+}
+
+// 순위 임베드. 갱신 시각은 디스코드 상대 시간 표기라 "N초 전"으로 계속 바뀌어 보임
+fn leaderboard_embed(rows: &[(UserId, i64)]) -> CreateEmbed {
+    let body = if rows.is_empty() {
+        "아직 기록이 없습니다.".to_string()
+    } else {
+        rows.iter()
+            .enumerate()
+            .map(|(i, (user_id, secs))| {
+                format!("{}. <@{}> — {}", i + 1, user_id, format_duration(*secs as u64))
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+    CreateEmbed::new()
+        .title("🏆 보이스 시간 순위")
+        .description(body)
+        .field("마지막 갱신", format!("<t:{}:R>", unix_now()), false)
+}
+
+// 순위 새로고침 버튼: 다시 조회해 원래 메시지를 수정. 1초 안에 다시 누르면 조회하지 않고 무시
+pub async fn handle_voicetop_refresh(ctx: &Context, comp: &ComponentInteraction) {
+    let Some(guild_id) = comp
+        .data
+        .custom_id
+        .strip_prefix(VOICETOP_REFRESH_PREFIX)
+        .and_then(|id| id.parse::<u64>().ok())
+        .map(GuildId::new)
+        .filter(|&g| comp.guild_id == Some(g))
+    else {
+        return;
+    };
+
+    let response = if let Err(message) = check_privacy(ctx, comp.member.as_ref(), guild_id).await {
+        CreateInteractionResponse::Message(
+            CreateInteractionResponseMessage::new()
+                .content(message)
+                .ephemeral(true),
+        )
+    } else if !debounce(ctx, comp.user.id).await {
+        CreateInteractionResponse::Acknowledge
+    } else {
+        let Some(pool) = storage::pool(ctx).await else {
+            return;
+        };
+        match storage::top_users(&pool, guild_id, TOP_LIMIT).await {
+            Ok(rows) => CreateInteractionResponse::UpdateMessage(
+                CreateInteractionResponseMessage::new()
+                    .embed(leaderboard_embed(&rows))
+                    .components(vec![refresh_button(guild_id)]),
+            ),
+            Err(e) => {
+                report_error(ctx, "보이스 순위 조회", &e).await;
+                CreateInteractionResponse::Message(
+                    CreateInteractionResponseMessage::new()
+                        .content("순위를 불러오지 못했습니다.")
+                        .ephemeral(true),
+                )
+            }
+        }
+    };
+    if let Err(e) = comp.create_response(&ctx.http, response).await {
+        report_error(ctx, "보이스 순위 새로고침 응답", &e).await;
+    }
+}
+
+// 새로고침해도 되면 true (사용자별 REFRESH_DEBOUNCE 간격)
+async fn debounce(ctx: &Context, user_id: UserId) -> bool {
+    let cooldowns = {
+        let data = ctx.data.read().await;
+        data.get::<Cooldowns>().cloned()
+    };
+    match cooldowns {
+        Some(cooldowns) => cooldowns
+            .try_start("voicetop_refresh", CooldownScope::User, user_id.get(), REFRESH_DEBOUNCE)
+            .await
+            .is_ok(),
+        None => true,
+    }
+}
+
+// /voiceduration [channel]: 채널이 활성화된 지 얼마나 됐는지. 채널을 안 주면 활성화된 채널 전체
+pub async fn handle_voiceduration(ctx: &Context, cmd: &CommandInteraction) {
+    let Some(guild_id) = cmd.guild_id else {
+        deny(ctx, cmd, GUILD_ONLY).await;
+        return;
+    };
+    let tracker = {
+        let data = ctx.data.read().await;
+        data.get::<ChannelActivityTracker>().cloned()
+    };
+    let Some(tracker) = tracker else {
+        return;
+    };
+    let mut active: Vec<(ChannelId, u64, usize)> = tracker
+        .active_sessions(guild_id)
+        .await
+        .into_iter()
+        .map(|(channel, elapsed, peak)| (channel, elapsed.as_secs(), peak))
+        .collect();
+
+    let channel_id = cmd
+        .data
+        .options
+        .iter()
+        .find(|o| o.name == "channel")
+        .and_then(|o| o.value.as_channel_id());
+    let content = match channel_id {
+        Some(channel_id) => match active.iter().find(|(c, _, _)| *c == channel_id) {
+            Some(&(_, secs, peak)) => format!(
+                "🟢 <#{}> 채널이 {}째 활성화되어 있습니다\n최대 {}명 · 활동 점수 {}\n{}",
+                channel_id,
+                format_duration(secs),
+                peak,
+                activity_score(peak, secs),
+                SCORE_HELP
+            ),
+            None => format!("⚪ <#{}> 채널은 활성화되어 있지 않습니다", channel_id),
+        },
+        None if active.is_empty() => "⚪ 활성화된 보이스 채널이 없습니다".to_string(),
+        None => {
+            // 오래 활성화된 채널부터
+            active.sort_by_key(|&(_, secs, _)| std::cmp::Reverse(secs));
+            let lines = active
+                .iter()
+                .map(|&(channel_id, secs, peak)| {
+                    format!(
+                        "🟢 <#{}> — {} (최대 {}명 · 점수 {})",
+                        channel_id,
+                        format_duration(secs),
+                        peak,
+                        activity_score(peak, secs)
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+            truncate(&format!("활성화된 보이스 채널\n{}\n{}", SCORE_HELP, lines), MESSAGE_LIMIT)
+        }
+    };
+    respond(ctx, cmd, CreateInteractionResponseMessage::new().content(content)).await;
+}
+
+// /session <id>: 길드 활성화 번호로 활성화 기록 하나를 자세히 보여줌. 진행 중인 활성화도 찾음
+pub async fn handle_session(ctx: &Context, cmd: &CommandInteraction) {
+    let Some(guild_id) = cmd.guild_id else {
+        deny(ctx, cmd, GUILD_ONLY).await;
+        return;
+    };
+    if let Err(message) = check_privacy(ctx, cmd.member.as_deref(), guild_id).await {
+        deny(ctx, cmd, message).await;
+        return;
+    }
+    let Some(pool) = storage::pool(ctx).await else {
+        return;
+    };
+    let Some(session_id) = cmd
+        .data
+        .options
+        .iter()
+        .find(|o| o.name == "id")
+        .and_then(|o| o.value.as_i64())
+        .and_then(|id| u64::try_from(id).ok())
+    else {
+        return;
+    };
+
+    let content = match storage::get_session(&pool, guild_id, session_id).await {
+        Ok(Some(session)) => {
+            let secs = (session.ended_at - session.started_at).max(0) as u64;
+            let mut content = format!(
+                "📋 활성화 #{} — <#{}>\n시작: <t:{}:f>\n종료: <t:{}:f>\n지속 시간: {}\n최대 {}명 · 활동 점수 {}",
+                session.session_id,
+                session.channel_id,
+                session.started_at,
+                session.ended_at,
+                format_duration(secs),
+                session.peak_members,
+                session.activity_score
+            );
+            if let Some(starter_id) = session.starter_id {
+                content.push_str(&format!("\n시작한 사람: <@{}>", starter_id));
+            }
+            content
+        }
+        Ok(None) => {
+            let tracker = {
+                let data = ctx.data.read().await;
+                data.get::<ChannelActivityTracker>().cloned()
+            };
+            let active = match tracker {
+                Some(tracker) => tracker.active_session(guild_id, session_id).await,
+                None => None,
+            };
+            match active {
+                Some((channel_id, elapsed, peak)) => {
+                    let secs = elapsed.as_secs();
+                    format!(
+                        "🟢 활성화 #{} — <#{}> (진행 중)\n시작: <t:{}:f>\n지속 시간: {}\n최대 {}명 · 활동 점수 {}",
+                        session_id,
+                        channel_id,
+                        unix_now() - secs as i64,
+                        format_duration(secs),
+                        peak,
+                        activity_score(peak, secs)
+                    )
+                }
+                None => format!("활성화 #{} 기록이 없습니다.", session_id),
+            }
+        }
+        Err(e) => {
+            report_error(ctx, "활성화 기록 조회", &e).await;
+            "기록을 불러오지 못했습니다.".to_string()
+        }
+    };
+    respond(ctx, cmd, CreateInteractionResponseMessage::new().content(content)).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serenity::all::Permissions;
+
+    const STATS_ROLE: RoleId = RoleId::new(7);
+
+    fn member(pending: bool, roles: &[RoleId], permissions: Permissions) -> Member {
+        let mut member = Member::default();
+        member.pending = pending;
+        member.roles = roles.to_vec();
+        member.permissions = Some(permissions);
+        member
+    }
+
+    #[test]
+    fn public_allows_everyone() {
+        assert!(privacy_allows(PrivacyLevel::Public, None, None).is_ok());
+        assert!(privacy_allows(PrivacyLevel::Public, Some(STATS_ROLE), None).is_ok());
+    }
+
+    #[test]
+    fn members_only_requires_member() {
+        let level = PrivacyLevel::MembersOnly;
+        assert!(privacy_allows(level, None, None).is_err());
+        assert!(privacy_allows(level, None, Some(&member(false, &[], Permissions::empty()))).is_ok());
+        // 규칙 동의 전인 멤버는 거부
+        assert!(privacy_allows(level, None, Some(&member(true, &[], Permissions::empty()))).is_err());
+    }
+
+    #[test]
+    fn members_only_checks_role() {
+        let level = PrivacyLevel::MembersOnly;
+        let role = Some(STATS_ROLE);
+        assert!(privacy_allows(level, role, Some(&member(false, &[], Permissions::empty()))).is_err());
+        assert!(privacy_allows(level, role, Some(&member(false, &[STATS_ROLE], Permissions::empty()))).is_ok());
+        // 서버 관리자는 역할이 없어도 볼 수 있음
+        assert!(privacy_allows(level, role, Some(&member(false, &[], Permissions::MANAGE_GUILD))).is_ok());
+    }
+
+    #[test]
+    fn admin_only_requires_manage_guild() {
+        let level = PrivacyLevel::AdminOnly;
+        assert!(privacy_allows(level, None, None).is_err());
+        assert!(privacy_allows(level, None, Some(&member(false, &[STATS_ROLE], Permissions::empty()))).is_err());
+        assert!(privacy_allows(level, None, Some(&member(false, &[], Permissions::ADMINISTRATOR))).is_ok());
+    }
+}
@@ -1,88 +1,263 @@
 use serenity::async_trait;
-use serenity::all::CommandInteraction;
-use serenity::all::Command;
-use serenity::all::CommandOptionType;
-use serenity::all::CreateCommand;
-use serenity::all::CreateCommandOption;
-use serenity::all::CreateInteractionResponse;
-use serenity::all::CreateInteractionResponseMessage;
-use serenity::all::CommandDataOptionValue;
+use serenity::all::Cache;
+use serenity::all::ChannelId;
+use serenity::all::Guild;
+use serenity::all::GuildChannel;
+use serenity::all::GuildId;
 use serenity::all::Interaction;
+use serenity::all::Message;
+use serenity::all::PartialGuildChannel;
 use serenity::all::Ready;
+use serenity::all::ResumedEvent;
+use serenity::all::Role;
+use serenity::all::RoleId;
+use serenity::all::ShardStageUpdateEvent;
+use serenity::all::User;
+use serenity::all::UserId;
 use serenity::model::voice::VoiceState;
 use serenity::prelude::*;
-use std::collections::HashMap;
+use sqlx::SqlitePool;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
-use std::time::Instant;
-use tokio::sync::RwLock;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+use tokio::sync::{Mutex, RwLock};
+use tracing::Instrument;
 
-// 보이스 채널의 활성화 시작 시간을 추적
-pub struct ChannelActivityTracker {
-    pub(crate) active_channels: Arc<RwLock<HashMap<u64, Instant>>>,
+use crate::channel_status;
+#[cfg(feature = "chime")]
+use crate::chime;
+use crate::commands::{dispatch, dispatch_autocomplete, dispatch_component, dispatch_modal, register_global_commands, register_guild_commands, BotOwner};
+use crate::error::{report_bot_error, BotError, ErrorContext};
+use crate::config_check::{check_guild, get_usable_config};
+use crate::event_metrics::{self, Handler, Phase};
+use crate::guild_config::{next_session_id, GuildConfig};
+use crate::health::HealthState;
+use crate::mention::{handle_bot_mention, is_bot_mention};
+use crate::notification::{self, queue_send, send_notification, ChannelDetails};
+use crate::notification_batch::{self, BatchEntry};
+use crate::presence::start_presence_task;
+use crate::reminders::restore_reminders;
+use crate::scheduled_events;
+use crate::scheduler::start_scheduler;
+use crate::setup::post_setup_wizard;
+use crate::shards::{record_event, shard_of};
+use crate::storage::{self, VoiceSession};
+use crate::streaks;
+use crate::temp_channels;
+use crate::threads::{announce_thread_event, ThreadEvent};
+use crate::tts_announce;
+use crate::user_prefs::get_user_prefs;
+use crate::voice_events::{activity_score, transition, VoiceAction, VoiceEvent};
+use crate::voice_log::{self, EventKind, VoiceLogEntry};
+
+// 길드 하나의 채널 활성화 상태 (채널 ID -> 시작 시각)
+#[derive(Debug, Default)]
+pub struct GuildTracker {
+    pub sessions: HashMap<u64, Instant>,
+    // 활성화된 채널의 최대 동시 접속자 수 (활동 점수 계산용)
+    pub peaks: HashMap<u64, usize>,
+    // 활성화된 채널의 길드별 활성화 번호 (시작할 때 받음)
+    pub session_ids: HashMap<u64, u64>,
+    // 활성화를 시작한 사람 (세션의 첫 사람 입장, 봇 제외). None이면 알 수 없음 (재시작 후 보정 등)
+    pub starters: HashMap<u64, Option<UserId>>,
 }
 
-impl ChannelActivityTracker {
-    pub fn new() -> Self {
-        Self {
-            active_channels: Arc::new(RwLock::new(HashMap::new())),
+impl GuildTracker {
+    // 활성화된 채널이면 최대 접속자 수 갱신
+    fn record_peak(&mut self, channel_id: ChannelId, members: usize) {
+        if self.sessions.contains_key(&channel_id.get()) {
+            let peak = self.peaks.entry(channel_id.get()).or_default();
+            *peak = (*peak).max(members);
+        }
+    }
+
+    // 이벤트를 세션 상태에 반영하고 실행할 작업과, 새로 기록한 시작한 사람의 채널을 돌려줌
+    fn apply(
+        &mut self,
+        event: VoiceEvent,
+        user_id: UserId,
+        bot: bool,
+        now: Instant,
+    ) -> (Vec<VoiceAction>, Option<ChannelId>) {
+        let actions = transition(&mut self.sessions, event, now);
+        let mut new_starter = None;
+        if let Some((channel, members)) = event.joined_channel() {
+            self.record_peak(channel, members);
+            if !bot && self.record_starter(channel, user_id) {
+                new_starter = Some(channel);
+            }
+        }
+        (actions, new_starter)
+    }
+
+    // 비활성화된 채널의 최대 접속자 수를 꺼내고 지움
+    fn take_peak(&mut self, channel_id: ChannelId) -> usize {
+        self.peaks.remove(&channel_id.get()).unwrap_or(0)
+    }
+
+    // 활성화된 채널에 사람이 처음 들어왔으면 시작한 사람으로 기록. 봇만 있던 채널이면 처음 들어온 사람이 됨.
+    // 새로 기록했으면 true
+    fn record_starter(&mut self, channel_id: ChannelId, user_id: UserId) -> bool {
+        if !self.sessions.contains_key(&channel_id.get()) || self.starters.contains_key(&channel_id.get()) {
+            return false;
+        }
+        self.starters.insert(channel_id.get(), Some(user_id));
+        true
+    }
+
+    // 비활성화된 채널을 시작한 사람을 꺼내고 지움
+    fn take_starter(&mut self, channel_id: ChannelId) -> Option<UserId> {
+        self.starters.remove(&channel_id.get()).flatten()
+    }
+
+    // 비활성화된 채널의 활성화 정보를 꺼내고 지움 (record_session_end)
+    fn take_ended(&mut self, channel_id: ChannelId, duration: Duration) -> EndedSession {
+        EndedSession {
+            channel_id,
+            session_id: self.session_ids.remove(&channel_id.get()),
+            duration,
+            peak_members: self.take_peak(channel_id),
+            starter_id: self.take_starter(channel_id),
         }
     }
 }
 
-impl TypeMapKey for ChannelActivityTracker {
-    type Value = Arc<RwLock<HashMap<u64, Instant>>>;
+// 알림을 보낼 채널 (/setchannel). 설정하지 않았거나 쓸 수 없으면 None.
+// 다른 봇의 입장/퇴장은 인원과 세션에는 반영하되 알림은 보내지 않음 (notify_bots로 켤 수 있음)
+fn notification_target(config: &GuildConfig, bot: bool) -> Option<ChannelId> {
+    config.notification_channel.filter(|_| !bot || config.notify_bots)
 }
 
-pub fn new_tracker_store() -> Arc<RwLock<HashMap<u64, Instant>>> {
-    Arc::new(RwLock::new(HashMap::new()))
+// 끝난 활성화. session_id가 없으면 시작할 때 번호를 받지 못한 것
+struct EndedSession {
+    channel_id: ChannelId,
+    session_id: Option<u64>,
+    duration: Duration,
+    peak_members: usize,
+    starter_id: Option<UserId>,
 }
 
-pub struct VoiceHandler;
+// 보이스 채널의 활성화 시작 시간을 길드별로 추적. 길드마다 잠금이 따로 있어
+// 같은 길드의 이벤트는 차례로, 다른 길드의 이벤트는 동시에 처리됨
+#[derive(Default)]
+pub struct VoiceTrackerStore {
+    guilds: RwLock<HashMap<GuildId, Arc<Mutex<GuildTracker>>>>,
+}
 
-#[async_trait]
-impl EventHandler for VoiceHandler {
-    async fn ready(&self, ctx: Context, ready: Ready) {
-        println!("{}님의 봇이 준비되었습니다!", ready.user.name);
-        // 슬래시 커맨드 등록: /calc
-        let cmd = CreateCommand::new("calc")
-            .description("수식을 PEMDAS 우선순위로 계산합니다")
-            .add_option(
-                CreateCommandOption::new(CommandOptionType::String, "expr", "계산할 수식")
-                    .required(true),
-            );
-
-        if let Err(e) = Command::create_global_command(&ctx.http, cmd)
-        .await
-        {
-            eprintln!("/calc 등록 실패: {:?}", e);
+impl VoiceTrackerStore {
+    // 길드의 추적 상태 (없으면 새로 만듦). 잠금은 호출한 쪽이 처리가 끝날 때까지 잡고 있음
+    pub async fn guild(&self, guild_id: GuildId) -> Arc<Mutex<GuildTracker>> {
+        if let Some(tracker) = self.guilds.read().await.get(&guild_id) {
+            return tracker.clone();
         }
+        self.guilds.write().await.entry(guild_id).or_default().clone()
+    }
 
-        // 길드 커맨드로도 즉시 등록 (봇이 속한 모든 길드)
-        for guild_id in ctx.cache.guilds() {
-            let guild_cmd = CreateCommand::new("calc")
-                .description("입력된 수식을 계산합니다.")
-                .add_option(
-                    CreateCommandOption::new(CommandOptionType::String, "expr", "계산할 수식")
-                        .required(true),
-                );
-            if let Err(e) = guild_id.create_command(&ctx.http, guild_cmd).await {
-                eprintln!("/calc 길드 등록 실패 ({}): {:?}", guild_id, e);
-            }
+    // 길드에서 활성화된 채널과 활성화된 지 지난 시간, 지금까지의 최대 접속자 수
+    pub async fn active_sessions(&self, guild_id: GuildId) -> Vec<(ChannelId, Duration, usize)> {
+        let tracker = self.guild(guild_id).await;
+        let tracker = tracker.lock().await;
+        tracker
+            .sessions
+            .iter()
+            .map(|(&channel, start)| {
+                let peak = tracker.peaks.get(&channel).copied().unwrap_or(0);
+                (ChannelId::new(channel), start.elapsed(), peak)
+            })
+            .collect()
+    }
+
+    // 활성화 번호가 session_id인 진행 중인 활성화 (/session)
+    pub async fn active_session(&self, guild_id: GuildId, session_id: u64) -> Option<(ChannelId, Duration, usize)> {
+        let tracker = self.guild(guild_id).await;
+        let tracker = tracker.lock().await;
+        let (&channel, _) = tracker.session_ids.iter().find(|&(_, &id)| id == session_id)?;
+        let start = tracker.sessions.get(&channel)?;
+        let peak = tracker.peaks.get(&channel).copied().unwrap_or(0);
+        Some((ChannelId::new(channel), start.elapsed(), peak))
+    }
+
+    // 모든 길드에서 활성화된 채널 수
+    pub async fn active_channels(&self) -> usize {
+        let trackers: Vec<Arc<Mutex<GuildTracker>>> = self.guilds.read().await.values().cloned().collect();
+        let mut count = 0;
+        for tracker in trackers {
+            count += tracker.lock().await.sessions.len();
+        }
+        count
+    }
+}
+
+pub struct ChannelActivityTracker;
+
+impl TypeMapKey for ChannelActivityTracker {
+    type Value = Arc<VoiceTrackerStore>;
+}
+
+pub fn new_tracker_store() -> Arc<VoiceTrackerStore> {
+    Arc::new(VoiceTrackerStore::default())
+}
+
+// 채널이 지금 활성화되어 있는지
+pub async fn is_channel_active(tracker: &VoiceTrackerStore, guild_id: GuildId, channel_id: ChannelId) -> bool {
+    let guild_tracker = tracker.guild(guild_id).await;
+    guild_tracker.lock().await.sessions.contains_key(&channel_id.get())
+}
+
+// 이벤트 핸들러가 직접 들고 있는 공유 상태. 커맨드 등 다른 곳에서 필요한 값은
+// 같은 인스턴스를 TypeMap에도 등록해 둠 (ChannelActivityTracker, Storage)
+pub struct AppState {
+    pub voice_tracker: Arc<VoiceTrackerStore>,
+    // 사용자별 보이스 채널 접속 시작 시간 (누적 시간 기록용)
+    pub voice_members: RwLock<HashMap<(GuildId, UserId), Instant>>,
+    pub storage: SqlitePool,
+    pub health: Arc<HealthState>,
+    // 저장된 리마인더는 첫 ready에서 한 번만 다시 예약
+    pub reminders_restored: AtomicBool,
+    // 마지막으로 준비된 클라이언트의 디스코드 캐시 (대시보드 API가 참가한 길드와 접속자 수 확인에 사용)
+    pub cache: RwLock<Option<Arc<Cache>>>,
+}
+
+impl AppState {
+    pub fn new(
+        voice_tracker: Arc<VoiceTrackerStore>,
+        storage: SqlitePool,
+        health: Arc<HealthState>,
+    ) -> Self {
+        Self {
+            voice_tracker,
+            voice_members: RwLock::new(HashMap::new()),
+            storage,
+            health,
+            reminders_restored: AtomicBool::new(false),
+            cache: RwLock::new(None),
         }
     }
+}
+
+pub struct VoiceHandler {
+    state: Arc<AppState>,
+}
+
+impl VoiceHandler {
+    pub fn new(state: Arc<AppState>) -> Self {
+        Self { state }
+    }
 
-    async fn voice_state_update(
+    // 보이스 상태 변경 처리 (voice_state_update가 처리 시간을 재며 호출)
+    async fn handle_voice_state_update(
         &self,
         ctx: Context,
         old: Option<VoiceState>,
         new: VoiceState,
     ) {
-        let data = ctx.data.read().await;
-        let tracker = data
-            .get::<ChannelActivityTracker>()
-            .expect("활동 추적기를 찾을 수 없습니다")
-            .clone();
-        drop(data);
+        record_event(&ctx).await;
+        self.state.health.record_event();
+
+        let state = &self.state;
+        let tracker = &state.voice_tracker;
 
         let guild_id = match new.guild_id {
             Some(id) => id,
@@ -94,144 +269,725 @@ impl EventHandler for VoiceHandler {
             None => return,
         };
 
-        // 텍스트 채널 ID (알림을 보낼 채널)
-        // 여기를 실제 텍스트 채널 ID로 변경하세요
-        let notification_channel_id = serenity::model::id::ChannelId::new(1422179903373185094);
-        
-        // 멘션할 역할 ID (선택사항)
-        let mention_role_id = serenity::model::id::RoleId::new(1422182421415202879);
-
-        match (old.as_ref().and_then(|v| v.channel_id), new.channel_id) {
-            // 보이스 채널에 입장
-            (None, Some(channel_id)) | (Some(_), Some(channel_id)) 
-                if old.as_ref().and_then(|v| v.channel_id) != Some(channel_id) => {
-                
-                let channel_name = get_channel_name(&ctx, guild_id, channel_id).await;
-                
-                // 채널의 현재 인원 수 확인
-                let member_count = count_voice_members(&ctx, guild_id, channel_id).await;
-                
-                let mut tracker_lock = tracker.write().await;
-                
-                // 첫 번째 사람이 입장한 경우
-                if member_count == 1 {
-                    tracker_lock.insert(channel_id.get(), Instant::now());
-                    
-                    let _ = notification_channel_id
-                        .say(
-                            &ctx.http,
-                            format!(
-                                "🟢 **#{}** 방이 활성화되었습니다. <@&{}>",
-                                channel_name, mention_role_id
-                            ),
-                        )
-                        .await;
+        let config = get_usable_config(&ctx, guild_id).await;
+
+        // 인원 확인부터 상태 변경, 기록까지 같은 길드의 다른 이벤트와 섞이지 않도록 길드 잠금을 잡음
+        let guild_tracker = tracker.guild(guild_id).await;
+        let mut guild_tracker = guild_tracker.lock().await;
+
+        // 디스코드 상태 변경을 이벤트로 변환 (채널 변화가 없는 음소거 등은 무시)
+        let event = event_metrics::phase(Phase::Cache, async {
+            match (old.as_ref().and_then(|v| v.channel_id), new.channel_id) {
+                (None, Some(channel)) => Some(VoiceEvent::Join {
+                    user: user.id,
+                    channel,
+                    members: count_voice_members(&ctx, guild_id, channel).await,
+                }),
+                (Some(channel), None) => Some(VoiceEvent::Leave {
+                    user: user.id,
+                    channel,
+                    members: count_voice_members(&ctx, guild_id, channel).await,
+                }),
+                (Some(from), Some(to)) if from != to => Some(VoiceEvent::Move {
+                    user: user.id,
+                    from,
+                    from_members: count_voice_members(&ctx, guild_id, from).await,
+                    to,
+                    to_members: count_voice_members(&ctx, guild_id, to).await,
+                }),
+                _ => None,
+            }
+        })
+        .await;
+        let Some(event) = event else {
+            return;
+        };
+        record_event_type(&event);
+
+        if config.enable_voice_log {
+            event_metrics::phase(Phase::Storage, log_voice_event(&ctx, guild_id, user, event)).await;
+        }
+
+        // 허브 채널에 들어오면 임시 채널을 만들어 옮기고, 임시 채널이 비면 삭제 (알림 설정과 무관)
+        if let Some(hub) = config.hub_channel
+            && !user.bot
+            && event.joined_channel().map(|(channel, _)| channel) == Some(hub)
+        {
+            let name = new.member.as_ref().map_or(user.name.as_str(), |m| m.display_name());
+            temp_channels::spawn_create(&ctx, guild_id, hub, user.id, name.to_string());
+        }
+        if let Some((channel, 0)) = event.left_channel() {
+            temp_channels::channel_emptied(&ctx, guild_id, channel).await;
+        }
+
+        // 알림을 보낼 텍스트 채널. 없어도 세션과 기록은 그대로 처리하고 알림만 건너뜀
+        let notification_channel = notification_target(&config, user.bot);
+        let notify = notification_channel.is_some();
+
+        let (actions, new_starter) = guild_tracker.apply(event, user.id, user.bot, Instant::now());
+
+        // 입장 TTS 안내 (서버가 켜 두었고, 봇이 아니며, 본인이 거부하지 않은 경우)
+        if config.tts_join_announcements
+            && !user.bot
+            && !get_user_prefs(&ctx, user.id).await.tts_opt_out
+        {
+            let name = new.member.as_ref().map_or(user.name.as_str(), |m| m.display_name());
+            for action in &actions {
+                if let VoiceAction::AnnounceJoin { channel, .. } = action {
+                    tts_announce::announce_join(&ctx, guild_id, *channel, name).await;
                 }
-                
-                // 입장 알림
-                let _ = notification_channel_id
-                    .say(
-                        &ctx.http,
-                        format!(
-                            "➡️ {} 님이 **#{}** 에 입장했습니다.",
-                            user.name, channel_name
-                        ),
-                    )
-                    .await;
             }
+        }
 
-            // 보이스 채널에서 퇴장
-            (Some(old_channel_id), None) => {
-                let channel_name = get_channel_name(&ctx, guild_id, old_channel_id).await;
-                
-                // 퇴장 알림
-                let _ = notification_channel_id
-                    .say(
-                        &ctx.http,
-                        format!(
-                            "⬅️ {} 님이 **#{}** 방에서 퇴장했습니다.",
-                            user.name, channel_name
-                        ),
+        // 알림은 이벤트 처리가 끝난 뒤 별도 작업에서 순서대로 전송
+        let mut outgoing = Vec::new();
+        for action in actions {
+            match action {
+                VoiceAction::MemberJoined { user } => member_joined(state, guild_id, user).await,
+                VoiceAction::MemberLeft { user } => {
+                    if let Err(e) = event_metrics::phase(Phase::Storage, member_left(&ctx, state, guild_id, user)).await {
+                        let context = ErrorContext::new("사용자 보이스 시간 저장").guild(guild_id);
+                        report_bot_error(&ctx, context, &e).await;
+                    }
+                }
+                VoiceAction::StartSession { channel } => {
+                    let saved =
+                        event_metrics::phase(Phase::Storage, record_session_start(&ctx, state, guild_id, channel)).await;
+                    match saved {
+                        Ok(session_id) => {
+                            guild_tracker.session_ids.insert(channel.get(), session_id);
+                        }
+                        Err(e) => {
+                            let context = ErrorContext::new("채널 활성화 저장").guild(guild_id).channel(channel);
+                            report_bot_error(&ctx, context, &e).await;
+                        }
+                    }
+                    channel_status::refresh(&ctx, guild_id, channel).await;
+                    // 알림음은 텍스트 알림과 따로 재생 (실패해도 알림에 영향 없음)
+                    #[cfg(feature = "chime")]
+                    if config.activation_chime && config.hub_channel != Some(channel) && !user.bot {
+                        chime::spawn_activation_chime(&ctx, guild_id, channel, config.chime_volume);
+                    }
+                }
+                // 허브 채널은 잠깐 거쳐 가는 곳이므로 알리지 않음
+                VoiceAction::AnnounceActivate { channel, .. }
+                | VoiceAction::AnnounceJoin { channel, .. }
+                | VoiceAction::AnnounceLeave { channel, .. }
+                    if config.hub_channel == Some(channel) => {}
+                VoiceAction::AnnounceActivate { .. } if !notify => {}
+                VoiceAction::AnnounceActivate { channel, members } => {
+                    let channel_name = get_channel_name(&ctx, guild_id, channel).await;
+                    let details = get_channel_details(&ctx, guild_id, channel);
+                    // 채널에서 진행 중인 예약 이벤트 (없거나 조회에 실패하면 일반 알림)
+                    let event = scheduled_events::live_event(&ctx, guild_id, channel).await;
+                    // 활성화 시 멘션할 대상 (/setrole, /config mention, 선택사항).
+                    // event_mentions를 켜면 이벤트 진행 중에는 관심 표시한 멤버로 대신함
+                    let mut mentions = config.activation_mentions(channel);
+                    if config.event_mentions && let Some(event) = &event {
+                        let interested = scheduled_events::interested_users(&ctx, guild_id, event.id).await;
+                        if !interested.is_empty() {
+                            mentions = interested;
+                        }
+                    }
+                    for message in notification::activated(
+                        config.activate_template.as_deref(),
+                        &user.name,
+                        &channel_name,
+                        &details,
+                        members,
+                        &mentions,
+                        event.as_ref(),
+                    ) {
+                        outgoing.push((message, "활성화 알림 전송"));
+                    }
+                }
+                VoiceAction::AnnounceJoin { .. } | VoiceAction::AnnounceLeave { .. }
+                    if !notify || !config.notify_join_leave => {}
+                // 입장/퇴장 알림은 짧은 시간 동안 모아서 한 번에 전송
+                VoiceAction::AnnounceJoin { channel, members, .. } => {
+                    let Some(notification_channel_id) = notification_channel else {
+                        continue;
+                    };
+                    let channel_name = get_channel_name(&ctx, guild_id, channel).await;
+                    let details = get_channel_details(&ctx, guild_id, channel);
+                    let line = notification::join_line(config.join_template.as_deref(), &user.name, &channel_name, members);
+                    let single = notification::joined(line.clone(), &details, members);
+                    notification_batch::push(
+                        &ctx,
+                        guild_id,
+                        notification_channel_id,
+                        BatchEntry { channel_id: channel, joined: true, line, single },
+                    )
+                    .await;
+                }
+                VoiceAction::AnnounceLeave { channel, members, .. } => {
+                    let Some(notification_channel_id) = notification_channel else {
+                        continue;
+                    };
+                    let channel_name = get_channel_name(&ctx, guild_id, channel).await;
+                    let details = get_channel_details(&ctx, guild_id, channel);
+                    let line = notification::leave_line(config.leave_template.as_deref(), &user.name, &channel_name, members);
+                    let single = notification::left(line.clone(), &details, members);
+                    notification_batch::push(
+                        &ctx,
+                        guild_id,
+                        notification_channel_id,
+                        BatchEntry { channel_id: channel, joined: false, line, single },
                     )
                     .await;
-                
-                // 채널의 현재 인원 수 확인
-                let member_count = count_voice_members(&ctx, guild_id, old_channel_id).await;
-                
-                // 마지막 사람이 퇴장한 경우
-                if member_count == 0 {
-                    let mut tracker_lock = tracker.write().await;
-                    
-                    if let Some(start_time) = tracker_lock.remove(&old_channel_id.get()) {
-                        let duration = start_time.elapsed();
-                        let hours = duration.as_secs() / 3600;
-                        let minutes = (duration.as_secs() % 3600) / 60;
-                        let seconds = duration.as_secs() % 60;
-                        
-                        let _ = notification_channel_id
-                            .say(
-                                &ctx.http,
-                                format!(
-                                    "🔴 **#{}** 방이 비활성화되었습니다. 활성화 시간: {}시간 {}분 {}초",
-                                    channel_name, hours, minutes, seconds
-                                ),
-                            )
-                            .await;
+                }
+                VoiceAction::EndSession { channel, duration } => {
+                    // 채널이 비었으므로 아직 보내지 않은 이 채널의 입장/퇴장 알림은 취소
+                    notification_batch::cancel_channel(&ctx, guild_id, channel).await;
+                    let ended = guild_tracker.take_ended(channel, duration);
+                    let saved =
+                        event_metrics::phase(Phase::Storage, record_session_end(&ctx, state, guild_id, &ended)).await;
+                    if let Err(e) = saved {
+                        let context = ErrorContext::new("세션 저장").guild(guild_id).channel(channel);
+                        report_bot_error(&ctx, context, &e).await;
+                    }
+                    channel_status::refresh(&ctx, guild_id, channel).await;
+                    if config.hub_channel == Some(channel) {
+                        continue;
                     }
+                    let channel_name = get_channel_name(&ctx, guild_id, channel).await;
+                    let starter_name = ended.starter_id.map(|id| get_member_name(&ctx, guild_id, id));
+                    outgoing.push((
+                        notification::deactivated(
+                            config.deactivate_template.as_deref(),
+                            &channel_name,
+                            duration.as_secs(),
+                            false,
+                            starter_name.as_deref(),
+                        ),
+                        "비활성화 알림 전송",
+                    ));
                 }
             }
+        }
+        // 세션 시작 기록이 저장된 뒤 시작한 사람도 저장 (재시작 후에도 유지)
+        if let Some(channel) = new_starter
+            && guild_tracker.sessions.contains_key(&channel.get())
+            && let Err(e) = storage::set_active_channel_starter(&state.storage, channel, user.id).await
+        {
+            let context = ErrorContext::new("활성화 시작한 사람 저장").guild(guild_id).channel(channel);
+            report_bot_error(&ctx, context, &BotError::from(e)).await;
+        }
+        if let Some(notification_channel_id) = notification_channel {
+            queue_send(&ctx, guild_id, notification_channel_id, outgoing).await;
+        }
+    }
+}
+
+#[async_trait]
+impl EventHandler for VoiceHandler {
+    async fn ready(&self, ctx: Context, ready: Ready) {
+        tracing::info!("{}님의 봇이 준비되었습니다! (샤드 {})", ready.user.name, ctx.shard_id);
+        self.state
+            .health
+            .set_shard_count(ready.shard.map(|s| s.total).unwrap_or(1));
+        // 클라이언트를 다시 만들면 캐시도 새로 생기므로 ready마다 교체
+        *self.state.cache.write().await = Some(ctx.cache.clone());
+
+        // 소유자 전용 커맨드를 위해 애플리케이션 소유자 조회
+        if let Err(e) = load_owner(&ctx).await {
+            report_bot_error(&ctx, ErrorContext::new("애플리케이션 정보 조회"), &e).await;
+        }
+
+        // DM에서 쓸 슬래시 커맨드 등록
+        register_global_commands(&ctx).await;
+
+        // 서버에서 쓸 커맨드는 길드 커맨드로 등록 (봇이 속한 모든 길드, 서버에서 끈 커맨드 제외)
+        for guild_id in ctx.cache.guilds() {
+            register_guild_commands(&ctx, guild_id).await;
+        }
+
+        // 보이스 활동을 반영하는 상태 메시지 갱신 시작
+        start_presence_task(&ctx).await;
+
+        // 주기 작업 실행 시작
+        start_scheduler(&ctx).await;
+
+        if !self.state.reminders_restored.swap(true, Ordering::SeqCst) {
+            restore_reminders(&ctx, &self.state.storage).await;
+        }
+    }
+
+    async fn shard_stage_update(&self, _: Context, event: ShardStageUpdateEvent) {
+        self.state.health.stage_changed(event.old, event.new);
+    }
+
+    // 캐시가 채워진 뒤 (시작 시, 샤드 재연결 시) 추적 상태를 실제 보이스 상태와 맞춤
+    async fn cache_ready(&self, ctx: Context, guilds: Vec<GuildId>) {
+        reconcile_tracker(&ctx, &self.state, &guilds).await;
+        // 봇이 꺼진 사이 활성화가 끝난 채널의 이름 되돌리기
+        channel_status::recover(&ctx, &guilds).await;
+        // 봇이 꺼진 사이 비었거나 지워진 임시 채널 정리
+        temp_channels::recover(&ctx, &guilds).await;
+    }
+
+    // 세션 재개 중 놓친 이벤트가 있을 수 있으므로 이 샤드의 길드를 다시 확인
+    async fn resume(&self, ctx: Context, _: ResumedEvent) {
+        let shard_count = ctx.cache.shard_count();
+        let guilds: Vec<GuildId> = ctx
+            .cache
+            .guilds()
+            .into_iter()
+            .filter(|g| shard_of(g.get(), shard_count) == ctx.shard_id.0)
+            .collect();
+        reconcile_tracker(&ctx, &self.state, &guilds).await;
+    }
+
+    async fn guild_create(&self, ctx: Context, guild: Guild, is_new: Option<bool>) {
+        // 아직 커맨드를 등록하지 않은 길드에만 등록 (재연결 시 오는 guild_create는 건너뜀)
+        register_guild_commands(&ctx, guild.id).await;
+
+        // 설정된 채널/역할을 쓸 수 있는지 확인 (문제가 있으면 관리자에게 알리고 해당 설정은 건너뜀)
+        check_guild(&ctx, guild.id).await;
+
+        // 안내 메시지는 새로 참가한 길드에만
+        if is_new != Some(true) {
+            return;
+        }
+        tracing::info!("새 길드에 참가했습니다: {} ({})", guild.name, guild.id);
+
+        // 시스템 채널(없으면 쓸 수 있는 첫 텍스트 채널)에 안내와 설정 마법사 게시
+        post_setup_wizard(&ctx, &guild).await;
+    }
+
+    async fn voice_state_update(&self, ctx: Context, old: Option<VoiceState>, new: VoiceState) {
+        let span = voice_event_span(new.guild_id, new.user_id);
+        event_metrics::instrument(Handler::VoiceStateUpdate, self.handle_voice_state_update(ctx, old, new))
+            .instrument(span)
+            .await;
+    }
+
+    // 채널 권한이나 역할이 바뀌면 설정을 다시 검사 (고쳐졌으면 다시 사용)
+    async fn channel_update(&self, ctx: Context, _: Option<GuildChannel>, new: GuildChannel) {
+        check_guild(&ctx, new.guild_id).await;
+    }
+
+    async fn channel_delete(&self, ctx: Context, channel: GuildChannel, _: Option<Vec<Message>>) {
+        check_guild(&ctx, channel.guild_id).await;
+        temp_channels::forget(&ctx, channel.id).await;
+    }
+
+    async fn guild_role_update(&self, ctx: Context, _: Option<Role>, new: Role) {
+        check_guild(&ctx, new.guild_id).await;
+    }
+
+    async fn guild_role_delete(&self, ctx: Context, guild_id: GuildId, _: RoleId, _: Option<Role>) {
+        check_guild(&ctx, guild_id).await;
+    }
+
+    async fn thread_create(&self, ctx: Context, thread: GuildChannel) {
+        announce_thread_event(
+            &ctx,
+            thread.guild_id,
+            thread.id,
+            thread.parent_id,
+            Some(&thread.name),
+            ThreadEvent::Created,
+        )
+        .await;
+    }
 
-            _ => {}
+    // 보관 상태로 바뀐 경우만 알림
+    async fn thread_update(&self, ctx: Context, old: Option<GuildChannel>, new: GuildChannel) {
+        let archived = |t: &GuildChannel| t.thread_metadata.is_some_and(|m| m.archived);
+        if archived(&new) && !old.as_ref().is_some_and(archived) {
+            announce_thread_event(
+                &ctx,
+                new.guild_id,
+                new.id,
+                new.parent_id,
+                Some(&new.name),
+                ThreadEvent::Archived,
+            )
+            .await;
+        }
+    }
+
+    async fn thread_delete(
+        &self,
+        ctx: Context,
+        thread: PartialGuildChannel,
+        full_thread_data: Option<GuildChannel>,
+    ) {
+        announce_thread_event(
+            &ctx,
+            thread.guild_id,
+            thread.id,
+            Some(thread.parent_id),
+            full_thread_data.as_ref().map(|t| t.name.as_str()),
+            ThreadEvent::Deleted,
+        )
+        .await;
+    }
+
+    // 봇을 멘션한 메시지를 명령으로 처리
+    async fn message(&self, ctx: Context, msg: Message) {
+        if is_bot_mention(&msg, ctx.cache.current_user().id) {
+            handle_bot_mention(&ctx, &msg).await;
         }
     }
 
     async fn interaction_create(&self, ctx: Context, interaction: Interaction) {
-        if let Interaction::Command(cmd) = interaction {
-            if cmd.data.name == "calc" {
-                handle_calc(&ctx, &cmd).await;
+        record_event(&ctx).await;
+        self.state.health.record_event();
+        event_metrics::instrument(Handler::InteractionCreate, async {
+            match interaction {
+                Interaction::Command(cmd) => dispatch(&ctx, &cmd).await,
+                Interaction::Component(comp) => dispatch_component(&ctx, &comp).await,
+                Interaction::Autocomplete(ac) => dispatch_autocomplete(&ctx, &ac).await,
+                Interaction::Modal(modal) => dispatch_modal(&ctx, &modal).await,
+                _ => {}
             }
+        })
+        .await;
+    }
+}
+
+// 이벤트마다 만드는 루트 스팬. 캐시 조회, 저장, 디스코드 호출 단계가 그 아래에 기록됨
+fn voice_event_span(guild_id: Option<GuildId>, user_id: UserId) -> tracing::Span {
+    tracing::info_span!(
+        "voice_event",
+        guild_id = guild_id.map(|g| g.get()),
+        user_id = user_id.get(),
+        event_type = tracing::field::Empty,
+    )
+}
+
+// 이벤트 종류는 상태를 비교한 뒤에야 알 수 있어서 나중에 기록
+fn record_event_type(event: &VoiceEvent) {
+    tracing::Span::current().record("event_type", event.name());
+}
+
+// /voicelog용 이벤트 기록
+async fn log_voice_event(ctx: &Context, guild_id: GuildId, user: &User, event: VoiceEvent) {
+    let (kind, channel_id, channel) = match event {
+        VoiceEvent::Join { channel, .. } => {
+            (EventKind::Join, channel, get_channel_name(ctx, guild_id, channel).await)
+        }
+        VoiceEvent::Leave { channel, .. } => {
+            (EventKind::Leave, channel, get_channel_name(ctx, guild_id, channel).await)
+        }
+        VoiceEvent::Move { from, to, .. } => {
+            let from_name = get_channel_name(ctx, guild_id, from).await;
+            let to_name = get_channel_name(ctx, guild_id, to).await;
+            (EventKind::Move, to, format!("{} → #{}", from_name, to_name))
+        }
+    };
+    voice_log::record(
+        ctx,
+        guild_id,
+        VoiceLogEntry {
+            ts: storage::unix_now(),
+            user_id: user.id,
+            user_name: user.name.clone(),
+            event: kind,
+            channel_id,
+            channel,
+        },
+    )
+    .await;
+}
+
+// 사용자의 보이스 접속 시작 기록 (이미 접속 중이면 유지)
+async fn member_joined(state: &AppState, guild_id: GuildId, user_id: UserId) {
+    state
+        .voice_members
+        .write()
+        .await
+        .entry((guild_id, user_id))
+        .or_insert_with(Instant::now);
+}
+
+// 사용자의 보이스 접속 종료: 접속했던 시간을 누적 기록과 연속 기록에 더함
+async fn member_left(ctx: &Context, state: &AppState, guild_id: GuildId, user_id: UserId) -> Result<(), BotError> {
+    let joined_at = state.voice_members.write().await.remove(&(guild_id, user_id));
+    let Some(joined_at) = joined_at else {
+        return Ok(());
+    };
+    let secs = joined_at.elapsed().as_secs() as i64;
+    storage::add_user_time(&state.storage, guild_id, user_id, secs).await?;
+    streaks::record_voice_time(ctx, guild_id, user_id, secs).await;
+    Ok(())
+}
+
+// 캐시의 보이스 상태에 맞춰 사용자별 접속 기록을 고침. 고친 기록 수 반환.
+// 놓친 사이에 나간 사용자는 언제 나갔는지 모르므로 시간을 더하지 않고 기록만 지움
+// (지금까지로 계산하면 연결이 끊겨 있던 동안에도 접속해 있던 것으로 셈)
+fn reconcile_members(
+    members: &mut HashMap<(GuildId, UserId), Instant>,
+    guild_id: GuildId,
+    voice_users: &HashMap<UserId, ChannelId>,
+    now: Instant,
+) -> usize {
+    let before = members.len();
+    members.retain(|&(g, u), _| g != guild_id || voice_users.contains_key(&u));
+    let mut fixes = before - members.len();
+    for &user_id in voice_users.keys() {
+        if let Entry::Vacant(entry) = members.entry((guild_id, user_id)) {
+            entry.insert(now);
+            fixes += 1;
         }
     }
+    fixes
 }
 
-async fn handle_calc(ctx: &Context, cmd: &CommandInteraction) {
-    // expr 옵션 추출
-    let expr_val = cmd
-        .data
-        .options
-        .iter()
-        .find(|o| o.name == "expr")
-        .and_then(|o| match &o.value {
-            CommandDataOptionValue::String(s) => Some(s.as_str()),
-            _ => None,
-        })
-        .unwrap_or("");
-
-    if expr_val.is_empty() {
-        let _ = cmd
-            .create_response(
-                &ctx.http,
-                CreateInteractionResponse::Message(
-                    CreateInteractionResponseMessage::new().content("표현식을 입력하세요."),
-                ),
-            )
+// 채널 활성화 시작: 활성화 번호를 받고 재시작 후에도 이어서 추적하도록 저장
+async fn record_session_start(
+    ctx: &Context,
+    state: &AppState,
+    guild_id: GuildId,
+    channel_id: ChannelId,
+) -> Result<u64, BotError> {
+    let session_id = next_session_id(ctx, guild_id).await?;
+    storage::save_active_channel(&state.storage, guild_id, channel_id, session_id, storage::unix_now()).await?;
+    Ok(session_id)
+}
+
+// 채널 비활성화 시 세션 기록. 세션 저장이 실패해도 활성화 기록은 지움 (재시작 후 잘못 복원되지 않도록)
+async fn record_session_end(
+    ctx: &Context,
+    state: &AppState,
+    guild_id: GuildId,
+    ended: &EndedSession,
+) -> Result<(), BotError> {
+    let ended_at = storage::unix_now();
+    let EndedSession {
+        channel_id,
+        session_id,
+        duration,
+        peak_members,
+        starter_id,
+    } = *ended;
+    // 시작할 때 번호를 받지 못했으면 지금 받음
+    let session_id = match session_id {
+        Some(session_id) => Ok(session_id),
+        None => next_session_id(ctx, guild_id).await,
+    };
+    let inserted = match session_id {
+        Ok(session_id) => {
+            let session = VoiceSession {
+                guild_id,
+                session_id,
+                channel_id,
+                started_at: ended_at - duration.as_secs() as i64,
+                ended_at,
+                peak_members: peak_members as i64,
+                activity_score: activity_score(peak_members, duration.as_secs()),
+                starter_id,
+            };
+            storage::insert_session(&state.storage, &session).await.map(|_| ())
+        }
+        Err(e) => Err(e),
+    };
+    storage::remove_active_channel(&state.storage, channel_id).await?;
+    inserted?;
+    Ok(())
+}
+
+// 애플리케이션 소유자(팀이면 팀 소유자)를 조회해 BotOwner에 저장
+async fn load_owner(ctx: &Context) -> Result<(), BotError> {
+    let info = ctx.http.get_current_application_info().await?;
+    let owner_id = info
+        .team
+        .map(|team| team.owner_user_id)
+        .or(info.owner.map(|owner| owner.id));
+    if let Some(owner_id) = owner_id {
+        ctx.data.write().await.insert::<BotOwner>(owner_id);
+    }
+    Ok(())
+}
+
+// 저장된 진행 중 활성화로 추적기 복원 (시작 시 호출)
+pub async fn restore_tracker(
+    pool: &SqlitePool,
+    tracker: &VoiceTrackerStore,
+) -> Result<usize, sqlx::Error> {
+    let active = storage::load_active_channels(pool).await?;
+    let now = storage::unix_now();
+    for (guild_id, channel_id, session_id, started_at, starter) in &active {
+        let elapsed = Duration::from_secs((now - started_at).max(0) as u64);
+        let start = Instant::now().checked_sub(elapsed).unwrap_or_else(Instant::now);
+        let guild_tracker = tracker.guild(*guild_id).await;
+        let mut guild_tracker = guild_tracker.lock().await;
+        guild_tracker.sessions.insert(channel_id.get(), start);
+        // 번호가 없는 (번호를 쓰기 전에 저장된) 활성화는 끝날 때 번호를 받음
+        if *session_id > 0 {
+            guild_tracker.session_ids.insert(channel_id.get(), *session_id);
+        }
+        guild_tracker.starters.insert(channel_id.get(), *starter);
+    }
+    Ok(active.len())
+}
+
+// 캐시의 보이스 상태 기준으로 추적기 보정: 사람이 있는데 추적되지 않는 채널은 지금부터 추적하고,
+// 추적 중인데 비어 있는 채널은 종료 처리 후 알림
+async fn reconcile_tracker(ctx: &Context, state: &AppState, guild_ids: &[GuildId]) {
+    let tracker = &state.voice_tracker;
+    let members = &state.voice_members;
+
+    let (mut started, mut closed, mut member_fixes) = (0, 0, 0);
+    for &guild_id in guild_ids {
+        let guild_tracker = tracker.guild(guild_id).await;
+        let mut guild_tracker = guild_tracker.lock().await;
+        let Some((channels, voice_users, humans)) = ctx.cache.guild(guild_id).map(|g| {
+            let channels: HashSet<ChannelId> = g.channels.keys().copied().collect();
+            let voice_users: HashMap<UserId, ChannelId> = g
+                .voice_states
+                .values()
+                .filter_map(|vs| vs.channel_id.map(|c| (vs.user_id, c)))
+                .collect();
+            let mut humans: HashMap<ChannelId, Vec<UserId>> = HashMap::new();
+            for (&user_id, &channel_id) in &voice_users {
+                if !g.members.get(&user_id).is_some_and(|m| m.user.bot) {
+                    humans.entry(channel_id).or_default().push(user_id);
+                }
+            }
+            (channels, voice_users, humans)
+        }) else {
+            continue;
+        };
+        let mut populated: HashMap<ChannelId, usize> = HashMap::new();
+        for &channel_id in voice_users.values() {
+            *populated.entry(channel_id).or_default() += 1;
+        }
+
+        let (to_start, to_close) = {
+            let tracker = &mut guild_tracker.sessions;
+            let to_close: Vec<(ChannelId, Instant)> = channels
+                .iter()
+                .filter(|c| !populated.contains_key(c))
+                .filter_map(|c| tracker.remove(&c.get()).map(|start| (*c, start)))
+                .collect();
+            let to_start: Vec<ChannelId> = populated
+                .keys()
+                .filter(|c| !tracker.contains_key(&c.get()))
+                .copied()
+                .collect();
+            for channel_id in &to_start {
+                tracker.insert(channel_id.get(), Instant::now());
+            }
+            (to_start, to_close)
+        };
+        // 재시작으로 복원한 채널은 최대 접속자 수를 모르므로 지금 인원부터 다시 셈
+        for (&channel_id, &count) in &populated {
+            guild_tracker.record_peak(channel_id, count);
+        }
+        let to_close: Vec<EndedSession> = to_close
+            .into_iter()
+            .map(|(channel_id, start)| guild_tracker.take_ended(channel_id, start.elapsed()))
+            .collect();
+        // 놓친 사이 시작된 채널은 누가 먼저 들어왔는지 모르므로 사람이 한 명일 때만 시작한 사람으로 봄
+        let starters: Vec<(ChannelId, Option<UserId>)> = to_start
+            .iter()
+            .map(|&channel_id| {
+                let starter = match humans.get(&channel_id).map(Vec::as_slice) {
+                    Some(&[only]) => Some(only),
+                    _ => None,
+                };
+                guild_tracker.starters.insert(channel_id.get(), starter);
+                (channel_id, starter)
+            })
+            .collect();
+
+        for &(channel_id, starter) in &starters {
+            let saved = async {
+                let session_id = record_session_start(ctx, state, guild_id, channel_id).await?;
+                if let Some(starter) = starter {
+                    storage::set_active_channel_starter(&state.storage, channel_id, starter).await?;
+                }
+                Ok::<_, BotError>(session_id)
+            }
             .await;
-        return;
+            match saved {
+                Ok(session_id) => {
+                    guild_tracker.session_ids.insert(channel_id.get(), session_id);
+                }
+                Err(e) => {
+                    let context = ErrorContext::new("채널 활성화 저장").guild(guild_id).channel(channel_id);
+                    report_bot_error(ctx, context, &e).await;
+                }
+            }
+            channel_status::refresh(ctx, guild_id, channel_id).await;
+        }
+
+        if !to_close.is_empty() {
+            let config = get_usable_config(ctx, guild_id).await;
+            for ended in &to_close {
+                let channel_id = ended.channel_id;
+                if let Err(e) = record_session_end(ctx, state, guild_id, ended).await {
+                    let context = ErrorContext::new("세션 저장").guild(guild_id).channel(channel_id);
+                    report_bot_error(ctx, context, &e).await;
+                }
+                channel_status::refresh(ctx, guild_id, channel_id).await;
+                if let Some(notification_channel) = config.notification_channel {
+                    let channel_name = get_channel_name(ctx, guild_id, channel_id).await;
+                    let starter_name = ended.starter_id.map(|id| get_member_name(ctx, guild_id, id));
+                    send_notification(
+                        ctx,
+                        guild_id,
+                        notification_channel,
+                        notification::deactivated(
+                            config.deactivate_template.as_deref(),
+                            &channel_name,
+                            ended.duration.as_secs(),
+                            true,
+                            starter_name.as_deref(),
+                        ),
+                        "비활성화 알림 전송",
+                    )
+                    .await;
+                }
+            }
+        }
+
+        // 사용자별 접속 기록도 같은 기준으로 보정
+        member_fixes += reconcile_members(&mut *members.write().await, guild_id, &voice_users, Instant::now());
+        started += to_start.len();
+        closed += to_close.len();
     }
 
-    let result_text = match crate::calc::evaluate(expr_val) {
-        Ok(v) => format!("{} = {}", expr_val, v),
-        Err(e) => format!("{} -> 오류: {}", expr_val, e),
+    tracing::info!(
+        "추적 상태 보정 (샤드 {}): 길드 {}개, 추적 시작 {}개, 종료 {}개, 사용자 기록 {}건",
+        ctx.shard_id,
+        guild_ids.len(),
+        started,
+        closed,
+        member_fixes
+    );
+}
+
+// 채널 주제와 인원 제한 가져오기 (설정되지 않았으면 None)
+fn get_channel_details(
+    ctx: &Context,
+    guild_id: serenity::model::id::GuildId,
+    channel_id: serenity::model::id::ChannelId,
+) -> ChannelDetails {
+    let Some(guild) = ctx.cache.guild(guild_id) else {
+        return ChannelDetails::default();
     };
+    let Some(channel) = guild.channels.get(&channel_id) else {
+        return ChannelDetails::default();
+    };
+    ChannelDetails::new(channel.topic.as_deref(), channel.user_limit)
+}
 
-    let _ = cmd
-        .create_response(
-            &ctx.http,
-            CreateInteractionResponse::Message(
-                CreateInteractionResponseMessage::new().content(result_text),
-            ),
-        )
-        .await;
+// 초 단위 시간을 "X시간 Y분 Z초" 형식으로
+pub fn format_duration(secs: u64) -> String {
+    format!("{}시간 {}분 {}초", secs / 3600, (secs % 3600) / 60, secs % 60)
+}
+
+// 알림에 표시할 멤버 이름 (서버 별명 우선, 캐시에 없으면 ID)
+fn get_member_name(ctx: &Context, guild_id: GuildId, user_id: UserId) -> String {
+    ctx.cache
+        .guild(guild_id)
+        .and_then(|g| g.members.get(&user_id).map(|m| m.display_name().to_string()))
+        .or_else(|| ctx.cache.user(user_id).map(|u| u.name.clone()))
+        .unwrap_or_else(|| user_id.to_string())
 }
 
 // 채널 이름 가져오기
@@ -240,10 +996,10 @@ async fn get_channel_name(
     guild_id: serenity::model::id::GuildId,
     channel_id: serenity::model::id::ChannelId,
 ) -> String {
-    if let Some(guild) = ctx.cache.guild(guild_id) {
-        if let Some(channel) = guild.channels.get(&channel_id) {
-            return channel.name.clone();
-        }
+    if let Some(guild) = ctx.cache.guild(guild_id)
+        && let Some(channel) = guild.channels.get(&channel_id)
+    {
+        return channel_status::base_name(&channel.name).to_string();
     }
     "알 수 없는 채널".to_string()
 }
@@ -264,4 +1020,179 @@ async fn count_voice_members(
     0
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serenity::all::ChannelId;
+    use std::sync::Mutex as StdMutex;
+    use tracing::field::{Field, Visit};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing_subscriber::layer::{Context as LayerContext, Layer, SubscriberExt};
+    use tracing_subscriber::registry::LookupSpan;
+
+    type SpanFields = HashMap<String, HashMap<String, String>>;
+
+    // 스팬 이름별로 기록된 필드를 모아 두는 레이어
+    #[derive(Clone, Default)]
+    struct Capture(Arc<StdMutex<SpanFields>>);
 
+    struct FieldVisitor<'a>(&'a mut HashMap<String, String>);
+
+    impl Visit for FieldVisitor<'_> {
+        fn record_str(&mut self, field: &Field, value: &str) {
+            self.0.insert(field.name().to_string(), value.to_string());
+        }
+
+        fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+            self.0.insert(field.name().to_string(), format!("{:?}", value));
+        }
+    }
+
+    impl<S: tracing::Subscriber + for<'a> LookupSpan<'a>> Layer<S> for Capture {
+        fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: LayerContext<'_, S>) {
+            let mut spans = self.0.lock().unwrap();
+            let fields = spans.entry(attrs.metadata().name().to_string()).or_default();
+            attrs.record(&mut FieldVisitor(fields));
+            // 부모 스팬 이름도 필드처럼 남김
+            if let Some(parent) = ctx.span(id).and_then(|span| span.parent()) {
+                fields.insert("parent".to_string(), parent.name().to_string());
+            }
+        }
+
+        fn on_record(&self, id: &Id, values: &Record<'_>, ctx: LayerContext<'_, S>) {
+            let Some(span) = ctx.span(id) else {
+                return;
+            };
+            let mut spans = self.0.lock().unwrap();
+            values.record(&mut FieldVisitor(spans.entry(span.name().to_string()).or_default()));
+        }
+    }
+
+    impl Capture {
+        fn fields(&self, span: &str) -> HashMap<String, String> {
+            self.0.lock().unwrap().get(span).cloned().unwrap_or_default()
+        }
+    }
+
+    fn captured(f: impl FnOnce()) -> Capture {
+        let capture = Capture::default();
+        tracing::subscriber::with_default(tracing_subscriber::registry().with(capture.clone()), f);
+        capture
+    }
+
+    #[test]
+    fn voice_event_span_records_fields() {
+        let capture = captured(|| {
+            let span = voice_event_span(Some(GuildId::new(10)), UserId::new(20));
+            let _entered = span.enter();
+            record_event_type(&VoiceEvent::Move {
+                user: UserId::new(20),
+                from: ChannelId::new(1),
+                from_members: 0,
+                to: ChannelId::new(2),
+                to_members: 1,
+            });
+        });
+        let fields = capture.fields("voice_event");
+        assert_eq!(fields.get("guild_id").map(String::as_str), Some("10"));
+        assert_eq!(fields.get("user_id").map(String::as_str), Some("20"));
+        assert_eq!(fields.get("event_type").map(String::as_str), Some("move"));
+    }
+
+    #[test]
+    fn voice_event_span_without_guild() {
+        // 길드 밖 이벤트는 guild_id 없이, 종류를 알기 전에는 event_type도 비어 있음
+        let capture = captured(|| {
+            let _span = voice_event_span(None, UserId::new(20));
+        });
+        let fields = capture.fields("voice_event");
+        assert!(!fields.contains_key("guild_id"));
+        assert!(!fields.contains_key("event_type"));
+        assert_eq!(fields.get("user_id").map(String::as_str), Some("20"));
+    }
+
+    #[test]
+    fn phase_spans_are_children_of_voice_event() {
+        let capture = captured(|| {
+            let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+            let span = voice_event_span(Some(GuildId::new(10)), UserId::new(20));
+            runtime.block_on(event_metrics::phase(Phase::Storage, async {}).instrument(span));
+        });
+        let fields = capture.fields("phase");
+        assert_eq!(fields.get("phase").map(String::as_str), Some("storage"));
+        assert_eq!(fields.get("parent").map(String::as_str), Some("voice_event"));
+    }
+
+    #[test]
+    fn reconcile_drops_stale_members_without_time() {
+        let guild = GuildId::new(1);
+        let other_guild = GuildId::new(2);
+        let (alice, bob, carol) = (UserId::new(10), UserId::new(11), UserId::new(12));
+        let joined = Instant::now();
+        let now = joined + Duration::from_secs(3600);
+        let mut members = HashMap::from([
+            ((guild, alice), joined),
+            ((guild, bob), joined),
+            ((other_guild, bob), joined),
+        ]);
+        // 캐시에는 ALICE와 새로 들어온 CAROL만 남아 있음
+        let voice_users = HashMap::from([(alice, ChannelId::new(100)), (carol, ChannelId::new(100))]);
+
+        assert_eq!(reconcile_members(&mut members, guild, &voice_users, now), 2);
+        // 나간 BOB은 시간을 더하지 않고 지우고, 계속 있던 ALICE의 입장 시각은 유지
+        assert_eq!(
+            members,
+            HashMap::from([((guild, alice), joined), ((guild, carol), now), ((other_guild, bob), joined)])
+        );
+        // 이미 맞으면 고칠 것이 없음
+        assert_eq!(reconcile_members(&mut members, guild, &voice_users, now), 0);
+    }
+
+    // /setchannel이 없는 길드도 세션은 기록하고 알림만 보내지 않음
+    #[test]
+    fn joins_without_notification_channel_still_record_session() {
+        let config = GuildConfig { notification_channel: None, ..GuildConfig::default() };
+        let (alice, bob) = (UserId::new(1), UserId::new(2));
+        let lobby = ChannelId::new(10);
+        let now = Instant::now();
+        let mut tracker = GuildTracker::default();
+
+        assert_eq!(notification_target(&config, false), None);
+        let (actions, starter) =
+            tracker.apply(VoiceEvent::Join { user: alice, channel: lobby, members: 1 }, alice, false, now);
+        assert!(actions.contains(&VoiceAction::StartSession { channel: lobby }));
+        assert_eq!(starter, Some(lobby));
+        tracker.apply(VoiceEvent::Join { user: bob, channel: lobby, members: 2 }, bob, false, now);
+
+        assert!(tracker.sessions.contains_key(&lobby.get()));
+        assert_eq!(tracker.peaks.get(&lobby.get()), Some(&2));
+        assert_eq!(tracker.starters.get(&lobby.get()), Some(&Some(alice)));
+    }
+
+    #[test]
+    fn notification_target_skips_bots_unless_enabled() {
+        let channel = ChannelId::new(99);
+        let mut config = GuildConfig { notification_channel: Some(channel), ..GuildConfig::default() };
+        assert_eq!(notification_target(&config, false), Some(channel));
+        assert_eq!(notification_target(&config, true), None);
+        config.notify_bots = true;
+        assert_eq!(notification_target(&config, true), Some(channel));
+    }
+
+    // 세션 맵에 있는 채널만 활성화, 다른 길드의 같은 채널은 따로 봄
+    #[tokio::test]
+    async fn channel_active_follows_sessions() {
+        let (guild, other_guild) = (GuildId::new(1), GuildId::new(2));
+        let (lobby, games) = (ChannelId::new(10), ChannelId::new(11));
+        let store = VoiceTrackerStore::default();
+        assert!(!is_channel_active(&store, guild, lobby).await);
+
+        store.guild(guild).await.lock().await.sessions = HashMap::from([(lobby.get(), Instant::now())]);
+        assert!(is_channel_active(&store, guild, lobby).await);
+        assert!(!is_channel_active(&store, guild, games).await);
+        assert!(!is_channel_active(&store, other_guild, lobby).await);
+
+        store.guild(guild).await.lock().await.sessions.clear();
+        assert!(!is_channel_active(&store, guild, lobby).await);
+    }
+}
@@ -0,0 +1,122 @@
+use serenity::all::CommandDataOptionValue;
+use serenity::all::CommandInteraction;
+use serenity::all::CreateEmbed;
+use serenity::all::CreateEmbedFooter;
+use serenity::all::CreateInteractionResponseMessage;
+use serenity::all::CreateMessage;
+use serenity::all::UserId;
+use serenity::prelude::*;
+
+use crate::commands::respond;
+use crate::dry_run;
+use crate::error::{report_bot_error, require, BotError, ErrorContext};
+use crate::guild_config::SAVE_FAILED;
+use crate::notifier::{notifier, NotifyTarget};
+use crate::storage::{self, unix_now, Storage};
+use crate::user_prefs::{prefs_store, update_user_prefs};
+use crate::voice_tracker::format_duration;
+
+const SECS_PER_DAY: i64 = 86400;
+// 요약 기간과 일별 기록 보관 기간 (일)
+const REPORT_DAYS: i64 = 7;
+const RETAINED_DAYS: i64 = 35;
+
+// /weeklyreport opt-in | opt-out
+pub async fn handle_weeklyreport(ctx: &Context, cmd: &CommandInteraction) {
+    let Some(sub) = cmd.data.options.first() else {
+        return;
+    };
+    if !matches!(sub.value, CommandDataOptionValue::SubCommand(_)) {
+        return;
+    }
+    let opt_in = match sub.name.as_str() {
+        "opt-in" => true,
+        "opt-out" => false,
+        _ => return,
+    };
+    let content = if !update_user_prefs(ctx, cmd.user.id, |p| p.weekly_dm_summary = opt_in).await {
+        SAVE_FAILED
+    } else if opt_in {
+        "매주 일요일에 지난 7일 보이스 시간 요약을 DM으로 보내드립니다. DM을 받을 수 없으면 자동으로 해제됩니다."
+    } else {
+        "주간 요약 DM을 더 이상 보내지 않습니다."
+    };
+    respond(
+        ctx,
+        cmd,
+        CreateInteractionResponseMessage::new()
+            .content(content)
+            .ephemeral(true),
+    )
+    .await;
+}
+
+// 예약 작업 (매일): 일요일(UTC)에만 신청한 사용자에게 요약 DM 전송. 오래된 일별 기록도 정리
+pub async fn send_weekly_reports(ctx: Context) {
+    if let Err(e) = run_weekly_reports(&ctx).await {
+        report_bot_error(&ctx, ErrorContext::new("주간 요약 DM"), &e).await;
+    }
+}
+
+async fn run_weekly_reports(ctx: &Context) -> Result<(), BotError> {
+    let pool = require::<Storage>(ctx).await?;
+    let today = unix_now().div_euclid(SECS_PER_DAY);
+    storage::prune_user_voice_daily(&pool, today - RETAINED_DAYS).await?;
+
+    // 1970-01-01은 목요일이므로 (일 수 + 4) % 7 == 0 이 일요일
+    if (today + 4).rem_euclid(7) != 0 {
+        return Ok(());
+    }
+    let Some(store) = prefs_store(ctx).await else {
+        return Ok(());
+    };
+    let users: Vec<UserId> = store
+        .read()
+        .await
+        .iter()
+        .filter(|(_, prefs)| prefs.weekly_dm_summary)
+        .map(|(user_id, _)| *user_id)
+        .collect();
+
+    let since_day = today - REPORT_DAYS;
+    for user_id in users {
+        let by_guild = storage::user_time_since(&pool, user_id, since_day).await?;
+        let Some(&(top_guild, top_secs)) = by_guild.first() else {
+            continue;
+        };
+        let total: i64 = by_guild.iter().map(|(_, secs)| secs).sum();
+        let rank = storage::global_time_rank(&pool, since_day, total).await?;
+        let guild_name = ctx
+            .cache
+            .guild(top_guild)
+            .map(|g| g.name.clone())
+            .unwrap_or_else(|| format!("서버 {}", top_guild));
+
+        let embed = CreateEmbed::new()
+            .title("🎧 지난 7일 보이스 요약")
+            .field("총 보이스 시간", format_duration(total as u64), false)
+            .field(
+                "가장 많이 있던 서버",
+                format!("{} ({})", guild_name, format_duration(top_secs as u64)),
+                false,
+            )
+            .field("전체 순위", format!("{}위", rank), true)
+            .footer(CreateEmbedFooter::new("/weeklyreport opt-out 으로 그만 받을 수 있습니다"));
+        // 여러 서버에 걸친 DM이라 전체 드라이런만 적용
+        let detail = format!("사용자 {}: 총 {}", user_id, format_duration(total as u64));
+        if dry_run::suppressed(ctx, None, "주간 요약 DM", detail).await {
+            continue;
+        }
+
+        let sent = notifier(ctx)
+            .await
+            .send(NotifyTarget::Dm(user_id), CreateMessage::new().embed(embed))
+            .await;
+        // DM을 막아 둔 사용자는 신청을 해제해 매주 실패하지 않도록
+        if let Err(e) = sent {
+            tracing::warn!("주간 요약 DM 실패 ({}), 신청을 해제합니다: {}", user_id, e);
+            update_user_prefs(ctx, user_id, |p| p.weekly_dm_summary = false).await;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,83 @@
+// 실행 파일의 디스코드 없이 동작하는 서브커맨드 (calc, repl / --repl, validate-config)
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+fn aurobot(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_aurobot"))
+        .args(args)
+        // 개발 환경의 .env / 환경 변수가 결과를 바꾸지 않도록
+        .env_remove("DISCORD_TOKEN")
+        .env_remove("AUROBOT_CONFIG")
+        .env_remove("DATABASE_URL")
+        .output()
+        .expect("실행 실패")
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+#[test]
+fn calc_prints_result() {
+    let output = aurobot(&["calc", "2 + 3 * 4"]);
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "14\n");
+}
+
+#[test]
+fn calc_error_exits_with_failure() {
+    let output = aurobot(&["calc", "1 / 0"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stdout(&output).is_empty());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("0으로 나눌 수 없습니다"));
+}
+
+#[test]
+fn calc_needs_no_token() {
+    // run과 달리 토큰 없이도 검증을 통과해야 함
+    let output = aurobot(&["calc", "2**8"]);
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "256\n");
+}
+
+// REPL에 입력을 넣고 끝까지 실행한 결과
+fn repl(arg: &str, input: &[u8]) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_aurobot"))
+        .arg(arg)
+        .env_remove("DISCORD_TOKEN")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("실행 실패");
+    child.stdin.take().unwrap().write_all(input).unwrap();
+    child.wait_with_output().unwrap()
+}
+
+#[test]
+fn repl_keeps_previous_answer() {
+    let output = repl("--repl", b"5*3\nans+7\n\n1/0\n");
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "15\n22\n오류: 0으로 나눌 수 없습니다\n");
+}
+
+#[test]
+fn repl_subcommand_matches_flag() {
+    // `aurobot repl`도 --repl과 같은 REPL을 실행해야 함 (토큰 없이)
+    let output = repl("repl", b"2^10\nans/4\n");
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "1024\n256\n");
+}
+
+#[test]
+fn validate_config_requires_path() {
+    let output = aurobot(&["validate-config"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("--config-path"));
+}
+
+#[test]
+fn help_exits_successfully() {
+    let output = aurobot(&["--help"]);
+    assert!(output.status.success());
+    assert!(stdout(&output).contains("--log-level"));
+}